@@ -34,6 +34,13 @@ impl CleanupGuard {
             slot: guards.insert(Box::new(f)),
         }
     }
+
+    /// Drops the guard without invoking its callback
+    pub fn disarm(self) {
+        let guards = &mut *LIVE_GUARDS.lock().unwrap();
+        let _ = guards.remove(self.slot);
+        std::mem::forget(self);
+    }
 }
 
 impl Drop for CleanupGuard {
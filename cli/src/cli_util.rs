@@ -21,6 +21,7 @@ use std::env;
 use std::ffi::OsString;
 use std::fmt;
 use std::fmt::Debug;
+use std::fs;
 use std::io;
 use std::io::Write as _;
 use std::mem;
@@ -66,6 +67,7 @@ use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigSource;
 use jj_lib::config::StackedConfig;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::file_util;
 use jj_lib::fileset;
 use jj_lib::fileset::FilesetDiagnostics;
 use jj_lib::fileset::FilesetExpression;
@@ -100,6 +102,7 @@ use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::repo_path::UiPathParseError;
+use jj_lib::repo_path::UiPathStyle;
 use jj_lib::revset;
 use jj_lib::revset::ResolvedRevsetExpression;
 use jj_lib::revset::RevsetAliasesMap;
@@ -117,7 +120,9 @@ use jj_lib::revset::UserRevsetExpression;
 use jj_lib::rewrite::restore_tree;
 use jj_lib::settings::HumanByteSize;
 use jj_lib::settings::UserSettings;
+use jj_lib::store_migration::StoreMigrationError;
 use jj_lib::str_util::StringPattern;
+use jj_lib::transaction::RevertOperationError;
 use jj_lib::transaction::Transaction;
 use jj_lib::view::View;
 use jj_lib::working_copy;
@@ -151,6 +156,8 @@ use crate::command_error::internal_error_with_message;
 use crate::command_error::print_parse_diagnostics;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
+use crate::command_error::ChildExitError;
 use crate::command_error::CommandError;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::commit_templater::CommitTemplateLanguageExtension;
@@ -167,6 +174,9 @@ use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
 use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
+use crate::hooks::run_pre_commit_rewrite_hook;
+use crate::hooks::run_working_copy_hook;
+use crate::hooks::WorkingCopyHook;
 use crate::merge_tools::DiffEditor;
 use crate::merge_tools::MergeEditor;
 use crate::merge_tools::MergeToolConfigError;
@@ -174,10 +184,13 @@ use crate::operation_templater::OperationTemplateLanguage;
 use crate::operation_templater::OperationTemplateLanguageExtension;
 use crate::revset_util;
 use crate::revset_util::RevsetExpressionEvaluator;
+use crate::temp_files;
 use crate::template_builder;
 use crate::template_builder::TemplateLanguage;
 use crate::template_parser::TemplateAliasesMap;
 use crate::template_parser::TemplateDiagnostics;
+use crate::template_parser::TemplateParseError;
+use crate::template_parser::TemplateParseErrorKind;
 use crate::templater::TemplateRenderer;
 use crate::templater::WrapTemplateProperty;
 use crate::text_util;
@@ -395,8 +408,8 @@ impl CommandHelper {
     {
         let mut diagnostics = TemplateDiagnostics::new();
         let aliases = load_template_aliases(ui, self.settings().config())?;
-        let template =
-            template_builder::parse(language, &mut diagnostics, template_text, &aliases)?;
+        let template = template_builder::parse(language, &mut diagnostics, template_text, &aliases)
+            .map_err(|err| template_parse_error_with_settings_hint(err, self.settings()))?;
         print_parse_diagnostics(ui, "In template expression", &diagnostics)?;
         Ok(template)
     }
@@ -533,6 +546,7 @@ impl CommandHelper {
             Ok(op) => {
                 let repo = workspace.repo_loader().load_at(&op)?;
                 let mut workspace_command = self.for_workable_repo(ui, workspace, repo)?;
+                let old_wc_commit_id = workspace_command.get_wc_commit_id().unwrap().clone();
 
                 // Snapshot the current working copy on top of the last known working-copy
                 // operation, then merge the divergent operations. The wc_commit_id of the
@@ -544,18 +558,19 @@ impl CommandHelper {
 
                 let wc_commit_id = workspace_command.get_wc_commit_id().unwrap();
                 let repo = workspace_command.repo().clone();
+                let old_wc_commit = repo.store().get_commit(&old_wc_commit_id)?;
                 let stale_wc_commit = repo.store().get_commit(wc_commit_id)?;
 
                 let mut workspace_command = self.workspace_helper_no_snapshot(ui)?;
                 let checkout_options = workspace_command.checkout_options();
 
-                let repo = workspace_command.repo().clone();
+                let mut tx = workspace_command.start_transaction().into_inner();
                 let (mut locked_ws, desired_wc_commit) =
                     workspace_command.unchecked_start_working_copy_mutation()?;
                 match WorkingCopyFreshness::check_stale(
                     locked_ws.locked_wc(),
                     &desired_wc_commit,
-                    &repo,
+                    tx.base_repo(),
                 )? {
                     WorkingCopyFreshness::Fresh | WorkingCopyFreshness::Updated(_) => {
                         writeln!(
@@ -565,6 +580,18 @@ impl CommandHelper {
                     }
                     WorkingCopyFreshness::WorkingCopyStale
                     | WorkingCopyFreshness::SiblingOperation => {
+                        let desired_wc_commit = reconcile_stale_working_copy_changes(
+                            &mut tx,
+                            &old_wc_commit,
+                            &stale_wc_commit,
+                            &desired_wc_commit,
+                        )?;
+                        let repo = if tx.repo().has_changes() {
+                            tx.repo_mut().rebase_descendants()?;
+                            tx.commit("reconcile stale working copy")?
+                        } else {
+                            tx.base_repo().clone()
+                        };
                         let stats = update_stale_working_copy(
                             locked_ws,
                             repo.op_id().clone(),
@@ -572,6 +599,7 @@ impl CommandHelper {
                             &desired_wc_commit,
                             &checkout_options,
                         )?;
+                        workspace_command.user_repo = ReadonlyUserRepo::new(repo);
                         workspace_command.print_updated_working_copy_stats(
                             ui,
                             Some(&stale_wc_commit),
@@ -581,7 +609,7 @@ impl CommandHelper {
                         writeln!(
                             ui.status(),
                             "Updated working copy to fresh commit {}",
-                            short_commit_hash(desired_wc_commit.id())
+                            workspace_command.format_commit_summary(&desired_wc_commit)
                         )?;
                     }
                 };
@@ -788,6 +816,7 @@ pub struct WorkspaceCommandEnvironment {
     immutable_heads_expression: Rc<UserRevsetExpression>,
     short_prefixes_expression: Option<Rc<UserRevsetExpression>>,
     conflict_marker_style: ConflictMarkerStyle,
+    track_empty_directories: bool,
 }
 
 impl WorkspaceCommandEnvironment {
@@ -799,6 +828,7 @@ impl WorkspaceCommandEnvironment {
         let path_converter = RepoPathUiConverter::Fs {
             cwd: command.cwd().to_owned(),
             base: workspace.workspace_root().to_owned(),
+            style: settings.get("ui.paths")?,
         };
         let mut env = Self {
             command: command.clone(),
@@ -810,7 +840,12 @@ impl WorkspaceCommandEnvironment {
             immutable_heads_expression: RevsetExpression::root(),
             short_prefixes_expression: None,
             conflict_marker_style: settings.get("ui.conflict-marker-style")?,
+            track_empty_directories: settings.get_bool("snapshot.track-empty-directories")?,
         };
+        if let Some(dir) = settings.get::<PathBuf>("ui.temp-dir").optional()? {
+            // Relative to the .jj directory, e.g. "tmp" for ".jj/tmp"
+            temp_files::set_temp_dir_root(workspace.workspace_root().join(".jj").join(dir));
+        }
         env.immutable_heads_expression = env.load_immutable_heads_expression(ui)?;
         env.short_prefixes_expression = env.load_short_prefixes_expression(ui)?;
         Ok(env)
@@ -873,6 +908,11 @@ impl WorkspaceCommandEnvironment {
         self.conflict_marker_style
     }
 
+    /// Whether empty directories are tracked by marker files
+    pub fn track_empty_directories(&self) -> bool {
+        self.track_empty_directories
+    }
+
     fn load_immutable_heads_expression(
         &self,
         ui: &Ui,
@@ -965,7 +1005,8 @@ impl WorkspaceCommandEnvironment {
             &mut diagnostics,
             template_text,
             &self.template_aliases_map,
-        )?;
+        )
+        .map_err(|err| template_parse_error_with_settings_hint(err, &self.settings))?;
         print_parse_diagnostics(ui, "In template expression", &diagnostics)?;
         Ok(template)
     }
@@ -1005,6 +1046,7 @@ pub struct WorkspaceCommandHelper {
     op_summary_template_text: String,
     may_update_working_copy: bool,
     working_copy_shared_with_git: bool,
+    repo_owned_by_other_user: bool,
 }
 
 enum SnapshotWorkingCopyError {
@@ -1044,6 +1086,8 @@ impl WorkspaceCommandHelper {
             loaded_at_head && !env.command.global_args().ignore_working_copy;
         let working_copy_shared_with_git =
             crate::git_util::is_colocated_git_workspace(&workspace, &repo);
+        let repo_owned_by_other_user = !env.command.global_args().allow_other_owner
+            && !file_util::is_owned_by_current_user(workspace.repo_path())?;
 
         let helper = Self {
             workspace,
@@ -1053,6 +1097,7 @@ impl WorkspaceCommandHelper {
             op_summary_template_text,
             may_update_working_copy,
             working_copy_shared_with_git,
+            repo_owned_by_other_user,
         };
         // Parse commit_summary template early to report error before starting
         // mutable operation.
@@ -1083,6 +1128,23 @@ impl WorkspaceCommandHelper {
         }
     }
 
+    /// Checks that the repo store is owned by the current user, so that writing
+    /// to it won't fail halfway or leave files owned by the wrong user.
+    pub fn check_repo_owner(&self) -> Result<(), CommandError> {
+        if self.repo_owned_by_other_user {
+            Err(user_error_with_hint(
+                format!(
+                    "The repository at {} is owned by another user",
+                    self.workspace.repo_path().display()
+                ),
+                "Run the command as the owner of the repository, or use --allow-other-owner \
+                 to modify it anyway.",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Note that unless you have a good reason not to do so, you should always
     /// call [`print_snapshot_stats`] with the [`SnapshotStats`] returned by
     /// this function to present possible untracked files to the user.
@@ -1091,6 +1153,7 @@ impl WorkspaceCommandHelper {
         if !self.may_update_working_copy {
             return Ok(SnapshotStats::default());
         }
+        self.check_repo_owner().map_err(snapshot_command_error)?;
 
         #[cfg(feature = "git")]
         if self.working_copy_shared_with_git {
@@ -1237,6 +1300,7 @@ impl WorkspaceCommandHelper {
     pub fn checkout_options(&self) -> CheckoutOptions {
         CheckoutOptions {
             conflict_marker_style: self.env.conflict_marker_style(),
+            track_empty_directories: self.env.track_empty_directories(),
         }
     }
 
@@ -1365,12 +1429,30 @@ to the current parents may contain changes from multiple commits.
             &RepoPathUiConverter::Fs {
                 cwd: "".into(),
                 base: "".into(),
+                style: UiPathStyle::default(),
             },
         )?;
         print_parse_diagnostics(ui, "In `snapshot.auto-track`", &diagnostics)?;
         Ok(expression.to_matcher())
     }
 
+    /// Matcher of the files configured as generated by `diff.generated-files`.
+    pub fn generated_files_matcher(&self, ui: &Ui) -> Result<Box<dyn Matcher>, CommandError> {
+        let mut diagnostics = FilesetDiagnostics::new();
+        let pattern = self.settings().get_string("diff.generated-files")?;
+        let expression = fileset::parse(
+            &mut diagnostics,
+            &pattern,
+            &RepoPathUiConverter::Fs {
+                cwd: "".into(),
+                base: "".into(),
+                style: UiPathStyle::default(),
+            },
+        )?;
+        print_parse_diagnostics(ui, "In `diff.generated-files`", &diagnostics)?;
+        Ok(expression.to_matcher())
+    }
+
     pub fn snapshot_options_with_start_tracking_matcher<'a>(
         &self,
         start_tracking_matcher: &'a dyn Matcher,
@@ -1384,6 +1466,10 @@ to the current parents may contain changes from multiple commits.
             max_new_file_size = u64::MAX;
         }
         let conflict_marker_style = self.env.conflict_marker_style();
+        let track_empty_directories = self.env.track_empty_directories();
+        let detect_conflict_markers = self
+            .settings()
+            .get_bool("snapshot.detect-conflict-markers")?;
         Ok(SnapshotOptions {
             base_ignores,
             fsmonitor_settings,
@@ -1391,6 +1477,9 @@ to the current parents may contain changes from multiple commits.
             start_tracking_matcher,
             max_new_file_size,
             conflict_marker_style,
+            track_empty_directories,
+            detect_conflict_markers,
+            dry_run: false,
         })
     }
 
@@ -1659,6 +1748,49 @@ to the current parents may contain changes from multiple commits.
         Ok(self.attach_revset_evaluator(expression))
     }
 
+    /// Parses the given revsets and the whitespace-separated list of exact
+    /// commit or change IDs in `revisions_file` (if any), and returns the
+    /// union of them. A relative `revisions_file` is resolved against the
+    /// current directory.
+    ///
+    /// Unlike revset symbols, the IDs from the file must be full-length so
+    /// that they can be resolved in one index lookup.
+    pub fn parse_union_revsets_and_file(
+        &self,
+        ui: &Ui,
+        revision_args: &[RevisionArg],
+        revisions_file: Option<&Path>,
+    ) -> Result<RevsetExpressionEvaluator<'_>, CommandError> {
+        let evaluator = self.parse_union_revsets(ui, revision_args)?;
+        let Some(path) = revisions_file else {
+            return Ok(evaluator);
+        };
+        let text = fs::read_to_string(self.env.command.cwd().join(path)).map_err(|err| {
+            user_error_with_message(format!("Failed to read {}", path.display()), err)
+        })?;
+        let mut commit_ids = vec![];
+        let mut change_ids = vec![];
+        for value in text.split_whitespace() {
+            if let Some(id) = CommitId::try_from_hex(value) {
+                commit_ids.push(id);
+            } else if let Some(id) = ChangeId::try_from_reverse_hex(value) {
+                change_ids.push(id);
+            } else {
+                return Err(user_error(format!(
+                    "Invalid commit or change ID {value:?} in {}",
+                    path.display()
+                )));
+            }
+        }
+        let ids_expression = RevsetExpression::exact_ids(commit_ids, change_ids);
+        let expression = if revision_args.is_empty() {
+            ids_expression
+        } else {
+            evaluator.expression().union(&ids_expression)
+        };
+        Ok(self.attach_revset_evaluator(expression))
+    }
+
     pub fn attach_revset_evaluator(
         &self,
         expression: Rc<UserRevsetExpression>,
@@ -1839,6 +1971,52 @@ to the current parents may contain changes from multiple commits.
         Err(error)
     }
 
+    /// Asks for confirmation if rewriting the given commits and their
+    /// descendants would touch more than `ui.rewrite-confirmation-threshold`
+    /// commits.
+    ///
+    /// This should be called before the transaction is started. If `yes` is
+    /// true, or if the threshold is 0, no confirmation is needed. In a
+    /// non-interactive session, the default answer "no" is taken.
+    pub fn confirm_rewrite<'a>(
+        &self,
+        ui: &Ui,
+        commits: impl IntoIterator<Item = &'a CommitId>,
+        yes: bool,
+    ) -> Result<(), CommandError> {
+        if yes {
+            return Ok(());
+        }
+        let threshold: usize = self.settings().get("ui.rewrite-confirmation-threshold")?;
+        if threshold == 0 {
+            return Ok(());
+        }
+        let commit_ids = commits.into_iter().cloned().collect_vec();
+        // Stop counting once the threshold is exceeded.
+        let num_rewritten = RevsetExpression::commits(commit_ids)
+            .descendants()
+            .evaluate(self.repo().as_ref())?
+            .iter()
+            .take(threshold + 1)
+            .process_results(|iter| iter.count())?;
+        if num_rewritten <= threshold {
+            return Ok(());
+        }
+        writeln!(
+            ui.warning_default(),
+            "This operation would rewrite more than {threshold} commits \
+             (`ui.rewrite-confirmation-threshold`)."
+        )?;
+        if !ui.prompt_yes_no("Do you want to continue?", Some(false))? {
+            return Err(user_error_with_hint(
+                "Aborted rewriting commits",
+                "Pass --yes to skip this confirmation, or raise \
+                 `ui.rewrite-confirmation-threshold`.",
+            ));
+        }
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     fn snapshot_working_copy(
         &mut self,
@@ -1864,6 +2042,10 @@ to the current parents may contain changes from multiple commits.
         let options = self
             .snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)
             .map_err(snapshot_command_error)?;
+        let report_snapshot = self
+            .settings()
+            .get_bool("ui.report-snapshot")
+            .map_err(snapshot_command_error)?;
 
         // Compare working-copy tree and operation with repo's, and reload as needed.
         let mut locked_ws = self
@@ -1901,14 +2083,23 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                     ));
                 }
                 Ok(WorkingCopyFreshness::SiblingOperation) => {
-                    return Err(SnapshotWorkingCopyError::StaleWorkingCopy(internal_error(
-                        format!(
-                            "The repo was loaded at operation {}, which seems to be a sibling of \
-                             the working copy's operation {}",
-                            short_operation_hash(repo.op_id()),
-                            short_operation_hash(&old_op_id)
+                    // This can happen if the operation log was rewritten (e.g. by
+                    // `jj op abandon --ignore-working-copy`) or synced from
+                    // another machine. The working copy can be reconciled the
+                    // same way as a stale one.
+                    return Err(SnapshotWorkingCopyError::StaleWorkingCopy(
+                        user_error_with_hint(
+                            format!(
+                                "The working copy was updated at operation {}, which is not an \
+                                 ancestor of the current operation {}.",
+                                short_operation_hash(&old_op_id),
+                                short_operation_hash(repo.op_id())
+                            ),
+                            "Run `jj workspace update-stale` to update it.
+See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
+                             for more information.",
                         ),
-                    )));
+                    ));
                 }
                 Err(OpStoreError::ObjectNotFound { .. }) => {
                     return Err(SnapshotWorkingCopyError::StaleWorkingCopy(
@@ -1932,6 +2123,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 .snapshot(&options)
                 .map_err(snapshot_command_error)?
         };
+        let mut snapshotted_commits = None;
         if new_tree_id != *wc_commit.tree_id() {
             let mut tx =
                 start_repo_transaction(&self.user_repo.repo, self.env.command.string_args());
@@ -1945,6 +2137,9 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             mut_repo
                 .set_wc_commit(workspace_name, commit.id().clone())
                 .map_err(snapshot_command_error)?;
+            if report_snapshot {
+                print_snapshot_changes(ui, &stats, &commit).map_err(snapshot_command_error)?;
+            }
 
             // Rebase descendants
             let num_rebased = mut_repo
@@ -1978,10 +2173,22 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 .commit("snapshot working copy")
                 .map_err(snapshot_command_error)?;
             self.user_repo = ReadonlyUserRepo::new(repo);
+            snapshotted_commits = Some((wc_commit, commit));
         }
         locked_ws
             .finish(self.user_repo.repo.op_id().clone())
             .map_err(snapshot_command_error)?;
+        if let Some((old_commit, new_commit)) = &snapshotted_commits {
+            run_working_copy_hook(
+                ui,
+                self.settings(),
+                self.workspace_root(),
+                WorkingCopyHook::PostSnapshot,
+                Some(old_commit),
+                new_commit,
+            )
+            .map_err(snapshot_command_error)?;
+        }
         Ok(stats)
     }
 
@@ -2000,7 +2207,15 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             new_commit,
             &checkout_options,
         )?;
-        self.print_updated_working_copy_stats(ui, maybe_old_commit, new_commit, &stats)
+        self.print_updated_working_copy_stats(ui, maybe_old_commit, new_commit, &stats)?;
+        run_working_copy_hook(
+            ui,
+            self.settings(),
+            self.workspace_root(),
+            WorkingCopyHook::PostCheckout,
+            maybe_old_commit,
+            new_commit,
+        )
     }
 
     fn print_updated_working_copy_stats(
@@ -2057,6 +2272,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         mut tx: Transaction,
         description: impl Into<String>,
     ) -> Result<(), CommandError> {
+        self.check_repo_owner()?;
         if !tx.repo().has_changes() {
             writeln!(ui.status(), "Nothing changed.")?;
             return Ok(());
@@ -2083,6 +2299,8 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             }
         }
 
+        run_pre_commit_rewrite_hook(self.settings(), self.workspace_root(), tx.repo())?;
+
         let old_repo = tx.base_repo().clone();
 
         let maybe_old_wc_commit = old_repo
@@ -2097,6 +2315,17 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             .map(|commit_id| tx.repo().store().get_commit(commit_id))
             .transpose()?;
 
+        // Checking out a conflicted commit materializes conflict markers over
+        // the files, so keep a reference to what was there before.
+        if let (Some(old_commit), Some(new_commit)) = (&maybe_old_wc_commit, &maybe_new_wc_commit) {
+            if old_commit.id() != new_commit.id() && new_commit.has_conflict()? {
+                tx.set_tag(
+                    working_copy_backup_tag(self.workspace_name()),
+                    old_commit.id().hex(),
+                );
+            }
+        }
+
         #[cfg(feature = "git")]
         if self.working_copy_shared_with_git {
             use std::error::Error as _;
@@ -2131,6 +2360,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         }
 
         self.report_repo_changes(ui, &old_repo)?;
+        self.report_new_divergence_and_orphans(ui, &old_repo)?;
 
         let settings = self.settings();
         let missing_user_name = settings.user_name().is_empty();
@@ -2217,7 +2447,7 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         new_conflicts_by_change_id
             .retain(|change_id, _commits| !removed_conflicts_by_change_id.contains_key(change_id));
 
-        // TODO: Also report new divergence and maybe resolved divergence
+        // TODO: Maybe report resolved divergence
         if !resolved_conflicts_by_change_id.is_empty() {
             // TODO: Report resolved and abandoned numbers separately. However,
             // that involves resolving the change_id among the visible commits in the new
@@ -2275,6 +2505,128 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         Ok(())
     }
 
+    /// Warns about changes that became divergent, and about commits that were
+    /// created on top of hidden commits, since the previous operation (when
+    /// `old_repo` was loaded).
+    ///
+    /// Only the commits added by the operation are checked, so divergence that
+    /// already existed isn't reported again.
+    fn report_new_divergence_and_orphans(
+        &self,
+        ui: &Ui,
+        old_repo: &Arc<ReadonlyRepo>,
+    ) -> Result<(), CommandError> {
+        let new_repo = self.repo().as_ref();
+        let old_heads =
+            RevsetExpression::commits(old_repo.view().heads().iter().cloned().collect());
+        let new_heads =
+            RevsetExpression::commits(new_repo.view().heads().iter().cloned().collect());
+        let added_commits: IndexMap<CommitId, ChangeId> = old_heads
+            .range(&new_heads)
+            .evaluate(new_repo)?
+            .commit_change_ids()
+            .try_collect()?;
+        self.report_new_divergence(ui, old_repo, added_commits.values())?;
+        self.report_new_orphans(ui, old_repo, &added_commits)?;
+        Ok(())
+    }
+
+    fn report_new_divergence<'a>(
+        &self,
+        ui: &Ui,
+        old_repo: &ReadonlyRepo,
+        added_change_ids: impl IntoIterator<Item = &'a ChangeId>,
+    ) -> Result<(), CommandError> {
+        let new_repo = self.repo().as_ref();
+        let added_change_ids: IndexSet<&ChangeId> = added_change_ids.into_iter().collect();
+        // The newest commit of each change is listed.
+        let mut divergent_commits = vec![];
+        for change_id in &added_change_ids {
+            let Some(commit_ids) = new_repo.resolve_change_id(change_id) else {
+                continue;
+            };
+            let was_divergent = old_repo
+                .resolve_change_id(change_id)
+                .is_some_and(|old_ids| old_ids.len() > 1);
+            if commit_ids.len() > 1 && !was_divergent {
+                let newest_id = commit_ids.last().unwrap();
+                divergent_commits.push(new_repo.store().get_commit(newest_id)?);
+            }
+        }
+        if divergent_commits.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            ui.warning_default(),
+            "The operation made {} change{} divergent:",
+            divergent_commits.len(),
+            if divergent_commits.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        )?;
+        print_updated_commits(
+            ui.stderr_formatter().as_mut(),
+            &self.commit_summary_template(),
+            &divergent_commits,
+        )?;
+        writeln!(
+            ui.hint_default(),
+            "Use `jj evolve` to merge the commits of each divergent change, or abandon the \
+             unneeded commits with `jj abandon <commit ID>`."
+        )?;
+        Ok(())
+    }
+
+    /// Warns about new commits whose parents were hidden before the operation,
+    /// e.g. fetched commits based on a commit that has since been rewritten or
+    /// abandoned locally.
+    fn report_new_orphans(
+        &self,
+        ui: &Ui,
+        old_repo: &ReadonlyRepo,
+        added_commits: &IndexMap<CommitId, ChangeId>,
+    ) -> Result<(), CommandError> {
+        let new_repo = self.repo().as_ref();
+        // Commits that existed before the operation but weren't visible.
+        let is_revived = |id: &CommitId| -> bool {
+            added_commits.contains_key(id) && old_repo.index().has_id(id)
+        };
+        let mut orphaned_commits = vec![];
+        for commit_id in added_commits.keys() {
+            if old_repo.index().has_id(commit_id) {
+                continue;
+            }
+            let commit = new_repo.store().get_commit(commit_id)?;
+            if commit.parent_ids().iter().any(is_revived) {
+                orphaned_commits.push(commit);
+            }
+        }
+        if orphaned_commits.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(
+            ui.warning_default(),
+            "The operation added {} commits on top of hidden commits:",
+            orphaned_commits.len()
+        )?;
+        print_updated_commits(
+            ui.stderr_formatter().as_mut(),
+            &self.commit_summary_template(),
+            &orphaned_commits,
+        )?;
+        writeln!(
+            ui.hint_default(),
+            "The hidden commits were rewritten or abandoned. If their changes are now divergent, \
+             `jj evolve` merges them and rebases the new commits. Otherwise, use `jj rebase -s \
+             <commit ID> -d <destination>` to move the new commits."
+        )?;
+        Ok(())
+    }
+
     pub fn report_repo_conflicts(
         &self,
         fmt: &mut dyn Formatter,
@@ -2413,6 +2765,30 @@ impl WorkspaceCommandTransaction<'_> {
         self.tx.repo_mut()
     }
 
+    /// Attaches a tag to the operation that will be created by this
+    /// transaction.
+    pub fn set_tag(&mut self, key: String, value: String) {
+        self.tx.set_tag(key, value);
+    }
+
+    /// Records this workspace's working-copy commit, as of the start of the
+    /// transaction, in the metadata of the operation to be created. Commands
+    /// that may discard the working-copy contents call this so `jj op restore
+    /// --working-copy-only` can find them without undoing anything else.
+    pub fn record_working_copy_backup(&mut self) {
+        let name = self.helper.workspace_name();
+        if let Some(wc_commit_id) = self.tx.base_repo().view().get_wc_commit_id(name) {
+            let value = wc_commit_id.hex();
+            self.tx.set_tag(working_copy_backup_tag(name), value);
+        }
+    }
+
+    /// Reverts the changes made by `op` on top of the current state.
+    pub fn revert_operation(&mut self, op: &Operation) -> Result<(), RevertOperationError> {
+        self.id_prefix_context.take(); // invalidate
+        self.tx.revert_operation(op)
+    }
+
     pub fn check_out(&mut self, commit: &Commit) -> Result<Commit, CheckOutCommitError> {
         let name = self.helper.workspace_name().to_owned();
         self.id_prefix_context.take(); // invalidate
@@ -2497,6 +2873,12 @@ impl WorkspaceCommandTransaction<'_> {
     }
 }
 
+/// Operation tag under which the working-copy commit of the given workspace is
+/// recorded before its contents may be discarded.
+pub fn working_copy_backup_tag(name: &WorkspaceName) -> String {
+    format!("working-copy-backup.{}", name.as_str())
+}
+
 pub fn find_workspace_dir(cwd: &Path) -> &Path {
     cwd.ancestors()
         .find(|path| path.join(".jj").is_dir())
@@ -2535,6 +2917,15 @@ jj git init --colocate",
             err @ (StoreLoadError::ReadError { .. } | StoreLoadError::Backend(_)),
         ) => internal_error_with_message("The repository appears broken or inaccessible", err),
         WorkspaceLoadError::StoreLoadError(StoreLoadError::Signing(err)) => user_error(err),
+        WorkspaceLoadError::StoreLoadError(StoreLoadError::Migration(
+            err @ StoreMigrationError::UnsupportedVersion { .. },
+        )) => user_error_with_hint(
+            err,
+            "The repository was written by a newer version of jj. Upgrade jj to use it.",
+        ),
+        WorkspaceLoadError::StoreLoadError(StoreLoadError::Migration(err)) => {
+            internal_error_with_message("Failed to upgrade the repository format", err)
+        }
         WorkspaceLoadError::WorkingCopyState(err) => internal_error(err),
         WorkspaceLoadError::DecodeRepoPath(_) | WorkspaceLoadError::Path(_) => user_error(err),
     }
@@ -2570,6 +2961,54 @@ pub fn start_repo_transaction(repo: &Arc<ReadonlyRepo>, string_args: &[String])
     tx
 }
 
+/// Carries the changes snapshotted from a stale working copy over to the
+/// rewritten working-copy commit, and returns the commit to check out.
+///
+/// If the working-copy commit was rewritten concurrently (e.g. described or
+/// rebased), the snapshot becomes a divergent commit when the operations are
+/// merged. The snapshotted changes are then moved into the rewritten commit,
+/// leaving any conflicts in it, and the divergent commit is abandoned.
+fn reconcile_stale_working_copy_changes(
+    tx: &mut Transaction,
+    old_wc_commit: &Commit,
+    snapshot_commit: &Commit,
+    desired_wc_commit: &Commit,
+) -> Result<Commit, CommandError> {
+    if snapshot_commit.tree_id() == old_wc_commit.tree_id() {
+        return Ok(desired_wc_commit.clone());
+    }
+    // The snapshot commit may have been rebased while merging operations.
+    let repo = tx.base_repo().clone();
+    let divergent_commit = repo
+        .resolve_change_id(desired_wc_commit.change_id())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|id| id != desired_wc_commit.id())
+        .map(|id| repo.store().get_commit(&id))
+        .filter_ok(|commit| {
+            commit.id() == snapshot_commit.id()
+                || commit
+                    .store_commit()
+                    .predecessors
+                    .contains(snapshot_commit.id())
+        })
+        .next()
+        .transpose()?;
+    let Some(divergent_commit) = divergent_commit else {
+        return Ok(desired_wc_commit.clone());
+    };
+    let new_tree = desired_wc_commit
+        .tree()?
+        .merge(&old_wc_commit.tree()?, &snapshot_commit.tree()?)?;
+    let new_commit = tx
+        .repo_mut()
+        .rewrite_commit(desired_wc_commit)
+        .set_tree_id(new_tree.id())
+        .write()?;
+    tx.repo_mut().record_abandoned_commit(&divergent_commit);
+    Ok(new_commit)
+}
+
 fn update_stale_working_copy(
     mut locked_ws: LockedWorkspace,
     op_id: OperationId,
@@ -2759,6 +3198,18 @@ pub fn print_snapshot_stats(
 ) -> io::Result<()> {
     print_untracked_files(ui, &stats.untracked_paths, path_converter)?;
 
+    if !stats.conflict_marker_paths.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "Some resolved files still contain conflict markers:"
+        )?;
+        let mut formatter = ui.stderr_formatter();
+        for path in &stats.conflict_marker_paths {
+            let ui_path = path_converter.format_file_path(path);
+            writeln!(formatter, "  {ui_path}")?;
+        }
+    }
+
     let large_files_sizes = stats
         .untracked_paths
         .values()
@@ -2782,6 +3233,20 @@ pub fn print_snapshot_stats(
     Ok(())
 }
 
+fn print_snapshot_changes(ui: &Ui, stats: &SnapshotStats, commit: &Commit) -> io::Result<()> {
+    writeln!(
+        ui.status(),
+        "Snapshotted working copy into {}: added {} file{}, modified {} file{}, removed {} file{}",
+        short_commit_hash(commit.id()),
+        stats.added_files,
+        if stats.added_files == 1 { "" } else { "s" },
+        stats.modified_files,
+        if stats.modified_files == 1 { "" } else { "s" },
+        stats.removed_files,
+        if stats.removed_files == 1 { "" } else { "s" },
+    )
+}
+
 pub fn print_checkout_stats(
     ui: &Ui,
     stats: &CheckoutStats,
@@ -3212,6 +3677,18 @@ pub struct GlobalArgs {
     /// `immutable_heads()` revset or the `immutable` template keyword.
     #[arg(long, global = true)]
     pub ignore_immutable: bool,
+    /// Allow modifying a repository owned by another user
+    ///
+    /// By default, Jujutsu refuses to snapshot the working copy or record new
+    /// operations if the repository's `.jj/repo` directory is owned by another
+    /// user, e.g. a repository created with `sudo` or by a shared service
+    /// account. Such commands would otherwise fail halfway with permission
+    /// errors, or create files the owner can't modify.
+    ///
+    /// Use `--ignore-working-copy` to run read-only commands without this
+    /// option.
+    #[arg(long, global = true)]
+    pub allow_other_owner: bool,
     /// Operation to load the repo at
     ///
     /// Operation to load the repo at. By default, Jujutsu loads the repo at the
@@ -3480,6 +3957,100 @@ fn resolve_aliases(
     }
 }
 
+/// Version of the environment passed to external commands. External commands
+/// should check `JJ_EXTERNAL_COMMAND_VERSION` before relying on the other
+/// variables.
+const EXTERNAL_COMMAND_VERSION: &str = "1";
+
+/// Unknown subcommand to be dispatched to a `jj-<name>` executable.
+#[derive(Clone, Debug)]
+struct ExternalCommand {
+    name: String,
+    args: Vec<String>,
+    /// Value of the `--repository` argument.
+    repository: Option<String>,
+}
+
+/// Returns the subcommand if it's neither a built-in command nor an alias.
+/// Aliases must have been resolved already.
+fn find_external_command(app: &Command, string_args: &[String]) -> Option<ExternalCommand> {
+    let matches = app
+        .clone()
+        .allow_external_subcommands(true)
+        .try_get_matches_from(string_args)
+        .ok()?;
+    let (name, submatches) = matches.subcommand()?;
+    if app.find_subcommand(name).is_some() {
+        return None;
+    }
+    let args = submatches
+        .get_many::<OsString>("")
+        .unwrap_or_default()
+        .map(|arg| arg.to_str().unwrap().to_owned())
+        .collect();
+    Some(ExternalCommand {
+        name: name.to_owned(),
+        args,
+        repository: matches.get_one::<String>("repository").cloned(),
+    })
+}
+
+/// Runs the `jj-<name>` executable found in `PATH`. Returns `None` if there's
+/// no such executable.
+///
+/// The external command can find the workspace and the `jj` executable to call
+/// back into through environment variables.
+fn run_external_command(
+    command: &ExternalCommand,
+    workspace_loader: Option<&dyn WorkspaceLoader>,
+) -> Option<Result<(), CommandError>> {
+    let program = format!("jj-{}", command.name);
+    let mut cmd = std::process::Command::new(&program);
+    cmd.args(&command.args);
+    cmd.env("JJ_EXTERNAL_COMMAND_VERSION", EXTERNAL_COMMAND_VERSION);
+    cmd.env("JJ_VERSION", env!("CARGO_PKG_VERSION"));
+    if let Ok(exe) = env::current_exe() {
+        cmd.env("JJ_EXE", exe);
+    }
+    if let Some(loader) = workspace_loader {
+        cmd.env("JJ_WORKSPACE_ROOT", loader.workspace_root());
+        cmd.env("JJ_REPO_PATH", loader.repo_path());
+    }
+    tracing::info!(?cmd, "running external command");
+    let status = match cmd.status() {
+        Ok(status) => status,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+        Err(err) => {
+            return Some(Err(user_error_with_message(
+                format!("Failed to execute external command '{program}'"),
+                err,
+            )));
+        }
+    };
+    if status.success() {
+        return Some(Ok(()));
+    }
+    // Pass on the exit status as if the external command were run directly.
+    let exit_code = status.code().or_else(|| {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt as _;
+            status.signal().map(|signal| 128 + signal)
+        }
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    });
+    if let Some(code) = exit_code.and_then(|code| u8::try_from(code).ok()) {
+        Some(Err(user_error(ChildExitError { code })))
+    } else {
+        Some(Err(user_error(format!(
+            "External command '{program}' was terminated by: {status}"
+        ))))
+    }
+}
+
 /// Parse args that must be interpreted early, e.g. before printing help.
 fn parse_early_args(
     app: &Command,
@@ -3896,6 +4467,26 @@ impl<'a> CliRunner<'a> {
             warn_if_args_mismatch(ui, &self.app, &config, &string_args)?;
         }
 
+        if let Some(external_command) = find_external_command(&self.app, &string_args) {
+            let external_loader = if let Some(path) = &external_command.repository {
+                let abs_path = cwd.join(path);
+                let abs_path = dunce::canonicalize(&abs_path).unwrap_or(abs_path);
+                let loader = self
+                    .workspace_loader_factory
+                    .create(&abs_path)
+                    .map_err(|err| map_workspace_load_error(err, Some(path)))?;
+                Some(loader)
+            } else {
+                None
+            };
+            let workspace_loader = external_loader
+                .as_deref()
+                .or_else(|| maybe_cwd_workspace_loader.as_deref().ok());
+            if let Some(result) = run_external_command(&external_command, workspace_loader) {
+                return result;
+            }
+        }
+
         let (matches, args) = parse_args(&self.app, &string_args)
             .map_err(|err| map_clap_cli_error(err, ui, &config))?;
         if args.global_args.debug {
@@ -4047,6 +4638,30 @@ fn format_template_aliases_hint(template_aliases: &TemplateAliasesMap) -> String
     hint
 }
 
+/// Adds a hint if an unknown template keyword is the name of a
+/// `templates.<name>` setting, which can't be referenced from templates.
+fn template_parse_error_with_settings_hint(
+    err: TemplateParseError,
+    settings: &UserSettings,
+) -> CommandError {
+    let hint = match err.kind() {
+        TemplateParseErrorKind::NoSuchKeyword { name, .. }
+            if settings.get_string(["templates", name.as_str()]).is_ok() =>
+        {
+            Some(format!(
+                "`templates.{name}` is a command setting, not a template alias. Define \
+                 `template-aliases.{name}` to use it as a named template."
+            ))
+        }
+        _ => None,
+    };
+    let mut cmd_err = CommandError::from(err);
+    if let Some(hint) = hint {
+        cmd_err.add_hint(hint);
+    }
+    cmd_err
+}
+
 // If -R or --config* is specified, check if the expanded arguments differ.
 fn warn_if_args_mismatch(
     ui: &Ui,
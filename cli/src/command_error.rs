@@ -23,6 +23,7 @@ use std::sync::Arc;
 use itertools::Itertools as _;
 use jj_lib::absorb::AbsorbError;
 use jj_lib::backend::BackendError;
+use jj_lib::bisect::BisectionError;
 use jj_lib::config::ConfigFileSaveError;
 use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigLoadError;
@@ -50,8 +51,10 @@ use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetParseError;
 use jj_lib::revset::RevsetParseErrorKind;
 use jj_lib::revset::RevsetResolutionError;
+use jj_lib::rewrite::DivergentCommitsMergeError;
 use jj_lib::str_util::StringPatternParseError;
 use jj_lib::trailer::TrailerParseError;
+use jj_lib::transaction::RevertOperationError;
 use jj_lib::transaction::TransactionCommitError;
 use jj_lib::view::RenameWorkspaceError;
 use jj_lib::working_copy::RecoverWorkspaceError;
@@ -174,6 +177,14 @@ impl ErrorWithMessage {
     }
 }
 
+/// Exit status of a child process which has reported its failure by itself.
+/// `jj` exits with the same code without printing anything.
+#[derive(Debug, Error)]
+#[error("Child process exited with {code}")]
+pub struct ChildExitError {
+    pub code: u8,
+}
+
 pub fn user_error(err: impl Into<Box<dyn error::Error + Send + Sync>>) -> CommandError {
     CommandError::new(CommandErrorKind::User, err)
 }
@@ -363,7 +374,9 @@ impl From<OpHeadResolutionError> for CommandError {
     fn from(err: OpHeadResolutionError) -> Self {
         match err {
             OpHeadResolutionError::NoHeads => {
-                internal_error_with_message("Corrupt repository", err)
+                let mut cmd_err = internal_error_with_message("Corrupt repository", err);
+                cmd_err.add_hint("Run `jj op recover` to restore the operation heads.");
+                cmd_err
             }
         }
     }
@@ -409,6 +422,19 @@ impl From<ResetError> for CommandError {
     }
 }
 
+impl From<RevertOperationError> for CommandError {
+    fn from(err: RevertOperationError) -> Self {
+        match err {
+            RevertOperationError::RootOperation | RevertOperationError::MergeOperation => {
+                user_error(err)
+            }
+            RevertOperationError::Backend(err) => err.into(),
+            RevertOperationError::OpStore(err) => err.into(),
+            RevertOperationError::RepoLoader(err) => err.into(),
+        }
+    }
+}
+
 impl From<TransactionCommitError> for CommandError {
     fn from(err: TransactionCommitError) -> Self {
         internal_error(err)
@@ -517,6 +543,7 @@ impl From<TrailerParseError> for CommandError {
 
 #[cfg(feature = "git")]
 mod git {
+    use jj_lib::git::GitBackupRefsError;
     use jj_lib::git::GitExportError;
     use jj_lib::git::GitFetchError;
     use jj_lib::git::GitImportError;
@@ -555,6 +582,12 @@ jj currently does not support partial clones. To use jj with this repository, tr
         }
     }
 
+    impl From<GitBackupRefsError> for CommandError {
+        fn from(err: GitBackupRefsError) -> Self {
+            user_error_with_message("Failed to update backup refs in underlying Git repo", err)
+        }
+    }
+
     impl From<GitFetchError> for CommandError {
         fn from(err: GitFetchError) -> Self {
             if let GitFetchError::InvalidBranchPattern(pattern) = &err {
@@ -718,6 +751,26 @@ impl From<AbsorbError> for CommandError {
     }
 }
 
+impl From<BisectionError> for CommandError {
+    fn from(err: BisectionError) -> Self {
+        match err {
+            BisectionError::Backend(err) => err.into(),
+            BisectionError::RevsetEvaluation(err) => err.into(),
+        }
+    }
+}
+
+impl From<DivergentCommitsMergeError> for CommandError {
+    fn from(err: DivergentCommitsMergeError) -> Self {
+        match err {
+            DivergentCommitsMergeError::NoCommonPredecessor(_)
+            | DivergentCommitsMergeError::NotDivergent => user_error(err),
+            DivergentCommitsMergeError::WalkPredecessors(err) => err.into(),
+            DivergentCommitsMergeError::Backend(err) => err.into(),
+        }
+    }
+}
+
 impl From<FixError> for CommandError {
     fn from(err: FixError) -> Self {
         match err {
@@ -917,6 +970,9 @@ fn try_handle_command_result(ui: &mut Ui, result: Result<(), CommandError>) -> i
     };
     let err = &cmd_err.error;
     let hints = &cmd_err.hints;
+    if let Some(ChildExitError { code }) = err.downcast_ref() {
+        return Ok(*code);
+    }
     match cmd_err.kind {
         CommandErrorKind::User => {
             print_error(ui, "Error: ", err, hints)?;
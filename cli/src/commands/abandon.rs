@@ -67,6 +67,11 @@ pub(crate) struct AbandonArgs {
     /// Do not modify the content of the children of the abandoned commits
     #[arg(long)]
     restore_descendants: bool,
+    /// Do not ask for confirmation when many commits would be rewritten
+    ///
+    /// See `ui.rewrite-confirmation-threshold`.
+    #[arg(long)]
+    yes: bool,
 }
 
 #[instrument(skip_all)]
@@ -107,8 +112,16 @@ pub(crate) fn cmd_abandon(
         return Ok(());
     }
     workspace_command.check_rewritable(&to_abandon)?;
+    workspace_command.confirm_rewrite(ui, &to_abandon, args.yes)?;
 
     let mut tx = workspace_command.start_transaction();
+    if tx
+        .base_workspace_helper()
+        .get_wc_commit_id()
+        .is_some_and(|id| to_abandon.contains(id))
+    {
+        tx.record_working_copy_backup();
+    }
     let options = RewriteRefsOptions {
         delete_abandoned_bookmarks: !args.retain_bookmarks,
     };
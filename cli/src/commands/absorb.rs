@@ -13,10 +13,14 @@
 // limitations under the License.
 
 use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
 use jj_lib::absorb::absorb_hunks;
 use jj_lib::absorb::split_hunks_to_trees;
 use jj_lib::absorb::AbsorbSource;
 use jj_lib::matchers::EverythingMatcher;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
 use pollster::FutureExt as _;
 use tracing::instrument;
 
@@ -62,6 +66,14 @@ pub(crate) struct AbsorbArgs {
     /// Move only changes to these paths (instead of all paths)
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+    /// Only display the revisions the changes would be absorbed into
+    #[arg(long)]
+    dry_run: bool,
+    /// Do not ask for confirmation when many commits would be rewritten
+    ///
+    /// See `ui.rewrite-confirmation-threshold`.
+    #[arg(long)]
+    yes: bool,
 }
 
 #[instrument(skip_all)]
@@ -93,6 +105,32 @@ pub(crate) fn cmd_absorb(
 
     workspace_command.check_rewritable(selected_trees.target_commits.keys())?;
 
+    if args.dry_run {
+        if let Some(mut formatter) = ui.status_formatter() {
+            let target_ids = selected_trees.target_commits.keys().cloned().collect_vec();
+            let target_commits: Vec<_> = RevsetExpression::commits(target_ids)
+                .evaluate(repo)?
+                .iter()
+                .commits(repo.store())
+                .try_collect()?;
+            if !target_commits.is_empty() {
+                writeln!(
+                    formatter,
+                    "Would absorb changes into {} revisions:",
+                    target_commits.len()
+                )?;
+                print_updated_commits(
+                    formatter.as_mut(),
+                    &workspace_command.commit_summary_template(),
+                    &target_commits,
+                )?;
+            }
+        }
+        writeln!(ui.status(), "Dry-run requested, not absorbing.")?;
+        return Ok(());
+    }
+    workspace_command.confirm_rewrite(ui, selected_trees.target_commits.keys(), args.yes)?;
+
     let mut tx = workspace_command.start_transaction();
     let stats = absorb_hunks(tx.repo_mut(), &source, selected_trees.target_commits)?;
 
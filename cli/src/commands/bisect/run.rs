@@ -0,0 +1,204 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::bisect::BisectionResult;
+use jj_lib::bisect::Bisector;
+use jj_lib::bisect::Evaluation;
+use jj_lib::bisect::NextStep;
+use jj_lib::commit::Commit;
+use jj_lib::object_id::ObjectId as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Run a command to find the first bad revision
+///
+/// The revisions that are descendants of the good revisions and ancestors of
+/// the bad revisions are bisected. In each step, a revision in the middle of
+/// the remaining range is checked out in a new working-copy commit, and the
+/// command is run. If the command exits with status 0, the revision is good.
+/// If it exits with status 125, the revision can't be tested and is skipped.
+/// Any other exit status below 128 means that the revision is bad. Other exit
+/// statuses, or termination by a signal, abort the bisection.
+///
+/// The good, bad, and skipped revisions found so far are recorded as tags of
+/// each operation in the operation log. When the bisection finishes, the first
+/// bad revision is checked out.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BisectRunArgs {
+    /// Revisions known to be good
+    #[arg(
+        long,
+        short,
+        required = true,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    good: Vec<RevisionArg>,
+    /// Revisions known to be bad
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    bad: Vec<RevisionArg>,
+    /// Command to run on each revision
+    #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+    command: Vec<String>,
+}
+
+pub(crate) fn cmd_bisect_run(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BisectRunArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let good_ids: Vec<_> = workspace_command
+        .parse_union_revsets(ui, &args.good)?
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+    let bad_ids: Vec<_> = workspace_command
+        .parse_union_revsets(ui, &args.bad)?
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+    if bad_ids.is_empty() {
+        return Err(user_error("No bad revisions to bisect"));
+    }
+
+    // The bisected commits all exist in the initial repo. The working-copy
+    // commits created while bisecting don't need to be visible to the
+    // bisector.
+    let repo = workspace_command.repo().clone();
+    let mut bisector = Bisector::new(repo.as_ref(), good_ids, bad_ids);
+    let result = loop {
+        let commit = match bisector.next_step()? {
+            NextStep::Evaluate(commit) => commit,
+            NextStep::Done(result) => break result,
+        };
+        check_out_commit(
+            ui,
+            &mut workspace_command,
+            &bisector,
+            &commit,
+            format!("bisect: check out commit {}", commit.id().hex()),
+        )?;
+        let evaluation = run_command(&args.command)?;
+        let verdict = match evaluation {
+            Evaluation::Good => "good",
+            Evaluation::Bad => "bad",
+            Evaluation::Skip => "skipped",
+        };
+        writeln!(ui.status(), "The revision is {verdict}.")?;
+        bisector.mark(commit.id().clone(), evaluation);
+    };
+
+    match result {
+        BisectionResult::Found(commits) => {
+            if let [commit] = commits.as_slice() {
+                check_out_commit(
+                    ui,
+                    &mut workspace_command,
+                    &bisector,
+                    commit,
+                    format!("bisect: check out first bad commit {}", commit.id().hex()),
+                )?;
+                let mut formatter = ui.stdout_formatter();
+                write!(formatter, "The first bad revision is: ")?;
+                workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            } else {
+                let mut formatter = ui.stdout_formatter();
+                writeln!(formatter, "The first bad revisions are:")?;
+                for commit in &commits {
+                    write!(formatter, "  ")?;
+                    workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                    writeln!(formatter)?;
+                }
+            }
+        }
+        BisectionResult::Indeterminate(commits) => {
+            let mut formatter = ui.stdout_formatter();
+            writeln!(
+                formatter,
+                "Could not find the first bad revision because some revisions were skipped. \
+                 It is one of:"
+            )?;
+            for commit in &commits {
+                write!(formatter, "  ")?;
+                workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks out `commit`, recording the results so far in the operation tags.
+fn check_out_commit(
+    ui: &Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    bisector: &Bisector,
+    commit: &Commit,
+    description: String,
+) -> Result<(), CommandError> {
+    // The command might have modified the working copy.
+    workspace_command.maybe_snapshot(ui)?;
+    let mut tx = workspace_command.start_transaction();
+    for (key, ids) in [
+        ("bisect-good", bisector.good_commits()),
+        ("bisect-bad", bisector.bad_commits()),
+        ("bisect-skipped", bisector.skipped_commits()),
+    ] {
+        let value = ids.iter().map(|id| id.hex()).sorted().join(" ");
+        tx.set_tag(key.to_owned(), value);
+    }
+    tx.check_out(commit)?;
+    tx.finish(ui, description)
+}
+
+fn run_command(command: &[String]) -> Result<Evaluation, CommandError> {
+    let (program, args) = command.split_first().expect("command should be required");
+    let status = std::process::Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|err| {
+            user_error_with_message(
+                format!("Failed to execute external command '{program}'"),
+                err,
+            )
+        })?;
+    match status.code() {
+        Some(0) => Ok(Evaluation::Good),
+        Some(125) => Ok(Evaluation::Skip),
+        Some(1..128) => Ok(Evaluation::Bad),
+        Some(exit_code) => Err(user_error(format!(
+            "External command exited with {exit_code}, aborting bisection"
+        ))),
+        None => Err(user_error(format!(
+            "External command was terminated by: {status}"
+        ))),
+    }
+}
@@ -53,9 +53,17 @@ use crate::ui::Ui;
 pub struct BookmarkListArgs {
     /// Show all tracking and non-tracking remote bookmarks including the ones
     /// whose targets are synchronized with the local bookmarks
-    #[arg(long, short, alias = "all")]
+    #[arg(long, short)]
     all_remotes: bool,
 
+    /// Show all local and remote bookmarks, and mark local bookmarks whose
+    /// targets differ from their tracked remote bookmarks as "(diverged)"
+    ///
+    /// This is like `--all-remotes`, but renders the bookmarks using the
+    /// `templates.bookmark_list_all` template by default.
+    #[arg(long)]
+    all: bool,
+
     /// Show all tracking and non-tracking remote bookmarks belonging
     /// to this remote
     ///
@@ -70,7 +78,7 @@ pub struct BookmarkListArgs {
     #[arg(
         long = "remote",
         value_name = "REMOTE",
-        conflicts_with_all = ["all_remotes"],
+        conflicts_with_all = ["all_remotes", "all"],
         value_parser = StringPattern::parse,
         add = ArgValueCandidates::new(complete::git_remotes),
     )]
@@ -78,11 +86,11 @@ pub struct BookmarkListArgs {
 
     /// Show remote tracked bookmarks only. Omits local Git-tracking bookmarks
     /// by default
-    #[arg(long, short, conflicts_with_all = ["all_remotes"])]
+    #[arg(long, short, conflicts_with_all = ["all_remotes", "all"])]
     tracked: bool,
 
     /// Show conflicted bookmarks only
-    #[arg(long, short, conflicts_with_all = ["all_remotes"])]
+    #[arg(long, short, conflicts_with_all = ["all_remotes", "all"])]
     conflicted: bool,
 
     /// Show bookmarks whose local name matches
@@ -171,10 +179,14 @@ pub fn cmd_bookmark_list(
         None
     };
 
+    let all_remotes = args.all_remotes || args.all;
     let template: TemplateRenderer<Rc<CommitRef>> = {
         let language = workspace_command.commit_template_language();
         let text = match &args.template {
             Some(value) => value.to_owned(),
+            None if args.all => workspace_command
+                .settings()
+                .get("templates.bookmark_list_all")?,
             None => workspace_command
                 .settings()
                 .get("templates.bookmark_list")?,
@@ -208,7 +220,7 @@ pub fn cmd_bookmark_list(
 
         if args.tracked {
             tracked_remote_refs.retain(|&(remote, _)| !jj_lib::git::is_special_git_remote(remote));
-        } else if !args.all_remotes && args.remotes.is_none() {
+        } else if !all_remotes && args.remotes.is_none() {
             tracked_remote_refs.retain(|&(_, remote_ref)| remote_ref.target != *local_target);
         }
 
@@ -228,7 +240,7 @@ pub fn cmd_bookmark_list(
             bookmark_list_items.push(RefListItem { primary, tracked });
         }
 
-        if !args.tracked && (args.all_remotes || args.remotes.is_some()) {
+        if !args.tracked && (all_remotes || args.remotes.is_some()) {
             bookmark_list_items.extend(untracked_remote_refs.iter().map(
                 |&(remote, remote_ref)| RefListItem {
                     primary: CommitRef::remote_only(name, remote, remote_ref.target.clone()),
@@ -170,6 +170,21 @@ pub fn cmd_bookmark_move(
         write!(formatter, "Moved {} bookmarks to ", matched_bookmarks.len())?;
         tx.write_commit_summary(formatter.as_mut(), &target_commit)?;
         writeln!(formatter)?;
+        let resolved_bookmark_count = matched_bookmarks
+            .iter()
+            .filter(|(_, old_target)| old_target.has_conflict())
+            .count();
+        if resolved_bookmark_count > 0 {
+            writeln!(
+                formatter,
+                "Resolved conflicts in {resolved_bookmark_count} bookmark{s}",
+                s = if resolved_bookmark_count == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+            )?;
+        }
     }
     if matched_bookmarks.len() > 1 && args.names.is_empty() {
         writeln!(
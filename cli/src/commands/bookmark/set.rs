@@ -77,6 +77,7 @@ pub fn cmd_bookmark_set(
     let bookmark_names = &args.names;
     let mut new_bookmark_count = 0;
     let mut moved_bookmark_count = 0;
+    let mut resolved_bookmark_count = 0;
     for name in bookmark_names {
         let old_target = repo.view().get_local_bookmark(name);
         // If a bookmark is absent locally but is still tracking remote bookmarks,
@@ -86,6 +87,9 @@ pub fn cmd_bookmark_set(
         } else if old_target.as_normal() != Some(target_commit.id()) {
             moved_bookmark_count += 1;
         }
+        if old_target.has_conflict() {
+            resolved_bookmark_count += 1;
+        }
         if !args.allow_backwards && !is_fast_forward(repo, old_target, target_commit.id()) {
             return Err(user_error_with_hint(
                 format!(
@@ -119,6 +123,17 @@ pub fn cmd_bookmark_set(
             tx.write_commit_summary(formatter.as_mut(), &target_commit)?;
             writeln!(formatter)?;
         }
+        if resolved_bookmark_count > 0 {
+            writeln!(
+                formatter,
+                "Resolved conflicts in {resolved_bookmark_count} bookmark{s}",
+                s = if resolved_bookmark_count == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+            )?;
+        }
     }
     if bookmark_names.len() > 1 && args.revision.is_none() {
         writeln!(ui.hint_default(), "Use -r to specify the target revision.")?;
@@ -0,0 +1,86 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::commands::parents::show_related_commits;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Show the children of a revision
+///
+/// Only visible children are listed, newest first. This is a shortcut for `jj
+/// log --no-graph -r 'children(REV)'`.
+///
+/// Use `-T 'json(self) ++ "\n"'` to print the children as JSON lines.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ChildrenArgs {
+    /// The revision to show the children of
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+
+    /// Render each child using the given template
+    ///
+    /// All 0-argument methods of the [`Commit` type] are available as
+    /// keywords in the template expression. See [`jj help -k templates`] for
+    /// more information.
+    ///
+    /// If not specified, this defaults to the `templates.log` setting.
+    ///
+    /// [`Commit` type]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/#commit-type
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/
+    #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_children(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ChildrenArgs,
+) -> Result<(), CommandError> {
+    show_related_commits(
+        ui,
+        command,
+        &args.revision,
+        args.template.as_deref(),
+        |workspace_command, commit| {
+            let repo = workspace_command.repo().as_ref();
+            let children: Vec<_> = RevsetExpression::commit(commit.id().clone())
+                .children()
+                .evaluate(repo)?
+                .iter()
+                .commits(repo.store())
+                .try_collect()?;
+            Ok(children)
+        },
+    )
+}
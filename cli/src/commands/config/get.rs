@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::Infallible;
 use std::io::Write as _;
 
 use clap_complete::ArgValueCandidates;
@@ -33,6 +34,8 @@ use crate::ui::Ui;
 /// user.name="Martin von Zweigbergk"
 /// $ jj config get user.name
 /// Martin von Zweigbergk
+///
+/// Arrays and tables are printed in TOML syntax.
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub struct ConfigGetArgs {
@@ -48,21 +51,16 @@ pub fn cmd_config_get(
 ) -> Result<(), CommandError> {
     let stringified = command
         .settings()
-        .get_value_with(&args.name, |value| match value {
+        .get_value_with::<_, Infallible>(&args.name, |value| match value {
             // Remove extra formatting from a string value
             ConfigValue::String(v) => Ok(v.into_value()),
             // Print other values in TOML syntax (but whitespace trimmed)
             ConfigValue::Integer(_)
             | ConfigValue::Float(_)
             | ConfigValue::Boolean(_)
-            | ConfigValue::Datetime(_) => Ok(value.decorated("", "").to_string()),
-            // TODO: maybe okay to just print array or table in TOML syntax?
-            ConfigValue::Array(_) => {
-                Err("Expected a value convertible to a string, but is an array")
-            }
-            ConfigValue::InlineTable(_) => {
-                Err("Expected a value convertible to a string, but is a table")
-            }
+            | ConfigValue::Datetime(_)
+            | ConfigValue::Array(_)
+            | ConfigValue::InlineTable(_) => Ok(value.decorated("", "").to_string()),
         })?;
     writeln!(ui.stdout(), "{stringified}")?;
     Ok(())
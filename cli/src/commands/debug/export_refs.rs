@@ -0,0 +1,58 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Write as _;
+
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Write Git refs for all commits referenced by the operation log
+///
+/// Refs are written under `refs/jj/backup/<operation id>/<commit id>`, so
+/// Git-based backup or mirroring tools can see every commit the operation log
+/// refers to. Commits reachable from other backed-up commits don't get refs of
+/// their own.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugExportRefsArgs {
+    /// Remove all backup refs instead of writing them
+    #[arg(long)]
+    remove: bool,
+}
+
+pub fn cmd_debug_export_refs(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugExportRefsArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let stats = if args.remove {
+        git::remove_backup_refs(repo.store())?
+    } else {
+        git::export_backup_refs(repo)?
+    };
+    writeln!(
+        ui.status(),
+        "Added {} refs, removed {} refs, kept {} refs.",
+        stats.added_count,
+        stats.removed_count,
+        stats.kept_count
+    )?;
+    Ok(())
+}
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 mod copy_detection;
+#[cfg(feature = "git")]
+mod export_refs;
 mod fileset;
 mod index;
 mod init_simple;
 mod local_working_copy;
 mod operation;
+mod proto;
 mod reindex;
 mod revset;
 mod snapshot;
@@ -34,6 +37,10 @@ use jj_lib::local_working_copy::LocalWorkingCopy;
 
 use self::copy_detection::cmd_debug_copy_detection;
 use self::copy_detection::CopyDetectionArgs;
+#[cfg(feature = "git")]
+use self::export_refs::cmd_debug_export_refs;
+#[cfg(feature = "git")]
+use self::export_refs::DebugExportRefsArgs;
 use self::fileset::cmd_debug_fileset;
 use self::fileset::DebugFilesetArgs;
 use self::index::cmd_debug_index;
@@ -44,6 +51,8 @@ use self::local_working_copy::cmd_debug_local_working_copy;
 use self::local_working_copy::DebugLocalWorkingCopyArgs;
 use self::operation::cmd_debug_operation;
 use self::operation::DebugOperationArgs;
+use self::proto::cmd_debug_proto;
+use self::proto::DebugProtoCommand;
 use self::reindex::cmd_debug_reindex;
 use self::reindex::DebugReindexArgs;
 use self::revset::cmd_debug_revset;
@@ -68,12 +77,20 @@ use crate::ui::Ui;
 #[command(hide = true)]
 pub enum DebugCommand {
     CopyDetection(CopyDetectionArgs),
+    #[cfg(feature = "git")]
+    ExportRefs(DebugExportRefsArgs),
     Fileset(DebugFilesetArgs),
     Index(DebugIndexArgs),
     InitSimple(DebugInitSimpleArgs),
     LocalWorkingCopy(DebugLocalWorkingCopyArgs),
     #[command(visible_alias = "view")]
     Operation(DebugOperationArgs),
+    /// Dump or restore individual store records as JSON
+    ///
+    /// These commands are meant for repairing corrupted records. Bytes fields
+    /// such as ids are represented as hex strings.
+    #[command(subcommand)]
+    Proto(DebugProtoCommand),
     Reindex(DebugReindexArgs),
     Revset(DebugRevsetArgs),
     Snapshot(DebugSnapshotArgs),
@@ -91,11 +108,14 @@ pub fn cmd_debug(
 ) -> Result<(), CommandError> {
     match subcommand {
         DebugCommand::CopyDetection(args) => cmd_debug_copy_detection(ui, command, args),
+        #[cfg(feature = "git")]
+        DebugCommand::ExportRefs(args) => cmd_debug_export_refs(ui, command, args),
         DebugCommand::Fileset(args) => cmd_debug_fileset(ui, command, args),
         DebugCommand::Index(args) => cmd_debug_index(ui, command, args),
         DebugCommand::InitSimple(args) => cmd_debug_init_simple(ui, command, args),
         DebugCommand::LocalWorkingCopy(args) => cmd_debug_local_working_copy(ui, command, args),
         DebugCommand::Operation(args) => cmd_debug_operation(ui, command, args),
+        DebugCommand::Proto(args) => cmd_debug_proto(ui, command, args),
         DebugCommand::Reindex(args) => cmd_debug_reindex(ui, command, args),
         DebugCommand::Revset(args) => cmd_debug_revset(ui, command, args),
         DebugCommand::Snapshot(args) => cmd_debug_snapshot(ui, command, args),
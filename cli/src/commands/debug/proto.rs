@@ -0,0 +1,236 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use clap::ValueEnum as _;
+use jj_lib::backend::CommitId;
+use jj_lib::local_working_copy::TreeState;
+use jj_lib::op_store::OperationId;
+use jj_lib::op_store::ViewId;
+use jj_lib::simple_op_store::SimpleOpStore;
+use jj_lib::workspace::Workspace;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::check_local_disk_wc;
+use crate::cli_util::CommandHelper;
+use crate::command_error::internal_error;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum DebugProtoCommand {
+    Dump(DebugProtoDumpArgs),
+    Restore(DebugProtoRestoreArgs),
+}
+
+/// Print a store record as JSON
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugProtoDumpArgs {
+    /// The kind of the record
+    #[arg(value_enum)]
+    kind: ProtoRecordKind,
+    /// The full hex id of the operation, view, or commit
+    ///
+    /// Not used for the tree state, which belongs to the current workspace.
+    id: Option<String>,
+}
+
+/// Replace a store record with the given JSON
+///
+/// The record is validated before it is written. Operations and views are
+/// stored under the given id even if it no longer matches the content hash.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugProtoRestoreArgs {
+    /// The kind of the record
+    #[arg(value_enum)]
+    kind: ProtoRecordKind,
+    /// The full hex id of the operation, view, or commit
+    ///
+    /// Not used for the tree state, which belongs to the current workspace.
+    id: Option<String>,
+    /// Read the JSON from the file instead of stdin
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProtoRecordKind {
+    /// Operation in the operation store
+    Operation,
+    /// View in the operation store
+    View,
+    /// Working-copy state of the current workspace
+    TreeState,
+    /// Metadata of a commit that isn't stored in the Git commit object
+    ExtraMetadata,
+}
+
+pub fn cmd_debug_proto(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &DebugProtoCommand,
+) -> Result<(), CommandError> {
+    // Don't load the repo, so the records can be repaired even if the current
+    // operation or view is broken.
+    let workspace = command.load_workspace()?;
+    match subcommand {
+        DebugProtoCommand::Dump(args) => {
+            let json = match args.kind {
+                ProtoRecordKind::Operation => {
+                    let id = parse_id(args.id.as_deref(), OperationId::try_from_hex)?;
+                    to_json(&simple_op_store(&workspace)?.read_operation_proto(&id)?)?
+                }
+                ProtoRecordKind::View => {
+                    let id = parse_id(args.id.as_deref(), ViewId::try_from_hex)?;
+                    to_json(&simple_op_store(&workspace)?.read_view_proto(&id)?)?
+                }
+                ProtoRecordKind::TreeState => {
+                    check_no_id(args.id.as_deref())?;
+                    let wc = check_local_disk_wc(workspace.working_copy().as_any())?;
+                    let proto = TreeState::read_proto(wc.state_path()).map_err(|err| {
+                        internal_error_with_message("Failed to read tree state", err)
+                    })?;
+                    to_json(&proto)?
+                }
+                ProtoRecordKind::ExtraMetadata => {
+                    let id = parse_id(args.id.as_deref(), CommitId::try_from_hex)?;
+                    dump_extra_metadata(&workspace, &id)?
+                }
+            };
+            writeln!(ui.stdout(), "{json}")?;
+        }
+        DebugProtoCommand::Restore(args) => {
+            let json = match &args.file {
+                Some(path) => fs::read_to_string(path).map_err(|err| {
+                    user_error_with_message(format!("Failed to read {}", path.display()), err)
+                })?,
+                None => {
+                    let mut json = String::new();
+                    io::stdin().read_to_string(&mut json)?;
+                    json
+                }
+            };
+            match args.kind {
+                ProtoRecordKind::Operation => {
+                    let id = parse_id(args.id.as_deref(), OperationId::try_from_hex)?;
+                    let proto = from_json(&json)?;
+                    simple_op_store(&workspace)?
+                        .write_operation_proto(&id, &proto)
+                        .map_err(user_error)?;
+                }
+                ProtoRecordKind::View => {
+                    let id = parse_id(args.id.as_deref(), ViewId::try_from_hex)?;
+                    let proto = from_json(&json)?;
+                    simple_op_store(&workspace)?
+                        .write_view_proto(&id, &proto)
+                        .map_err(user_error)?;
+                }
+                ProtoRecordKind::TreeState => {
+                    check_no_id(args.id.as_deref())?;
+                    let wc = check_local_disk_wc(workspace.working_copy().as_any())?;
+                    let proto = from_json(&json)?;
+                    TreeState::write_proto(wc.state_path(), &proto).map_err(user_error)?;
+                }
+                ProtoRecordKind::ExtraMetadata => {
+                    let id = parse_id(args.id.as_deref(), CommitId::try_from_hex)?;
+                    restore_extra_metadata(&workspace, &id, &json)?;
+                }
+            }
+            let kind = args.kind.to_possible_value().unwrap();
+            writeln!(ui.status(), "Restored {} record", kind.get_name())?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_id<T>(id: Option<&str>, try_from_hex: fn(&str) -> Option<T>) -> Result<T, CommandError> {
+    let id = id.ok_or_else(|| user_error("The id of the record is required"))?;
+    try_from_hex(id).ok_or_else(|| user_error(format!("Invalid hex id: {id}")))
+}
+
+fn check_no_id(id: Option<&str>) -> Result<(), CommandError> {
+    if id.is_some() {
+        return Err(user_error("The tree state record has no id"));
+    }
+    Ok(())
+}
+
+fn simple_op_store(workspace: &Workspace) -> Result<&SimpleOpStore, CommandError> {
+    workspace
+        .repo_loader()
+        .op_store()
+        .as_any()
+        .downcast_ref()
+        .ok_or_else(|| user_error("This command requires the standard operation store"))
+}
+
+fn to_json(proto: &impl Serialize) -> Result<String, CommandError> {
+    // Convert to a JSON value first so object keys are sorted.
+    let value = serde_json::to_value(proto).map_err(internal_error)?;
+    serde_json::to_string_pretty(&value).map_err(internal_error)
+}
+
+fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, CommandError> {
+    serde_json::from_str(json).map_err(|err| user_error_with_message("Invalid record", err))
+}
+
+#[cfg(feature = "git")]
+fn dump_extra_metadata(workspace: &Workspace, id: &CommitId) -> Result<String, CommandError> {
+    use jj_lib::object_id::ObjectId as _;
+
+    let git_backend = jj_lib::git::get_git_backend(workspace.repo_loader().store())?;
+    let proto = git_backend
+        .read_extra_metadata_proto(id)?
+        .ok_or_else(|| user_error(format!("No extra metadata for commit {}", id.hex())))?;
+    to_json(&proto)
+}
+
+#[cfg(not(feature = "git"))]
+fn dump_extra_metadata(_workspace: &Workspace, _id: &CommitId) -> Result<String, CommandError> {
+    Err(user_error("This command requires the Git backend"))
+}
+
+#[cfg(feature = "git")]
+fn restore_extra_metadata(
+    workspace: &Workspace,
+    id: &CommitId,
+    json: &str,
+) -> Result<(), CommandError> {
+    let git_backend = jj_lib::git::get_git_backend(workspace.repo_loader().store())?;
+    let proto = from_json(json)?;
+    git_backend
+        .write_extra_metadata_proto(id, &proto)
+        .map_err(user_error)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "git"))]
+fn restore_extra_metadata(
+    _workspace: &Workspace,
+    _id: &CommitId,
+    _json: &str,
+) -> Result<(), CommandError> {
+    Err(user_error("This command requires the Git backend"))
+}
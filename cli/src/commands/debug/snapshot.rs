@@ -13,6 +13,10 @@
 // limitations under the License.
 
 use std::fmt::Debug;
+use std::io::Write as _;
+
+use jj_lib::working_copy::SnapshotChangeReason;
+use jj_lib::working_copy::SnapshotOptions;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -20,14 +24,50 @@ use crate::ui::Ui;
 
 /// Trigger a snapshot in the op log
 #[derive(clap::Args, Clone, Debug)]
-pub struct DebugSnapshotArgs {}
+pub struct DebugSnapshotArgs {
+    /// List the files a snapshot would change without recording it
+    ///
+    /// Each file is printed with the reason the snapshot considered it
+    /// changed. Neither the working-copy state nor the operation log is
+    /// updated.
+    #[arg(long)]
+    dry_run: bool,
+}
 
 pub fn cmd_debug_snapshot(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &DebugSnapshotArgs,
+    args: &DebugSnapshotArgs,
 ) -> Result<(), CommandError> {
-    // workspace helper will snapshot as needed
-    command.workspace_helper(ui)?;
+    if !args.dry_run {
+        // workspace helper will snapshot as needed
+        command.workspace_helper(ui)?;
+        return Ok(());
+    }
+
+    let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let options = SnapshotOptions {
+        dry_run: true,
+        ..workspace_command.snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)?
+    };
+    let (mut locked_ws, _wc_commit) = workspace_command.start_working_copy_mutation()?;
+    let (_new_tree_id, stats) = locked_ws.locked_wc().snapshot(&options)?;
+    // Release the lock without saving the updated working-copy state.
+    drop(locked_ws);
+
+    let mut formatter = ui.stdout_formatter();
+    for (path, reason) in &stats.changed_paths {
+        let (status, reason) = match reason {
+            SnapshotChangeReason::NewFile => ("A", "new file"),
+            SnapshotChangeReason::Removed => ("D", "removed"),
+            SnapshotChangeReason::FileTypeChanged => ("M", "file type changed"),
+            SnapshotChangeReason::SizeChanged => ("M", "size changed"),
+            SnapshotChangeReason::MtimeChanged => ("M", "mtime changed"),
+            SnapshotChangeReason::AmbiguousMtime => ("M", "ambiguous mtime"),
+        };
+        let ui_path = workspace_command.format_file_path(path);
+        writeln!(formatter, "{status} {ui_path} ({reason})")?;
+    }
     Ok(())
 }
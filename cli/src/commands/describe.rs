@@ -13,9 +13,11 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::fs;
 use std::io;
 use std::io::Read as _;
 use std::iter;
+use std::path::PathBuf;
 
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
@@ -27,9 +29,11 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::description_util::add_trailers_with_template;
+use crate::description_util::cleanup_description;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::edit_multiple_descriptions;
@@ -67,15 +71,26 @@ pub(crate) struct DescribeArgs {
         long = "message",
         short,
         value_name = "MESSAGE",
-        conflicts_with = "stdin"
+        conflicts_with_all = ["stdin", "message_file"]
     )]
     message_paragraphs: Vec<String>,
     /// Read the change description from stdin
     ///
-    /// If multiple revisions are specified, the same description will be used
-    /// for all of them.
+    /// Lines starting with "JJ:" are removed. If multiple revisions are
+    /// specified, the same description will be used for all of them.
     #[arg(long)]
     stdin: bool,
+    /// Read the change description from the given file
+    ///
+    /// Lines starting with "JJ:" are removed. If multiple revisions are
+    /// specified, the same description will be used for all of them.
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with = "stdin"
+    )]
+    message_file: Option<PathBuf>,
     /// Don't open an editor
     ///
     /// This is mainly useful in combination with e.g. `--reset-author`.
@@ -83,8 +98,8 @@ pub(crate) struct DescribeArgs {
     no_edit: bool,
     /// Open an editor
     ///
-    /// Forces an editor to open when using `--stdin` or `--message` to
-    /// allow the message to be edited afterwards.
+    /// Forces an editor to open when using `--stdin`, `--message-file`, or
+    /// `--message` to allow the message to be edited afterwards.
     #[arg(long)]
     edit: bool,
     /// Reset the author to the configured user
@@ -147,7 +162,12 @@ pub(crate) fn cmd_describe(
     let shared_description = if args.stdin {
         let mut buffer = String::new();
         io::stdin().read_to_string(&mut buffer)?;
-        Some(buffer)
+        Some(cleanup_description(&buffer))
+    } else if let Some(path) = &args.message_file {
+        let text = fs::read_to_string(command.cwd().join(path)).map_err(|err| {
+            user_error_with_message(format!("Failed to read {}", path.display()), err)
+        })?;
+        Some(cleanup_description(&text))
     } else if !args.message_paragraphs.is_empty() {
         Some(join_message_paragraphs(&args.message_paragraphs))
     } else {
@@ -25,11 +25,13 @@ use crate::cli_util::print_unmatched_explicit_paths;
 use crate::cli_util::short_commit_hash;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::get_copy_records;
 use crate::diff_util::show_templated;
+use crate::diff_util::unified_diff_options_for;
 use crate::diff_util::DiffFormatArgs;
 use crate::ui::Ui;
 
@@ -46,6 +48,10 @@ use crate::ui::Ui;
 /// (perhaps a bookmark name) to the working-copy commit.
 ///
 /// If no option is specified, it defaults to `-r @`.
+///
+/// To inspect a merge commit, `--first-parent` shows the changes compared to
+/// its first parent only, and `--cc` shows the changes that differ from all of
+/// its parents (e.g. conflict resolutions) as a Git-style combined diff.
 #[derive(clap::Args, Clone, Debug)]
 #[command(mut_arg("ignore_all_space", |a| a.short('w')))]
 #[command(mut_arg("ignore_space_change", |a| a.short('b')))]
@@ -100,6 +106,39 @@ pub(crate) struct DiffArgs {
         add = ArgValueCompleter::new(complete::modified_revision_or_range_files),
     )]
     paths: Vec<String>,
+    /// Show changes to generated files in full
+    ///
+    /// By default, changes to the files matching the `diff.generated-files`
+    /// setting are collapsed to a single line per file, except in the `--git`,
+    /// `--name-only`, and `--types` formats and external diff tools.
+    #[arg(long)]
+    include_generated: bool,
+    /// Show changes compared to the first parent only
+    ///
+    /// Merge commits are compared to their first parent instead of the
+    /// automatic merge of all of their parents. This shows everything the
+    /// merge brought into the first-parent line of history.
+    #[arg(long, conflicts_with_all = ["from", "to"])]
+    first_parent: bool,
+    /// Show a combined diff of a merge commit against all of its parents
+    ///
+    /// Like Git's `--cc`, only hunks where the merge result differs from every
+    /// parent are shown, such as conflict resolutions and changes made in the
+    /// merge itself. Hunks taken unmodified from one of the parents are
+    /// omitted. The diff is always shown in Git format.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "from",
+            "to",
+            "first_parent",
+            "template",
+            "short-format",
+            "color_words",
+            "tool",
+        ],
+    )]
+    cc: bool,
     /// Render each file diff entry using the given template
     ///
     /// All 0-argument methods of the [`TreeDiffEntry` type] are available as
@@ -133,6 +172,42 @@ pub(crate) fn cmd_diff(
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let matcher = fileset_expression.to_matcher();
 
+    if args.cc {
+        let revision_args = args
+            .revisions
+            .as_deref()
+            .unwrap_or(std::slice::from_ref(&RevisionArg::AT));
+        let [revision_arg] = revision_args else {
+            return Err(user_error("--cc requires a single revision"));
+        };
+        let commit = workspace_command.resolve_single_rev(ui, revision_arg)?;
+        let mut parent_trees: Vec<_> = commit
+            .parents()
+            .map(|parent| parent?.tree())
+            .try_collect()?;
+        if parent_trees.is_empty() {
+            parent_trees.push(commit.parent_tree(repo.as_ref())?);
+        }
+        let to_tree = commit.tree()?;
+        let options = unified_diff_options_for(workspace_command.settings(), &args.format)?;
+        let diff_renderer = workspace_command.diff_renderer(vec![]);
+        ui.request_pager();
+        diff_renderer.show_combined_diff(
+            ui.stdout_formatter().as_mut(),
+            &parent_trees,
+            &to_tree,
+            &matcher,
+            &options,
+        )?;
+        print_unmatched_explicit_paths(
+            ui,
+            &workspace_command,
+            &fileset_expression,
+            parent_trees.iter().chain([&to_tree]),
+        )?;
+        return Ok(());
+    }
+
     let from_tree;
     let to_tree;
     let mut copy_records = CopyRecords::default();
@@ -176,7 +251,14 @@ pub(crate) fn cmd_diff(
             .try_collect()?;
 
         // Collect parents outside of revset to preserve parent order
-        let parents: IndexSet<_> = roots.iter().flat_map(|c| c.parents()).try_collect()?;
+        let parents: IndexSet<_> = if args.first_parent {
+            roots
+                .iter()
+                .flat_map(|c| c.parents().take(1))
+                .try_collect()?
+        } else {
+            roots.iter().flat_map(|c| c.parents()).try_collect()?
+        };
         let parents = parents.into_iter().collect_vec();
         from_tree = merge_commit_trees(repo.as_ref(), &parents)?;
         to_tree = merge_commit_trees(repo.as_ref(), &heads)?;
@@ -193,7 +275,7 @@ pub(crate) fn cmd_diff(
     // enable long format if explicitly specified (assuming -T is for short or
     // summary output.)
     let maybe_template;
-    let diff_renderer;
+    let mut diff_renderer;
     if let Some(text) = &args.template {
         let language = workspace_command.commit_template_language();
         let template = workspace_command
@@ -204,6 +286,10 @@ pub(crate) fn cmd_diff(
     } else {
         maybe_template = None;
         diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+        if !args.include_generated {
+            diff_renderer
+                .set_generated_files_matcher(workspace_command.generated_files_matcher(ui)?);
+        }
     }
 
     ui.request_pager();
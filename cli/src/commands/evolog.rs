@@ -165,7 +165,7 @@ pub(crate) fn cmd_evolog(
         let evolution_nodes = evolution_nodes.take(args.limit.unwrap_or(usize::MAX));
         let evolution_nodes: Box<dyn Iterator<Item = _>> = if args.reversed {
             let nodes = reverse_graph(evolution_nodes, |entry| entry.commit.id())?;
-            Box::new(nodes.into_iter().map(Ok))
+            Box::new(nodes.map(Ok))
         } else {
             Box::new(evolution_nodes)
         };
@@ -0,0 +1,156 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCompleter;
+use indexmap::IndexSet;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::rewrite::merge_divergent_commits;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::description_util::add_trailers;
+use crate::description_util::combine_messages_for_editing;
+use crate::description_util::description_template;
+use crate::description_util::edit_description;
+use crate::description_util::try_combine_messages;
+use crate::ui::Ui;
+
+/// Resolve divergent changes by merging their visible commits
+///
+/// For each change in the selected revisions that has more than one visible
+/// commit, the changes of all its commits are merged into a single commit,
+/// using the latest predecessor the commits have in common as the merge base.
+/// The merged commit is placed on the parents of the most recent of the
+/// divergent commits. Descendants of the divergent commits are rebased onto
+/// the merged commit.
+///
+/// If the divergent commits have different non-empty descriptions, you will be
+/// asked for the combined description.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct EvolveArgs {
+    /// Resolve divergence of changes in these revisions
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        default_value = "mutable()",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_evolve(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &EvolveArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().clone();
+    let selected_commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let change_ids: IndexSet<_> = selected_commits
+        .iter()
+        .map(|commit| commit.change_id().clone())
+        .collect();
+
+    let mut divergent_changes: Vec<Vec<Commit>> = vec![];
+    for change_id in &change_ids {
+        let commit_ids = repo.resolve_change_id(change_id).unwrap_or_default();
+        if commit_ids.len() < 2 {
+            continue;
+        }
+        // Sort the commits newest first so the merge is placed predictably.
+        let commits: Vec<Commit> = RevsetExpression::commits(commit_ids)
+            .evaluate(repo.as_ref())?
+            .iter()
+            .commits(repo.store())
+            .try_collect()?;
+        divergent_changes.push(commits);
+    }
+    if divergent_changes.is_empty() {
+        writeln!(ui.status(), "No divergent changes to resolve")?;
+        return Ok(());
+    }
+    workspace_command.check_rewritable(divergent_changes.iter().flatten().map(Commit::id))?;
+
+    let text_editor = workspace_command.text_editor()?;
+    let mut tx = workspace_command.start_transaction();
+    let mut merged_commits = vec![];
+    for divergent_commits in divergent_changes {
+        let mut merged = merge_divergent_commits(tx.repo_mut(), divergent_commits)?;
+        let [destination, sources @ ..] = &merged.divergent_commits[..] else {
+            unreachable!();
+        };
+        // Divergent commits often share the description, which shouldn't be
+        // repeated in the combined one.
+        let sources = sources
+            .iter()
+            .filter(|commit| commit.description() != destination.description())
+            .cloned()
+            .collect_vec();
+        let commit_builder = &mut merged.commit_builder;
+        let description = if let Some(description) = try_combine_messages(&sources, destination) {
+            if description.is_empty() {
+                description
+            } else {
+                commit_builder.set_description(description);
+                add_trailers(ui, &tx, commit_builder)?
+            }
+        } else {
+            let combined =
+                combine_messages_for_editing(ui, &tx, &sources, destination, commit_builder)?;
+            commit_builder.set_description(combined);
+            let temp_commit = commit_builder.write_hidden()?;
+            let intro = "Enter a description for the merged commit.";
+            let template = description_template(ui, &tx, intro, &temp_commit)?;
+            edit_description(&text_editor, &template)?
+        };
+        commit_builder.set_description(description);
+        merged_commits.push(merged.write(tx.repo_mut())?);
+    }
+    let num_rebased = tx.repo_mut().rebase_descendants()?;
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(
+            formatter,
+            "Resolved divergence of {} changes",
+            merged_commits.len()
+        )?;
+        for commit in &merged_commits {
+            write!(formatter, "  ")?;
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+    }
+    tx.finish(
+        ui,
+        format!("resolve divergence of {} changes", merged_commits.len()),
+    )?;
+    Ok(())
+}
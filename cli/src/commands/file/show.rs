@@ -12,14 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Cursor;
 use std::io::Write as _;
 
 use clap_complete::ArgValueCompleter;
 use jj_lib::backend::BackendResult;
-use jj_lib::conflicts::materialize_merge_result;
+use jj_lib::conflicts::materialize_merge_result_to_bytes;
 use jj_lib::conflicts::materialize_tree_value;
 use jj_lib::conflicts::MaterializedTreeValue;
-use jj_lib::file_util::copy_async_to_sync;
+use jj_lib::file_util::copy_async_range_to_sync;
 use jj_lib::fileset::FilePattern;
 use jj_lib::fileset::FilesetExpression;
 use jj_lib::merge::MergedTreeValue;
@@ -41,6 +42,10 @@ use crate::ui::Ui;
 ///
 /// If the given path is a directory, files in the directory will be visited
 /// recursively.
+///
+/// Use `--offset` and `--length` to print only a byte range of each file. The
+/// file contents may still be read in full from the backend; the Git backend
+/// always loads whole blobs into memory.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileShowArgs {
     /// The revision to get the file contents from
@@ -59,6 +64,12 @@ pub(crate) struct FileShowArgs {
         add = ArgValueCompleter::new(complete::all_revision_files),
     )]
     paths: Vec<String>,
+    /// Skip this many bytes at the beginning of each file
+    #[arg(long, value_name = "BYTES", default_value_t = 0)]
+    offset: u64,
+    /// Print at most this many bytes of each file
+    #[arg(long, value_name = "BYTES")]
+    length: Option<u64>,
 }
 
 #[instrument(skip_all)]
@@ -83,7 +94,7 @@ pub(crate) fn cmd_file_show(
         }
         if !value.is_tree() {
             ui.request_pager();
-            write_tree_entries(ui, &workspace_command, [(path, Ok(value))])?;
+            write_tree_entries(ui, &workspace_command, [(path, Ok(value))], args)?;
             return Ok(());
         }
     }
@@ -94,6 +105,7 @@ pub(crate) fn cmd_file_show(
         ui,
         &workspace_command,
         tree.entries_matching(matcher.as_ref()),
+        args,
     )?;
     print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
     Ok(())
@@ -115,6 +127,7 @@ fn write_tree_entries<P: AsRef<RepoPath>>(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
     entries: impl IntoIterator<Item = (P, BackendResult<MergedTreeValue>)>,
+    args: &FileShowArgs,
 ) -> Result<(), CommandError> {
     let repo = workspace_command.repo();
     for (path, result) in entries {
@@ -130,14 +143,26 @@ fn write_tree_entries<P: AsRef<RepoPath>>(
                 )?;
             }
             MaterializedTreeValue::File(file) => {
-                copy_async_to_sync(file.reader, ui.stdout_formatter().as_mut()).block_on()?;
+                copy_async_range_to_sync(
+                    file.reader,
+                    ui.stdout_formatter().as_mut(),
+                    args.offset,
+                    args.length,
+                )
+                .block_on()?;
             }
             MaterializedTreeValue::FileConflict(file) => {
-                materialize_merge_result(
+                let content = materialize_merge_result_to_bytes(
                     &file.contents,
                     workspace_command.env().conflict_marker_style(),
-                    &mut ui.stdout_formatter(),
-                )?;
+                );
+                copy_async_range_to_sync(
+                    Cursor::new(content),
+                    ui.stdout_formatter().as_mut(),
+                    args.offset,
+                    args.length,
+                )
+                .block_on()?;
             }
             MaterializedTreeValue::OtherConflict { id } => {
                 ui.stdout_formatter().write_all(id.describe().as_bytes())?;
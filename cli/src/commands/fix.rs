@@ -30,6 +30,7 @@ use jj_lib::fix::FixError;
 use jj_lib::fix::ParallelFileFixer;
 use jj_lib::matchers::Matcher;
 use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::repo_path::UiPathStyle;
 use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
 use pollster::FutureExt as _;
@@ -40,6 +41,7 @@ use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::config_error;
 use crate::command_error::print_parse_diagnostics;
+use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::config::CommandNameAndArgs;
@@ -119,6 +121,10 @@ pub(crate) struct FixArgs {
     /// specified, all files in the repo will be fixed.
     #[arg(long)]
     include_unchanged_files: bool,
+    /// Run only the named tools from the `fix.tools` table, even if they are
+    /// disabled
+    #[arg(long = "tool", value_name = "NAME")]
+    tools: Vec<String>,
 }
 
 #[instrument(skip_all)]
@@ -129,7 +135,7 @@ pub(crate) fn cmd_fix(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let workspace_root = workspace_command.workspace_root().to_owned();
-    let tools_config = get_tools_config(ui, workspace_command.settings())?;
+    let tools_config = get_tools_config(ui, workspace_command.settings(), &args.tools)?;
     let root_commits: Vec<CommitId> = if args.source.is_empty() {
         let revs = workspace_command.settings().get_string("revsets.fix")?;
         workspace_command.parse_revset(ui, &RevisionArg::from(revs))?
@@ -265,14 +271,16 @@ fn run_tool(
 
 /// Represents an entry in the `fix.tools` config table.
 struct ToolConfig {
+    /// The key of the entry in the `fix.tools` table.
+    name: String,
     /// The command that will be run to fix a matching file.
     command: CommandNameAndArgs,
     /// The matcher that determines if this tool matches a file.
     matcher: Box<dyn Matcher>,
     /// Whether the tool is enabled
     enabled: bool,
-    // TODO: Store the `name` field here and print it with the command's stderr, to clearly
-    // associate any errors/warnings with the tool and its configuration entry.
+    // TODO: Print the `name` field with the command's stderr, to clearly associate
+    // any errors/warnings with the tool and its configuration entry.
 }
 
 /// Represents the `fix.tools` config table.
@@ -301,7 +309,14 @@ fn default_tool_enabled() -> bool {
 /// Fails if any of the commands or patterns are obviously unusable, but does
 /// not check for issues that might still occur later like missing executables.
 /// This is a place where we could fail earlier in some cases, though.
-fn get_tools_config(ui: &mut Ui, settings: &UserSettings) -> Result<ToolsConfig, CommandError> {
+///
+/// If `selected_tools` is not empty, only the tools with these names are
+/// returned, regardless of whether they are enabled.
+fn get_tools_config(
+    ui: &mut Ui,
+    settings: &UserSettings,
+    selected_tools: &[String],
+) -> Result<ToolsConfig, CommandError> {
     let mut tools: Vec<ToolConfig> = settings
         .table_keys("fix.tools")
         // Sort keys early so errors are deterministic.
@@ -319,6 +334,7 @@ fn get_tools_config(ui: &mut Ui, settings: &UserSettings) -> Result<ToolsConfig,
                             &RepoPathUiConverter::Fs {
                                 cwd: "".into(),
                                 base: "".into(),
+                                style: UiPathStyle::default(),
                             },
                         )
                     })
@@ -326,6 +342,7 @@ fn get_tools_config(ui: &mut Ui, settings: &UserSettings) -> Result<ToolsConfig,
             );
             print_parse_diagnostics(ui, &format!("In `fix.tools.{name}`"), &diagnostics)?;
             Ok(ToolConfig {
+                name: name.to_owned(),
                 command: tool.command,
                 matcher: expression.to_matcher(),
                 enabled: tool.enabled,
@@ -335,6 +352,16 @@ fn get_tools_config(ui: &mut Ui, settings: &UserSettings) -> Result<ToolsConfig,
     if tools.is_empty() {
         return Err(config_error("No `fix.tools` are configured"));
     }
+    if !selected_tools.is_empty() {
+        if let Some(name) = selected_tools
+            .iter()
+            .find(|name| !tools.iter().any(|t| &t.name == *name))
+        {
+            return Err(user_error(format!("No such entry in `fix.tools`: {name}")));
+        }
+        tools.retain(|t| selected_tools.contains(&t.name));
+        return Ok(ToolsConfig { tools });
+    }
     tools.retain(|t| t.enabled);
     if tools.is_empty() {
         Err(config_error(
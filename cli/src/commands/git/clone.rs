@@ -27,6 +27,7 @@ use jj_lib::repo::Repo as _;
 use jj_lib::str_util::StringPattern;
 use jj_lib::workspace::Workspace;
 
+use super::get_fetch_tags;
 use super::write_repository_level_trunk_alias;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::WorkspaceCommandHelper;
@@ -217,10 +218,17 @@ fn fetch_new_remote(
     let settings = workspace_command.settings();
     let git_settings = settings.git_settings()?;
     let track_default = settings.get_bool("git.track-default-bookmark-on-clone")?;
+    let fetch_tags = get_fetch_tags(settings, remote_name)?;
     let mut tx = workspace_command.start_transaction();
     let mut git_fetch = GitFetch::new(tx.repo_mut(), &git_settings)?;
     with_remote_git_callbacks(ui, |cb| {
-        git_fetch.fetch(remote_name, &[StringPattern::everything()], cb, depth)
+        git_fetch.fetch(
+            remote_name,
+            &[StringPattern::everything()],
+            fetch_tags,
+            cb,
+            depth,
+        )
     })?;
     let default_branch = git_fetch.get_default_branch(remote_name)?;
     let import_stats = git_fetch.import_refs()?;
@@ -13,12 +13,15 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::num::NonZeroU32;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::git;
 use jj_lib::git::GitFetch;
+use jj_lib::git::GitFetchTags;
+use jj_lib::git::GitImportStats;
 use jj_lib::ref_name::RemoteName;
 use jj_lib::repo::Repo as _;
 use jj_lib::str_util::StringPattern;
@@ -29,10 +32,12 @@ use crate::cli_util::WorkspaceCommandTransaction;
 use crate::command_error::config_error;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
+use crate::commands::git::get_fetch_tags;
 use crate::commands::git::get_single_remote;
 use crate::complete;
 use crate::git_util::print_git_import_stats;
 use crate::git_util::with_remote_git_callbacks;
+use crate::hooks::run_post_fetch_hook;
 use crate::ui::Ui;
 
 /// Fetch from a Git remote
@@ -76,6 +81,29 @@ pub struct GitFetchArgs {
     /// Fetch from all remotes
     #[arg(long, conflicts_with = "remotes")]
     all_remotes: bool,
+    /// Fetch all tags from the remotes
+    ///
+    /// By default, the `remotes.<name>.fetch-tags` or `git.fetch-tags` setting
+    /// decides which tags are fetched.
+    #[arg(long, conflicts_with = "no_tags")]
+    tags: bool,
+    /// Don't fetch tags, nor import them
+    #[arg(long)]
+    no_tags: bool,
+    /// Delete local tags which no longer exist on the remotes
+    ///
+    /// This implies `--tags`.
+    #[arg(long, conflicts_with = "no_tags")]
+    prune_tags: bool,
+    /// Fetch only the given number of commits from the tip of each branch
+    ///
+    /// The depth is passed to `git fetch` as is. It's mainly useful in shallow
+    /// repositories created by `jj git clone --depth`. If the repository isn't
+    /// shallow, Git makes it shallow at the fetched commits. Fetching with a
+    /// larger depth than before deepens the repository, which isn't supported
+    /// yet and may leave the commit graph inconsistent.
+    #[arg(long)]
+    depth: Option<NonZeroU32>,
 }
 
 #[tracing::instrument(skip_all)]
@@ -118,8 +146,34 @@ pub fn cmd_git_fetch(
         .sorted()
         .collect_vec();
 
+    let tags_override = if args.tags || args.prune_tags {
+        Some(GitFetchTags::All)
+    } else if args.no_tags {
+        Some(GitFetchTags::None)
+    } else {
+        None
+    };
+    let remotes_with_tags: Vec<_> = remotes
+        .iter()
+        .map(|&remote| {
+            let tags = match tags_override {
+                Some(tags) => tags,
+                None => get_fetch_tags(workspace_command.settings(), remote)?,
+            };
+            Ok::<_, CommandError>((remote, tags))
+        })
+        .try_collect()?;
+
+    let old_repo = workspace_command.repo().clone();
     let mut tx = workspace_command.start_transaction();
-    do_git_fetch(ui, &mut tx, &remotes, &args.branch)?;
+    let import_stats = do_git_fetch(
+        ui,
+        &mut tx,
+        &remotes_with_tags,
+        &args.branch,
+        args.prune_tags,
+        args.depth,
+    )?;
     tx.finish(
         ui,
         format!(
@@ -127,6 +181,7 @@ pub fn cmd_git_fetch(
             remotes.iter().map(|n| n.as_symbol()).join(",")
         ),
     )?;
+    run_post_fetch_hook(ui, &workspace_command, &old_repo, &import_stats)?;
     Ok(())
 }
 
@@ -167,20 +222,27 @@ fn parse_remote_pattern(remote: &str) -> Result<StringPattern, CommandError> {
 fn do_git_fetch(
     ui: &mut Ui,
     tx: &mut WorkspaceCommandTransaction,
-    remotes: &[&RemoteName],
+    remotes: &[(&RemoteName, GitFetchTags)],
     branch_names: &[StringPattern],
-) -> Result<(), CommandError> {
+    prune_tags: bool,
+    depth: Option<NonZeroU32>,
+) -> Result<GitImportStats, CommandError> {
     let git_settings = tx.settings().git_settings()?;
     let mut git_fetch = GitFetch::new(tx.repo_mut(), &git_settings)?;
 
-    for remote_name in remotes {
+    for &(remote_name, tags) in remotes {
         with_remote_git_callbacks(ui, |callbacks| {
-            git_fetch.fetch(remote_name, branch_names, callbacks, None)
+            git_fetch.fetch(remote_name, branch_names, tags, callbacks, depth)
         })?;
     }
+    let remote_names = remotes.iter().map(|&(remote, _)| remote).collect_vec();
+    if prune_tags {
+        git_fetch.prune_tags(&remote_names)?;
+    }
     let import_stats = git_fetch.import_refs()?;
     print_git_import_stats(ui, tx.repo(), &import_stats, true)?;
-    warn_if_branches_not_found(ui, tx, branch_names, remotes)
+    warn_if_branches_not_found(ui, tx, branch_names, &remote_names)?;
+    Ok(import_stats)
 }
 
 fn warn_if_branches_not_found(
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ffi::OsStr;
+use std::fs;
 use std::io;
 use std::io::Write as _;
 use std::path::Path;
@@ -25,8 +27,14 @@ use jj_lib::file_util;
 use jj_lib::git;
 use jj_lib::git::parse_git_ref;
 use jj_lib::git::GitRefKind;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::matchers::PrefixMatcher;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::repo_path::RepoPathComponent;
 use jj_lib::view::View;
 use jj_lib::workspace::Workspace;
 
@@ -40,9 +48,11 @@ use crate::command_error::user_error_with_hint;
 use crate::command_error::user_error_with_message;
 use crate::command_error::CommandError;
 use crate::commands::git::maybe_add_gitignore;
+use crate::commands::sparse::update_sparse_patterns_with;
 use crate::git_util::is_colocated_git_workspace;
 use crate::git_util::print_git_export_stats;
 use crate::git_util::print_git_import_stats;
+use crate::text_util::complete_newline;
 use crate::ui::Ui;
 
 /// Create a new Git backed repo.
@@ -79,6 +89,94 @@ pub struct GitInitArgs {
     /// This option is mutually exclusive with `--colocate`.
     #[arg(long, conflicts_with = "colocate", value_hint = clap::ValueHint::DirPath)]
     git_repo: Option<String>,
+
+    /// Seed the new repo from a template directory
+    ///
+    /// The directory must contain a `jj-template.toml` manifest, which may
+    /// specify a repo-level `config` file, a directory of `files` to copy into
+    /// the working copy, `sparse` patterns, and a `description` for the
+    /// initial commit. Paths are relative to the template directory.
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::DirPath)]
+    template: Option<String>,
+}
+
+/// Name of the manifest file in a repository template directory.
+const TEMPLATE_MANIFEST: &str = "jj-template.toml";
+
+/// Contents of a repository template manifest.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RepoTemplate {
+    /// Directory containing the manifest. Other paths are relative to it.
+    #[serde(skip)]
+    root: PathBuf,
+    /// TOML file to install as the repo-level config.
+    config: Option<PathBuf>,
+    /// Directory whose contents are copied into the working copy.
+    files: Option<PathBuf>,
+    /// Sparse patterns for the new workspace.
+    sparse: Option<Vec<String>>,
+    /// Description of the initial commit made from the copied files.
+    description: Option<String>,
+}
+
+impl RepoTemplate {
+    fn load(root: &Path) -> Result<Self, CommandError> {
+        let manifest_path = root.join(TEMPLATE_MANIFEST);
+        let text = fs::read_to_string(&manifest_path).map_err(|err| {
+            user_error_with_message(
+                format!(
+                    "Failed to read repository template manifest {}",
+                    manifest_path.display()
+                ),
+                err,
+            )
+        })?;
+        let template: Self = toml_edit::de::from_str(&text).map_err(|err| {
+            user_error_with_message(
+                format!(
+                    "Failed to parse repository template manifest {}",
+                    manifest_path.display()
+                ),
+                err,
+            )
+        })?;
+        Ok(Self {
+            root: root.to_owned(),
+            ..template
+        })
+    }
+
+    fn sparse_patterns(&self) -> Result<Option<Vec<RepoPathBuf>>, CommandError> {
+        let Some(patterns) = &self.sparse else {
+            return Ok(None);
+        };
+        let patterns: Vec<_> = patterns
+            .iter()
+            .map(|pattern| {
+                RepoPathBuf::from_relative_path(pattern).map_err(|err| {
+                    user_error_with_message(format!("Invalid sparse pattern {pattern:?}"), err)
+                })
+            })
+            .try_collect()?;
+        Ok(Some(
+            patterns.into_iter().sorted_unstable().dedup().collect(),
+        ))
+    }
+
+    /// Installs the template config as the repo-level config. Must be called
+    /// before anything else writes to the repo config file.
+    fn write_repo_config(&self, repo_path: &Path) -> Result<(), CommandError> {
+        if let Some(config) = &self.config {
+            fs::copy(self.root.join(config), repo_path.join("config.toml")).map_err(|err| {
+                user_error_with_message(
+                    format!("Failed to copy template config {}", config.display()),
+                    err,
+                )
+            })?;
+        }
+        Ok(())
+    }
 }
 
 pub fn cmd_git_init(
@@ -93,6 +191,11 @@ pub fn cmd_git_init(
         return Err(cli_error("--at-op is not respected"));
     }
     let cwd = command.cwd();
+    let template = args
+        .template
+        .as_ref()
+        .map(|path| RepoTemplate::load(&cwd.join(path)))
+        .transpose()?;
     let wc_path = cwd.join(&args.destination);
     let wc_path = file_util::create_or_reuse_dir(&wc_path)
         .and_then(|_| dunce::canonicalize(wc_path))
@@ -104,7 +207,11 @@ pub fn cmd_git_init(
         &wc_path,
         args.colocate,
         args.git_repo.as_deref(),
+        template.as_ref(),
     )?;
+    if let Some(template) = &template {
+        apply_template(ui, command, &wc_path, template)?;
+    }
 
     let relative_wc_path = file_util::relative_path(cwd, &wc_path);
     writeln!(
@@ -122,6 +229,7 @@ fn do_init(
     workspace_root: &Path,
     colocate: bool,
     git_repo: Option<&str>,
+    template: Option<&RepoTemplate>,
 ) -> Result<(), CommandError> {
     #[derive(Clone, Debug)]
     enum GitInitMode {
@@ -162,12 +270,18 @@ fn do_init(
     match &init_mode {
         GitInitMode::Colocate => {
             let (workspace, repo) = Workspace::init_colocated_git(&settings, workspace_root)?;
+            if let Some(template) = template {
+                template.write_repo_config(workspace.repo_path())?;
+            }
             let workspace_command = command.for_workable_repo(ui, workspace, repo)?;
             maybe_add_gitignore(&workspace_command)?;
         }
         GitInitMode::External(git_repo_path) => {
             let (workspace, repo) =
                 Workspace::init_external_git(&settings, workspace_root, git_repo_path)?;
+            if let Some(template) = template {
+                template.write_repo_config(workspace.repo_path())?;
+            }
             // Import refs first so all the reachable commits are indexed in
             // chronological order.
             let colocated = is_colocated_git_workspace(&workspace, &repo);
@@ -190,7 +304,140 @@ fn do_init(
             print_trackable_remote_bookmarks(ui, workspace_command.repo().view())?;
         }
         GitInitMode::Internal => {
-            Workspace::init_internal_git(&settings, workspace_root)?;
+            let (workspace, _repo) = Workspace::init_internal_git(&settings, workspace_root)?;
+            if let Some(template) = template {
+                template.write_repo_config(workspace.repo_path())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sets up the working copy of a newly initialized repo from `template`.
+fn apply_template(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    workspace_root: &Path,
+    template: &RepoTemplate,
+) -> Result<(), CommandError> {
+    // Reload with the settings that include the template's repo config.
+    let settings = command.settings_for_new_workspace(workspace_root)?;
+    let workspace = command.load_workspace_at(workspace_root, &settings)?;
+    let repo = workspace.repo_loader().load_at_head()?;
+    let mut workspace_command = command.for_workable_repo(ui, workspace, repo)?;
+    let sparse_patterns = template.sparse_patterns()?;
+    if let Some(patterns) = &sparse_patterns {
+        update_sparse_patterns_with(ui, &mut workspace_command, |_ui, _old_patterns| {
+            Ok(patterns.clone())
+        })?;
+    }
+    if let Some(files) = &template.files {
+        // Files outside the sparse patterns wouldn't be snapshotted, so they
+        // aren't copied at all.
+        let matcher: Box<dyn Matcher> = match &sparse_patterns {
+            Some(patterns) => Box::new(PrefixMatcher::new(patterns)),
+            None => Box::new(EverythingMatcher),
+        };
+        let mut skipped_paths = vec![];
+        copy_dir_contents(
+            &template.root.join(files),
+            workspace_root,
+            RepoPath::root(),
+            matcher.as_ref(),
+            &mut skipped_paths,
+        )
+        .map_err(|err| {
+            user_error_with_message(
+                format!("Failed to copy template files from {}", files.display()),
+                err,
+            )
+        })?;
+        if !skipped_paths.is_empty() {
+            writeln!(
+                ui.warning_default(),
+                "Skipped template files outside the sparse patterns:"
+            )?;
+            for path in skipped_paths.iter().sorted() {
+                writeln!(
+                    ui.stderr(),
+                    "  {}",
+                    workspace_command.format_file_path(path)
+                )?;
+            }
+        }
+    }
+    workspace_command.maybe_snapshot(ui)?;
+    if let Some(description) = &template.description {
+        let mut tx = workspace_command.start_transaction();
+        let wc_commit_id = tx
+            .base_workspace_helper()
+            .get_wc_commit_id()
+            .unwrap()
+            .clone();
+        let wc_commit = tx.repo().store().get_commit(&wc_commit_id)?;
+        let initial_commit = tx
+            .repo_mut()
+            .rewrite_commit(&wc_commit)
+            .set_description(complete_newline(description))
+            .write()?;
+        tx.check_out(&initial_commit)?;
+        tx.finish(ui, "create initial commit from template")?;
+    }
+    Ok(())
+}
+
+/// Recursively copies the contents of `src` into the existing `dst` directory,
+/// which is at `dir` in the working copy. Files not matched by `matcher` are
+/// added to `skipped_paths` instead.
+fn copy_dir_contents(
+    src: &Path,
+    dst: &Path,
+    dir: &RepoPath,
+    matcher: &dyn Matcher,
+    skipped_paths: &mut Vec<RepoPathBuf>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let path = dir.join(to_repo_path_component(&file_name)?);
+        let dst_path = dst.join(&file_name);
+        if entry.file_type()?.is_dir() {
+            if matcher.visit(&path).is_nothing() {
+                collect_file_paths(&entry.path(), &path, skipped_paths)?;
+                continue;
+            }
+            file_util::create_or_reuse_dir(&dst_path)?;
+            copy_dir_contents(&entry.path(), &dst_path, &path, matcher, skipped_paths)?;
+        } else if matcher.matches(&path) {
+            fs::copy(entry.path(), &dst_path)?;
+        } else {
+            skipped_paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn to_repo_path_component(name: &OsStr) -> io::Result<&RepoPathComponent> {
+    name.to_str()
+        .and_then(|name| RepoPathComponent::new(name).ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid file name {name:?}"),
+            )
+        })
+}
+
+/// Adds the paths of the files in the `src` directory, which would be at `dir`
+/// in the working copy, to `paths`.
+fn collect_file_paths(src: &Path, dir: &RepoPath, paths: &mut Vec<RepoPathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let path = dir.join(to_repo_path_component(&entry.file_name())?);
+        if entry.file_type()?.is_dir() {
+            collect_file_paths(&entry.path(), &path, paths)?;
+        } else {
+            paths.push(path);
         }
     }
     Ok(())
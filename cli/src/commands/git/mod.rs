@@ -25,11 +25,16 @@ use std::path::Path;
 
 use clap::Subcommand;
 use jj_lib::config::ConfigFile;
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::config::ConfigSource;
 use jj_lib::git;
+use jj_lib::git::GitFetchTags;
 use jj_lib::git::UnexpectedGitBackendError;
+use jj_lib::ref_name::RemoteName;
 use jj_lib::ref_name::RemoteNameBuf;
 use jj_lib::ref_name::RemoteRefSymbol;
+use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
 
 use self::clone::cmd_git_clone;
@@ -116,6 +121,22 @@ fn get_single_remote(store: &Store) -> Result<Option<RemoteNameBuf>, UnexpectedG
     })
 }
 
+/// Looks up which tags should be fetched from the remote. The
+/// `remotes.<name>.fetch-tags` setting takes precedence over `git.fetch-tags`.
+fn get_fetch_tags(
+    settings: &UserSettings,
+    remote: &RemoteName,
+) -> Result<GitFetchTags, ConfigGetError> {
+    if let Some(tags) = settings
+        .get(["remotes", remote.as_str(), "fetch-tags"])
+        .optional()?
+    {
+        Ok(tags)
+    } else {
+        settings.get("git.fetch-tags")
+    }
+}
+
 /// Sets repository level `trunk()` alias to the specified remote symbol.
 fn write_repository_level_trunk_alias(
     ui: &Ui,
@@ -64,13 +64,15 @@ use crate::commands::git::get_single_remote;
 use crate::complete;
 use crate::formatter::Formatter;
 use crate::git_util::with_remote_git_callbacks;
+use crate::hooks::run_post_push_hook;
 use crate::revset_util::parse_bookmark_name;
 use crate::ui::Ui;
 
 /// Push to a Git remote
 ///
 /// By default, pushes tracking bookmarks pointing to
-/// `remote_bookmarks(remote=<remote>)..@`. Use `--bookmark` to push specific
+/// `remote_bookmarks(remote=<remote>)..@`, and deletes remote bookmarks whose
+/// tracking local bookmarks were deleted. Use `--bookmark` to push specific
 /// bookmarks. Use `--all` to push all bookmarks. Use `--change` to generate
 /// bookmark names based on the change IDs of specific commits.
 ///
@@ -224,6 +226,7 @@ pub fn cmd_git_push(
         &default_remote
     };
 
+    let old_repo = workspace_command.repo().clone();
     let mut tx = workspace_command.start_transaction();
     let view = tx.repo().view();
     let tx_description;
@@ -377,6 +380,31 @@ pub fn cmd_git_push(
             }
         }
 
+        if use_default_revset {
+            // Deleted bookmarks have no local target to be selected by the
+            // default revset, but their deletion should still be propagated to
+            // the tracked remote bookmarks.
+            for (name, targets) in view.local_remote_bookmarks(remote) {
+                if targets.local_target.is_present()
+                    || !targets.remote_ref.is_tracked()
+                    || !seen_bookmarks.insert(name)
+                {
+                    continue;
+                }
+                let allow_delete = true;
+                match classify_bookmark_update(
+                    name.to_remote_symbol(remote),
+                    targets,
+                    allow_new,
+                    allow_delete,
+                ) {
+                    Ok(Some(update)) => bookmark_updates.push((name.to_owned(), update)),
+                    Ok(None) => {}
+                    Err(reason) => reason.print(ui)?,
+                }
+            }
+        }
+
         tx_description = format!(
             "push {names} to git remote {remote}",
             names = make_bookmark_term(
@@ -448,6 +476,13 @@ pub fn cmd_git_push(
     })?;
     process_push_stats(&push_stats)?;
     tx.finish(ui, tx_description)?;
+    run_post_push_hook(
+        ui,
+        &workspace_command,
+        &old_repo,
+        remote,
+        &targets.branch_updates,
+    )?;
     Ok(())
 }
 
@@ -0,0 +1,72 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use jj_lib::git::GitFetch;
+use jj_lib::ref_name::RemoteNameBuf;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::git_util::print_git_import_stats;
+use crate::ui::Ui;
+
+/// Forget remote bookmarks which were deleted on a Git remote
+///
+/// The remote is queried for its current branches, but no objects are
+/// fetched. Remote bookmarks which no longer exist on the remote are deleted,
+/// as `jj git fetch` does for the bookmarks it fetches. Tracked local bookmarks
+/// are deleted too unless they were moved locally.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitRemotePruneArgs {
+    /// The remote's name
+    #[arg(add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: RemoteNameBuf,
+    /// Only list the remote bookmarks which would be deleted
+    #[arg(long)]
+    dry_run: bool,
+}
+
+pub fn cmd_git_remote_prune(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitRemotePruneArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut tx = workspace_command.start_transaction();
+    let git_settings = tx.settings().git_settings()?;
+    let mut git_fetch = GitFetch::new(tx.repo_mut(), &git_settings)?;
+    let stale_names = git_fetch.find_stale_remote_bookmarks(&args.remote)?;
+    if stale_names.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    if args.dry_run {
+        if let Some(mut formatter) = ui.status_formatter() {
+            writeln!(formatter, "Would delete remote bookmarks:")?;
+            for name in &stale_names {
+                write!(formatter, "  ")?;
+                let symbol = name.to_remote_symbol(&args.remote);
+                write!(formatter.labeled("bookmark"), "{symbol}")?;
+                writeln!(formatter)?;
+            }
+        }
+        writeln!(ui.status(), "Dry-run requested, not pruning.")?;
+        return Ok(());
+    }
+    git_fetch.prune(&args.remote, &stale_names)?;
+    let import_stats = git_fetch.import_refs()?;
+    print_git_import_stats(ui, tx.repo(), &import_stats, true)?;
+    tx.finish(ui, format!("prune git remote {}", args.remote.as_symbol()))
+}
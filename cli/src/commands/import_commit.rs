@@ -0,0 +1,167 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::path::PathBuf;
+
+use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::FilesMatcher;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::rewrite::copy_tree_to_store;
+use jj_lib::rewrite::merge_commit_trees;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Apply a commit from another repository as a new commit
+///
+/// Reads the specified revision from the repository at `--from-repo`, copies
+/// the files it touches into this repository, and applies its changes on top
+/// of the destination revision(s) by merging trees, like `jj duplicate` would
+/// within a single repository. The new commit keeps the description and author
+/// of the original one.
+///
+/// The other repository must be a jj workspace, which can be colocated with
+/// Git. Use `--from-dir` and `--to-dir` to move the changes between
+/// subdirectories, for example when the same project is vendored at different
+/// locations.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ImportCommitArgs {
+    /// Path to a workspace of the repository to import the commit from
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::DirPath)]
+    from_repo: PathBuf,
+    /// The revision to import, evaluated in the other repository
+    #[arg(long, short, value_name = "REVSET")]
+    revision: RevisionArg,
+    /// The revision(s) to apply the commit onto
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    destination: Vec<RevisionArg>,
+    /// Only import changes under this directory of the other repository
+    ///
+    /// The directory prefix is removed from the imported paths. The path is
+    /// relative to the root of the other workspace.
+    #[arg(long, value_name = "DIR")]
+    from_dir: Option<PathBuf>,
+    /// Place the imported changes under this directory
+    ///
+    /// The path is relative to the workspace root.
+    #[arg(long, value_name = "DIR")]
+    to_dir: Option<PathBuf>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_import_commit(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ImportCommitArgs,
+) -> Result<(), CommandError> {
+    let parse_dir = |dir: &Option<PathBuf>| match dir {
+        Some(dir) => RepoPathBuf::from_relative_path(dir)
+            .map_err(|err| user_error_with_message(format!("Invalid path {dir:?}"), err)),
+        None => Ok(RepoPathBuf::root()),
+    };
+    let from_dir = parse_dir(&args.from_dir)?;
+    let to_dir = parse_dir(&args.to_dir)?;
+
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let parent_ids = workspace_command
+        .resolve_some_revsets_default_single(ui, &args.destination)?
+        .into_iter()
+        .collect_vec();
+
+    // The source repo may have its own repo config, e.g. for revset aliases.
+    let source_root = command.cwd().join(&args.from_repo);
+    let source_settings = command.settings_for_new_workspace(&source_root)?;
+    let source_workspace = command.load_workspace_at(&source_root, &source_settings)?;
+    let source_repo = source_workspace.repo_loader().load_at_head()?;
+    let source_workspace_command =
+        command.for_workable_repo(ui, source_workspace, source_repo.clone())?;
+    let source_commit = source_workspace_command.resolve_single_rev(ui, &args.revision)?;
+
+    let map_path = |path: &RepoPath| {
+        let relative_path = path.strip_prefix(&from_dir)?;
+        let mut new_path = to_dir.clone();
+        new_path.extend(relative_path.components());
+        Some(new_path)
+    };
+    // Only the changed files need to be copied. Paths missing from both sides
+    // are left unchanged by the merge below.
+    let source_parent_tree = source_commit.parent_tree(source_repo.as_ref())?;
+    let source_tree = source_commit.tree()?;
+    let changed_paths: Vec<_> = source_parent_tree
+        .diff_stream(&source_tree, &EverythingMatcher)
+        .map(|entry| entry.path)
+        .collect()
+        .block_on();
+    let matcher = FilesMatcher::new(&changed_paths);
+    let store = workspace_command.repo().store().clone();
+    let base_tree_id =
+        copy_tree_to_store(&source_parent_tree, &matcher, &store, map_path).block_on()?;
+    let imported_tree_id =
+        copy_tree_to_store(&source_tree, &matcher, &store, map_path).block_on()?;
+
+    let mut tx = workspace_command.start_transaction();
+    let parent_commits: Vec<_> = parent_ids
+        .iter()
+        .map(|id| tx.repo().store().get_commit(id))
+        .try_collect()?;
+    let merged_tree = merge_commit_trees(tx.repo(), &parent_commits)?;
+    let base_tree = store.get_root_tree(&base_tree_id)?;
+    let imported_tree = store.get_root_tree(&imported_tree_id)?;
+    let new_tree = merged_tree.merge(&base_tree, &imported_tree)?;
+    let new_commit = tx
+        .repo_mut()
+        .new_commit(parent_ids, new_tree.id())
+        .set_description(source_commit.description())
+        .set_author(source_commit.author().clone())
+        .write()?;
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(
+            formatter,
+            "Imported commit {} as ",
+            short_commit_hash(source_commit.id())
+        )?;
+        tx.write_commit_summary(formatter.as_mut(), &new_commit)?;
+        writeln!(formatter)?;
+    }
+    tx.finish(
+        ui,
+        format!(
+            "import commit {} from {}",
+            source_commit.id().hex(),
+            args.from_repo.display()
+        ),
+    )?;
+    Ok(())
+}
@@ -16,10 +16,13 @@ use std::slice;
 
 use clap::ArgGroup;
 use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::evolution::walk_predecessors;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
@@ -29,9 +32,15 @@ use crate::ui::Ui;
 ///
 /// This excludes changes from other commits by temporarily rebasing `--from`
 /// onto `--to`'s parents. If you wish to compare the same change across
-/// versions, consider `jj evolog -p` instead.
+/// versions, use `--revision` to compare a commit with its previous version,
+/// or consider `jj evolog -p` to see the whole history.
 #[derive(clap::Args, Clone, Debug)]
-#[command(group(ArgGroup::new("to_diff").args(&["from", "to"]).multiple(true).required(true)))]
+#[command(group(
+    ArgGroup::new("to_diff")
+        .args(&["from", "to", "revision"])
+        .multiple(true)
+        .required(true)
+))]
 #[command(mut_arg("ignore_all_space", |a| a.short('w')))]
 #[command(mut_arg("ignore_space_change", |a| a.short('b')))]
 pub(crate) struct InterdiffArgs {
@@ -51,6 +60,18 @@ pub(crate) struct InterdiffArgs {
         add = ArgValueCompleter::new(complete::revset_expression_all),
     )]
     to: Option<RevisionArg>,
+    /// Show changes to this revision since its previous version
+    ///
+    /// The previous version is the predecessor recorded in the operation log,
+    /// i.e. the commit shown below it in `jj evolog`.
+    #[arg(
+        long,
+        short,
+        value_name = "REVSET",
+        conflicts_with_all = ["from", "to"],
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: Option<RevisionArg>,
     /// Restrict the diff to these paths
     #[arg(
         value_name = "FILESETS",
@@ -69,10 +90,31 @@ pub(crate) fn cmd_interdiff(
     args: &InterdiffArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
-    let from =
-        workspace_command.resolve_single_rev(ui, args.from.as_ref().unwrap_or(&RevisionArg::AT))?;
-    let to =
-        workspace_command.resolve_single_rev(ui, args.to.as_ref().unwrap_or(&RevisionArg::AT))?;
+    let (from_commits, to) = if let Some(revision) = &args.revision {
+        let to = workspace_command.resolve_single_rev(ui, revision)?;
+        let repo = workspace_command.repo();
+        let entry = walk_predecessors(repo, slice::from_ref(to.id()))
+            .filter_ok(|entry| entry.commit.id() == to.id())
+            .next()
+            .transpose()?;
+        let predecessors: Vec<_> = match &entry {
+            Some(entry) => entry.predecessors().try_collect()?,
+            None => vec![],
+        };
+        if predecessors.is_empty() {
+            return Err(user_error(format!(
+                "Revision {} has no previous version",
+                workspace_command.format_commit_summary(&to)
+            )));
+        }
+        (predecessors, to)
+    } else {
+        let from = workspace_command
+            .resolve_single_rev(ui, args.from.as_ref().unwrap_or(&RevisionArg::AT))?;
+        let to = workspace_command
+            .resolve_single_rev(ui, args.to.as_ref().unwrap_or(&RevisionArg::AT))?;
+        (vec![from], to)
+    };
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
@@ -81,7 +123,7 @@ pub(crate) fn cmd_interdiff(
     diff_renderer.show_inter_diff(
         ui,
         ui.stdout_formatter().as_mut(),
-        slice::from_ref(&from),
+        &from_commits,
         &to,
         matcher.as_ref(),
         ui.term_width(),
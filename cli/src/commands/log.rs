@@ -12,20 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::slice;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
+use futures::executor::block_on_stream;
 use itertools::Itertools as _;
+use jj_lib::backend::BackendResult;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::graph::reverse_graph;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
 use jj_lib::graph::TopoGroupedGraphIterator;
-use jj_lib::repo::Repo as _;
+use jj_lib::matchers::FilesMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::store::Store;
 use tracing::instrument;
 
 use crate::cli_util::format_template;
@@ -75,6 +85,12 @@ pub(crate) struct LogArgs {
         add = ArgValueCompleter::new(complete::revset_expression_all),
     )]
     revisions: Vec<RevisionArg>,
+    /// Also show the revisions listed in the given file
+    ///
+    /// The file should contain full commit or change IDs separated by
+    /// whitespace.
+    #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+    revisions_file: Option<PathBuf>,
     /// Show revisions modifying the given paths
     #[arg(
         value_name = "FILESETS",
@@ -82,6 +98,18 @@ pub(crate) struct LogArgs {
         add = ArgValueCompleter::new(complete::log_files),
     )]
     paths: Vec<String>,
+    /// Show the history of the given file, following it across renames
+    ///
+    /// The file is tracked backwards from the heads of `--revisions` (or from
+    /// the working-copy commit), switching to the old path at each rename.
+    /// Revisions where the file was renamed are marked in the output.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "paths",
+        value_hint = clap::ValueHint::FilePath,
+    )]
+    follow: Option<String>,
     /// Limit number of revisions to show
     ///
     /// Applied after revisions are filtered and reordered topologically, but
@@ -128,13 +156,38 @@ pub(crate) fn cmd_log(
     let settings = workspace_command.settings();
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
-    let revset_expression = {
+    let revisions_file = args.revisions_file.as_deref();
+    let has_revisions = !args.revisions.is_empty() || revisions_file.is_some();
+    let mut followed_commits = HashMap::new();
+    let revset_expression = if let Some(follow_path) = &args.follow {
+        let path = workspace_command.parse_file_path(follow_path)?;
+        let candidates_expression = if !has_revisions {
+            workspace_command.parse_revset(ui, &RevisionArg::from("::@".to_owned()))?
+        } else {
+            workspace_command.parse_union_revsets_and_file(ui, &args.revisions, revisions_file)?
+        };
+        let repo = workspace_command.repo().as_ref();
+        let candidate_ids: Vec<CommitId> = candidates_expression
+            .evaluate_to_commit_ids()?
+            .try_collect()?;
+        let head_ids: Vec<CommitId> = RevsetExpression::commits(candidate_ids.clone())
+            .heads()
+            .evaluate(repo)?
+            .iter()
+            .try_collect()?;
+        followed_commits = follow_file_history(repo, &head_ids, &path, args.limit)?;
+        let shown_ids = candidate_ids
+            .into_iter()
+            .filter(|id| followed_commits.contains_key(id))
+            .collect();
+        workspace_command.attach_revset_evaluator(RevsetExpression::commits(shown_ids))
+    } else {
         // only use default revset if neither revset nor path are specified
-        let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
+        let mut expression = if !has_revisions && args.paths.is_empty() {
             let revset_string = settings.get_string("revsets.log")?;
             workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
-        } else if !args.revisions.is_empty() {
-            workspace_command.parse_union_revsets(ui, &args.revisions)?
+        } else if has_revisions {
+            workspace_command.parse_union_revsets_and_file(ui, &args.revisions, revisions_file)?
         } else {
             // a path was specified so we use all() and add path filter later
             workspace_command.attach_revset_evaluator(RevsetExpression::all())
@@ -201,7 +254,7 @@ pub(crate) fn cmd_log(
                 // because the prioritized commit must exist in the input set.
                 let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
                 if args.reversed {
-                    Box::new(reverse_graph(forward_iter, |id| id)?.into_iter().map(Ok))
+                    Box::new(reverse_graph(forward_iter, |id| id)?.map(Ok))
                 } else {
                     Box::new(forward_iter)
                 }
@@ -248,13 +301,26 @@ pub(crate) fn cmd_log(
                 if !buffer.ends_with(b"\n") {
                     buffer.push(b'\n');
                 }
+                let followed = followed_commits.get(commit.id());
+                if let Some(source) = followed.and_then(|f| f.renamed_from.as_ref()) {
+                    let mut formatter = ui.new_formatter(&mut buffer);
+                    writeln!(
+                        formatter.as_mut().labeled("rename"),
+                        "(renamed from {})",
+                        workspace_command.format_file_path(source)
+                    )?;
+                }
                 if let Some(renderer) = &diff_renderer {
+                    let followed_matcher = followed.map(FollowedCommit::to_matcher);
+                    let matcher = followed_matcher
+                        .as_ref()
+                        .map_or(matcher.as_ref(), |m| m as &dyn Matcher);
                     let mut formatter = ui.new_formatter(&mut buffer);
                     renderer.show_patch(
                         ui,
                         formatter.as_mut(),
                         &commit,
-                        matcher.as_ref(),
+                        matcher,
                         within_graph.width(),
                     )?;
                 }
@@ -299,9 +365,21 @@ pub(crate) fn cmd_log(
                 let commit = commit_or_error?;
                 with_content_format
                     .write(formatter, |formatter| template.format(&commit, formatter))?;
+                let followed = followed_commits.get(commit.id());
+                if let Some(source) = followed.and_then(|f| f.renamed_from.as_ref()) {
+                    writeln!(
+                        formatter.labeled("rename"),
+                        "(renamed from {})",
+                        workspace_command.format_file_path(source)
+                    )?;
+                }
                 if let Some(renderer) = &diff_renderer {
+                    let followed_matcher = followed.map(FollowedCommit::to_matcher);
+                    let matcher = followed_matcher
+                        .as_ref()
+                        .map_or(matcher.as_ref(), |m| m as &dyn Matcher);
                     let width = ui.term_width();
-                    renderer.show_patch(ui, formatter, &commit, matcher.as_ref(), width)?;
+                    renderer.show_patch(ui, formatter, &commit, matcher, width)?;
                 }
             }
         }
@@ -309,7 +387,7 @@ pub(crate) fn cmd_log(
 
     // Check to see if the user might have specified a path when they intended
     // to specify a revset.
-    if let ([], [only_path]) = (args.revisions.as_slice(), args.paths.as_slice()) {
+    if let (false, [only_path]) = (has_revisions, args.paths.as_slice()) {
         if only_path == "." && workspace_command.parse_file_path(only_path)?.is_root() {
             // For users of e.g. Mercurial, where `.` indicates the current commit.
             writeln!(
@@ -333,3 +411,100 @@ pub(crate) fn cmd_log(
 
     Ok(())
 }
+
+/// Commit which modified a followed file.
+struct FollowedCommit {
+    /// Path of the file in this commit.
+    path: RepoPathBuf,
+    /// Path of the file in the parent commit if it was renamed or copied.
+    renamed_from: Option<RepoPathBuf>,
+}
+
+impl FollowedCommit {
+    fn to_matcher(&self) -> FilesMatcher {
+        FilesMatcher::new(itertools::chain([&self.path], &self.renamed_from))
+    }
+}
+
+/// Walks ancestors of `head_ids` and collects commits which modified the file
+/// at `path`, switching to the source path where the file was renamed.
+///
+/// A branch of history ends where the file didn't exist in the parent. The
+/// walk stops once `limit` commits have been collected.
+fn follow_file_history(
+    repo: &dyn Repo,
+    head_ids: &[CommitId],
+    path: &RepoPathBuf,
+    limit: Option<usize>,
+) -> Result<HashMap<CommitId, FollowedCommit>, CommandError> {
+    let limit = limit.unwrap_or(usize::MAX);
+    let store = repo.store();
+    let mut pending_paths: HashMap<CommitId, RepoPathBuf> = head_ids
+        .iter()
+        .map(|id| (id.clone(), path.clone()))
+        .collect();
+    let mut followed_commits = HashMap::new();
+    let ancestors = RevsetExpression::commits(head_ids.to_vec())
+        .ancestors()
+        .evaluate(repo)?;
+    for commit_id in ancestors.iter() {
+        if pending_paths.is_empty() || followed_commits.len() >= limit {
+            break;
+        }
+        let commit_id = commit_id?;
+        let Some(path) = pending_paths.remove(&commit_id) else {
+            continue;
+        };
+        let commit = store.get_commit(&commit_id)?;
+        let value = commit.tree()?.path_value(&path)?;
+        let mut modified = false;
+        let mut renamed_from = None;
+        for parent in commit.parents() {
+            let parent = parent?;
+            let parent_tree = parent.tree()?;
+            let mut parent_path = path.clone();
+            let mut parent_value = parent_tree.path_value(&path)?;
+            if value.is_present() && parent_value.is_absent() {
+                if let Some(source) = find_copy_source(store, parent.id(), &commit_id, &path)? {
+                    parent_value = parent_tree.path_value(&source)?;
+                    parent_path = source;
+                }
+            }
+            if parent_value.is_absent() {
+                modified |= value.is_present();
+                continue;
+            }
+            if parent_path != path {
+                modified = true;
+                renamed_from = Some(parent_path.clone());
+            } else if parent_value != value {
+                modified = true;
+            }
+            pending_paths
+                .entry(parent.id().clone())
+                .or_insert(parent_path);
+        }
+        if modified {
+            followed_commits.insert(commit_id, FollowedCommit { path, renamed_from });
+        }
+    }
+    Ok(followed_commits)
+}
+
+/// Looks up the path `target` was copied or renamed from between `parent_id`
+/// and `commit_id`.
+fn find_copy_source(
+    store: &Store,
+    parent_id: &CommitId,
+    commit_id: &CommitId,
+    target: &RepoPathBuf,
+) -> BackendResult<Option<RepoPathBuf>> {
+    let stream = store.get_copy_records(Some(slice::from_ref(target)), parent_id, commit_id)?;
+    for record in block_on_stream(stream) {
+        let record = record?;
+        if record.target == *target && record.source != *target {
+            return Ok(Some(record.source));
+        }
+    }
+    Ok(None)
+}
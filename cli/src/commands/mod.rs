@@ -17,7 +17,9 @@ mod absorb;
 mod backout;
 #[cfg(feature = "bench")]
 mod bench;
+mod bisect;
 mod bookmark;
+mod children;
 mod commit;
 mod config;
 mod debug;
@@ -27,19 +29,23 @@ mod diffedit;
 mod duplicate;
 mod edit;
 mod evolog;
+mod evolve;
 mod file;
 mod fix;
 #[cfg(feature = "git")]
 mod git;
 mod help;
+mod import_commit;
 mod interdiff;
 mod log;
 mod new;
 mod next;
 mod operation;
 mod parallelize;
+mod parents;
 mod prev;
 mod rebase;
+mod reorder;
 mod resolve;
 mod restore;
 mod revert;
@@ -94,7 +100,10 @@ enum Command {
     #[command(subcommand)]
     Bench(bench::BenchCommand),
     #[command(subcommand)]
+    Bisect(bisect::BisectCommand),
+    #[command(subcommand)]
     Bookmark(bookmark::BookmarkCommand),
+    Children(children::ChildrenArgs),
     Commit(commit::CommitArgs),
     #[command(subcommand)]
     Config(config::ConfigCommand),
@@ -107,6 +116,7 @@ enum Command {
     Edit(edit::EditArgs),
     #[command(alias = "obslog", visible_alias = "evolution-log")]
     Evolog(evolog::EvologArgs),
+    Evolve(evolve::EvolveArgs),
     #[command(subcommand)]
     File(file::FileCommand),
     Fix(fix::FixArgs),
@@ -114,6 +124,7 @@ enum Command {
     #[command(subcommand)]
     Git(git::GitCommand),
     Help(help::HelpArgs),
+    ImportCommit(import_commit::ImportCommitArgs),
     Interdiff(interdiff::InterdiffArgs),
     Log(log::LogArgs),
     New(new::NewArgs),
@@ -122,14 +133,14 @@ enum Command {
     #[command(visible_alias = "op")]
     Operation(operation::OperationCommand),
     Parallelize(parallelize::ParallelizeArgs),
+    Parents(parents::ParentsArgs),
     Prev(prev::PrevArgs),
     Rebase(rebase::RebaseArgs),
+    Reorder(reorder::ReorderArgs),
     Resolve(resolve::ResolveArgs),
     Restore(restore::RestoreArgs),
     Revert(revert::RevertArgs),
     Root(root::RootArgs),
-    #[command(hide = true)]
-    // TODO: Flesh out.
     Run(run::RunArgs),
     Show(show::ShowArgs),
     Sign(sign::SignArgs),
@@ -164,7 +175,9 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Backout(args) => backout::cmd_backout(ui, command_helper, args),
         #[cfg(feature = "bench")]
         Command::Bench(args) => bench::cmd_bench(ui, command_helper, args),
+        Command::Bisect(args) => bisect::cmd_bisect(ui, command_helper, args),
         Command::Bookmark(args) => bookmark::cmd_bookmark(ui, command_helper, args),
+        Command::Children(args) => children::cmd_children(ui, command_helper, args),
         Command::Commit(args) => commit::cmd_commit(ui, command_helper, args),
         Command::Config(args) => config::cmd_config(ui, command_helper, args),
         Command::Debug(args) => debug::cmd_debug(ui, command_helper, args),
@@ -178,15 +191,19 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         #[cfg(feature = "git")]
         Command::Git(args) => git::cmd_git(ui, command_helper, args),
         Command::Help(args) => help::cmd_help(ui, command_helper, args),
+        Command::ImportCommit(args) => import_commit::cmd_import_commit(ui, command_helper, args),
         Command::Interdiff(args) => interdiff::cmd_interdiff(ui, command_helper, args),
         Command::Log(args) => log::cmd_log(ui, command_helper, args),
         Command::New(args) => new::cmd_new(ui, command_helper, args),
         Command::Next(args) => next::cmd_next(ui, command_helper, args),
         Command::Evolog(args) => evolog::cmd_evolog(ui, command_helper, args),
+        Command::Evolve(args) => evolve::cmd_evolve(ui, command_helper, args),
         Command::Operation(args) => operation::cmd_operation(ui, command_helper, args),
         Command::Parallelize(args) => parallelize::cmd_parallelize(ui, command_helper, args),
+        Command::Parents(args) => parents::cmd_parents(ui, command_helper, args),
         Command::Prev(args) => prev::cmd_prev(ui, command_helper, args),
         Command::Rebase(args) => rebase::cmd_rebase(ui, command_helper, args),
+        Command::Reorder(args) => reorder::cmd_reorder(ui, command_helper, args),
         Command::Resolve(args) => resolve::cmd_resolve(ui, command_helper, args),
         Command::Restore(args) => restore::cmd_restore(ui, command_helper, args),
         Command::Revert(args) => revert::cmd_revert(ui, command_helper, args),
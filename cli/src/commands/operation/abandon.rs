@@ -19,6 +19,8 @@ use std::slice;
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::workspace::Workspace;
 
 use crate::cli_util::short_operation_hash;
 use crate::cli_util::CommandHelper;
@@ -104,19 +106,38 @@ pub fn cmd_op_abandon(
         return Err(err);
     }
 
-    // Reparent descendants, count the number of abandoned operations.
-    let stats = op_walk::reparent_range(
-        op_store.as_ref(),
-        &abandon_head_ops,
+    reparent_onto(
+        ui,
+        command,
+        &mut workspace,
         &current_head_ops,
+        &abandon_head_ops,
         &abandon_root_op,
-    )?;
+    )
+}
+
+/// Reparents the operation range `root_ops..current_head_ops` onto `dest_op`
+/// and updates the operation heads and the working-copy operation
+/// accordingly.
+pub(super) fn reparent_onto(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    workspace: &mut Workspace,
+    current_head_ops: &[Operation],
+    root_ops: &[Operation],
+    dest_op: &Operation,
+) -> Result<(), CommandError> {
+    let repo_loader = workspace.repo_loader();
+    let op_store = repo_loader.op_store();
+    let op_heads_store = repo_loader.op_heads_store();
+    // Reparent descendants, count the number of abandoned operations.
+    let stats = op_walk::reparent_range(op_store.as_ref(), root_ops, current_head_ops, dest_op)?;
     assert_eq!(
         current_head_ops.len(),
         stats.new_head_ids.len(),
-        "all current_head_ops should be reparented as they aren't included in abandon_head_ops"
+        "all current_head_ops should be reparented as they aren't included in root_ops"
     );
-    let reparented_head_ops = || iter::zip(&current_head_ops, &stats.new_head_ids);
+    let reparented_head_ops = || iter::zip(current_head_ops, &stats.new_head_ids);
     if reparented_head_ops().all(|(old, new_id)| old.id() == new_id) {
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
@@ -0,0 +1,114 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::backend::Timestamp;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::op_walk;
+use jj_lib::op_walk::OpRetentionPolicy;
+
+use super::abandon::reparent_onto;
+use crate::cli_util::CommandHelper;
+use crate::command_error::cli_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Discard operations outside of the retention window
+///
+/// Operations which are older than the `--max-age-days` or which don't fit in
+/// the most recent `--max-count` operations are abandoned, and the oldest
+/// retained operations are reparented onto the root operation. Their views are
+/// kept as is, so `jj undo` and `jj op restore` keep working within the
+/// retained window.
+///
+/// The defaults are read from the `operation.retention.max-count` and
+/// `operation.retention.max-age-days` settings. As with `jj op abandon`, the
+/// abandoned operations and unreachable commits can later be garbage
+/// collected by using `jj util gc` command.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationCompactArgs {
+    /// Maximum number of operations to keep
+    #[arg(long, value_name = "COUNT")]
+    max_count: Option<usize>,
+
+    /// Abandon operations older than this many days
+    #[arg(long, value_name = "DAYS")]
+    max_age_days: Option<u32>,
+}
+
+pub fn cmd_op_compact(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationCompactArgs,
+) -> Result<(), CommandError> {
+    let settings = command.settings();
+    let max_count = match args.max_count {
+        Some(count) => Some(count),
+        None => settings
+            .get::<usize>("operation.retention.max-count")
+            .optional()?,
+    };
+    let max_age_days = match args.max_age_days {
+        Some(days) => Some(days),
+        None => settings
+            .get::<u32>("operation.retention.max-age-days")
+            .optional()?,
+    };
+    if max_count == Some(0) {
+        return Err(cli_error("--max-count must be at least 1"));
+    }
+    let min_end_time = max_age_days.map(|days| {
+        let MillisSinceEpoch(now) = Timestamp::now().timestamp;
+        MillisSinceEpoch(now - i64::from(days) * 86400 * 1000)
+    });
+    let policy = OpRetentionPolicy {
+        max_count,
+        min_end_time,
+    };
+    if policy.is_unlimited() {
+        return Err(user_error_with_hint(
+            "No retention policy is configured",
+            "Pass --max-count or --max-age-days, or set `operation.retention.max-count` or \
+             `operation.retention.max-age-days`",
+        ));
+    }
+
+    // Don't load the repo so that this command can be used to recover from
+    // corrupted repo state.
+    let mut workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op_store = repo_loader.op_store();
+    let op_heads_store = repo_loader.op_heads_store();
+    if command.global_args().at_operation.is_some() {
+        return Err(cli_error("--at-op is not respected"));
+    }
+    let current_head_ops = op_walk::get_current_head_ops(op_store, op_heads_store.as_ref())?;
+    let expired_ops = op_walk::find_expired_ops(&current_head_ops, &policy)?;
+    if expired_ops.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    let root_op = repo_loader.root_operation();
+    reparent_onto(
+        ui,
+        command,
+        &mut workspace,
+        &current_head_ops,
+        &expired_ops,
+        &root_op,
+    )
+}
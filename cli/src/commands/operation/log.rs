@@ -12,16 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::iter;
 use std::slice;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::graph::reverse_graph;
 use jj_lib::graph::GraphEdge;
+use jj_lib::graph::GraphNode;
 use jj_lib::op_store::OpStoreError;
+use jj_lib::op_store::OperationId;
 use jj_lib::op_walk;
 use jj_lib::operation::Operation;
 use jj_lib::repo::RepoLoader;
+use jj_lib::str_util::StringPattern;
 
 use super::diff::show_op_diff;
 use crate::cli_util::format_template;
@@ -56,6 +62,21 @@ pub struct OperationLogArgs {
     /// Show operations in the opposite order (older operations first)
     #[arg(long)]
     reversed: bool,
+    /// Show only operations that changed these local bookmarks
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select bookmarks by [wildcard pattern]. In the graph, operations that
+    /// aren't shown are elided.
+    ///
+    /// [wildcard pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(
+        long = "bookmark",
+        value_name = "BOOKMARK",
+        value_parser = StringPattern::parse,
+        add = ArgValueCandidates::new(complete::bookmarks),
+    )]
+    bookmarks: Vec<StringPattern>,
     /// Don't show the graph, show a flat list of operations
     #[arg(long)]
     no_graph: bool,
@@ -199,20 +220,29 @@ fn do_op_log(
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
-    let iter =
-        op_walk::walk_ancestors(slice::from_ref(current_op)).take(args.limit.unwrap_or(usize::MAX));
+    let ops = op_walk::walk_ancestors(slice::from_ref(current_op));
+    let limit = args.limit.unwrap_or(usize::MAX);
+    let is_shown = |op: &Operation| op_changes_bookmarks(op, &args.bookmarks);
 
     if !args.no_graph {
         let mut raw_output = formatter.raw()?;
         let mut graph = get_graphlog(graph_style, raw_output.as_mut());
-        let iter = iter.map(|op| -> Result<_, OpStoreError> {
-            let op = op?;
-            let ids = op.parent_ids();
-            let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
-            Ok((op, edges))
-        });
+        let iter: Box<dyn Iterator<Item = _> + '_> = if args.bookmarks.is_empty() {
+            Box::new(ops.take(limit).map(|op| -> Result<_, OpStoreError> {
+                let op = op?;
+                let ids = op.parent_ids();
+                let edges = ids.iter().cloned().map(GraphEdge::direct).collect_vec();
+                Ok((op, edges))
+            }))
+        } else {
+            let mut filter = OpGraphFilter::new(is_shown);
+            Box::new(
+                ops.filter_map(move |op| op.and_then(|op| filter.node(op)).transpose())
+                    .take(limit),
+            )
+        };
         let iter_nodes: Box<dyn Iterator<Item = _>> = if args.reversed {
-            Box::new(reverse_graph(iter, Operation::id)?.into_iter().map(Ok))
+            Box::new(reverse_graph(iter, Operation::id)?.map(Ok))
         } else {
             Box::new(iter)
         };
@@ -239,7 +269,19 @@ fn do_op_log(
             )?;
         }
     } else {
-        let iter: Box<dyn Iterator<Item = _>> = if args.reversed {
+        let iter: Box<dyn Iterator<Item = _> + '_> = if args.bookmarks.is_empty() {
+            Box::new(ops)
+        } else {
+            Box::new(
+                ops.map(|op| -> Result<_, OpStoreError> {
+                    let op = op?;
+                    Ok(is_shown(&op)?.then_some(op))
+                })
+                .filter_map(Result::transpose),
+            )
+        };
+        let iter = iter.take(limit);
+        let iter: Box<dyn Iterator<Item = _> + '_> = if args.reversed {
             Box::new(iter.collect_vec().into_iter().rev())
         } else {
             Box::new(iter)
@@ -255,3 +297,124 @@ fn do_op_log(
 
     Ok(())
 }
+
+/// Returns true if any local bookmark matching the `patterns` points to
+/// different targets in the `op` and its parent operations.
+fn op_changes_bookmarks(op: &Operation, patterns: &[StringPattern]) -> Result<bool, OpStoreError> {
+    let view = op.view()?;
+    let parent_views: Vec<_> = op.parents().map(|parent| parent?.view()).try_collect()?;
+    let names: HashSet<_> = iter::once(&view)
+        .chain(&parent_views)
+        .flat_map(|view| {
+            patterns
+                .iter()
+                .flat_map(|pattern| view.local_bookmarks_matching(pattern))
+                .map(|(name, _)| name)
+        })
+        .collect();
+    Ok(names.into_iter().any(|name| {
+        let target = view.get_local_bookmark(name);
+        parent_views
+            .iter()
+            .all(|parent_view| parent_view.get_local_bookmark(name) != target)
+    }))
+}
+
+/// Builds graph nodes of the operations for which `is_shown` returns true.
+/// Edges to omitted operations are replaced with indirect edges to their
+/// nearest shown ancestors, which are only looked up as far as needed.
+struct OpGraphFilter<F> {
+    is_shown: F,
+    shown_cache: HashMap<OperationId, bool>,
+    // Nearest shown ancestors of omitted operations
+    omitted_heads: HashMap<OperationId, Vec<OperationId>>,
+}
+
+impl<F> OpGraphFilter<F>
+where
+    F: FnMut(&Operation) -> Result<bool, OpStoreError>,
+{
+    fn new(is_shown: F) -> Self {
+        OpGraphFilter {
+            is_shown,
+            shown_cache: HashMap::new(),
+            omitted_heads: HashMap::new(),
+        }
+    }
+
+    fn is_shown(&mut self, op: &Operation) -> Result<bool, OpStoreError> {
+        if let Some(&shown) = self.shown_cache.get(op.id()) {
+            return Ok(shown);
+        }
+        let shown = (self.is_shown)(op)?;
+        self.shown_cache.insert(op.id().clone(), shown);
+        Ok(shown)
+    }
+
+    /// Returns the graph node of the `op` if it should be shown.
+    fn node(
+        &mut self,
+        op: Operation,
+    ) -> Result<Option<GraphNode<Operation, OperationId>>, OpStoreError> {
+        if !self.is_shown(&op)? {
+            return Ok(None);
+        }
+        let mut edges: Vec<GraphEdge<OperationId>> = vec![];
+        for parent in op.parents() {
+            let parent = parent?;
+            let parent_edges = if self.is_shown(&parent)? {
+                vec![GraphEdge::direct(parent.id().clone())]
+            } else {
+                self.nearest_shown_ancestors(parent)?
+                    .iter()
+                    .cloned()
+                    .map(GraphEdge::indirect)
+                    .collect()
+            };
+            for edge in parent_edges {
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+        }
+        Ok(Some((op, edges)))
+    }
+
+    /// Returns the nearest shown ancestors of the omitted `op`.
+    fn nearest_shown_ancestors(&mut self, op: Operation) -> Result<&[OperationId], OpStoreError> {
+        let op_id = op.id().clone();
+        // Visit parents before their children without recursion, since long
+        // runs of omitted operations are common.
+        let mut stack = vec![(op, false)];
+        while let Some((op, parents_visited)) = stack.pop() {
+            if self.omitted_heads.contains_key(op.id()) {
+                continue;
+            }
+            let parents: Vec<Operation> = op.parents().try_collect()?;
+            if parents_visited {
+                let mut ids: Vec<OperationId> = vec![];
+                for parent in &parents {
+                    let parent_ids = if self.is_shown(parent)? {
+                        slice::from_ref(parent.id())
+                    } else {
+                        &self.omitted_heads[parent.id()]
+                    };
+                    for id in parent_ids {
+                        if !ids.contains(id) {
+                            ids.push(id.clone());
+                        }
+                    }
+                }
+                self.omitted_heads.insert(op.id().clone(), ids);
+            } else {
+                stack.push((op, true));
+                for parent in parents {
+                    if !self.is_shown(&parent)? && !self.omitted_heads.contains_key(parent.id()) {
+                        stack.push((parent, false));
+                    }
+                }
+            }
+        }
+        Ok(&self.omitted_heads[&op_id])
+    }
+}
@@ -13,25 +13,34 @@
 // limitations under the License.
 
 mod abandon;
+mod compact;
 mod diff;
 mod log;
+mod recover;
 mod restore;
 mod show;
 pub mod undo;
+mod wait;
 
 use abandon::cmd_op_abandon;
 use abandon::OperationAbandonArgs;
 use clap::Subcommand;
+use compact::cmd_op_compact;
+use compact::OperationCompactArgs;
 use diff::cmd_op_diff;
 use diff::OperationDiffArgs;
 use log::cmd_op_log;
 use log::OperationLogArgs;
+use recover::cmd_op_recover;
+use recover::OperationRecoverArgs;
 use restore::cmd_op_restore;
 use restore::OperationRestoreArgs;
 use show::cmd_op_show;
 use show::OperationShowArgs;
 use undo::cmd_op_undo;
 use undo::OperationUndoArgs;
+use wait::cmd_op_wait;
+use wait::OperationWaitArgs;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -46,11 +55,14 @@ use crate::ui::Ui;
 #[derive(Subcommand, Clone, Debug)]
 pub enum OperationCommand {
     Abandon(OperationAbandonArgs),
+    Compact(OperationCompactArgs),
     Diff(OperationDiffArgs),
     Log(OperationLogArgs),
+    Recover(OperationRecoverArgs),
     Restore(OperationRestoreArgs),
     Show(OperationShowArgs),
     Undo(OperationUndoArgs),
+    Wait(OperationWaitArgs),
 }
 
 pub fn cmd_operation(
@@ -60,11 +72,14 @@ pub fn cmd_operation(
 ) -> Result<(), CommandError> {
     match subcommand {
         OperationCommand::Abandon(args) => cmd_op_abandon(ui, command, args),
+        OperationCommand::Compact(args) => cmd_op_compact(ui, command, args),
         OperationCommand::Diff(args) => cmd_op_diff(ui, command, args),
         OperationCommand::Log(args) => cmd_op_log(ui, command, args),
+        OperationCommand::Recover(args) => cmd_op_recover(ui, command, args),
         OperationCommand::Restore(args) => cmd_op_restore(ui, command, args),
         OperationCommand::Show(args) => cmd_op_show(ui, command, args),
         OperationCommand::Undo(args) => cmd_op_undo(ui, command, args),
+        OperationCommand::Wait(args) => cmd_op_wait(ui, command, args),
     }
 }
 
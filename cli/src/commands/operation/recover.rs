@@ -0,0 +1,71 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::op_heads_store;
+
+use crate::cli_util::short_operation_hash;
+use crate::cli_util::CommandHelper;
+use crate::command_error::cli_error;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Recover lost operation heads
+///
+/// If the operation heads are deleted or point to missing operations, the repo
+/// can't be loaded even though the operations are still stored. This command
+/// scans the operation store and makes the operations that have no child
+/// operations the heads again. If there are several such operations, they are
+/// merged like concurrent operations the next time the repo is loaded.
+///
+/// Operations that can't be read are skipped with a warning.
+///
+/// Operations discarded by `jj op abandon` are kept until they're garbage
+/// collected by `jj util gc`, so they may reappear as concurrent operations.
+/// Abandon them again if needed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationRecoverArgs {}
+
+pub fn cmd_op_recover(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &OperationRecoverArgs,
+) -> Result<(), CommandError> {
+    // Don't load the repo since it can't be loaded without operation heads.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op_store = repo_loader.op_store();
+    if command.global_args().at_operation.is_some() {
+        return Err(cli_error("--at-op is not respected"));
+    }
+    let recovered = op_heads_store::recover_op_heads::<CommandError>(
+        repo_loader.op_heads_store().as_ref(),
+        op_store,
+    )?;
+    for (id, err) in &recovered.unreadable_ops {
+        writeln!(
+            ui.warning_default(),
+            "Skipped unreadable operation {}: {err}",
+            short_operation_hash(id)
+        )?;
+    }
+    writeln!(ui.status(), "Operation heads are now:")?;
+    for id in &recovered.head_ids {
+        let op = op_store.read_operation(id)?;
+        let description = op.metadata.description.lines().next().unwrap_or_default();
+        writeln!(ui.status(), "  {} {description}", short_operation_hash(id))?;
+    }
+    Ok(())
+}
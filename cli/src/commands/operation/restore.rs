@@ -12,13 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::slice;
+
+use chrono::TimeZone as _;
 use clap_complete::ArgValueCandidates;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
+use jj_lib::repo::Repo as _;
+use jj_lib::time_util;
 
 use super::view_with_desired_portions_restored;
 use super::UndoWhatToRestore;
 use super::DEFAULT_UNDO_WHAT;
+use crate::cli_util::short_operation_hash;
+use crate::cli_util::working_copy_backup_tag;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::ui::Ui;
@@ -27,6 +41,16 @@ use crate::ui::Ui;
 ///
 /// This restores the repo to the state at the specified operation, effectively
 /// undoing all later operations. It does so by creating a new operation.
+///
+/// Instead of an operation, a point in time can be specified with `--at`, in
+/// which case the latest operation at or before that time is restored.
+///
+/// With `--working-copy-only`, only the file contents of the working-copy
+/// commit are restored. Commands that may discard the working-copy contents
+/// (`jj restore` and `jj abandon` of the working-copy commit, and checking out
+/// a conflicted commit) record a backup of the previous working-copy commit in
+/// their operation. Without an operation, the latest such backup is restored,
+/// which brings back the lost files without undoing anything else.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationRestoreArgs {
     /// The operation to restore to
@@ -34,14 +58,33 @@ pub struct OperationRestoreArgs {
     /// Use `jj op log` to find an operation to restore to. Use e.g. `jj
     /// --at-op=<operation ID> log` before restoring to an operation to see the
     /// state of the repo at that operation.
-    #[arg(add = ArgValueCandidates::new(complete::operations))]
-    operation: String,
+    #[arg(
+        required_unless_present_any = ["at", "working_copy_only"],
+        add = ArgValueCandidates::new(complete::operations),
+    )]
+    operation: Option<String>,
+
+    /// Restore to the latest operation at or before this time
+    ///
+    /// Accepts dates like `2024-01-02 10:00` or relative times like `2 hours
+    /// ago`.
+    #[arg(long, value_name = "DATE", conflicts_with = "operation")]
+    at: Option<String>,
 
     /// What portions of the local state to restore (can be repeated)
     ///
     /// This option is EXPERIMENTAL.
     #[arg(long, value_enum, default_values_t = DEFAULT_UNDO_WHAT)]
     what: Vec<UndoWhatToRestore>,
+
+    /// Only restore the working-copy commit's contents
+    ///
+    /// The contents of the current working-copy commit are replaced by the
+    /// contents the working-copy commit of this workspace had at the given
+    /// operation, or by the latest working-copy backup if no operation is
+    /// given. The rest of the repo is left as is.
+    #[arg(long, conflicts_with = "what")]
+    working_copy_only: bool,
 }
 
 pub fn cmd_op_restore(
@@ -50,7 +93,30 @@ pub fn cmd_op_restore(
     args: &OperationRestoreArgs,
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
-    let target_op = workspace_command.resolve_single_op(&args.operation)?;
+    if args.working_copy_only && args.operation.is_none() && args.at.is_none() {
+        return restore_working_copy_backup(ui, &mut workspace_command);
+    }
+    let target_op = if let Some(at) = &args.at {
+        let now = if let Some(timestamp) = command.settings().operation_timestamp() {
+            chrono::Local
+                .timestamp_millis_opt(timestamp.timestamp.0)
+                .unwrap()
+        } else {
+            chrono::Local::now()
+        };
+        let time = time_util::parse_timestamp(at, now)
+            .map_err(|err| user_error_with_message(format!("Invalid date: {at}"), err))?;
+        let head_op = workspace_command.repo().operation();
+        op_walk::find_op_at_time(slice::from_ref(head_op), time)?
+            .ok_or_else(|| user_error(format!("No operation found at or before {at}")))?
+    } else {
+        // The operation argument is required unless --at or
+        // --working-copy-only is specified.
+        workspace_command.resolve_single_op(args.operation.as_ref().unwrap())?
+    };
+    if args.working_copy_only {
+        return restore_working_copy_contents(ui, &mut workspace_command, &target_op);
+    }
     let mut tx = workspace_command.start_transaction();
     let new_view = view_with_desired_portions_restored(
         target_op.view()?.store_view(),
@@ -68,3 +134,102 @@ pub fn cmd_op_restore(
 
     Ok(())
 }
+
+fn restore_working_copy_contents(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    target_op: &Operation,
+) -> Result<(), CommandError> {
+    let workspace_name = workspace_command.workspace_name().to_owned();
+    let Some(old_wc_commit_id) = target_op.view()?.get_wc_commit_id(&workspace_name).cloned()
+    else {
+        return Err(user_error(format!(
+            "Workspace {} had no working-copy commit at operation {}",
+            workspace_name.as_symbol(),
+            short_operation_hash(target_op.id())
+        )));
+    };
+    let old_wc_commit = workspace_command
+        .repo()
+        .store()
+        .get_commit(&old_wc_commit_id)?;
+    restore_working_copy_tree(
+        ui,
+        workspace_command,
+        &old_wc_commit,
+        target_op,
+        "Restored working-copy contents from operation: ",
+        format!("restore working copy to operation {}", target_op.id().hex()),
+    )
+}
+
+fn restore_working_copy_backup(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+) -> Result<(), CommandError> {
+    let tag = working_copy_backup_tag(workspace_command.workspace_name());
+    let head_op = workspace_command.repo().operation().clone();
+    let mut backup = None;
+    for op in op_walk::walk_ancestors(slice::from_ref(&head_op)) {
+        let op = op?;
+        if let Some(value) = op.metadata().tags.get(&tag) {
+            let commit_id = CommitId::try_from_hex(value).ok_or_else(|| {
+                user_error(format!(
+                    "Invalid working-copy backup in operation {}",
+                    short_operation_hash(op.id())
+                ))
+            })?;
+            backup = Some((op, commit_id));
+            break;
+        }
+    }
+    let Some((backup_op, backup_commit_id)) = backup else {
+        return Err(user_error(
+            "No working-copy backup found in the operation log",
+        ));
+    };
+    let backup_commit = workspace_command
+        .repo()
+        .store()
+        .get_commit(&backup_commit_id)?;
+    restore_working_copy_tree(
+        ui,
+        workspace_command,
+        &backup_commit,
+        &backup_op,
+        "Restored working-copy contents backed up by operation: ",
+        format!(
+            "restore working copy from backup in operation {}",
+            backup_op.id().hex()
+        ),
+    )
+}
+
+fn restore_working_copy_tree(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    source_commit: &Commit,
+    source_op: &Operation,
+    message: &str,
+    description: String,
+) -> Result<(), CommandError> {
+    let Some(wc_commit_id) = workspace_command.get_wc_commit_id() else {
+        return Err(user_error("Nothing checked out in this workspace"));
+    };
+    let wc_commit = workspace_command.repo().store().get_commit(wc_commit_id)?;
+    workspace_command.check_rewritable([wc_commit.id()])?;
+
+    let mut tx = workspace_command.start_transaction();
+    tx.repo_mut()
+        .rewrite_commit(&wc_commit)
+        .set_tree_id(source_commit.tree_id().clone())
+        .write()?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "{message}")?;
+        let template = tx.base_workspace_helper().operation_summary_template();
+        template.format(source_op, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+    tx.finish(ui, description)?;
+    Ok(())
+}
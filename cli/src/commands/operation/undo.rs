@@ -78,10 +78,7 @@ pub fn cmd_op_undo(
     };
 
     let mut tx = workspace_command.start_transaction();
-    let repo_loader = tx.base_repo().loader();
-    let bad_repo = repo_loader.load_at(&bad_op)?;
-    let parent_repo = repo_loader.load_at(&parent_of_bad_op)?;
-    tx.repo_mut().merge(&bad_repo, &parent_repo)?;
+    tx.revert_operation(&bad_op)?;
     let new_view = view_with_desired_portions_restored(
         tx.repo().view().store_view(),
         tx.base_repo().view().store_view(),
@@ -0,0 +1,93 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::time::Duration;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_heads_store;
+use jj_lib::op_walk;
+use jj_lib::transaction::LAST_OPERATION_FILE_NAME;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::cli_error;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Wait until a new operation is recorded
+///
+/// Blocks until the head of the operation log is no longer the given
+/// operation, then prints the id of the new head operation. This can be used
+/// by scripts that need to run after an operation started by another process
+/// has completed, without polling `jj op log`.
+///
+/// The working copy is not snapshotted, so this command doesn't record an
+/// operation by itself.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationWaitArgs {
+    /// Wait until the head is no longer this operation
+    ///
+    /// Defaults to the current head. If the head has already moved past the
+    /// operation, the command returns immediately.
+    #[arg(long, add = ArgValueCandidates::new(complete::operations))]
+    from: Option<String>,
+    /// Give up after this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+}
+
+pub fn cmd_op_wait(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationWaitArgs,
+) -> Result<(), CommandError> {
+    // Don't load the repo. Snapshotting the working copy would record an
+    // operation.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op_store = repo_loader.op_store();
+    let op_heads_store = repo_loader.op_heads_store();
+    if command.global_args().at_operation.is_some() {
+        return Err(cli_error("--at-op is not respected"));
+    }
+    let current_head_ops = op_walk::get_current_head_ops(op_store, op_heads_store.as_ref())?;
+    let old_op_ids = if let Some(op_str) = &args.from {
+        let op = op_walk::resolve_op_at(op_store, &current_head_ops, op_str)?;
+        vec![op.id().clone()]
+    } else {
+        current_head_ops
+            .iter()
+            .map(|op| op.id().clone())
+            .collect_vec()
+    };
+
+    let timeout = args.timeout.map(Duration::from_secs);
+    let last_operation_path = workspace.repo_path().join(LAST_OPERATION_FILE_NAME);
+    let new_op_heads = op_heads_store::wait_for_op_heads_change(
+        op_heads_store.as_ref(),
+        &old_op_ids,
+        &last_operation_path,
+        timeout,
+    )?;
+    if new_op_heads.is_none() {
+        return Err(user_error("Timed out waiting for a new operation"));
+    }
+    let new_op = command.resolve_operation(ui, repo_loader)?;
+    writeln!(ui.stdout(), "{}", new_op.id().hex())?;
+    Ok(())
+}
@@ -53,6 +53,9 @@ use crate::ui::Ui;
 /// Therefore, `jj parallelize '1 | 3'` is a no-op. That's because 2, which is
 /// not in the target set, was a descendant of 1 before, so it remains a
 /// descendant, and it was an ancestor of 3 before, so it remains an ancestor.
+///
+/// If a revision in the set depends on the changes of another revision in the
+/// set, it will have conflicts after being parallelized.
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub(crate) struct ParallelizeArgs {
@@ -0,0 +1,109 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::templater::TemplateRenderer;
+use crate::ui::Ui;
+
+/// Show the parents of a revision
+///
+/// The parents are listed in the order they are recorded in the commit, one
+/// per template output. This is a shortcut for `jj log --no-graph -r
+/// 'parents(REV)'` that doesn't reorder the parents.
+///
+/// Use `-T 'json(self) ++ "\n"'` to print the parents as JSON lines.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ParentsArgs {
+    /// The revision to show the parents of
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
+    revision: RevisionArg,
+
+    /// Render each parent using the given template
+    ///
+    /// All 0-argument methods of the [`Commit` type] are available as
+    /// keywords in the template expression. See [`jj help -k templates`] for
+    /// more information.
+    ///
+    /// If not specified, this defaults to the `templates.log` setting.
+    ///
+    /// [`Commit` type]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/#commit-type
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/
+    #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_parents(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ParentsArgs,
+) -> Result<(), CommandError> {
+    show_related_commits(
+        ui,
+        command,
+        &args.revision,
+        args.template.as_deref(),
+        |_workspace_command, commit| Ok(commit.parents().try_collect()?),
+    )
+}
+
+/// Resolves `revision`, and prints the commits returned by `related_commits`
+/// using the given template, or `templates.log` if not specified.
+///
+/// Shared by `jj parents` and `jj children`.
+pub(crate) fn show_related_commits(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    revision: &RevisionArg,
+    template_text: Option<&str>,
+    related_commits: impl FnOnce(&WorkspaceCommandHelper, &Commit) -> Result<Vec<Commit>, CommandError>,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, revision)?;
+    let template: TemplateRenderer<Commit> = {
+        let text = match template_text {
+            Some(value) => value.to_owned(),
+            None => workspace_command.settings().get_string("templates.log")?,
+        };
+        workspace_command
+            .parse_commit_template(ui, &text)?
+            .labeled(["log", "commit"])
+    };
+    let commits = related_commits(&workspace_command, &commit)?;
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for commit in &commits {
+        template.format(commit, formatter.as_mut())?;
+    }
+    Ok(())
+}
@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use std::io::Write as _;
-use std::sync::Arc;
+use std::path::Path;
+use std::path::PathBuf;
 
 use clap::ArgGroup;
 use clap_complete::ArgValueCompleter;
@@ -21,7 +22,6 @@ use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::object_id::ObjectId as _;
-use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::rewrite::compute_move_commits;
@@ -36,7 +36,6 @@ use tracing::instrument;
 
 use crate::cli_util::compute_commit_location;
 use crate::cli_util::print_updated_commits;
-use crate::cli_util::short_commit_hash;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
@@ -311,6 +310,17 @@ pub(crate) struct RebaseArgs {
         add = ArgValueCompleter::new(complete::revset_expression_mutable),
     )]
     revisions: Vec<RevisionArg>,
+    /// Rebase the revisions listed in the given file, like `-r`
+    ///
+    /// The file should contain full commit or change IDs separated by
+    /// whitespace. It can be combined with `-r`.
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["branch", "source"],
+    )]
+    revisions_file: Option<PathBuf>,
 
     #[command(flatten)]
     destination: RebaseDestinationArgs,
@@ -319,7 +329,7 @@ pub(crate) struct RebaseArgs {
     /// abandoned. It will not be abandoned if it was already empty before the
     /// rebase. Will never skip merge commits with multiple non-empty
     /// parents.
-    #[arg(long)]
+    #[arg(long, visible_alias = "skip-empty")]
     skip_emptied: bool,
 
     /// Keep divergent commits while rebasing
@@ -329,6 +339,12 @@ pub(crate) struct RebaseArgs {
     /// destination with identical changes.
     #[arg(long)]
     keep_divergent: bool,
+
+    /// Do not ask for confirmation when many commits would be rewritten
+    ///
+    /// See `ui.rewrite-confirmation-threshold`.
+    #[arg(long)]
+    yes: bool,
 }
 
 #[derive(clap::Args, Clone, Debug)]
@@ -384,13 +400,23 @@ pub(crate) fn cmd_rebase(
         simplify_ancestor_merge: false,
     };
     let mut workspace_command = command.workspace_helper(ui)?;
-    let loc = if !args.revisions.is_empty() {
-        plan_rebase_revisions(ui, &workspace_command, &args.revisions, &args.destination)?
+    let revisions_file = args.revisions_file.as_deref();
+    let loc = if !args.revisions.is_empty() || revisions_file.is_some() {
+        plan_rebase_revisions(
+            ui,
+            &workspace_command,
+            &args.revisions,
+            revisions_file,
+            &args.destination,
+        )?
     } else if !args.source.is_empty() {
         plan_rebase_source(ui, &workspace_command, &args.source, &args.destination)?
     } else {
         plan_rebase_branch(ui, &workspace_command, &args.branch, &args.destination)?
     };
+    let (MoveCommitsTarget::Commits(target_ids) | MoveCommitsTarget::Roots(target_ids)) =
+        &loc.target;
+    workspace_command.confirm_rewrite(ui, target_ids, args.yes)?;
 
     let mut tx = workspace_command.start_transaction();
     let mut computed_move = compute_move_commits(tx.repo(), &loc)?;
@@ -424,10 +450,11 @@ fn plan_rebase_revisions(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
     revisions: &[RevisionArg],
+    revisions_file: Option<&Path>,
     rebase_destination: &RebaseDestinationArgs,
 ) -> Result<MoveCommitsLocation, CommandError> {
     let target_commit_ids: Vec<_> = workspace_command
-        .parse_union_revsets(ui, revisions)?
+        .parse_union_revsets_and_file(ui, revisions, revisions_file)?
         .evaluate_to_commit_ids()?
         .try_collect()?; // in reverse topological order
     workspace_command.check_rewritable(&target_commit_ids)?;
@@ -443,9 +470,10 @@ fn plan_rebase_revisions(
     if rebase_destination.destination.is_some() && new_child_ids.is_empty() {
         for id in &target_commit_ids {
             if new_parent_ids.contains(id) {
+                let commit = workspace_command.repo().store().get_commit(id)?;
                 return Err(user_error(format!(
                     "Cannot rebase {} onto itself",
-                    short_commit_hash(id),
+                    workspace_command.format_commit_summary(&commit),
                 )));
             }
         }
@@ -478,7 +506,7 @@ fn plan_rebase_source(
     if rebase_destination.destination.is_some() && new_child_ids.is_empty() {
         for id in &source_commit_ids {
             let commit = workspace_command.repo().store().get_commit(id)?;
-            check_rebase_destinations(workspace_command.repo(), &new_parent_ids, &commit)?;
+            check_rebase_destinations(workspace_command, &new_parent_ids, &commit)?;
         }
     }
 
@@ -527,7 +555,7 @@ fn plan_rebase_branch(
     if rebase_destination.destination.is_some() && new_child_ids.is_empty() {
         for id in &root_commit_ids {
             let commit = workspace_command.repo().store().get_commit(id)?;
-            check_rebase_destinations(workspace_command.repo(), &new_parent_ids, &commit)?;
+            check_rebase_destinations(workspace_command, &new_parent_ids, &commit)?;
         }
     }
 
@@ -539,16 +567,18 @@ fn plan_rebase_branch(
 }
 
 fn check_rebase_destinations(
-    repo: &Arc<ReadonlyRepo>,
+    workspace_command: &WorkspaceCommandHelper,
     new_parents: &[CommitId],
     commit: &Commit,
 ) -> Result<(), CommandError> {
+    let repo = workspace_command.repo();
     for parent_id in new_parents {
         if repo.index().is_ancestor(commit.id(), parent_id) {
+            let parent = repo.store().get_commit(parent_id)?;
             return Err(user_error(format!(
                 "Cannot rebase {} onto descendant {}",
-                short_commit_hash(commit.id()),
-                short_commit_hash(parent_id)
+                workspace_command.format_commit_summary(commit),
+                workspace_command.format_commit_summary(&parent),
             )));
         }
     }
@@ -0,0 +1,217 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::rewrite::RewriteRefsOptions;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Reorder revisions within a linear stack
+///
+/// The revisions are given in their new order, from the bottom of the stack
+/// to the top. Running `jj reorder --order 3 1 2` will transform the history
+/// like this:
+/// ```text
+/// 4            4
+/// |            |
+/// 3            2
+/// |    ->      |
+/// 2            1
+/// |            |
+/// 1            3
+/// |            |
+/// 0            0
+/// ```
+///
+/// Descendants of the top of the stack are rebased onto the new top. Other
+/// descendants of the reordered revisions are rebased along with the revision
+/// they're based on.
+///
+/// All revisions are rewritten in a single operation. The changes are replayed
+/// in the new order before anything is rewritten, and the revisions that would
+/// become conflicted are reported up front. The operation can be reverted by
+/// `jj undo`.
+#[derive(clap::Args, Clone, Debug)]
+#[command(verbatim_doc_comment)]
+pub(crate) struct ReorderArgs {
+    /// The revisions in their new order, from the bottom of the stack to the
+    /// top
+    ///
+    /// Each value should resolve to a single revision.
+    #[arg(
+        long,
+        short,
+        value_name = "REVSET",
+        required = true,
+        num_args = 1..,
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    order: Vec<RevisionArg>,
+    /// The stack to reorder (default: the revisions in `--order`)
+    ///
+    /// If specified, `--order` must list each revision of this stack.
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_mutable),
+    )]
+    revisions: Option<Vec<RevisionArg>>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_reorder(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ReorderArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let new_order: Vec<Commit> = args
+        .order
+        .iter()
+        .map(|arg| workspace_command.resolve_single_rev(ui, arg))
+        .try_collect()?;
+    // The stack is ordered with parents before children.
+    let stack: Vec<Commit> = if let Some(revisions) = &args.revisions {
+        workspace_command
+            .parse_union_revsets(ui, revisions)?
+            .evaluate_to_commits()?
+            .try_collect()?
+    } else {
+        let repo = workspace_command.repo().as_ref();
+        RevsetExpression::commits(new_order.iter().ids().cloned().collect())
+            .evaluate(repo)?
+            .iter()
+            .commits(repo.store())
+            .try_collect()?
+    };
+    let stack = stack.into_iter().rev().collect_vec();
+
+    for (parent, child) in stack.iter().tuple_windows() {
+        if child.parent_ids() != [parent.id().clone()] {
+            return Err(user_error_with_hint(
+                "The revisions to reorder must form a linear stack",
+                format!(
+                    "Revision {} isn't the only parent of revision {}.",
+                    workspace_command.format_commit_summary(parent),
+                    workspace_command.format_commit_summary(child),
+                ),
+            ));
+        }
+    }
+    let stack_ids: HashSet<&CommitId> = stack.iter().ids().collect();
+    let new_order_ids: HashSet<&CommitId> = new_order.iter().ids().collect();
+    if new_order_ids.len() != new_order.len() || new_order_ids != stack_ids {
+        return Err(user_error(
+            "Each revision of the stack must be listed exactly once in the new order",
+        ));
+    }
+    if stack.iter().ids().eq(new_order.iter().ids()) {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    workspace_command.check_rewritable(stack.iter().ids())?;
+
+    // Replay the changes in the new order to find the revisions that will
+    // become conflicted before rewriting anything.
+    let repo = workspace_command.repo().as_ref();
+    let mut new_base_tree = stack[0].parent_tree(repo)?;
+    let mut new_conflicts = vec![];
+    for commit in &new_order {
+        let old_base_tree = commit.parent_tree(repo)?;
+        let new_tree = new_base_tree.merge(&old_base_tree, &commit.tree()?)?;
+        if new_tree.has_conflict() && !commit.has_conflict()? {
+            new_conflicts.push(commit);
+        }
+        new_base_tree = new_tree;
+    }
+    if !new_conflicts.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "The new order will cause conflicts in these revisions:"
+        )?;
+        if let Some(mut formatter) = ui.status_formatter() {
+            for commit in &new_conflicts {
+                write!(formatter, "  ")?;
+                workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            }
+        }
+    }
+
+    let old_top_id = stack.last().unwrap().id().clone();
+    let new_top_id = new_order.last().unwrap().id().clone();
+    let mut new_parents_map: HashMap<CommitId, Vec<CommitId>> = HashMap::new();
+    new_parents_map.insert(new_order[0].id().clone(), stack[0].parent_ids().to_vec());
+    for (parent, child) in new_order.iter().tuple_windows() {
+        new_parents_map.insert(child.id().clone(), vec![parent.id().clone()]);
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let mut num_reordered = 0;
+    let mut num_rebased = 0;
+    tx.repo_mut().transform_descendants_with_options(
+        vec![stack[0].id().clone()],
+        &new_parents_map,
+        &RewriteRefsOptions::default(),
+        |mut rewriter| {
+            let old_commit_id = rewriter.old_commit().id().clone();
+            let is_reordered = stack_ids.contains(&old_commit_id);
+            if !is_reordered && rewriter.old_commit().parent_ids().contains(&old_top_id) {
+                // Keep descendants of the stack on top of it.
+                let new_parents = rewriter
+                    .old_commit()
+                    .parent_ids()
+                    .iter()
+                    .map(|id| if *id == old_top_id { &new_top_id } else { id })
+                    .cloned()
+                    .collect_vec();
+                rewriter.set_new_rewritten_parents(&new_parents);
+            }
+            if rewriter.parents_changed() {
+                rewriter.rebase()?.write()?;
+                if is_reordered {
+                    num_reordered += 1;
+                } else {
+                    num_rebased += 1;
+                }
+            }
+            Ok(())
+        },
+    )?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Reordered {num_reordered} commits")?;
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+    }
+    tx.finish(ui, format!("reorder {} commits", stack.len()))
+}
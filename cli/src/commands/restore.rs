@@ -160,6 +160,9 @@ pub(crate) fn cmd_restore(
         writeln!(ui.status(), "Nothing changed.")?;
     } else {
         let mut tx = workspace_command.start_transaction();
+        if tx.base_workspace_helper().get_wc_commit_id() == Some(to_commit.id()) {
+            tx.record_working_copy_backup();
+        }
         tx.repo_mut()
             .rewrite_commit(&to_commit)
             .set_tree_id(new_tree_id)
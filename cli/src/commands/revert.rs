@@ -31,6 +31,7 @@ use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::description_util::add_trailers;
 use crate::ui::Ui;
 
 /// Apply the reverse of the given revision(s)
@@ -38,8 +39,12 @@ use crate::ui::Ui;
 /// The reverse of each of the given revisions is applied sequentially in
 /// reverse topological order at the given location.
 ///
+/// For example, `jj revert -r X -B @` undoes the changes of X in the working
+/// copy by inserting the reverse changes before the working-copy commit.
+///
 /// The description of the new revisions can be customized with the
-/// `templates.revert_description` config variable.
+/// `templates.revert_description` config variable, and the trailers from
+/// `templates.commit_trailers` are added to it.
 #[derive(clap::Args, Clone, Debug)]
 #[command(group(ArgGroup::new("location").args(&["destination", "insert_after", "insert_before"]).required(true).multiple(true)))]
 pub(crate) struct RevertArgs {
@@ -144,11 +149,14 @@ pub(crate) fn cmd_revert(
         let old_tree = commit_to_revert.tree()?;
         let new_tree = new_base_tree.merge(&old_tree, &old_base_tree)?;
         let new_parent_ids = parent_ids.clone();
-        let new_commit = tx
+        let mut commit_builder = tx
             .repo_mut()
             .new_commit(new_parent_ids, new_tree.id())
             .set_description(new_commit_description)
-            .write()?;
+            .detach();
+        let description = add_trailers(ui, &tx, &commit_builder)?;
+        commit_builder.set_description(description);
+        let new_commit = commit_builder.write(tx.repo_mut())?;
         parent_ids = vec![new_commit.id().clone()];
         reverted_commits.push(new_commit);
         new_base_tree = new_tree;
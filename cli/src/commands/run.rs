@@ -14,57 +14,303 @@
 
 //! This file contains the internal implementation of `run`.
 
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::path::Path;
+use std::process::Command;
+use std::process::Output;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::MergedTreeId;
+use jj_lib::commit::Commit;
+use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::gitignore::GitIgnoreFile;
+use jj_lib::local_working_copy::TreeState;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::repo::Repo as _;
+use jj_lib::store::Store;
+use jj_lib::working_copy::CheckoutOptions;
+use jj_lib::working_copy::SnapshotOptions;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::internal_error_with_message;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
 use crate::command_error::CommandError;
+use crate::complete;
+use crate::temp_files::new_utf8_temp_dir;
 use crate::ui::Ui;
 
-/// (**Stub**, does not work yet) Run a command across a set of revisions.
-///
+/// Run a command across a set of revisions
 ///
-/// All recorded state will be persisted in the `.jj` directory, so occasionally
-/// a `jj run --clean` is needed to clean up disk space.
+/// Each revision is checked out into its own temporary working copy, and the
+/// command is run there by the shell. The revisions are processed from the
+/// oldest to the newest. The output of the command is printed along with the
+/// result for each revision, and `jj run` fails if the command failed on any
+/// of them.
 ///
-/// # Example
+/// With `--rewrite`, the changes the command made to the working copy are
+/// recorded by rewriting the revision, and descendants are rebased on top.
+/// Changes are only recorded for revisions the command succeeded on. New files
+/// larger than `snapshot.max-new-file-size` are not recorded.
 ///
-/// # Run pre-commit on your local work
-/// $ jj run 'pre-commit run .github/pre-commit.yaml' -r (trunk()..@) -j 4
+/// For example, the following runs the tests on your local work, using 4
+/// parallel jobs:
 ///
-/// This allows pre-commit integration and other funny stuff.
+/// ```shell
+/// $ jj run 'cargo test' -r 'trunk()..@' -j 4
+/// ```
 #[derive(clap::Args, Clone, Debug)]
 #[command(verbatim_doc_comment)]
 pub struct RunArgs {
-    /// The command to run across all selected revisions.
+    /// The command to run across all selected revisions
     shell_command: String,
-    /// The revisions to change.
-    #[arg(long, short, default_value = "@", value_name = "REVSETS")]
+    /// The revisions to run the command on
+    #[arg(
+        long,
+        short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCompleter::new(complete::revset_expression_all),
+    )]
     revisions: Vec<RevisionArg>,
     /// A no-op option to match the interface of `git rebase -x`.
     #[arg(short = 'x', hide = true)]
     unused_command: bool,
-    /// How many processes should run in parallel, uses by default all cores.
+    /// How many processes should run in parallel, uses by default all cores
     #[arg(long, short)]
     jobs: Option<usize>,
+    /// Record the changes made by the command in the revisions
+    #[arg(long)]
+    rewrite: bool,
+}
+
+/// Settings for materializing and snapshotting the temporary working copies.
+struct ScratchOptions {
+    store: Arc<Store>,
+    checkout_options: CheckoutOptions,
+    base_ignores: Arc<GitIgnoreFile>,
+    conflict_marker_style: ConflictMarkerStyle,
+    max_new_file_size: u64,
+    snapshot: bool,
+}
+
+/// Result of running the command on a single revision.
+struct CommitRunResult {
+    output: Output,
+    /// The snapshotted tree, if the working copy was snapshotted after running
+    /// the command.
+    new_tree_id: Option<MergedTreeId>,
 }
 
 pub fn cmd_run(ui: &mut Ui, command: &CommandHelper, args: &RunArgs) -> Result<(), CommandError> {
-    let workspace_command = command.workspace_helper(ui)?;
-    let _resolved_commits: Vec<_> = workspace_command
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut commits: Vec<Commit> = workspace_command
         .parse_union_revsets(ui, &args.revisions)?
         .evaluate_to_commits()?
         .try_collect()?;
+    // Run on parents before children.
+    commits.reverse();
+    if args.rewrite {
+        workspace_command.check_rewritable(commits.iter().ids())?;
+    }
     // Jobs are resolved in this order:
     // 1. Commandline argument iff > 0.
     // 2. the amount of cores available.
     // 3. a single job, if all of the above fails.
-    let _jobs = match args.jobs {
+    let jobs = match args.jobs {
         Some(0) | None => std::thread::available_parallelism().map(|t| t.into()).ok(),
         Some(jobs) => Some(jobs),
     }
     // Fallback to a single user-visible job.
     .unwrap_or(1usize);
-    Err(user_error("This is a stub, do not use"))
+
+    let options = ScratchOptions {
+        store: workspace_command.repo().store().clone(),
+        checkout_options: workspace_command.checkout_options(),
+        base_ignores: workspace_command.base_ignores()?,
+        conflict_marker_style: workspace_command.env().conflict_marker_style(),
+        max_new_file_size: workspace_command
+            .snapshot_options_with_start_tracking_matcher(&EverythingMatcher)?
+            .max_new_file_size,
+        snapshot: args.rewrite,
+    };
+    let temp_dir = new_utf8_temp_dir("jj-run-")?;
+    let queue = Mutex::new(commits.iter().enumerate());
+    let mut results = std::thread::scope(|s| {
+        let queue = &queue;
+        let temp_dir = &temp_dir;
+        let options = &options;
+        let shell_command = args.shell_command.as_str();
+        let handles = (0..jobs.min(commits.len()))
+            .map(|_| {
+                s.spawn(move || {
+                    let mut results = vec![];
+                    loop {
+                        let Some((index, commit)) = queue.lock().unwrap().next() else {
+                            break;
+                        };
+                        let scratch_dir = temp_dir.path().join(index.to_string());
+                        let result = run_on_commit(&scratch_dir, commit, shell_command, options);
+                        results.push((index, result));
+                    }
+                    results
+                })
+            })
+            .collect_vec();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect_vec()
+    });
+    results.sort_unstable_by_key(|(index, _)| *index);
+
+    let mut num_failed = 0;
+    let mut new_trees = HashMap::new();
+    for (commit, (_, result)) in commits.iter().zip(results) {
+        let CommitRunResult {
+            output,
+            new_tree_id,
+        } = result?;
+        ui.stdout().write_all(&output.stdout)?;
+        ui.stderr().write_all(&output.stderr)?;
+        if let Some(mut formatter) = ui.status_formatter() {
+            if output.status.success() {
+                write!(formatter, "Command succeeded on ")?;
+                workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter)?;
+            } else {
+                write!(formatter, "Command failed on ")?;
+                workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+                writeln!(formatter, " ({})", output.status)?;
+            }
+        }
+        if !output.status.success() {
+            num_failed += 1;
+        } else if let Some(tree_id) = new_tree_id {
+            if &tree_id != commit.tree_id() {
+                new_trees.insert(commit.id().clone(), tree_id);
+            }
+        }
+    }
+    drop(temp_dir);
+
+    if !new_trees.is_empty() {
+        rewrite_commits(ui, &mut workspace_command, new_trees)?;
+    }
+    if num_failed > 0 {
+        return Err(user_error(format!(
+            "Command failed on {num_failed} of {} revisions",
+            commits.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Checks out `commit` into a new working copy in `scratch_dir`, and runs the
+/// command there.
+fn run_on_commit(
+    scratch_dir: &Path,
+    commit: &Commit,
+    shell_command: &str,
+    options: &ScratchOptions,
+) -> Result<CommitRunResult, CommandError> {
+    let wc_dir = scratch_dir.join("wc");
+    let state_dir = scratch_dir.join("state");
+    std::fs::create_dir_all(&wc_dir)?;
+    std::fs::create_dir_all(&state_dir)?;
+    let mut tree_state = TreeState::init(options.store.clone(), wc_dir, state_dir)
+        .map_err(|err| internal_error_with_message("Failed to create working copy", err))?;
+    tree_state
+        .check_out(&commit.tree()?, &options.checkout_options)
+        .map_err(|err| internal_error_with_message("Failed to check out commit", err))?;
+
+    let output = shell(shell_command)
+        .current_dir(tree_state.working_copy_path())
+        .output()
+        .map_err(|err| {
+            user_error_with_message(format!("Failed to run command '{shell_command}'"), err)
+        })?;
+
+    let new_tree_id = if options.snapshot {
+        tree_state.snapshot(&SnapshotOptions {
+            base_ignores: options.base_ignores.clone(),
+            fsmonitor_settings: FsmonitorSettings::None,
+            progress: None,
+            start_tracking_matcher: &EverythingMatcher,
+            max_new_file_size: options.max_new_file_size,
+            conflict_marker_style: options.conflict_marker_style,
+            track_empty_directories: false,
+            detect_conflict_markers: false,
+            dry_run: false,
+        })?;
+        Some(tree_state.current_tree_id().clone())
+    } else {
+        None
+    };
+    std::fs::remove_dir_all(scratch_dir).ok();
+    Ok(CommitRunResult {
+        output,
+        new_tree_id,
+    })
+}
+
+fn shell(shell_command: &str) -> Command {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c");
+        command
+    };
+    command.arg(shell_command);
+    command
+}
+
+/// Applies the changes made by the command to the commits, and rebases their
+/// descendants. The changes are merged into the rebased trees, so changes
+/// made in ancestors are preserved.
+fn rewrite_commits(
+    ui: &Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    new_trees: HashMap<CommitId, MergedTreeId>,
+) -> Result<(), CommandError> {
+    let mut tx = workspace_command.start_transaction();
+    let store = tx.repo().store().clone();
+    let mut num_rewritten = 0;
+    let mut num_rebased = 0;
+    tx.repo_mut()
+        .transform_descendants(new_trees.keys().cloned().collect(), |rewriter| {
+            let old_tree = rewriter.old_commit().tree()?;
+            let new_tree_id = new_trees.get(rewriter.old_commit().id());
+            let commit_builder = rewriter.rebase()?;
+            if let Some(new_tree_id) = new_tree_id {
+                let rebased_tree = store.get_root_tree(commit_builder.tree_id())?;
+                let new_tree = store.get_root_tree(new_tree_id)?;
+                let merged_tree = rebased_tree.merge(&old_tree, &new_tree)?;
+                commit_builder.set_tree_id(merged_tree.id()).write()?;
+                num_rewritten += 1;
+            } else {
+                commit_builder.write()?;
+                num_rebased += 1;
+            }
+            Ok(())
+        })?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Rewrote {num_rewritten} commits")?;
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+    }
+    tx.finish(ui, format!("run command on {num_rewritten} commits"))
 }
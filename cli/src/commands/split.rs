@@ -11,16 +11,32 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::io::Write as _;
 
 use clap_complete::ArgValueCompleter;
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::backend::BackendResult;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::fileset;
+use jj_lib::fileset::FilesetDiagnostics;
+use jj_lib::fileset::FilesetExpression;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::FilesMatcher;
 use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::repo_path::RepoPathComponent;
+use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::repo_path::UiPathStyle;
 use jj_lib::rewrite::move_commits;
+use jj_lib::rewrite::restore_tree;
 use jj_lib::rewrite::CommitWithSelection;
 use jj_lib::rewrite::EmptyBehaviour;
 use jj_lib::rewrite::MoveCommitsLocation;
@@ -28,6 +44,8 @@ use jj_lib::rewrite::MoveCommitsTarget;
 use jj_lib::rewrite::RebaseOptions;
 use jj_lib::rewrite::RebasedCommit;
 use jj_lib::rewrite::RewriteRefsOptions;
+use jj_lib::settings::UserSettings;
+use pollster::FutureExt as _;
 use tracing::instrument;
 
 use crate::cli_util::compute_commit_location;
@@ -36,6 +54,9 @@ use crate::cli_util::DiffSelector;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
+use crate::command_error::config_error;
+use crate::command_error::print_parse_diagnostics;
+use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::complete;
@@ -43,6 +64,7 @@ use crate::description_util::add_trailers;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::join_message_paragraphs;
+use crate::text_util::complete_newline;
 use crate::ui::Ui;
 
 /// Split a revision in two
@@ -62,6 +84,13 @@ use crate::ui::Ui;
 ///
 /// Splitting an empty commit is not supported because the same effect can be
 /// achieved with `jj new`.
+///
+/// With `--by-config`, the revision is instead split into a stack of commits
+/// according to the path groups configured in `split.groups`, without opening
+/// any editor. See the [`split.groups` documentation] for details.
+///
+/// [`split.groups` documentation]:
+///     https://jj-vcs.github.io/jj/latest/config/#splitting-commits-by-path
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct SplitArgs {
     /// Interactively choose which parts to split
@@ -124,6 +153,22 @@ pub(crate) struct SplitArgs {
     /// child
     #[arg(long, short)]
     parallel: bool,
+    /// Split the revision into a stack of commits according to the
+    /// `split.groups` config
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "interactive",
+            "tool",
+            "destination",
+            "insert_after",
+            "insert_before",
+            "message_paragraphs",
+            "parallel",
+            "paths",
+        ],
+    )]
+    by_config: bool,
     /// Files matching any of these filesets are put in the selected changes
     #[arg(
         value_name = "FILESETS",
@@ -134,13 +179,12 @@ pub(crate) struct SplitArgs {
 }
 
 impl SplitArgs {
-    /// Resolves the raw SplitArgs into the components necessary to run the
-    /// command. Returns an error if the command cannot proceed.
-    fn resolve(
+    /// Resolves the revision to split. Returns an error if it cannot be split.
+    fn resolve_target_commit(
         &self,
         ui: &Ui,
         workspace_command: &WorkspaceCommandHelper,
-    ) -> Result<ResolvedSplitArgs, CommandError> {
+    ) -> Result<Commit, CommandError> {
         let target_commit = workspace_command.resolve_single_rev(ui, &self.revision)?;
         if target_commit.is_empty(workspace_command.repo().as_ref())? {
             return Err(user_error_with_hint(
@@ -152,6 +196,17 @@ impl SplitArgs {
             ));
         }
         workspace_command.check_rewritable([target_commit.id()])?;
+        Ok(target_commit)
+    }
+
+    /// Resolves the raw SplitArgs into the components necessary to run the
+    /// command. Returns an error if the command cannot proceed.
+    fn resolve(
+        &self,
+        ui: &Ui,
+        workspace_command: &WorkspaceCommandHelper,
+    ) -> Result<ResolvedSplitArgs, CommandError> {
+        let target_commit = self.resolve_target_commit(ui, workspace_command)?;
         let matcher = workspace_command
             .parse_file_patterns(ui, &self.paths)?
             .to_matcher();
@@ -203,6 +258,9 @@ pub(crate) fn cmd_split(
     command: &CommandHelper,
     args: &SplitArgs,
 ) -> Result<(), CommandError> {
+    if args.by_config {
+        return split_by_config(ui, command, args);
+    }
     let mut workspace_command = command.workspace_helper(ui)?;
     let ResolvedSplitArgs {
         target_commit,
@@ -305,7 +363,13 @@ pub(crate) fn cmd_split(
             new_child_ids,
         )?
     } else {
-        rewrite_descendants(&mut tx, &target, first_commit, second_commit, parallel)?
+        rewrite_descendants(
+            &mut tx,
+            &target.commit,
+            first_commit,
+            second_commit,
+            parallel,
+        )?
     };
     if let Some(mut formatter) = ui.status_formatter() {
         if num_rebased > 0 {
@@ -321,6 +385,249 @@ pub(crate) fn cmd_split(
     Ok(())
 }
 
+/// How `jj split --by-config` groups the changed files.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum SplitGroupBy {
+    /// Use the groups configured in the `split.groups` table.
+    Groups,
+    /// One group per top-level directory.
+    TopLevelDirectory,
+    /// One group per directory containing an `OWNERS` file, which owns the
+    /// files below it.
+    Owners,
+}
+
+/// Represents a group of files split into its own commit.
+struct SplitGroup {
+    /// The key of the entry in the `split.groups` table, or the directory of
+    /// the group.
+    name: String,
+    /// The matcher that determines which files belong to this group.
+    matcher: Box<dyn Matcher>,
+    /// The description for the commit created for this group.
+    description: Option<String>,
+}
+
+/// Simplifies deserialization of the config values while building a
+/// SplitGroup.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawSplitGroup {
+    patterns: Vec<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// Parses the `split.groups` config table. The groups are returned in the
+/// order of their names.
+fn get_split_groups(ui: &Ui, settings: &UserSettings) -> Result<Vec<SplitGroup>, CommandError> {
+    let groups: Vec<SplitGroup> = settings
+        .table_keys("split.groups")
+        .sorted()
+        .map(|name| -> Result<SplitGroup, CommandError> {
+            let mut diagnostics = FilesetDiagnostics::new();
+            let group: RawSplitGroup = settings.get(["split", "groups", name])?;
+            let expression = FilesetExpression::union_all(
+                group
+                    .patterns
+                    .iter()
+                    .map(|arg| {
+                        fileset::parse(
+                            &mut diagnostics,
+                            arg,
+                            &RepoPathUiConverter::Fs {
+                                cwd: "".into(),
+                                base: "".into(),
+                                style: UiPathStyle::default(),
+                            },
+                        )
+                    })
+                    .try_collect()?,
+            );
+            print_parse_diagnostics(ui, &format!("In `split.groups.{name}`"), &diagnostics)?;
+            Ok(SplitGroup {
+                name: name.to_owned(),
+                matcher: expression.to_matcher(),
+                description: group.description,
+            })
+        })
+        .try_collect()?;
+    if groups.is_empty() {
+        return Err(config_error("No `split.groups` are configured"));
+    }
+    Ok(groups)
+}
+
+/// Groups the files changed between `parent_tree` and `target_tree` by the
+/// directory returned by `group_dir`. The groups are returned in the order of
+/// their names. Files for which `group_dir` returns `None` are not grouped.
+fn get_directory_groups(
+    parent_tree: &MergedTree,
+    target_tree: &MergedTree,
+    mut group_dir: impl FnMut(&RepoPath) -> BackendResult<Option<RepoPathBuf>>,
+) -> Result<Vec<SplitGroup>, CommandError> {
+    let mut paths_by_dir: BTreeMap<RepoPathBuf, Vec<RepoPathBuf>> = BTreeMap::new();
+    let entries: Vec<_> = parent_tree
+        .diff_stream(target_tree, &EverythingMatcher)
+        .collect()
+        .block_on();
+    for entry in entries {
+        entry.values?;
+        if let Some(dir) = group_dir(&entry.path)? {
+            paths_by_dir.entry(dir).or_default().push(entry.path);
+        }
+    }
+    let groups = paths_by_dir
+        .into_iter()
+        .map(|(dir, paths)| SplitGroup {
+            name: if dir.is_root() {
+                ".".to_owned()
+            } else {
+                dir.as_internal_file_string().to_owned()
+            },
+            matcher: Box::new(FilesMatcher::new(paths)),
+            description: None,
+        })
+        .collect();
+    Ok(groups)
+}
+
+/// Returns the closest ancestor directory of `path` which contains an
+/// `OWNERS` file in `tree`.
+fn find_owners_dir(
+    tree: &MergedTree,
+    owners_dirs: &mut HashMap<RepoPathBuf, bool>,
+    path: &RepoPath,
+) -> BackendResult<Option<RepoPathBuf>> {
+    let owners_name = RepoPathComponent::new("OWNERS").unwrap();
+    for dir in path.ancestors().skip(1) {
+        let has_owners = match owners_dirs.get(dir) {
+            Some(&has_owners) => has_owners,
+            None => {
+                let has_owners = tree.path_value(&dir.join(owners_name))?.is_present();
+                owners_dirs.insert(dir.to_owned(), has_owners);
+                has_owners
+            }
+        };
+        if has_owners {
+            return Ok(Some(dir.to_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// Splits the target commit into a stack with one commit per group of changed
+/// files, plus one for the changes that didn't belong to any group.
+fn split_by_config(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &SplitArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let target_commit = args.resolve_target_commit(ui, &workspace_command)?;
+    let group_by: SplitGroupBy = workspace_command.settings().get("split.group-by")?;
+    let target_tree = target_commit.tree()?;
+    let parent_tree = target_commit.parent_tree(workspace_command.repo().as_ref())?;
+    let groups = match group_by {
+        SplitGroupBy::Groups => get_split_groups(ui, workspace_command.settings())?,
+        SplitGroupBy::TopLevelDirectory => {
+            get_directory_groups(&parent_tree, &target_tree, |path| {
+                // Files in the root directory are left in the remaining changes.
+                let dir = path.parent().and_then(|dir| dir.components().next());
+                Ok(dir.map(|name| RepoPath::root().join(name)))
+            })?
+        }
+        SplitGroupBy::Owners => {
+            let mut owners_dirs = HashMap::new();
+            get_directory_groups(&parent_tree, &target_tree, |path| {
+                find_owners_dir(&target_tree, &mut owners_dirs, path)
+            })?
+        }
+    };
+    let mut tx = workspace_command.start_transaction();
+
+    // Move the changes matching each group out of the target tree, one group
+    // at a time. A path matching several groups goes to the first of them.
+    let mut prev_tree = parent_tree;
+    let mut selections = vec![];
+    for group in &groups {
+        let tree_id = restore_tree(&target_tree, &prev_tree, group.matcher.as_ref()).block_on()?;
+        if tree_id == prev_tree.id() {
+            continue;
+        }
+        let tree = tx.repo().store().get_root_tree(&tree_id)?;
+        selections.push((group, tree.clone()));
+        prev_tree = tree;
+    }
+    if selections.is_empty() {
+        return Err(user_error(match group_by {
+            SplitGroupBy::Groups => "No changes in the revision match any of the `split.groups`",
+            SplitGroupBy::TopLevelDirectory => "No changes in the revision are in a directory",
+            SplitGroupBy::Owners => "No changes in the revision are covered by an `OWNERS` file",
+        }));
+    }
+
+    // The first commit keeps the change id of the target commit, like the
+    // selected changes of a regular split.
+    let mut new_commits: Vec<(Option<&str>, Commit)> = vec![];
+    let mut parent_ids = target_commit.parent_ids().to_vec();
+    for (group, tree) in &selections {
+        let mut commit_builder = tx.repo_mut().rewrite_commit(&target_commit).detach();
+        commit_builder
+            .set_parents(parent_ids)
+            .set_tree_id(tree.id());
+        if !new_commits.is_empty() {
+            commit_builder.generate_new_change_id();
+        }
+        let description = if let Some(description) = &group.description {
+            complete_newline(description)
+        } else if target_commit.description().is_empty() {
+            complete_newline(&group.name)
+        } else {
+            format!("{}: {}", group.name, target_commit.description())
+        };
+        commit_builder.set_description(description);
+        let description = add_trailers(ui, &tx, &commit_builder)?;
+        commit_builder.set_description(description);
+        let commit = commit_builder.write(tx.repo_mut())?;
+        parent_ids = vec![commit.id().clone()];
+        new_commits.push((Some(group.name.as_str()), commit));
+    }
+    if prev_tree.id() != target_tree.id() {
+        let mut commit_builder = tx.repo_mut().rewrite_commit(&target_commit).detach();
+        commit_builder
+            .set_parents(parent_ids)
+            .set_tree_id(target_tree.id())
+            .generate_new_change_id();
+        let commit = commit_builder.write(tx.repo_mut())?;
+        new_commits.push((None, commit));
+    }
+
+    let first_commit = new_commits.first().unwrap().1.clone();
+    let last_commit = new_commits.last().unwrap().1.clone();
+    let (_, _, num_rebased) =
+        rewrite_descendants(&mut tx, &target_commit, first_commit, last_commit, false)?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+        for (name, commit) in &new_commits {
+            match name {
+                Some(name) => write!(formatter, "{name}: ")?,
+                None => write!(formatter, "Remaining changes: ")?,
+            }
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    tx.finish(
+        ui,
+        format!("split commit {} by config", target_commit.id().hex()),
+    )?;
+    Ok(())
+}
+
 fn move_first_commit(
     tx: &mut WorkspaceCommandTransaction,
     target: &CommitWithSelection,
@@ -390,7 +697,7 @@ fn move_first_commit(
 
 fn rewrite_descendants(
     tx: &mut WorkspaceCommandTransaction,
-    target: &CommitWithSelection,
+    target_commit: &Commit,
     first_commit: Commit,
     second_commit: Commit,
     parallel: bool,
@@ -401,11 +708,11 @@ fn rewrite_descendants(
         // moves any bookmarks pointing to the target commit to the second
         // commit.
         tx.repo_mut()
-            .set_rewritten_commit(target.commit.id().clone(), second_commit.id().clone());
+            .set_rewritten_commit(target_commit.id().clone(), second_commit.id().clone());
     }
     let mut num_rebased = 0;
     tx.repo_mut()
-        .transform_descendants(vec![target.commit.id().clone()], |mut rewriter| {
+        .transform_descendants(vec![target_commit.id().clone()], |mut rewriter| {
             num_rebased += 1;
             if parallel && legacy_bookmark_behavior {
                 // The old_parent is the second commit due to the rewrite above.
@@ -422,7 +729,7 @@ fn rewrite_descendants(
     // Move the working copy commit (@) to the second commit for any workspaces
     // where the target commit is the working copy commit.
     for (name, working_copy_commit) in tx.base_repo().clone().view().wc_commit_ids() {
-        if working_copy_commit == target.commit.id() {
+        if working_copy_commit == target_commit.id() {
             tx.repo_mut().edit(name.clone(), &second_commit)?;
         }
     }
@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use std::io;
+use std::iter;
 
 use itertools::Itertools as _;
 use jj_lib::copies::CopyRecords;
+use jj_lib::git;
+use jj_lib::refs::count_ahead_behind;
 use jj_lib::repo::Repo as _;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
@@ -36,6 +39,8 @@ use crate::ui::Ui;
 ///  * The working copy commit and its parents, and a summary of the changes in
 ///    the working copy (compared to the merged parents)
 ///  * Conflicts in the working copy
+///  * Bookmarks on the working copy commit or its parents that are ahead of or
+///    behind their tracked remote bookmarks
 ///  * [Conflicted bookmarks]
 ///
 /// [Conflicted bookmarks]:
@@ -46,6 +51,12 @@ pub(crate) struct StatusArgs {
     /// Restrict the status display to these paths
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+    /// Show changes to generated files like other files
+    ///
+    /// By default, changes to the files matching the `diff.generated-files`
+    /// setting are collapsed to a single line per file.
+    #[arg(long)]
+    include_generated: bool,
 }
 
 #[instrument(skip_all)]
@@ -88,7 +99,12 @@ pub(crate) fn cmd_status(
                     let records = get_copy_records(repo.store(), parent, wc_commit.id(), &matcher)?;
                     copy_records.add_records(records)?;
                 }
-                let diff_renderer = workspace_command.diff_renderer(vec![DiffFormat::Summary]);
+                let mut diff_renderer = workspace_command.diff_renderer(vec![DiffFormat::Summary]);
+                if !args.include_generated {
+                    diff_renderer.set_generated_files_matcher(
+                        workspace_command.generated_files_matcher(ui)?,
+                    );
+                }
                 let width = ui.term_width();
                 diff_renderer.show_diff(
                     ui,
@@ -125,6 +141,55 @@ pub(crate) fn cmd_status(
             writeln!(formatter)?;
         }
 
+        let bookmark_names = iter::once(wc_commit.id())
+            .chain(wc_commit.parent_ids())
+            .flat_map(|id| repo.view().local_bookmarks_for_commit(id))
+            .map(|(name, _)| name)
+            .sorted()
+            .dedup()
+            .collect_vec();
+        for name in bookmark_names {
+            let bookmark_target = repo.view().get_bookmark(name);
+            for (remote_name, remote_ref) in bookmark_target.tracked_remote_refs() {
+                if git::is_special_git_remote(remote_name) || remote_ref.target.is_absent() {
+                    continue;
+                }
+                let counts = count_ahead_behind(
+                    repo.as_ref(),
+                    bookmark_target.local_target,
+                    &remote_ref.target,
+                )?;
+                if counts.is_synced() {
+                    continue;
+                }
+                write!(formatter, "Bookmark ")?;
+                write!(formatter.labeled("bookmark"), "{}", name.as_symbol())?;
+                let remote_symbol = name.to_remote_symbol(remote_name);
+                match (counts.ahead, counts.behind) {
+                    (ahead, (0, Some(0))) => {
+                        write!(formatter, " is ahead of ")?;
+                        write!(formatter.labeled("bookmark"), "{remote_symbol}")?;
+                        writeln!(formatter, " by {}", format_commit_count(ahead))?;
+                    }
+                    ((0, Some(0)), behind) => {
+                        write!(formatter, " is behind ")?;
+                        write!(formatter.labeled("bookmark"), "{remote_symbol}")?;
+                        writeln!(formatter, " by {}", format_commit_count(behind))?;
+                    }
+                    (ahead, behind) => {
+                        write!(formatter, " has diverged from ")?;
+                        write!(formatter.labeled("bookmark"), "{remote_symbol}")?;
+                        writeln!(
+                            formatter,
+                            " (ahead by {}, behind by {})",
+                            format_commit_count(ahead),
+                            format_commit_count(behind)
+                        )?;
+                    }
+                }
+            }
+        }
+
         if wc_commit.has_conflict()? {
             // TODO: Conflicts should also be filtered by the `matcher`. See the related
             // TODO on `MergedTree::conflicts()`.
@@ -212,3 +277,12 @@ pub(crate) fn cmd_status(
 
     Ok(())
 }
+
+fn format_commit_count((lower, upper): (usize, Option<usize>)) -> String {
+    let noun = if lower == 1 { "commit" } else { "commits" };
+    if upper == Some(lower) {
+        format!("{lower} {noun}")
+    } else {
+        format!("at least {lower} {noun}")
+    }
+}
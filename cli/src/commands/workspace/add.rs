@@ -20,6 +20,7 @@ use jj_lib::file_util;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::rewrite::merge_commit_trees;
 use jj_lib::workspace::Workspace;
 use tracing::instrument;
@@ -44,8 +45,13 @@ enum SparseInheritance {
 
 /// Add a workspace
 ///
+/// The destination must not exist yet, or be an empty directory.
+///
 /// By default, the new workspace inherits the sparse patterns of the current
-/// workspace. You can override this with the `--sparse-patterns` option.
+/// workspace. You can override this with the `--sparse-patterns` option, and
+/// add more paths with `--sparse-add`. For example, to create a workspace which
+/// only contains the `lib` directory, use
+/// `jj workspace add --sparse-patterns=empty --sparse-add=lib <DESTINATION>`.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceAddArgs {
     /// Where to create the new workspace
@@ -73,6 +79,14 @@ pub struct WorkspaceAddArgs {
     /// How to handle sparse patterns when creating a new workspace.
     #[arg(long, value_enum, default_value_t = SparseInheritance::Copy)]
     sparse_patterns: SparseInheritance,
+    /// Paths to add to the sparse patterns of the new workspace
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::AnyPath,
+        value_parser = |s: &str| RepoPathBuf::from_relative_path(s),
+    )]
+    sparse_add: Vec<RepoPathBuf>,
 }
 
 #[instrument(skip_all)]
@@ -84,7 +98,15 @@ pub fn cmd_workspace_add(
     let old_workspace_command = command.workspace_helper(ui)?;
     let destination_path = command.cwd().join(&args.destination);
     if destination_path.exists() {
-        return Err(user_error("Workspace already exists"));
+        // An empty directory (e.g. a mount point) can be reused as is.
+        let is_empty_dir = destination_path.is_dir()
+            && fs::read_dir(&destination_path)
+                .context(&destination_path)?
+                .next()
+                .is_none();
+        if !is_empty_dir {
+            return Err(user_error("Workspace already exists"));
+        }
     } else {
         fs::create_dir(&destination_path).context(&destination_path)?;
     }
@@ -145,6 +167,13 @@ pub fn cmd_workspace_add(
             Some(sparse_patterns)
         }
     };
+    // Nothing needs to be added if all files are included.
+    let sparsity = sparsity.map(|mut sparse_patterns| {
+        sparse_patterns.extend(args.sparse_add.iter().cloned());
+        sparse_patterns.sort_unstable();
+        sparse_patterns.dedup();
+        sparse_patterns
+    });
 
     if let Some(sparse_patterns) = sparsity {
         let checkout_options = new_workspace_command.checkout_options();
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::cmp::max;
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -50,6 +51,8 @@ use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
+use jj_lib::refs::count_ahead_behind;
+use jj_lib::refs::AheadBehindCounts;
 use jj_lib::repo::Repo;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::repo_path::RepoPathUiConverter;
@@ -57,6 +60,8 @@ use jj_lib::revset;
 use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetContainingFn;
 use jj_lib::revset::RevsetDiagnostics;
+use jj_lib::revset::RevsetEvaluationError;
+use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetModifier;
 use jj_lib::revset::RevsetParseContext;
 use jj_lib::revset::UserRevsetExpression;
@@ -83,6 +88,7 @@ use crate::template_builder::BuildContext;
 use crate::template_builder::CoreTemplateBuildFnTable;
 use crate::template_builder::CoreTemplatePropertyKind;
 use crate::template_builder::CoreTemplatePropertyVar;
+use crate::template_builder::TemplateBuildFunctionFnMap;
 use crate::template_builder::TemplateBuildMethodFnMap;
 use crate::template_builder::TemplateLanguage;
 use crate::template_parser;
@@ -95,6 +101,7 @@ use crate::templater;
 use crate::templater::BoxedSerializeProperty;
 use crate::templater::BoxedTemplateProperty;
 use crate::templater::ListTemplate;
+use crate::templater::Literal;
 use crate::templater::PlainTextFormattedProperty;
 use crate::templater::SizeHint;
 use crate::templater::Template;
@@ -666,8 +673,10 @@ pub struct CommitTemplateBuildFnTable<'repo> {
 impl<'repo> CommitTemplateBuildFnTable<'repo> {
     /// Creates new symbol table containing the builtin methods.
     fn builtin() -> Self {
+        let mut core = CoreTemplateBuildFnTable::builtin();
+        merge_fn_map(&mut core.functions, builtin_commit_functions());
         CommitTemplateBuildFnTable {
-            core: CoreTemplateBuildFnTable::builtin(),
+            core,
             commit_methods: builtin_commit_methods(),
             commit_list_methods: template_builder::builtin_unformattable_list_methods(),
             commit_ref_methods: builtin_commit_ref_methods(),
@@ -769,6 +778,7 @@ pub struct CommitKeywordCache<'repo> {
     bookmarks_index: OnceCell<Rc<CommitRefsIndex>>,
     tags_index: OnceCell<Rc<CommitRefsIndex>>,
     git_refs_index: OnceCell<Rc<CommitRefsIndex>>,
+    closest_tags: OnceCell<Rc<ClosestTagCache>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
 }
 
@@ -788,6 +798,22 @@ impl<'repo> CommitKeywordCache<'repo> {
             .get_or_init(|| Rc::new(build_commit_refs_index(repo.view().git_refs())))
     }
 
+    fn closest_tags(&self, repo: &dyn Repo) -> &Rc<ClosestTagCache> {
+        self.closest_tags.get_or_init(|| {
+            Rc::new(ClosestTagCache {
+                tags_index: self.tags_index(repo).clone(),
+                tagged_ids: repo
+                    .view()
+                    .tags()
+                    .values()
+                    .flat_map(|target| target.added_ids())
+                    .cloned()
+                    .collect(),
+                results: RefCell::new(HashMap::new()),
+            })
+        })
+    }
+
     pub fn is_immutable_fn(
         &self,
         language: &CommitTemplateLanguage<'repo>,
@@ -804,6 +830,40 @@ impl<'repo> CommitKeywordCache<'repo> {
     }
 }
 
+/// Global functions returning the context the template is evaluated in.
+fn builtin_commit_functions<'repo>(
+) -> TemplateBuildFunctionFnMap<'repo, CommitTemplateLanguage<'repo>> {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = TemplateBuildFunctionFnMap::<CommitTemplateLanguage>::new();
+    map.insert(
+        "operation_id",
+        |language, _diagnostics, _build_ctx, function| {
+            function.expect_no_arguments()?;
+            let id = language.repo.base_repo().op_id().hex();
+            Ok(Literal(id).into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "workspace_name",
+        |language, _diagnostics, _build_ctx, function| {
+            function.expect_no_arguments()?;
+            let name = language.workspace_name.as_str().to_owned();
+            Ok(Literal(name).into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "workspace_root",
+        |language, _diagnostics, _build_ctx, function| {
+            function.expect_no_arguments()?;
+            let RepoPathUiConverter::Fs { base, .. } = language.path_converter;
+            let root = base.to_string_lossy().into_owned();
+            Ok(Literal(root).into_dyn_wrapped())
+        },
+    );
+    map
+}
+
 fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Commit> {
     // Not using maplit::hashmap!{} or custom declarative macro here because
     // code completion inside macro is quite restricted.
@@ -971,6 +1031,53 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "closest_tag",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let cache = language.keyword_cache.closest_tags(repo).clone();
+            let out_property = self_property.and_then(move |commit| {
+                let closest = cache.get(repo, &commit)?;
+                Ok(closest.map(|(tag, _distance)| tag))
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "closest_tag_distance",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let cache = language.keyword_cache.closest_tags(repo).clone();
+            let out_property = self_property.and_then(move |commit| {
+                let closest = cache.get(repo, &commit)?;
+                let distance = closest.map(|(_tag, distance)| i64::try_from(distance));
+                Ok(distance.transpose()?)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "describe",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let cache = language.keyword_cache.closest_tags(repo).clone();
+            let out_property = self_property.and_then(move |commit| {
+                // Abbreviated like `commit_id.short(8)`. Without a tag, only
+                // the commit ID is printed like `git describe --always`.
+                let short_id = &commit.id().hex()[..8];
+                let description = match cache.get(repo, &commit)? {
+                    Some((tag, 0)) => tag.name().to_owned(),
+                    Some((tag, distance)) => format!("{}-{distance}-g{short_id}", tag.name()),
+                    None => short_id.to_owned(),
+                };
+                Ok(description)
+            });
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "git_refs",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1105,6 +1212,66 @@ fn extract_working_copies(repo: &dyn Repo, commit: &Commit) -> String {
     names.join(" ")
 }
 
+/// Closest tag and its distance from the commit.
+type ClosestTag = (Rc<CommitRef>, usize);
+
+/// Memoizes the closest tags of commits so that `closest_tag()`,
+/// `closest_tag_distance()`, and `describe()` don't walk the history several
+/// times for the same commit.
+struct ClosestTagCache {
+    tags_index: Rc<CommitRefsIndex>,
+    tagged_ids: Vec<CommitId>,
+    results: RefCell<HashMap<CommitId, Option<ClosestTag>>>,
+}
+
+impl ClosestTagCache {
+    fn get(
+        &self,
+        repo: &dyn Repo,
+        commit: &Commit,
+    ) -> Result<Option<ClosestTag>, RevsetEvaluationError> {
+        if let Some(result) = self.results.borrow().get(commit.id()) {
+            return Ok(result.clone());
+        }
+        let result = find_closest_tag(repo, &self.tags_index, &self.tagged_ids, commit)?;
+        self.results
+            .borrow_mut()
+            .insert(commit.id().clone(), result.clone());
+        Ok(result)
+    }
+}
+
+/// Finds the tag on the closest tagged ancestor of the `commit`, like
+/// `git describe`. Returns the tag and the number of commits reachable from
+/// the `commit` but not from the tagged commit.
+///
+/// This is the same commit as the one selected by the `closest(tags(), x)`
+/// revset.
+fn find_closest_tag(
+    repo: &dyn Repo,
+    tags_index: &CommitRefsIndex,
+    tagged_ids: &[CommitId],
+    commit: &Commit,
+) -> Result<Option<ClosestTag>, RevsetEvaluationError> {
+    let commit_expression = RevsetExpression::commit(commit.id().clone());
+    let closest = RevsetExpression::commits(tagged_ids.to_vec())
+        .closest(&commit_expression)
+        .evaluate(repo)?
+        .iter()
+        .next()
+        .transpose()?;
+    let Some(tagged_id) = closest else {
+        return Ok(None);
+    };
+    let tag = tags_index.get(&tagged_id)[0].clone();
+    let distance = RevsetExpression::commit(tagged_id)
+        .range(&commit_expression)
+        .evaluate(repo)?
+        .iter()
+        .process_results(|ids| ids.count())?;
+    Ok(Some((tag, distance)))
+}
+
 fn expect_fileset_literal(
     diagnostics: &mut TemplateDiagnostics,
     node: &ExpressionNode,
@@ -1192,10 +1359,8 @@ pub struct CommitRef {
 struct TrackingRef {
     /// Local ref target which tracks the other remote ref.
     target: RefTarget,
-    /// Number of commits ahead of the tracking `target`.
-    ahead_count: OnceCell<SizeHint>,
-    /// Number of commits behind of the tracking `target`.
-    behind_count: OnceCell<SizeHint>,
+    /// Number of commits ahead of and behind the tracking `target`.
+    counts: OnceCell<AheadBehindCounts>,
 }
 
 impl CommitRef {
@@ -1236,15 +1401,18 @@ impl CommitRef {
     ) -> Rc<Self> {
         let synced = remote_ref.is_tracked() && remote_ref.target == *local_target;
         let tracking_ref = remote_ref.is_tracked().then(|| {
-            let count = if synced {
-                OnceCell::from((0, Some(0))) // fast path for synced remotes
+            let counts = if synced {
+                // fast path for synced remotes
+                OnceCell::from(AheadBehindCounts {
+                    ahead: (0, Some(0)),
+                    behind: (0, Some(0)),
+                })
             } else {
                 OnceCell::new()
             };
             TrackingRef {
                 target: local_target.clone(),
-                ahead_count: count.clone(),
-                behind_count: count,
+                counts,
             }
         });
         Rc::new(CommitRef {
@@ -1327,31 +1495,21 @@ impl CommitRef {
 
     /// Number of commits ahead of the tracking local ref.
     fn tracking_ahead_count(&self, repo: &dyn Repo) -> Result<SizeHint, TemplatePropertyError> {
-        let Some(tracking) = &self.tracking_ref else {
-            return Err(TemplatePropertyError("Not a tracked remote ref".into()));
-        };
-        tracking
-            .ahead_count
-            .get_or_try_init(|| {
-                let self_ids = self.target.added_ids().cloned().collect_vec();
-                let other_ids = tracking.target.added_ids().cloned().collect_vec();
-                Ok(revset::walk_revs(repo, &self_ids, &other_ids)?.count_estimate()?)
-            })
-            .copied()
+        Ok(self.tracking_counts(repo)?.ahead)
     }
 
     /// Number of commits behind of the tracking local ref.
     fn tracking_behind_count(&self, repo: &dyn Repo) -> Result<SizeHint, TemplatePropertyError> {
+        Ok(self.tracking_counts(repo)?.behind)
+    }
+
+    fn tracking_counts(&self, repo: &dyn Repo) -> Result<AheadBehindCounts, TemplatePropertyError> {
         let Some(tracking) = &self.tracking_ref else {
             return Err(TemplatePropertyError("Not a tracked remote ref".into()));
         };
         tracking
-            .behind_count
-            .get_or_try_init(|| {
-                let self_ids = self.target.added_ids().cloned().collect_vec();
-                let other_ids = tracking.target.added_ids().cloned().collect_vec();
-                Ok(revset::walk_revs(repo, &other_ids, &self_ids)?.count_estimate()?)
-            })
+            .counts
+            .get_or_try_init(|| Ok(count_ahead_behind(repo, &self.target, &tracking.target)?))
             .copied()
     }
 }
@@ -1482,6 +1640,14 @@ fn builtin_commit_ref_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "synced",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|commit_ref| commit_ref.synced);
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "tracking_ahead_count",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1712,6 +1878,19 @@ where
                     )
                 })
                 .transpose()?;
+            // Don't load the setting if the length is specified so that the
+            // template doesn't fail because of an unrelated config error.
+            let default_len: usize = if len_node.is_some() {
+                0
+            } else {
+                language
+                    .settings()
+                    .get("ui.shortest-id-min-length")
+                    .map_err(|err| {
+                        let message = "Failed to load shortest id settings";
+                        TemplateParseError::expression(message, function.name_span).with_source(err)
+                    })?
+            };
             let repo = language.repo;
             let index = match language.id_prefix_context.populate(repo) {
                 Ok(index) => index,
@@ -1729,10 +1908,12 @@ where
                 }
             };
             // The length of the id printed will be the maximum of the minimum
-            // `len` and the length of the shortest unique prefix.
+            // `len` (or `ui.shortest-id-min-length` if unspecified) and the
+            // length of the shortest unique prefix.
             let out_property = (self_property, len_property).map(move |(id, len)| {
                 let prefix_len = id.shortest_prefix_len(repo, &index);
-                let mut hex = format!("{id:.len$}", len = max(prefix_len, len.unwrap_or(0)));
+                let len = len.unwrap_or(default_len);
+                let mut hex = format!("{id:.len$}", len = max(prefix_len, len));
                 let rest = hex.split_off(prefix_len);
                 ShortestIdPrefix { prefix: hex, rest }
             });
@@ -2400,6 +2581,7 @@ mod tests {
 
     use jj_lib::config::ConfigLayer;
     use jj_lib::config::ConfigSource;
+    use jj_lib::repo_path::UiPathStyle;
     use jj_lib::revset::RevsetAliasesMap;
     use jj_lib::revset::RevsetExpression;
     use jj_lib::revset::RevsetExtensions;
@@ -2441,6 +2623,7 @@ mod tests {
             let path_converter = RepoPathUiConverter::Fs {
                 cwd: test_workspace.workspace.workspace_root().to_owned(),
                 base: test_workspace.workspace.workspace_root().to_owned(),
+                style: UiPathStyle::default(),
             };
             // IdPrefixContext::new() expects Arc<RevsetExtensions>
             #[expect(clippy::arc_with_non_send_sync)]
@@ -2462,6 +2645,7 @@ mod tests {
             self.path_converter = RepoPathUiConverter::Fs {
                 cwd: self.test_workspace.workspace.workspace_root().join(path),
                 base: self.test_workspace.workspace.workspace_root().to_owned(),
+                style: UiPathStyle::default(),
             };
         }
 
@@ -2530,6 +2714,7 @@ mod tests {
         layer
             .set_value("debug.commit-timestamp", "2001-02-03T04:05:06+07:00")
             .unwrap();
+        layer.set_value("ui.shortest-id-min-length", 0).unwrap();
         config.add_layer(layer);
         UserSettings::from_config(config).unwrap()
     }
@@ -751,6 +751,9 @@ pub fn default_config_migrations() -> Vec<ConfigMigrationRule> {
                 Ok(format!(r#""{escaped}" ++ change_id.short()"#).into())
             },
         ),
+        // Support for this name was dropped in jj 0.18, but configs shared by
+        // organizations still set it. TODO: Delete in jj 0.38+
+        ConfigMigrationRule::rename_value("ui.default-revset", "revsets.log"),
     ]
 }
 
@@ -22,12 +22,14 @@ use jj_lib::trailer::parse_description_trailers;
 use jj_lib::trailer::parse_trailers;
 use thiserror::Error;
 
+use crate::cleanup_guard::CleanupGuard;
 use crate::cli_util::short_commit_hash;
 use crate::cli_util::WorkspaceCommandTransaction;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::config::CommandNameAndArgs;
 use crate::formatter::PlainTextFormatter;
+use crate::temp_files;
 use crate::templater::TemplateRenderer;
 use crate::text_util;
 use crate::ui::Ui;
@@ -111,23 +113,35 @@ impl TextEditor {
         let path = self
             .write_temp_file(content.as_ref(), suffix)
             .map_err(|err| TempTextEditError::new(err.into(), None))?;
-        self.edit_file(&path)
-            .map_err(|err| TempTextEditError::new(err.into(), Some(path.clone())))?;
-        let edited = fs::read_to_string(&path)
-            .context(&path)
-            .map_err(|err| TempTextEditError::new(err.into(), Some(path.clone())))?;
-        // Delete the file only if everything went well.
-        fs::remove_file(path).ok();
-        Ok(edited)
+        // The file is also deleted if the process is killed while the editor
+        // is running.
+        let guard = {
+            let path = path.clone();
+            CleanupGuard::new(move || {
+                fs::remove_file(path).ok();
+            })
+        };
+        let result: Result<_, Box<dyn std::error::Error + Send + Sync>> = self
+            .edit_file(&path)
+            .map_err(|err| err.into())
+            .and_then(|()| Ok(fs::read_to_string(&path).context(&path)?));
+        match result {
+            Ok(edited) => {
+                drop(guard);
+                Ok(edited)
+            }
+            Err(err) => {
+                // Keep the file so the edited content can be recovered.
+                guard.disarm();
+                Err(TempTextEditError::new(err, Some(path)))
+            }
+        }
     }
 
     fn write_temp_file(&self, content: &[u8], suffix: Option<&str>) -> Result<PathBuf, PathError> {
-        let dir = self.dir.clone().unwrap_or_else(tempfile::env::temp_dir);
-        let mut file = tempfile::Builder::new()
-            .prefix("editor-")
-            .suffix(suffix.unwrap_or(""))
-            .tempfile_in(&dir)
-            .context(&dir)?;
+        let dir = self.dir.clone().unwrap_or_else(temp_files::temp_dir_root);
+        let mut file =
+            temp_files::new_temp_file_in(&dir, "editor-", suffix.unwrap_or("")).context(&dir)?;
         file.write_all(content).context(file.path())?;
         let (_, path) = file
             .keep()
@@ -171,6 +185,15 @@ where
     text_util::complete_newline(description.trim_matches('\n'))
 }
 
+/// Cleans up a description read from a file or stdin.
+///
+/// Lines starting with "JJ:" are removed in the same way as when the
+/// description is edited in an editor, and the result ends with a single
+/// newline unless it's empty.
+pub fn cleanup_description(description: &str) -> String {
+    cleanup_description_lines(description.lines())
+}
+
 pub fn edit_description(editor: &TextEditor, description: &str) -> Result<String, CommandError> {
     let mut description = description.to_owned();
     append_blank_line(&mut description);
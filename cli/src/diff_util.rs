@@ -12,7 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::borrow::Borrow;
 use std::cmp::max;
 use std::io;
 use std::iter;
@@ -36,6 +35,7 @@ use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetError;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::materialize_tree_value;
 use jj_lib::conflicts::materialized_diff_stream;
 use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::conflicts::MaterializedFileValue;
@@ -45,22 +45,28 @@ use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopiesTreeDiffEntryPath;
 use jj_lib::copies::CopyOperation;
 use jj_lib::copies::CopyRecords;
-use jj_lib::diff::find_line_ranges;
-use jj_lib::diff::CompareBytesExactly;
-use jj_lib::diff::CompareBytesIgnoreAllWhitespace;
-use jj_lib::diff::CompareBytesIgnoreWhitespaceAmount;
 use jj_lib::diff::Diff;
 use jj_lib::diff::DiffHunk;
 use jj_lib::diff::DiffHunkKind;
+use jj_lib::diff_presentation::diff_by_line;
+use jj_lib::diff_presentation::unified_diff_hunks;
+use jj_lib::diff_presentation::unzip_diff_hunks_to_lines;
+use jj_lib::diff_presentation::DiffLineType;
+use jj_lib::diff_presentation::DiffTokenType;
+use jj_lib::diff_presentation::LineCompareMode;
+use jj_lib::diff_presentation::LineDiffOptions;
 use jj_lib::files;
 use jj_lib::files::ConflictDiffHunk;
 use jj_lib::files::DiffLineHunkSide;
 use jj_lib::files::DiffLineIterator;
 use jj_lib::files::DiffLineNumber;
+use jj_lib::matchers::DifferenceMatcher;
+use jj_lib::matchers::IntersectionMatcher;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::Merge;
 use jj_lib::merge::MergedTreeValue;
 use jj_lib::merged_tree::MergedTree;
+use jj_lib::merged_tree::TreeDiffEntry;
 use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo;
 use jj_lib::repo_path::InvalidRepoPathError;
@@ -69,7 +75,9 @@ use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::rewrite::rebase_to_dest_parent;
 use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
+use once_cell::sync::Lazy;
 use pollster::FutureExt as _;
+use regex::Regex;
 use thiserror::Error;
 use tracing::instrument;
 use unicode_width::UnicodeWidthStr as _;
@@ -82,10 +90,10 @@ use crate::formatter::Formatter;
 use crate::merge_tools;
 use crate::merge_tools::generate_diff;
 use crate::merge_tools::invoke_external_diff;
-use crate::merge_tools::new_utf8_temp_dir;
 use crate::merge_tools::DiffGenerateError;
 use crate::merge_tools::DiffToolMode;
 use crate::merge_tools::ExternalMergeTool;
+use crate::temp_files::new_utf8_temp_dir;
 use crate::templater::TemplateRenderer;
 use crate::text_util;
 use crate::ui::Ui;
@@ -153,6 +161,18 @@ pub enum DiffFormat {
     Tool(Box<ExternalMergeTool>),
 }
 
+impl DiffFormat {
+    /// Whether changes to generated files are collapsed to a single line in
+    /// this format. Formats meant to be consumed by other tools include them in
+    /// full.
+    fn collapses_generated_files(&self) -> bool {
+        match self {
+            Self::Summary | Self::Stat(_) | Self::ColorWords(_) => true,
+            Self::Types | Self::NameOnly | Self::Git(_) | Self::Tool(_) => false,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum BuiltinFormatKind {
     Summary,
@@ -319,6 +339,17 @@ fn diff_formats_from_args(
     Ok([short_format, long_format])
 }
 
+/// Returns the Git diff options configured by the settings and command
+/// arguments, regardless of the requested diff format.
+pub fn unified_diff_options_for(
+    settings: &UserSettings,
+    args: &DiffFormatArgs,
+) -> Result<UnifiedDiffOptions, ConfigGetError> {
+    let mut options = UnifiedDiffOptions::from_settings(settings)?;
+    options.merge_args(args);
+    Ok(options)
+}
+
 fn default_diff_format(
     settings: &UserSettings,
     args: &DiffFormatArgs,
@@ -366,6 +397,7 @@ pub struct DiffRenderer<'a> {
     path_converter: &'a RepoPathUiConverter,
     conflict_marker_style: ConflictMarkerStyle,
     formats: Vec<DiffFormat>,
+    generated_files_matcher: Option<Box<dyn Matcher>>,
 }
 
 impl<'a> DiffRenderer<'a> {
@@ -380,9 +412,16 @@ impl<'a> DiffRenderer<'a> {
             path_converter,
             conflict_marker_style,
             formats,
+            generated_files_matcher: None,
         }
     }
 
+    /// Collapses changes to files matching `matcher` to a single line each,
+    /// instead of rendering them in the requested formats.
+    pub fn set_generated_files_matcher(&mut self, matcher: Box<dyn Matcher>) {
+        self.generated_files_matcher = Some(matcher);
+    }
+
     /// Generates diff between `from_tree` and `to_tree`.
     #[expect(clippy::too_many_arguments)]
     pub fn show_diff(
@@ -422,7 +461,22 @@ impl<'a> DiffRenderer<'a> {
     ) -> Result<(), DiffRenderError> {
         let store = self.repo.store();
         let path_converter = self.path_converter;
+        let all_matcher = matcher;
+        let non_generated_matcher;
+        let collapsed_matcher: &dyn Matcher = if let Some(generated) = &self.generated_files_matcher
+        {
+            non_generated_matcher = DifferenceMatcher::new(all_matcher, generated.as_ref());
+            &non_generated_matcher
+        } else {
+            all_matcher
+        };
         for format in &self.formats {
+            // Machine-readable output includes generated files as usual.
+            let matcher = if format.collapses_generated_files() {
+                collapsed_matcher
+            } else {
+                all_matcher
+            };
             match format {
                 DiffFormat::Summary => {
                     let tree_diff =
@@ -505,9 +559,47 @@ impl<'a> DiffRenderer<'a> {
                 }
             }
         }
+        let collapses_generated_files = self
+            .formats
+            .iter()
+            .any(|format| format.collapses_generated_files());
+        if let Some(generated) = self
+            .generated_files_matcher
+            .as_ref()
+            .filter(|_| collapses_generated_files)
+        {
+            let generated_matcher = IntersectionMatcher::new(all_matcher, generated.as_ref());
+            let tree_diff =
+                from_tree.diff_stream_with_copies(to_tree, &generated_matcher, copy_records);
+            show_generated_files(formatter, tree_diff, path_converter).await?;
+        }
         Ok(())
     }
 
+    /// Generates Git-style combined diff of `to_tree` against all of its
+    /// `parent_trees`.
+    pub fn show_combined_diff(
+        &self,
+        formatter: &mut dyn Formatter,
+        parent_trees: &[MergedTree],
+        to_tree: &MergedTree,
+        matcher: &dyn Matcher,
+        options: &UnifiedDiffOptions,
+    ) -> Result<(), DiffRenderError> {
+        formatter.with_label("diff", |formatter| {
+            show_combined_git_diff(
+                formatter,
+                self.repo.store(),
+                parent_trees,
+                to_tree,
+                matcher,
+                options,
+                self.conflict_marker_style,
+            )
+            .block_on()
+        })
+    }
+
     /// Generates diff between `from_commits` and `to_commit` based off their
     /// parents. The `from_commits` will temporarily be rebased onto the
     /// `to_commit` parents to exclude unrelated changes.
@@ -585,55 +677,14 @@ pub enum ConflictDiffMethod {
     Pair,
 }
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub struct LineDiffOptions {
-    /// How equivalence of lines is tested.
-    pub compare_mode: LineCompareMode,
-    // TODO: add --ignore-blank-lines, etc. which aren't mutually exclusive.
-}
-
-impl LineDiffOptions {
-    fn merge_args(&mut self, args: &DiffFormatArgs) {
-        self.compare_mode = if args.ignore_all_space {
-            LineCompareMode::IgnoreAllSpace
-        } else if args.ignore_space_change {
-            LineCompareMode::IgnoreSpaceChange
-        } else {
-            LineCompareMode::Exact
-        };
-    }
-}
-
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-pub enum LineCompareMode {
-    /// Compares lines literally.
-    #[default]
-    Exact,
-    /// Compares lines ignoring any whitespace occurrences.
-    IgnoreAllSpace,
-    /// Compares lines ignoring changes in whitespace amount.
-    IgnoreSpaceChange,
-}
-
-fn diff_by_line<'input, T: AsRef<[u8]> + ?Sized + 'input>(
-    inputs: impl IntoIterator<Item = &'input T>,
-    options: &LineDiffOptions,
-) -> Diff<'input> {
-    // TODO: If we add --ignore-blank-lines, its tokenizer will have to attach
-    // blank lines to the preceding range. Maybe it can also be implemented as a
-    // post-process (similar to refine_changed_regions()) that expands unchanged
-    // regions across blank lines.
-    match options.compare_mode {
-        LineCompareMode::Exact => {
-            Diff::for_tokenizer(inputs, find_line_ranges, CompareBytesExactly)
-        }
-        LineCompareMode::IgnoreAllSpace => {
-            Diff::for_tokenizer(inputs, find_line_ranges, CompareBytesIgnoreAllWhitespace)
-        }
-        LineCompareMode::IgnoreSpaceChange => {
-            Diff::for_tokenizer(inputs, find_line_ranges, CompareBytesIgnoreWhitespaceAmount)
-        }
-    }
+fn merge_line_diff_args(options: &mut LineDiffOptions, args: &DiffFormatArgs) {
+    options.compare_mode = if args.ignore_all_space {
+        LineCompareMode::IgnoreAllSpace
+    } else if args.ignore_space_change {
+        LineCompareMode::IgnoreSpaceChange
+    } else {
+        LineCompareMode::Exact
+    };
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -673,7 +724,7 @@ impl ColorWordsDiffOptions {
         if let Some(context) = args.context {
             self.context = context;
         }
-        self.line_diff.merge_args(args);
+        merge_line_diff_args(&mut self.line_diff, args);
     }
 }
 
@@ -1321,6 +1372,10 @@ pub async fn show_color_words_diff(
                 (MaterializedTreeValue::Symlink { .. }, MaterializedTreeValue::Symlink { .. }) => {
                     "Symlink target changed at".to_string()
                 }
+                (
+                    MaterializedTreeValue::GitSubmodule(_),
+                    MaterializedTreeValue::GitSubmodule(_),
+                ) => "Modified Git submodule".to_string(),
                 (_, _) => {
                     let left_type = basic_diff_file_type(&left_value);
                     let right_type = basic_diff_file_type(&right_value);
@@ -1496,12 +1551,13 @@ fn git_diff_part(
             };
         }
         MaterializedTreeValue::GitSubmodule(id) => {
-            // TODO: What should we actually do here?
-            mode = "040000";
+            // Git renders a submodule as a gitlink entry whose "content" is
+            // the commit it points to.
+            mode = "160000";
             hash = id.hex();
             content = FileContent {
                 is_binary: false,
-                contents: BString::default(),
+                contents: format!("Subproject commit {hash}\n").into(),
             };
         }
         MaterializedTreeValue::FileConflict(file) => {
@@ -1539,6 +1595,8 @@ fn git_diff_part(
 pub struct UnifiedDiffOptions {
     /// Number of context lines to show.
     pub context: usize,
+    /// Whether to append the enclosing function line to hunk headers.
+    pub function_context: bool,
     /// How lines are tokenized and compared.
     pub line_diff: LineDiffOptions,
 }
@@ -1547,6 +1605,7 @@ impl UnifiedDiffOptions {
     pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
         Ok(UnifiedDiffOptions {
             context: settings.get("diff.git.context")?,
+            function_context: settings.get("diff.git.function-context")?,
             line_diff: LineDiffOptions::default(),
         })
     }
@@ -1555,176 +1614,13 @@ impl UnifiedDiffOptions {
         if let Some(context) = args.context {
             self.context = context;
         }
-        self.line_diff.merge_args(args);
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum DiffLineType {
-    Context,
-    Removed,
-    Added,
-}
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum DiffTokenType {
-    Matching,
-    Different,
-}
-
-type DiffTokenVec<'content> = Vec<(DiffTokenType, &'content [u8])>;
-
-struct UnifiedDiffHunk<'content> {
-    left_line_range: Range<usize>,
-    right_line_range: Range<usize>,
-    lines: Vec<(DiffLineType, DiffTokenVec<'content>)>,
-}
-
-impl<'content> UnifiedDiffHunk<'content> {
-    fn extend_context_lines(&mut self, lines: impl IntoIterator<Item = &'content [u8]>) {
-        let old_len = self.lines.len();
-        self.lines.extend(lines.into_iter().map(|line| {
-            let tokens = vec![(DiffTokenType::Matching, line)];
-            (DiffLineType::Context, tokens)
-        }));
-        self.left_line_range.end += self.lines.len() - old_len;
-        self.right_line_range.end += self.lines.len() - old_len;
-    }
-
-    fn extend_removed_lines(&mut self, lines: impl IntoIterator<Item = DiffTokenVec<'content>>) {
-        let old_len = self.lines.len();
-        self.lines
-            .extend(lines.into_iter().map(|line| (DiffLineType::Removed, line)));
-        self.left_line_range.end += self.lines.len() - old_len;
-    }
-
-    fn extend_added_lines(&mut self, lines: impl IntoIterator<Item = DiffTokenVec<'content>>) {
-        let old_len = self.lines.len();
-        self.lines
-            .extend(lines.into_iter().map(|line| (DiffLineType::Added, line)));
-        self.right_line_range.end += self.lines.len() - old_len;
-    }
-}
-
-fn unified_diff_hunks<'content>(
-    contents: [&'content BStr; 2],
-    options: &UnifiedDiffOptions,
-) -> Vec<UnifiedDiffHunk<'content>> {
-    let mut hunks = vec![];
-    let mut current_hunk = UnifiedDiffHunk {
-        left_line_range: 0..0,
-        right_line_range: 0..0,
-        lines: vec![],
-    };
-    let diff = diff_by_line(contents, &options.line_diff);
-    let mut diff_hunks = diff.hunks().peekable();
-    while let Some(hunk) = diff_hunks.next() {
-        match hunk.kind {
-            DiffHunkKind::Matching => {
-                // Just use the right (i.e. new) content. We could count the
-                // number of skipped lines separately, but the number of the
-                // context lines should match the displayed content.
-                let [_, right] = hunk.contents[..].try_into().unwrap();
-                let mut lines = right.split_inclusive(|b| *b == b'\n').fuse();
-                if !current_hunk.lines.is_empty() {
-                    // The previous hunk line should be either removed/added.
-                    current_hunk.extend_context_lines(lines.by_ref().take(options.context));
-                }
-                let before_lines = if diff_hunks.peek().is_some() {
-                    lines.by_ref().rev().take(options.context).collect()
-                } else {
-                    vec![] // No more hunks
-                };
-                let num_skip_lines = lines.count();
-                if num_skip_lines > 0 {
-                    let left_start = current_hunk.left_line_range.end + num_skip_lines;
-                    let right_start = current_hunk.right_line_range.end + num_skip_lines;
-                    if !current_hunk.lines.is_empty() {
-                        hunks.push(current_hunk);
-                    }
-                    current_hunk = UnifiedDiffHunk {
-                        left_line_range: left_start..left_start,
-                        right_line_range: right_start..right_start,
-                        lines: vec![],
-                    };
-                }
-                // The next hunk should be of DiffHunk::Different type if any.
-                current_hunk.extend_context_lines(before_lines.into_iter().rev());
-            }
-            DiffHunkKind::Different => {
-                let [left_lines, right_lines] =
-                    unzip_diff_hunks_to_lines(Diff::by_word(hunk.contents).hunks());
-                current_hunk.extend_removed_lines(left_lines);
-                current_hunk.extend_added_lines(right_lines);
-            }
-        }
+        merge_line_diff_args(&mut self.line_diff, args);
     }
-    if !current_hunk.lines.is_empty() {
-        hunks.push(current_hunk);
-    }
-    hunks
-}
-
-/// Splits `[left, right]` hunk pairs into `[left_lines, right_lines]`.
-fn unzip_diff_hunks_to_lines<'content, I>(diff_hunks: I) -> [Vec<DiffTokenVec<'content>>; 2]
-where
-    I: IntoIterator,
-    I::Item: Borrow<DiffHunk<'content>>,
-{
-    let mut left_lines: Vec<DiffTokenVec<'content>> = vec![];
-    let mut right_lines: Vec<DiffTokenVec<'content>> = vec![];
-    let mut left_tokens: DiffTokenVec<'content> = vec![];
-    let mut right_tokens: DiffTokenVec<'content> = vec![];
-
-    for hunk in diff_hunks {
-        let hunk = hunk.borrow();
-        match hunk.kind {
-            DiffHunkKind::Matching => {
-                // TODO: add support for unmatched contexts
-                debug_assert!(hunk.contents.iter().all_equal());
-                for token in hunk.contents[0].split_inclusive(|b| *b == b'\n') {
-                    left_tokens.push((DiffTokenType::Matching, token));
-                    right_tokens.push((DiffTokenType::Matching, token));
-                    if token.ends_with(b"\n") {
-                        left_lines.push(mem::take(&mut left_tokens));
-                        right_lines.push(mem::take(&mut right_tokens));
-                    }
-                }
-            }
-            DiffHunkKind::Different => {
-                let [left, right] = hunk.contents[..]
-                    .try_into()
-                    .expect("hunk should have exactly two inputs");
-                for token in left.split_inclusive(|b| *b == b'\n') {
-                    left_tokens.push((DiffTokenType::Different, token));
-                    if token.ends_with(b"\n") {
-                        left_lines.push(mem::take(&mut left_tokens));
-                    }
-                }
-                for token in right.split_inclusive(|b| *b == b'\n') {
-                    right_tokens.push((DiffTokenType::Different, token));
-                    if token.ends_with(b"\n") {
-                        right_lines.push(mem::take(&mut right_tokens));
-                    }
-                }
-            }
-        }
-    }
-
-    if !left_tokens.is_empty() {
-        left_lines.push(left_tokens);
-    }
-    if !right_tokens.is_empty() {
-        right_lines.push(right_tokens);
-    }
-    [left_lines, right_lines]
 }
 
-fn show_unified_diff_hunks(
-    formatter: &mut dyn Formatter,
-    contents: [&BStr; 2],
-    options: &UnifiedDiffOptions,
-) -> io::Result<()> {
+/// Converts a 0-based line range to the 1-based start line number of a hunk
+/// header.
+fn to_line_number(range: Range<usize>) -> usize {
     // "If the chunk size is 0, the first number is one lower than one would
     // expect." - https://www.artima.com/weblogs/viewpost.jsp?thread=164293
     //
@@ -1732,23 +1628,45 @@ fn show_unified_diff_hunks(
     // shall be the number of the preceding line, or 0 if the range is at the
     // start of the file."
     // - https://pubs.opengroup.org/onlinepubs/9799919799/utilities/diff.html
-    fn to_line_number(range: Range<usize>) -> usize {
-        if range.is_empty() {
-            range.start
-        } else {
-            range.start + 1
-        }
+    if range.is_empty() {
+        range.start
+    } else {
+        range.start + 1
     }
+}
 
-    for hunk in unified_diff_hunks(contents, options) {
-        writeln!(
-            formatter.labeled("hunk_header"),
-            "@@ -{},{} +{},{} @@",
-            to_line_number(hunk.left_line_range.clone()),
-            hunk.left_line_range.len(),
-            to_line_number(hunk.right_line_range.clone()),
-            hunk.right_line_range.len()
-        )?;
+fn show_unified_diff_hunks(
+    formatter: &mut dyn Formatter,
+    path: &RepoPath,
+    contents: [&BStr; 2],
+    options: &UnifiedDiffOptions,
+) -> io::Result<()> {
+    let function_pattern = options
+        .function_context
+        .then(|| function_line_pattern(path));
+    let left_lines = if function_pattern.is_some() {
+        contents[0].split_inclusive(|b| *b == b'\n').collect_vec()
+    } else {
+        vec![]
+    };
+    for hunk in unified_diff_hunks(contents, options.context, &options.line_diff) {
+        let function_line = function_pattern.and_then(|pattern| {
+            find_function_line(pattern, &left_lines[..hunk.left_line_range.start])
+        });
+        formatter.with_label("hunk_header", |formatter| {
+            write!(
+                formatter,
+                "@@ -{},{} +{},{} @@",
+                to_line_number(hunk.left_line_range.clone()),
+                hunk.left_line_range.len(),
+                to_line_number(hunk.right_line_range.clone()),
+                hunk.right_line_range.len()
+            )?;
+            if let Some(line) = function_line {
+                write!(formatter, " {line}")?;
+            }
+            writeln!(formatter)
+        })?;
         for (line_type, tokens) in &hunk.lines {
             let (label, sigil) = match line_type {
                 DiffLineType::Context => ("context", " "),
@@ -1783,6 +1701,48 @@ fn show_diff_line_tokens(
     Ok(())
 }
 
+/// Returns the pattern of lines that start a function (or another kind of
+/// section) in a file at `path`. Like Git's built-in `diff.<driver>.xfuncname`
+/// patterns, these are heuristics and don't need to parse the language.
+fn function_line_pattern(path: &RepoPath) -> &'static Regex {
+    static RUST: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(
+            r"^\s*(pub(\([^)]*\))?\s+)?((async|const|unsafe|extern\s+\S+)\s+)*((fn|struct|enum|union|trait|impl|mod)\b|macro_rules!)",
+        )
+        .unwrap()
+    });
+    static PYTHON: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*((async\s+)?def|class)\s").unwrap());
+    static GO: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(func|type)\s").unwrap());
+    // Same as Git's default: a line starting with an identifier character.
+    static DEFAULT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[[:alpha:]_$]").unwrap());
+
+    let file_name = path.split().map_or("", |(_, name)| name.as_internal_str());
+    let extension = file_name.rsplit_once('.').map_or("", |(_, ext)| ext);
+    match extension {
+        "rs" => &RUST,
+        "py" | "pyi" => &PYTHON,
+        "go" => &GO,
+        _ => &DEFAULT,
+    }
+}
+
+/// Finds the last line in `lines` matching `pattern`, and returns it without
+/// the trailing whitespace. Like Git, long lines are truncated.
+fn find_function_line<'a>(pattern: &Regex, lines: &[&'a [u8]]) -> Option<&'a str> {
+    const MAX_LEN: usize = 80;
+    let line = lines
+        .iter()
+        .rev()
+        .filter_map(|line| std::str::from_utf8(line).ok())
+        .find(|line| pattern.is_match(line))?
+        .trim_end();
+    let len = (0..=line.len().min(MAX_LEN))
+        .rev()
+        .find(|&i| line.is_char_boundary(i))
+        .unwrap();
+    Some(&line[..len])
+}
+
 pub async fn show_git_diff(
     formatter: &mut dyn Formatter,
     store: &Store,
@@ -1868,6 +1828,7 @@ pub async fn show_git_diff(
             })?;
             show_unified_diff_hunks(
                 formatter,
+                path.source(),
                 [&left_part.content.contents, &right_part.content.contents].map(BStr::new),
                 options,
             )?;
@@ -1876,6 +1837,244 @@ pub async fn show_git_diff(
     Ok(())
 }
 
+struct CombinedDiffHunk<'content> {
+    parent_line_ranges: Vec<Range<usize>>,
+    result_line_range: Range<usize>,
+    /// Lines prefixed with one `' '`, `'-'`, or `'+'` column per parent.
+    lines: Vec<(Vec<u8>, &'content [u8])>,
+}
+
+/// Builds hunks of a combined diff. Only regions where the `result` differs
+/// from all `parents` are included, so changes taken verbatim from one of the
+/// parents are omitted.
+fn combined_diff_hunks<'content>(
+    parents: &[&'content BStr],
+    result: &'content BStr,
+    options: &UnifiedDiffOptions,
+) -> Vec<CombinedDiffHunk<'content>> {
+    let result_lines = result.split_inclusive(|b| *b == b'\n').collect_vec();
+    // The slot `r` consists of the lines removed just before the result line
+    // `r`, and the result line `r` itself. The last slot has no result line.
+    let num_slots = result_lines.len() + 1;
+    let mut added = vec![vec![false; result_lines.len()]; parents.len()];
+    let mut removed: Vec<Vec<Vec<&[u8]>>> = vec![vec![vec![]; num_slots]; parents.len()];
+    for (i, parent) in parents.iter().enumerate() {
+        let diff = diff_by_line([*parent, result], &options.line_diff);
+        let mut r = 0;
+        for hunk in diff.hunks() {
+            let [left, right] = hunk.contents[..].try_into().unwrap();
+            let num_right_lines = right.split_inclusive(|b| *b == b'\n').count();
+            if hunk.kind == DiffHunkKind::Different {
+                removed[i][r].extend(left.split_inclusive(|b| *b == b'\n'));
+                added[i][r..r + num_right_lines].fill(true);
+            }
+            r += num_right_lines;
+        }
+    }
+    // Number of parent lines preceding each slot
+    let parent_line_positions = (0..parents.len())
+        .map(|i| {
+            let mut positions = vec![0; num_slots + 1];
+            for r in 0..num_slots {
+                let num_kept = usize::from(r < result_lines.len() && !added[i][r]);
+                positions[r + 1] = positions[r] + removed[i][r].len() + num_kept;
+            }
+            positions
+        })
+        .collect_vec();
+
+    let is_interesting = |r: usize| {
+        let all_added = r < result_lines.len() && added.iter().all(|added| added[r]);
+        let all_removed = removed.iter().all(|removed| !removed[r].is_empty());
+        all_added || all_removed
+    };
+    let mut slot_ranges: Vec<Range<usize>> = vec![];
+    for r in (0..num_slots).filter(|&r| is_interesting(r)) {
+        let start = r.saturating_sub(options.context);
+        let end = (r + 1 + options.context).min(num_slots);
+        match slot_ranges.last_mut() {
+            Some(last) if start <= last.end => last.end = end,
+            _ => slot_ranges.push(start..end),
+        }
+    }
+
+    slot_ranges
+        .into_iter()
+        .map(|slots| {
+            let mut lines = vec![];
+            for r in slots.clone() {
+                for (i, removed) in removed.iter().enumerate() {
+                    for &line in &removed[r] {
+                        let mut columns = vec![b' '; parents.len()];
+                        columns[i] = b'-';
+                        lines.push((columns, line));
+                    }
+                }
+                if let Some(&line) = result_lines.get(r) {
+                    let columns = added
+                        .iter()
+                        .map(|added| if added[r] { b'+' } else { b' ' })
+                        .collect();
+                    lines.push((columns, line));
+                }
+            }
+            CombinedDiffHunk {
+                parent_line_ranges: parent_line_positions
+                    .iter()
+                    .map(|positions| positions[slots.start]..positions[slots.end])
+                    .collect(),
+                result_line_range: slots.start..slots.end.min(result_lines.len()),
+                lines,
+            }
+        })
+        .collect()
+}
+
+fn show_combined_diff_hunks(
+    formatter: &mut dyn Formatter,
+    parents: &[&BStr],
+    result: &BStr,
+    options: &UnifiedDiffOptions,
+) -> io::Result<()> {
+    let marker = "@".repeat(parents.len() + 1);
+    for hunk in combined_diff_hunks(parents, result, options) {
+        formatter.with_label("hunk_header", |formatter| {
+            write!(formatter, "{marker}")?;
+            for range in &hunk.parent_line_ranges {
+                write!(
+                    formatter,
+                    " -{},{}",
+                    to_line_number(range.clone()),
+                    range.len()
+                )?;
+            }
+            writeln!(
+                formatter,
+                " +{},{} {marker}",
+                to_line_number(hunk.result_line_range.clone()),
+                hunk.result_line_range.len()
+            )
+        })?;
+        for (columns, content) in &hunk.lines {
+            let label = if columns.contains(&b'+') {
+                "added"
+            } else if columns.contains(&b'-') {
+                "removed"
+            } else {
+                "context"
+            };
+            formatter.with_label(label, |formatter| {
+                formatter.write_all(columns)?;
+                formatter.write_all(content)
+            })?;
+            if !content.ends_with(b"\n") {
+                write!(formatter, "\n\\ No newline at end of file\n")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shows a Git-style combined diff of `to_tree` against all of
+/// `parent_trees`. Only files which differ from every parent are included.
+pub async fn show_combined_git_diff(
+    formatter: &mut dyn Formatter,
+    store: &Store,
+    parent_trees: &[MergedTree],
+    to_tree: &MergedTree,
+    matcher: &dyn Matcher,
+    options: &UnifiedDiffOptions,
+    conflict_marker_style: ConflictMarkerStyle,
+) -> Result<(), DiffRenderError> {
+    let (first_tree, other_trees) = parent_trees
+        .split_first()
+        .expect("at least one parent tree should be given");
+    let mut tree_diff = first_tree.diff_stream(to_tree, matcher);
+    while let Some(TreeDiffEntry { path, values }) = tree_diff.next().await {
+        let (first_value, right_value) = values?;
+        let mut parent_values = vec![first_value];
+        for tree in other_trees {
+            let value = tree.path_value_async(&path).await?;
+            if value == right_value {
+                break;
+            }
+            parent_values.push(value);
+        }
+        if parent_values.len() < parent_trees.len() {
+            continue; // same as one of the parents
+        }
+
+        let mut parent_parts = vec![];
+        for value in parent_values {
+            // A directory in a parent is shown as an absent file.
+            let value = if value.is_tree() {
+                Merge::absent()
+            } else {
+                value
+            };
+            let value = materialize_tree_value(store, &path, value).await?;
+            parent_parts.push(git_diff_part(&path, value, conflict_marker_style)?);
+        }
+        let right_value = materialize_tree_value(store, &path, right_value).await?;
+        let right_part = git_diff_part(&path, right_value, conflict_marker_style)?;
+
+        let path_string = path.as_internal_file_string();
+        formatter.with_label("file_header", |formatter| {
+            writeln!(formatter, "diff --cc {path_string}")?;
+            let parent_modes = parent_parts.iter().map(|part| part.mode).collect_vec();
+            if parent_modes.iter().all(Option::is_none) {
+                writeln!(formatter, "new file mode {}", right_part.mode.unwrap())?;
+            } else if let Some(right_mode) = right_part.mode {
+                if parent_modes.iter().any(|&mode| mode != Some(right_mode)) {
+                    let parent_modes = parent_modes
+                        .iter()
+                        .map(|mode| mode.unwrap_or("000000"))
+                        .join(",");
+                    writeln!(formatter, "mode {parent_modes}..{right_mode}")?;
+                }
+            } else {
+                let parent_modes = parent_modes
+                    .iter()
+                    .map(|mode| mode.unwrap_or("000000"))
+                    .join(",");
+                writeln!(formatter, "deleted file mode {parent_modes}")?;
+            }
+            let parent_hashes = parent_parts.iter().map(|part| &part.hash).join(",");
+            writeln!(formatter, "index {parent_hashes}..{}", right_part.hash)
+        })?;
+
+        let left_path = if parent_parts.iter().all(|part| part.mode.is_none()) {
+            "/dev/null".to_owned()
+        } else {
+            format!("a/{path_string}")
+        };
+        let right_path = match right_part.mode {
+            Some(_) => format!("b/{path_string}"),
+            None => "/dev/null".to_owned(),
+        };
+        if parent_parts.iter().any(|part| part.content.is_binary) || right_part.content.is_binary {
+            writeln!(formatter, "Binary files differ")?;
+        } else {
+            formatter.with_label("file_header", |formatter| {
+                writeln!(formatter, "--- {left_path}")?;
+                writeln!(formatter, "+++ {right_path}")?;
+                io::Result::Ok(())
+            })?;
+            let parent_contents = parent_parts
+                .iter()
+                .map(|part| BStr::new(&part.content.contents))
+                .collect_vec();
+            show_combined_diff_hunks(
+                formatter,
+                &parent_contents,
+                BStr::new(&right_part.content.contents),
+                options,
+            )?;
+        }
+    }
+    Ok(())
+}
+
 #[instrument(skip_all)]
 pub async fn show_diff_summary(
     formatter: &mut dyn Formatter,
@@ -1907,6 +2106,7 @@ pub fn diff_status_label_and_char(
         }
     } else {
         match (before.is_present(), after.is_present()) {
+            (true, true) if is_type_change(before, after) => ("modified", 'T'),
             (true, true) => ("modified", 'M'),
             (false, true) => ("added", 'A'),
             (true, false) => ("removed", 'D'),
@@ -1915,6 +2115,18 @@ pub fn diff_status_label_and_char(
     }
 }
 
+/// Returns true if a resolved path changed between a regular file, a symlink,
+/// and a Git submodule. Mode-only changes such as the executable bit aren't
+/// considered type changes.
+fn is_type_change(before: &MergedTreeValue, after: &MergedTreeValue) -> bool {
+    match (before.as_resolved(), after.as_resolved()) {
+        (Some(Some(before)), Some(Some(after))) => {
+            mem::discriminant(before) != mem::discriminant(after)
+        }
+        _ => false,
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct DiffStatOptions {
     /// How lines are tokenized and compared.
@@ -1923,7 +2135,7 @@ pub struct DiffStatOptions {
 
 impl DiffStatOptions {
     fn merge_args(&mut self, args: &DiffFormatArgs) {
-        self.line_diff.merge_args(args);
+        merge_line_diff_args(&mut self.line_diff, args);
     }
 }
 
@@ -2125,6 +2337,22 @@ pub async fn show_names(
     Ok(())
 }
 
+/// Prints a single line for each changed generated file.
+async fn show_generated_files(
+    formatter: &mut dyn Formatter,
+    mut tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
+    path_converter: &RepoPathUiConverter,
+) -> io::Result<()> {
+    while let Some(CopiesTreeDiffEntry { path, .. }) = tree_diff.next().await {
+        let ui_path = path_converter.format_file_path(path.target());
+        writeln!(
+            formatter.labeled("generated"),
+            "Generated file changed: {ui_path}"
+        )?;
+    }
+    Ok(())
+}
+
 pub async fn show_templated(
     formatter: &mut dyn Formatter,
     mut tree_diff: BoxStream<'_, CopiesTreeDiffEntry>,
@@ -0,0 +1,427 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! User-configured commands run at certain points of a command.
+//!
+//! A hook is configured as `hooks.<name>`. The working-copy hooks receive the
+//! paths that changed in the working copy on stdin, one per line, which lets
+//! build systems and file watchers invalidate exactly the changed files. The
+//! `post-push` and `post-fetch` hooks receive the updated bookmarks as JSON. A
+//! failing `pre-commit-rewrite` hook aborts the transaction, which can be used
+//! to enforce commit message policies locally. Other hooks only warn on
+//! failure.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::rc::Rc;
+
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetResultExt as _;
+#[cfg(feature = "git")]
+use jj_lib::git::GitImportStats;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::ref_name::RefNameBuf;
+use jj_lib::ref_name::RemoteName;
+use jj_lib::ref_name::RemoteRefSymbolBuf;
+use jj_lib::refs::BookmarkPushUpdate;
+use jj_lib::repo::MutableRepo;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::ResolvedRevsetExpression;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::settings::UserSettings;
+use pollster::FutureExt as _;
+
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::config::CommandNameAndArgs;
+use crate::ui::Ui;
+
+/// Name of the hook run for each rewritten commit before a transaction is
+/// committed.
+pub const PRE_COMMIT_REWRITE_HOOK: &str = "pre-commit-rewrite";
+
+/// Name of the hook run after bookmarks were pushed to a remote.
+pub const POST_PUSH_HOOK: &str = "post-push";
+
+/// Name of the hook run after remote bookmarks were updated by a fetch.
+pub const POST_FETCH_HOOK: &str = "post-fetch";
+
+/// Maximum number of new commits listed per bookmark in the input of the
+/// `post-push` and `post-fetch` hooks.
+const MAX_HOOK_COMMITS: usize = 1000;
+
+/// Point at which a working-copy hook is run.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WorkingCopyHook {
+    /// After files in the working copy were updated to a new commit.
+    PostCheckout,
+    /// After changes in the working copy were recorded in the working-copy
+    /// commit.
+    PostSnapshot,
+}
+
+impl WorkingCopyHook {
+    /// Name of the hook in the `hooks` config table.
+    pub fn name(self) -> &'static str {
+        match self {
+            WorkingCopyHook::PostCheckout => "post-checkout",
+            WorkingCopyHook::PostSnapshot => "post-snapshot",
+        }
+    }
+}
+
+/// Runs the working-copy `hook` command if configured.
+///
+/// The paths changed from `old_commit` to `new_commit` are written to the
+/// command's stdin. If `old_commit` is `None`, all files of `new_commit` are
+/// reported. A failing hook is reported as a warning since the working copy
+/// has already been updated.
+pub fn run_working_copy_hook(
+    ui: &Ui,
+    settings: &UserSettings,
+    workspace_root: &Path,
+    hook: WorkingCopyHook,
+    old_commit: Option<&Commit>,
+    new_commit: &Commit,
+) -> Result<(), CommandError> {
+    let Some(command) = settings
+        .get::<CommandNameAndArgs>(["hooks", hook.name()])
+        .optional()?
+    else {
+        return Ok(());
+    };
+    let old_tree = match old_commit {
+        Some(commit) => commit.tree()?,
+        None => new_commit.store().root_commit().tree()?,
+    };
+    let changed_paths = collect_changed_paths(&old_tree, &new_commit.tree()?);
+    if changed_paths.is_empty() {
+        return Ok(());
+    }
+
+    let mut cmd = command.to_command();
+    cmd.current_dir(workspace_root)
+        .env("JJ_HOOK", hook.name())
+        .env("JJ_WORKSPACE_ROOT", workspace_root)
+        .env("JJ_NEW_COMMIT_ID", new_commit.id().hex());
+    if let Some(commit) = old_commit {
+        cmd.env("JJ_OLD_COMMIT_ID", commit.id().hex());
+    }
+    tracing::info!(?cmd, "running working-copy hook");
+    let input = changed_paths
+        .iter()
+        .map(|path| format!("{path}\n"))
+        .join("");
+    warn_on_hook_failure(
+        ui,
+        hook.name(),
+        &command,
+        run_with_stdin(&mut cmd, input.as_bytes()),
+    )
+}
+
+/// Returns the paths that differ between the two trees, in the repo's
+/// internal (slash-separated) form.
+fn collect_changed_paths(old_tree: &MergedTree, new_tree: &MergedTree) -> Vec<String> {
+    let mut tree_diff = old_tree.diff_stream(new_tree, &EverythingMatcher);
+    let mut paths = vec![];
+    async {
+        while let Some(entry) = tree_diff.next().await {
+            paths.push(entry.path.as_internal_file_string().to_owned());
+        }
+    }
+    .block_on();
+    paths
+}
+
+/// Runs the `pre-commit-rewrite` hook if configured.
+///
+/// The hook is run once for each visible commit rewritten in the transaction,
+/// parents first, with the commit description on stdin. Newly created commits
+/// aren't checked. If the hook fails, an error is returned so the transaction
+/// can be discarded.
+pub fn run_pre_commit_rewrite_hook(
+    settings: &UserSettings,
+    workspace_root: &Path,
+    repo: &MutableRepo,
+) -> Result<(), CommandError> {
+    let Some(command) = settings
+        .get::<CommandNameAndArgs>(["hooks", PRE_COMMIT_REWRITE_HOOK])
+        .optional()?
+    else {
+        return Ok(());
+    };
+    let rewritten_ids = repo
+        .commit_predecessors()
+        .iter()
+        .filter(|(_, predecessors)| !predecessors.is_empty())
+        .map(|(id, _)| id.clone())
+        .collect_vec();
+    if rewritten_ids.is_empty() {
+        return Ok(());
+    }
+    let commits: Vec<Commit> = RevsetExpression::commits(rewritten_ids)
+        .intersection(&RevsetExpression::visible_heads().ancestors())
+        .evaluate(repo)?
+        .iter()
+        .commits(repo.store())
+        .try_collect()?;
+    let wc_commit_ids: HashSet<_> = repo.view().wc_commit_ids().values().collect();
+
+    for commit in commits.iter().rev() {
+        let mut cmd = command.to_command();
+        cmd.current_dir(workspace_root)
+            .env("JJ_HOOK", PRE_COMMIT_REWRITE_HOOK)
+            .env("JJ_WORKSPACE_ROOT", workspace_root)
+            .env("JJ_COMMIT_ID", commit.id().hex())
+            .env("JJ_CHANGE_ID", commit.change_id().reverse_hex())
+            .env(
+                "JJ_IS_WORKING_COPY",
+                wc_commit_ids.contains(commit.id()).to_string(),
+            );
+        tracing::info!(?cmd, "running pre-commit-rewrite hook");
+        let status = run_with_stdin(&mut cmd, commit.description().as_bytes()).map_err(|err| {
+            user_error_with_message(
+                format!(
+                    "Failed to run the {PRE_COMMIT_REWRITE_HOOK} hook `{}`",
+                    command.split_name()
+                ),
+                err,
+            )
+        })?;
+        if !status.success() {
+            return Err(user_error(format!(
+                "The {PRE_COMMIT_REWRITE_HOOK} hook rejected commit {} ({status})",
+                short_commit_hash(commit.id())
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Runs the `post-push` hook if configured.
+///
+/// The pushed bookmarks are written to the command's stdin as JSON. See
+/// [`run_bookmark_update_hook()`] for the format. A failing hook is reported as
+/// a warning since the push has already happened.
+pub fn run_post_push_hook(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    old_repo: &ReadonlyRepo,
+    remote: &RemoteName,
+    updates: &[(RefNameBuf, BookmarkPushUpdate)],
+) -> Result<(), CommandError> {
+    let updates = updates
+        .iter()
+        .map(|(name, update)| BookmarkUpdate {
+            symbol: name.to_remote_symbol(remote).to_owned(),
+            old_ids: update.old_target.iter().cloned().collect(),
+            new_ids: update.new_target.iter().cloned().collect(),
+        })
+        .collect_vec();
+    run_bookmark_update_hook(
+        ui,
+        workspace_command,
+        POST_PUSH_HOOK,
+        &[("JJ_REMOTE", remote.as_str())],
+        old_repo,
+        &updates,
+    )
+}
+
+/// Runs the `post-fetch` hook if configured.
+///
+/// The remote bookmarks updated by the fetch are written to the command's
+/// stdin as JSON. See [`run_bookmark_update_hook()`] for the format. A failing
+/// hook is reported as a warning since the fetch has already happened.
+#[cfg(feature = "git")]
+pub fn run_post_fetch_hook(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    old_repo: &ReadonlyRepo,
+    stats: &GitImportStats,
+) -> Result<(), CommandError> {
+    let updates = stats
+        .changed_remote_bookmarks
+        .iter()
+        .map(|(symbol, (old_remote_ref, new_target))| BookmarkUpdate {
+            symbol: symbol.clone(),
+            old_ids: old_remote_ref.target.added_ids().cloned().collect(),
+            new_ids: new_target.added_ids().cloned().collect(),
+        })
+        .collect_vec();
+    run_bookmark_update_hook(
+        ui,
+        workspace_command,
+        POST_FETCH_HOOK,
+        &[],
+        old_repo,
+        &updates,
+    )
+}
+
+struct BookmarkUpdate {
+    symbol: RemoteRefSymbolBuf,
+    old_ids: Vec<CommitId>,
+    new_ids: Vec<CommitId>,
+}
+
+/// Runs the given hook with the bookmark `updates` written to its stdin as a
+/// JSON object like this:
+///
+/// ```json
+/// {
+///   "bookmarks": [
+///     {
+///       "name": "main",
+///       "remote": "origin",
+///       "old_targets": ["<commit id>"],
+///       "new_targets": ["<commit id>"],
+///       "commits": ["<commit id>", ...],
+///       "commits_truncated": false
+///     }
+///   ]
+/// }
+/// ```
+///
+/// `old_targets` and `new_targets` are empty if the bookmark didn't exist or
+/// was deleted, and have more than one entry if it is conflicted. `commits`
+/// lists the commits reachable from the new targets that weren't reachable
+/// from any bookmark of the same remote in `old_repo`, parents first. The root
+/// commit is never listed. At most [`MAX_HOOK_COMMITS`] commits closest to the
+/// new targets are listed, and `commits_truncated` tells whether more were
+/// omitted.
+fn run_bookmark_update_hook(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    hook_name: &str,
+    env: &[(&str, &str)],
+    old_repo: &ReadonlyRepo,
+    updates: &[BookmarkUpdate],
+) -> Result<(), CommandError> {
+    let Some(command) = workspace_command
+        .settings()
+        .get::<CommandNameAndArgs>(["hooks", hook_name])
+        .optional()?
+    else {
+        return Ok(());
+    };
+    if updates.is_empty() {
+        return Ok(());
+    }
+    let repo = workspace_command.repo();
+    let to_hex = |ids: &[CommitId]| ids.iter().map(|id| id.hex()).collect_vec();
+    let mut old_remote_heads_map: HashMap<&RemoteName, Rc<ResolvedRevsetExpression>> =
+        HashMap::new();
+    let mut bookmarks = vec![];
+    for update in updates {
+        let remote = update.symbol.remote.as_ref();
+        let old_remote_heads = old_remote_heads_map.entry(remote).or_insert_with(|| {
+            let ids = old_repo
+                .view()
+                .remote_bookmarks(remote)
+                .flat_map(|(_, remote_ref)| remote_ref.target.added_ids())
+                .cloned()
+                .collect_vec();
+            RevsetExpression::commits(ids).union(&RevsetExpression::root())
+        });
+        // Stop at the limit so that a remote with a long new history doesn't
+        // have to be walked entirely.
+        let mut commit_ids: Vec<CommitId> = old_remote_heads
+            .range(&RevsetExpression::commits(update.new_ids.clone()))
+            .evaluate(repo.as_ref())?
+            .iter()
+            .take(MAX_HOOK_COMMITS + 1)
+            .try_collect()?;
+        let truncated = commit_ids.len() > MAX_HOOK_COMMITS;
+        commit_ids.truncate(MAX_HOOK_COMMITS);
+        bookmarks.push(serde_json::json!({
+            "name": update.symbol.name.as_str(),
+            "remote": update.symbol.remote.as_str(),
+            "old_targets": to_hex(&update.old_ids),
+            "new_targets": to_hex(&update.new_ids),
+            "commits": commit_ids.iter().rev().map(|id| id.hex()).collect_vec(),
+            "commits_truncated": truncated,
+        }));
+    }
+    let input = serde_json::json!({ "bookmarks": bookmarks });
+
+    let workspace_root = workspace_command.workspace_root();
+    let mut cmd = command.to_command();
+    cmd.current_dir(workspace_root)
+        .env("JJ_HOOK", hook_name)
+        .env("JJ_WORKSPACE_ROOT", workspace_root)
+        .envs(env.iter().copied());
+    tracing::info!(?cmd, "running {hook_name} hook");
+    warn_on_hook_failure(
+        ui,
+        hook_name,
+        &command,
+        run_with_stdin(&mut cmd, input.to_string().as_bytes()),
+    )
+}
+
+/// Prints a warning if the hook couldn't be run or exited with an error.
+fn warn_on_hook_failure(
+    ui: &Ui,
+    hook_name: &str,
+    command: &CommandNameAndArgs,
+    result: io::Result<ExitStatus>,
+) -> Result<(), CommandError> {
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            writeln!(
+                ui.warning_default(),
+                "The {hook_name} hook exited with {status}"
+            )?;
+        }
+        Err(err) => {
+            writeln!(
+                ui.warning_default(),
+                "Failed to run the {hook_name} hook `{}`: {err}",
+                command.split_name()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn run_with_stdin(cmd: &mut std::process::Command, input: &[u8]) -> io::Result<ExitStatus> {
+    let mut child = cmd.stdin(Stdio::piped()).spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    // The hook may exit without reading its input.
+    match stdin.write_all(input) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(err) => return Err(err),
+    }
+    drop(stdin);
+    child.wait()
+}
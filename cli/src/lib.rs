@@ -39,11 +39,13 @@ pub mod git_util {
     }
 }
 pub mod graphlog;
+pub mod hooks;
 pub mod merge_tools;
 pub mod movement_util;
 pub mod operation_templater;
 mod progress;
 pub mod revset_util;
+pub mod temp_files;
 pub mod template_builder;
 pub mod template_parser;
 pub mod templater;
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::io::IsTerminal as _;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -48,6 +49,18 @@ pub enum BuiltinToolError {
     Unimplemented { item: &'static str, id: String },
     #[error("Backend error")]
     BackendError(#[from] jj_lib::backend::BackendError),
+    #[error("The builtin difftool/mergetool requires an interactive terminal")]
+    NotATerminal,
+}
+
+/// Fails early if the terminal UI can't be drawn, instead of leaving the
+/// terminal in a broken state.
+fn check_terminal() -> Result<(), BuiltinToolError> {
+    if std::io::stdout().is_terminal() {
+        Ok(())
+    } else {
+        Err(BuiltinToolError::NotATerminal)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -535,6 +548,7 @@ pub fn edit_diff_builtin(
     let tree_diff = left_tree.diff_stream_with_copies(right_tree, matcher, &copy_records);
     let (changed_files, files) =
         make_diff_files(&store, tree_diff, conflict_marker_style).block_on()?;
+    check_terminal()?;
     let mut input = scm_record::helpers::CrosstermInput;
     let recorder = scm_record::Recorder::new(
         scm_record::RecordState {
@@ -668,11 +682,13 @@ pub fn edit_merge_builtin(
     tree: &MergedTree,
     merge_tool_files: &[MergeToolFile],
 ) -> Result<MergedTreeId, BuiltinToolError> {
+    let files: Vec<_> = merge_tool_files.iter().map(make_merge_file).try_collect()?;
+    check_terminal()?;
     let mut input = scm_record::helpers::CrosstermInput;
     let recorder = scm_record::Recorder::new(
         scm_record::RecordState {
             is_read_only: false,
-            files: merge_tool_files.iter().map(make_merge_file).try_collect()?,
+            files,
             commits: Default::default(),
         },
         &mut input,
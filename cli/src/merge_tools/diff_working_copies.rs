@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 use std::fs::File;
-use std::io;
 use std::io::Write as _;
 use std::path::Path;
 use std::path::PathBuf;
@@ -23,11 +22,12 @@ use jj_lib::working_copy::CheckoutError;
 use jj_lib::working_copy::CheckoutOptions;
 use jj_lib::working_copy::SnapshotOptions;
 use pollster::FutureExt as _;
-use tempfile::TempDir;
 use thiserror::Error;
 
 use super::external::ExternalToolError;
 use super::DiffEditError;
+use crate::temp_files::new_utf8_temp_dir;
+use crate::temp_files::ScopedTempDir;
 
 #[derive(Debug, Error)]
 pub enum DiffCheckoutError {
@@ -40,7 +40,7 @@ pub enum DiffCheckoutError {
 }
 
 pub(crate) struct DiffWorkingCopies {
-    _temp_dir: TempDir, // Temp dir will be deleted when this is dropped
+    _temp_dir: ScopedTempDir, // Temp dir will be deleted when this is dropped
     left_tree_state: TreeState,
     right_tree_state: TreeState,
     output_tree_state: Option<TreeState>,
@@ -108,16 +108,6 @@ fn check_out(
     Ok(tree_state)
 }
 
-pub(crate) fn new_utf8_temp_dir(prefix: &str) -> io::Result<TempDir> {
-    let temp_dir = tempfile::Builder::new().prefix(prefix).tempdir()?;
-    if temp_dir.path().to_str().is_none() {
-        // Not using .display() as we know the path contains unprintable character
-        let message = format!("path {:?} is not valid UTF-8", temp_dir.path());
-        return Err(io::Error::new(io::ErrorKind::InvalidData, message));
-    }
-    Ok(temp_dir)
-}
-
 pub(crate) fn set_readonly_recursively(path: &Path) -> Result<(), std::io::Error> {
     // Directory permission is unchanged since files under readonly directory cannot
     // be removed.
@@ -311,6 +301,9 @@ diff editing in mind and be a little inaccurate.
             start_tracking_matcher: &EverythingMatcher,
             max_new_file_size: u64::MAX,
             conflict_marker_style,
+            track_empty_directories: false,
+            detect_conflict_markers: false,
+            dry_run: false,
         })?;
         Ok(output_tree_state.current_tree_id().clone())
     }
@@ -29,7 +29,6 @@ use pollster::FutureExt as _;
 use thiserror::Error;
 
 use super::diff_working_copies::check_out_trees;
-use super::diff_working_copies::new_utf8_temp_dir;
 use super::diff_working_copies::set_readonly_recursively;
 use super::diff_working_copies::DiffEditWorkingCopies;
 use super::diff_working_copies::DiffSide;
@@ -41,6 +40,7 @@ use super::MergeToolPartialResolutionError;
 use crate::config::find_all_variables;
 use crate::config::interpolate_variables;
 use crate::config::CommandNameAndArgs;
+use crate::temp_files::new_utf8_temp_dir;
 use crate::ui::Ui;
 
 /// Merge/diff tool loaded from the settings.
@@ -390,6 +390,7 @@ pub fn edit_diff_external(
         .unwrap_or(default_conflict_marker_style);
     let options = CheckoutOptions {
         conflict_marker_style,
+        track_empty_directories: false,
     };
 
     let got_output_field = find_all_variables(&editor.edit_args).contains(&"output");
@@ -438,6 +439,7 @@ pub fn generate_diff(
         .unwrap_or(default_conflict_marker_style);
     let options = CheckoutOptions {
         conflict_marker_style,
+        track_empty_directories: false,
     };
     let store = left_tree.store();
     let diff_wc = check_out_trees(store, left_tree, right_tree, matcher, None, &options)?;
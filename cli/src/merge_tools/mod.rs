@@ -47,7 +47,6 @@ use thiserror::Error;
 use self::builtin::edit_diff_builtin;
 use self::builtin::edit_merge_builtin;
 use self::builtin::BuiltinToolError;
-pub(crate) use self::diff_working_copies::new_utf8_temp_dir;
 use self::diff_working_copies::DiffCheckoutError;
 use self::external::edit_diff_external;
 pub use self::external::generate_diff;
@@ -480,6 +479,7 @@ mod tests {
     use jj_lib::config::ConfigLayer;
     use jj_lib::config::ConfigSource;
     use jj_lib::config::StackedConfig;
+    use jj_lib::repo_path::UiPathStyle;
 
     use super::*;
 
@@ -772,6 +772,7 @@ mod tests {
             let path_converter = RepoPathUiConverter::Fs {
                 cwd: "".into(),
                 base: "".into(),
+                style: UiPathStyle::default(),
             };
             MergeEditor::with_name(name, &settings, path_converter, ConflictMarkerStyle::Diff)
                 .map(|editor| editor.tool)
@@ -832,6 +833,7 @@ mod tests {
             let path_converter = RepoPathUiConverter::Fs {
                 cwd: "".into(),
                 base: "".into(),
+                style: UiPathStyle::default(),
             };
             MergeEditor::from_settings(&ui, &settings, path_converter, ConflictMarkerStyle::Diff)
                 .map(|editor| editor.tool)
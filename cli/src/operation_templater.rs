@@ -338,6 +338,22 @@ fn builtin_operation_methods() -> OperationTemplateBuildMethodFnMap<Operation> {
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "username",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|op| op.metadata().username.clone());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
+    map.insert(
+        "hostname",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|op| op.metadata().hostname.clone());
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "root",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -0,0 +1,91 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Temporary files and directories shared with external programs.
+//!
+//! They are created in the system temporary directory by default, or in the
+//! directory configured by `ui.temp-dir` once the workspace is loaded. Since
+//! they may contain repository contents, temporary directories are also
+//! removed when the process is killed by `SIGINT`/`SIGTERM`.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tempfile::NamedTempFile;
+use tempfile::TempDir;
+
+use crate::cleanup_guard::CleanupGuard;
+
+static TEMP_DIR_ROOT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Sets the directory in which temporary files are created for the remainder
+/// of the command. The directory will be created on demand.
+pub fn set_temp_dir_root(dir: impl Into<PathBuf>) {
+    *TEMP_DIR_ROOT.lock().unwrap() = Some(dir.into());
+}
+
+/// Returns the directory in which temporary files should be created. The
+/// directory might not exist yet.
+pub fn temp_dir_root() -> PathBuf {
+    TEMP_DIR_ROOT
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(tempfile::env::temp_dir)
+}
+
+/// Creates a new temporary file in `dir`, creating the directory if needed.
+///
+/// The file is deleted when dropped unless the caller keeps it.
+pub fn new_temp_file_in(dir: &Path, prefix: &str, suffix: &str) -> io::Result<NamedTempFile> {
+    fs::create_dir_all(dir)?;
+    tempfile::Builder::new()
+        .prefix(prefix)
+        .suffix(suffix)
+        .tempfile_in(dir)
+}
+
+/// Temporary directory which is deleted when dropped or when the process is
+/// killed by `SIGINT`/`SIGTERM`.
+pub struct ScopedTempDir {
+    dir: TempDir,
+    _guard: CleanupGuard,
+}
+
+impl ScopedTempDir {
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+}
+
+/// Creates a new temporary directory whose path is valid UTF-8.
+pub fn new_utf8_temp_dir(prefix: &str) -> io::Result<ScopedTempDir> {
+    let root = temp_dir_root();
+    fs::create_dir_all(&root)?;
+    let dir = tempfile::Builder::new().prefix(prefix).tempdir_in(root)?;
+    if dir.path().to_str().is_none() {
+        // Not using .display() as we know the path contains unprintable character
+        let message = format!("path {:?} is not valid UTF-8", dir.path());
+        return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+    }
+    let path = dir.path().to_owned();
+    let guard = CleanupGuard::new(move || {
+        // No-op if the directory has already been removed on drop.
+        fs::remove_dir_all(path).ok();
+    });
+    Ok(ScopedTempDir { dir, _guard: guard })
+}
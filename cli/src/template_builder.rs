@@ -947,6 +947,26 @@ fn builtin_string_methods<'a, L: TemplateLanguage<'a> + ?Sized>(
             Ok(out_property.into_dyn_wrapped())
         },
     );
+    map.insert(
+        "replace",
+        |language, diagnostics, build_ctx, self_property, function| {
+            let [needle_node, replacement_node] = function.expect_exact_arguments()?;
+            let needle_property =
+                expect_stringify_expression(language, diagnostics, build_ctx, needle_node)?;
+            let replacement_property =
+                expect_stringify_expression(language, diagnostics, build_ctx, replacement_node)?;
+            let out_property = (self_property, needle_property, replacement_property).map(
+                |(haystack, needle, replacement)| {
+                    if needle.is_empty() {
+                        haystack
+                    } else {
+                        haystack.replace(&needle, &replacement)
+                    }
+                },
+            );
+            Ok(out_property.into_dyn_wrapped())
+        },
+    );
     map.insert(
         "trim",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -1428,6 +1448,34 @@ where
             Ok(L::Property::wrap_list_template(template))
         },
     );
+    map.insert(
+        "any",
+        |language, diagnostics, build_ctx, self_property, function| {
+            let out_property = build_any_all_operation(
+                language,
+                diagnostics,
+                build_ctx,
+                self_property,
+                function,
+                false,
+            )?;
+            Ok(L::Property::wrap_property(out_property))
+        },
+    );
+    map.insert(
+        "all",
+        |language, diagnostics, build_ctx, self_property, function| {
+            let out_property = build_any_all_operation(
+                language,
+                diagnostics,
+                build_ctx,
+                self_property,
+                function,
+                true,
+            )?;
+            Ok(L::Property::wrap_property(out_property))
+        },
+    );
     map
 }
 
@@ -1474,6 +1522,50 @@ where
     Ok(out_property.into_dyn())
 }
 
+/// Builds expression that tests if any item satisfies the predicate, or if all
+/// items do if `all` is true. Items after the deciding one aren't evaluated.
+fn build_any_all_operation<'a, L, O, P>(
+    language: &L,
+    diagnostics: &mut TemplateDiagnostics,
+    build_ctx: &BuildContext<L::Property>,
+    self_property: P,
+    function: &FunctionCallNode,
+    all: bool,
+) -> TemplateParseResult<BoxedTemplateProperty<'a, bool>>
+where
+    L: TemplateLanguage<'a> + ?Sized,
+    L::Property: WrapTemplateProperty<'a, O>,
+    P: TemplateProperty + 'a,
+    P::Output: IntoIterator<Item = O>,
+    O: Clone + 'a,
+{
+    let [lambda_node] = function.expect_exact_arguments()?;
+    let item_placeholder = PropertyPlaceholder::new();
+    let item_predicate =
+        template_parser::catch_aliases(diagnostics, lambda_node, |diagnostics, node| {
+            let lambda = template_parser::expect_lambda(node)?;
+            build_lambda_expression(
+                build_ctx,
+                lambda,
+                &[&|| item_placeholder.clone().into_dyn_wrapped()],
+                |build_ctx, body| expect_boolean_expression(language, diagnostics, build_ctx, body),
+            )
+        })?;
+    let out_property = self_property.and_then(move |items| {
+        for item in items {
+            // Evaluate predicate with the current item
+            item_placeholder.set(item);
+            let result = item_predicate.extract();
+            item_placeholder.take();
+            if result? != all {
+                return Ok(!all);
+            }
+        }
+        Ok(all)
+    });
+    Ok(out_property.into_dyn())
+}
+
 /// Builds expression that extracts iterable property and applies template to
 /// each item.
 fn build_map_operation<'a, L, O, P>(
@@ -2665,6 +2757,25 @@ mod tests {
             env.render_ok(r#""a\nbb\nc".lines().filter(|s| s.len() == 1)"#),
             @"a c");
 
+        insta::assert_snapshot!(
+            env.render_ok(r#""a\nbb\nc".lines().any(|s| s.len() == 2)"#),
+            @"true");
+        insta::assert_snapshot!(
+            env.render_ok(r#""a\nbb\nc".lines().all(|s| s.len() == 1)"#),
+            @"false");
+        // Empty lists
+        insta::assert_snapshot!(env.render_ok(r#""".lines().any(|s| true)"#), @"false");
+        insta::assert_snapshot!(env.render_ok(r#""".lines().all(|s| false)"#), @"true");
+        // Not a boolean predicate
+        insta::assert_snapshot!(env.parse_err(r#""a".lines().any(|s| s.len())"#), @r#"
+         --> 1:21
+          |
+        1 | "a".lines().any(|s| s.len())
+          |                     ^-----^
+          |
+          = Expected expression of type `Boolean`, but actual type is `Integer`
+        "#);
+
         insta::assert_snapshot!(
             env.render_ok(r#""a\nb\nc".lines().map(|s| s ++ s)"#),
             @"aa bb cc");
@@ -2815,6 +2926,13 @@ mod tests {
             env.render_ok(r#""bar@other.example.com".remove_suffix("@other.example.com")"#),
             @"bar");
 
+        insta::assert_snapshot!(env.render_ok(r#""".replace("a", "b")"#), @"");
+        insta::assert_snapshot!(env.render_ok(r#""foo".replace("", "-")"#), @"foo");
+        insta::assert_snapshot!(env.render_ok(r#""a.b.c".replace(".", "/")"#), @"a/b/c");
+        insta::assert_snapshot!(
+            env.render_ok(r#""fix: foo, fix: bar".replace("fix: ", "")"#),
+            @"foo, bar");
+
         insta::assert_snapshot!(env.render_ok(r#"" \n \r    \t \r ".trim()"#), @"");
         insta::assert_snapshot!(env.render_ok(r#"" \n \r foo  bar \t \r ".trim()"#), @"foo  bar");
 
@@ -14,6 +14,7 @@ mod test_acls;
 mod test_advance_bookmarks;
 mod test_alias;
 mod test_backout_command;
+mod test_bisect_command;
 mod test_bookmark_command;
 mod test_builtin_aliases;
 mod test_commit_command;
@@ -31,6 +32,8 @@ mod test_diffedit_command;
 mod test_duplicate_command;
 mod test_edit_command;
 mod test_evolog_command;
+mod test_evolve_command;
+mod test_external_command;
 mod test_file_annotate_command;
 mod test_file_chmod_command;
 mod test_file_show_command;
@@ -50,19 +53,23 @@ mod test_gitignores;
 mod test_global_opts;
 mod test_help_command;
 mod test_immutable_commits;
+mod test_import_commit_command;
 mod test_interdiff_command;
 mod test_log_command;
 mod test_new_command;
 mod test_next_prev_commands;
 mod test_operations;
 mod test_parallelize_command;
+mod test_parents_children_commands;
 mod test_rebase_command;
+mod test_reorder_command;
 mod test_repo_change_report;
 mod test_resolve_command;
 mod test_restore_command;
 mod test_revert_command;
 mod test_revset_output;
 mod test_root;
+mod test_run_command;
 mod test_show_command;
 mod test_sign_unsign_commands;
 mod test_simplify_parents_command;
@@ -522,6 +522,60 @@ fn test_abandon_tracking_bookmarks() {
     ");
 }
 
+#[test]
+fn test_abandon_rewrite_confirmation() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("ui.rewrite-confirmation-threshold = 2");
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+
+    // Abandoning "a" would rewrite "a", "b", and "c". The session isn't
+    // interactive, so the default answer is taken and the command is aborted.
+    let output = work_dir.run_jj(["abandon", "a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: This operation would rewrite more than 2 commits (`ui.rewrite-confirmation-threshold`).
+    Do you want to continue? (yN): n
+    Error: Aborted rewriting commits
+    Hint: Pass --yes to skip this confirmation, or raise `ui.rewrite-confirmation-threshold`.
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  [roy] c
+    ○  [zsu] b
+    ○  [rlv] a
+    ◆  [zzz]
+    [EOF]
+    ");
+
+    work_dir.run_jj(["abandon", "a", "--yes"]).success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  [roy] c
+    ○  [zsu] b
+    ◆  [zzz]
+    [EOF]
+    ");
+
+    // Setting the threshold to 0 disables the confirmation
+    work_dir
+        .run_jj([
+            "abandon",
+            "b",
+            "--config=ui.rewrite-confirmation-threshold=0",
+        ])
+        .success();
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  [roy] c
+    ◆  [zzz]
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"separate(" ", "[" ++ change_id.short(3) ++ "]", bookmarks)"#;
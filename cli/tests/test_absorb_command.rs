@@ -954,6 +954,40 @@ fn test_absorb_immutable() {
     ");
 }
 
+#[test]
+fn test_absorb_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m1"]).success();
+    work_dir.write_file("file1", "1a\n1b\n");
+
+    work_dir.run_jj(["new", "-m2"]).success();
+    work_dir.write_file("file1", "1a\n1b\n2a\n2b\n");
+
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "1A\n1b\n2a\n2B\n");
+
+    let output = work_dir.run_jj(["absorb", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would absorb changes into 2 revisions:
+      2
+      1
+    Dry-run requested, not absorbing.
+    [EOF]
+    ");
+
+    // Nothing should be absorbed
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file1
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_diffs(work_dir: &TestWorkDir, revision: &str) -> CommandOutput {
     let template = r#"format_commit_summary_with_refs(self, "") ++ "\n""#;
@@ -0,0 +1,81 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+fn create_commits(work_dir: &TestWorkDir) {
+    work_dir.run_jj(["describe", "-mc1"]).success();
+    work_dir.write_file("file", "1\n");
+    for i in 2..=5 {
+        work_dir.run_jj(["new", &format!("-mc{i}")]).success();
+        work_dir.write_file("file", format!("{i}\n"));
+        if i == 3 {
+            work_dir.write_file("bad", "");
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_bisect_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commits(&work_dir);
+
+    let output = work_dir.run_jj([
+        "bisect",
+        "run",
+        "--config=templates.commit_summary='description.first_line()'",
+        "--good=description(c1)",
+        "sh",
+        "-c",
+        "test ! -e bad",
+    ]);
+    insta::assert_snapshot!(output.success().stdout, @r"
+    The first bad revision is: c3
+    [EOF]
+    ");
+
+    // The first bad revision is checked out
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@-", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    c3
+    [EOF]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_bisect_run_aborted() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    create_commits(&work_dir);
+
+    let output = work_dir.run_jj([
+        "bisect",
+        "run",
+        "--good=description(c1)",
+        "sh",
+        "-c",
+        "exit 130",
+    ]);
+    assert!(!output.status.success());
+    assert!(output
+        .stderr
+        .raw()
+        .contains("Error: External command exited with 130, aborting bisection"));
+}
@@ -560,6 +560,7 @@ fn test_bookmark_move_conflicting() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Moved 1 bookmarks to mzvwutvl 0f5f3e2c foo | (empty) A1
+    Resolved conflicts in 1 bookmark
     [EOF]
     ");
     insta::assert_snapshot!(get_log(), @r"
@@ -1345,6 +1346,9 @@ fn test_bookmark_track_conflict() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Started tracking 1 remote bookmarks.
+    Warning: The operation made 1 change divergent:
+      qpvuntsm?? 56b9f16b main?? | (empty) b
+    Hint: Use `jj evolve` to merge the commits of each divergent change, or abandon the unneeded commits with `jj abandon <commit ID>`.
     main (conflicted):
       + qpvuntsm?? 56b9f16b (empty) b
       + qpvuntsm?? 7d5ca8e4 (empty) a
@@ -1596,6 +1600,23 @@ fn test_bookmark_list() {
     [EOF]
     ");
 
+    // --all marks local bookmarks which differ from the tracked remotes
+    let output = local_dir.run_jj(["bookmark", "list", "--all"]);
+    insta::assert_snapshot!(output, @r"
+    local-only: wqnwkozp 0353dd35 (empty) local-only
+    remote-delete (deleted)
+      @origin: vruxwmqv b32031cf (empty) remote-delete
+    remote-sync: rlvkpnrz 7a07dbee (empty) remote-sync
+      @origin: rlvkpnrz 7a07dbee (empty) remote-sync
+    remote-unsync (diverged): wqnwkozp 0353dd35 (empty) local-only
+      @origin (ahead by 1 commits, behind by 1 commits): zsuskuln 553203ba (empty) remote-unsync
+    remote-untrack@origin: royxmykx 149bc756 (empty) remote-untrack
+    [EOF]
+    ------- stderr -------
+    Hint: Bookmarks marked as deleted can be *deleted permanently* on the remote by running `jj git push --deleted`. Use `jj bookmark forget` if you don't want that.
+    [EOF]
+    ");
+
     let output = local_dir.run_jj(["bookmark", "list", "--all-remotes", "--color=always"]);
     insta::assert_snapshot!(output, @r"
     [38;5;5mlocal-only[39m: [1m[38;5;13mw[38;5;8mqnwkozp[39m [38;5;12m03[38;5;8m53dd35[39m [38;5;10m(empty)[39m local-only[0m
@@ -1622,6 +1643,7 @@ fn test_bookmark_list() {
       separate(" ", "added_targets:", added_targets.map(|c| c.description().first_line())) ++ "\n",
       separate(" ", "tracked:", tracked) ++ "\n",
       separate(" ", "tracking_present:", tracking_present) ++ "\n",
+      separate(" ", "synced:", synced) ++ "\n",
       separate(" ", "tracking_ahead_count:", tracking_ahead_count.lower()) ++ "\n",
       separate(" ", "tracking_behind_count:", tracking_behind_count.lower()) ++ "\n",
     )
@@ -1636,6 +1658,7 @@ fn test_bookmark_list() {
     added_targets: local-only
     tracked: false
     tracking_present: false
+    synced: true
     tracking_ahead_count: <Error: Not a tracked remote ref>
     tracking_behind_count: <Error: Not a tracked remote ref>
     [remote-delete]
@@ -1646,6 +1669,7 @@ fn test_bookmark_list() {
     added_targets:
     tracked: false
     tracking_present: false
+    synced: false
     tracking_ahead_count: <Error: Not a tracked remote ref>
     tracking_behind_count: <Error: Not a tracked remote ref>
     [remote-delete@origin]
@@ -1656,6 +1680,7 @@ fn test_bookmark_list() {
     added_targets: remote-delete
     tracked: true
     tracking_present: false
+    synced: false
     tracking_ahead_count: 2
     tracking_behind_count: 0
     [remote-sync]
@@ -1666,6 +1691,7 @@ fn test_bookmark_list() {
     added_targets: remote-sync
     tracked: false
     tracking_present: false
+    synced: true
     tracking_ahead_count: <Error: Not a tracked remote ref>
     tracking_behind_count: <Error: Not a tracked remote ref>
     [remote-sync@origin]
@@ -1676,6 +1702,7 @@ fn test_bookmark_list() {
     added_targets: remote-sync
     tracked: true
     tracking_present: true
+    synced: true
     tracking_ahead_count: 0
     tracking_behind_count: 0
     [remote-unsync]
@@ -1686,6 +1713,7 @@ fn test_bookmark_list() {
     added_targets: local-only
     tracked: false
     tracking_present: false
+    synced: false
     tracking_ahead_count: <Error: Not a tracked remote ref>
     tracking_behind_count: <Error: Not a tracked remote ref>
     [remote-unsync@origin]
@@ -1696,6 +1724,7 @@ fn test_bookmark_list() {
     added_targets: remote-unsync
     tracked: true
     tracking_present: true
+    synced: false
     tracking_ahead_count: 1
     tracking_behind_count: 1
     [remote-untrack@origin]
@@ -1706,6 +1735,7 @@ fn test_bookmark_list() {
     added_targets: remote-untrack
     tracked: false
     tracking_present: false
+    synced: false
     tracking_ahead_count: <Error: Not a tracked remote ref>
     tracking_behind_count: <Error: Not a tracked remote ref>
     [EOF]
@@ -242,6 +242,55 @@ fn test_mine_is_true_when_author_is_user() {
     ");
 }
 
+#[test]
+fn test_log_context_functions() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["describe", "-m", "first"]).success();
+
+    let output = work_dir.run_jj([
+        "log",
+        "-r@",
+        "--no-graph",
+        "-T",
+        r#"workspace_name() ++ "\n" ++ operation_id() ++ "\n" ++ workspace_root() ++ "\n""#,
+    ]);
+    let op_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "--limit=1", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+    let root = work_dir.run_jj(["root"]).success().stdout.into_raw();
+    assert_eq!(
+        output.success().stdout.into_raw(),
+        format!("default\n{op_id}\n{root}")
+    );
+
+    // The operation is the one the repo is loaded at
+    let output = work_dir.run_jj(["log", "-r@", "--no-graph", "--at-op=@-", "-Toperation_id()"]);
+    let op_id = work_dir
+        .run_jj(["op", "log", "--no-graph", "--limit=1", "--at-op=@-", "-Tid"])
+        .success()
+        .stdout
+        .into_raw();
+    assert_eq!(output.success().stdout.into_raw(), op_id);
+
+    let output = work_dir.run_jj(["log", "-r@", "-T", "workspace_name(1)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse template: Function `workspace_name`: Expected 0 arguments
+    Caused by:  --> 1:16
+      |
+    1 | workspace_name(1)
+      |                ^
+      |
+      = Function `workspace_name`: Expected 0 arguments
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_log_json() {
     let test_env = TestEnvironment::default();
@@ -770,6 +819,60 @@ fn test_log_git_head() {
     ");
 }
 
+#[test]
+fn test_log_closest_tag() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = {
+        let mut git_repo_path = work_dir.root().to_owned();
+        git_repo_path.extend([".jj", "repo", "store", "git"]);
+        git::open(git_repo_path)
+    };
+
+    work_dir.run_jj(["new", "root()", "-m=c1"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "b1"])
+        .success();
+    work_dir.run_jj(["new", "-m=c2"]).success();
+    work_dir.run_jj(["new", "-m=c3"]).success();
+    work_dir.run_jj(["git", "export"]).success();
+    let src = git_repo.find_reference("refs/heads/b1").unwrap();
+    git_repo
+        .tag_reference(
+            "v1",
+            src.target().id(),
+            gix::refs::transaction::PreviousValue::Any,
+        )
+        .unwrap();
+    work_dir.run_jj(["git", "import"]).success();
+
+    let template = r#"separate(" ",
+      description.first_line(),
+      if(closest_tag, closest_tag.name() ++ "-" ++ closest_tag_distance),
+      describe,
+    ) ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=::@", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    c3 v1-2 v1-2-g0cb47959
+    c2 v1-1 v1-1-gb8923e58
+    c1 v1-0 v1
+    00000000
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r=closest(tags(), @)",
+        "-T=description",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    c1
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_customize_short_id() {
     let test_env = TestEnvironment::default();
@@ -207,6 +207,7 @@ fn test_bookmark_names() {
     --repository	Path to repository to operate on
     --ignore-working-copy	Don't snapshot the working copy, and don't update it
     --ignore-immutable	Allow rewriting immutable commits
+    --allow-other-owner	Allow modifying a repository owned by another user
     --at-operation	Operation to load the repo at
     --debug	Enable debug logging
     --color	When to colorize output
@@ -366,18 +367,21 @@ fn test_aliases_are_resolved(shell: Shell) {
         Shell::Bash => {
             insta::assert_snapshot!(output, @r"
             --revisions
+            --revisions-file
             --reversed[EOF]
             ");
         }
         Shell::Zsh => {
             insta::assert_snapshot!(output, @r"
             --revisions:Which revisions to show
+            --revisions-file:Also show the revisions listed in the given file
             --reversed:Show revisions in the opposite order (older revisions first)[EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @r"
             --revisions	Which revisions to show
+            --revisions-file	Also show the revisions listed in the given file
             --reversed	Show revisions in the opposite order (older revisions first)
             [EOF]
             ");
@@ -419,21 +423,21 @@ fn test_default_command_is_resolved(shell: Shell) {
         Shell::Bash => {
             insta::assert_snapshot!(output, @r"
             --revisions
-            --limit
+            --revisions-file
             [EOF]
             ");
         }
         Shell::Zsh => {
             insta::assert_snapshot!(output, @r"
             --revisions:Which revisions to show
-            --limit:Limit number of revisions to show
+            --revisions-file:Also show the revisions listed in the given file
             [EOF]
             ");
         }
         Shell::Fish => {
             insta::assert_snapshot!(output, @r"
             --revisions	Which revisions to show
-            --limit	Limit number of revisions to show
+            --revisions-file	Also show the revisions listed in the given file
             [EOF]
             ");
         }
@@ -1014,7 +1018,9 @@ fn test_config() {
 
     let output = test_env.complete_fish(["config", "get", "c"]);
     insta::assert_snapshot!(output, @r"
+    core.deterministic-change-ids	Whether to derive the change ids of new commits from their content instead of generating them randomly
     core.fsmonitor	Whether to use an external filesystem monitor, useful for large repos
+    core.tree-merge-cache	Whether to cache the results of tree merges in the repo
     core.watchman.register-snapshot-trigger	Whether to use triggers to monitor for changes in the background.
     [EOF]
     ");
@@ -1023,7 +1029,9 @@ fn test_config() {
     insta::assert_snapshot!(output, @r"
     colors	Mapping from jj formatter labels to colors
     core
+    core.deterministic-change-ids	Whether to derive the change ids of new commits from their content instead of generating them randomly
     core.fsmonitor	Whether to use an external filesystem monitor, useful for large repos
+    core.tree-merge-cache	Whether to cache the results of tree merges in the repo
     core.watchman
     core.watchman.register-snapshot-trigger	Whether to use triggers to monitor for changes in the background.
     [EOF]
@@ -1031,7 +1039,9 @@ fn test_config() {
 
     let output = test_env.complete_fish(["log", "--config", "c"]);
     insta::assert_snapshot!(output, @r"
+    core.deterministic-change-ids=	Whether to derive the change ids of new commits from their content instead of generating them randomly
     core.fsmonitor=	Whether to use an external filesystem monitor, useful for large repos
+    core.tree-merge-cache=	Whether to cache the results of tree merges in the repo
     core.watchman.register-snapshot-trigger=	Whether to use triggers to monitor for changes in the background.
     [EOF]
     ");
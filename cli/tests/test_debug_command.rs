@@ -211,6 +211,89 @@ fn test_debug_reindex() {
     ");
 }
 
+#[test]
+fn test_debug_snapshot_dry_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "b\n");
+    work_dir.run_jj(["debug", "snapshot"]).success();
+
+    work_dir.write_file("file1", "a\nchanged\n");
+    work_dir.remove_file("file2");
+    work_dir.write_file("file3", "c\n");
+    let output = work_dir.run_jj(["debug", "snapshot", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    M file1 (size changed)
+    D file2 (removed)
+    A file3 (new file)
+    [EOF]
+    ");
+
+    // Nothing was recorded, so the same changes are reported again
+    let output = work_dir.run_jj(["debug", "snapshot", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    M file1 (size changed)
+    D file2 (removed)
+    A file3 (new file)
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary", "--ignore-working-copy"]);
+    insta::assert_snapshot!(output, @r"
+    A file1
+    A file2
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_debug_export_refs() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new"]).success();
+
+    // The parent of the working-copy commit is reachable from its ref
+    let output = work_dir.run_jj(["debug", "export-refs"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 1 refs, removed 0 refs, kept 0 refs.
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["debug", "export-refs"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 0 refs, removed 0 refs, kept 1 refs.
+    [EOF]
+    ");
+
+    // The predecessor of a rewritten commit is kept
+    work_dir.run_jj(["describe", "-m", "foo"]).success();
+    let output = work_dir.run_jj(["debug", "export-refs"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 1 refs, removed 0 refs, kept 1 refs.
+    [EOF]
+    ");
+
+    // Refs are renamed after the remaining operations
+    work_dir.run_jj(["op", "abandon", "..@-"]).success();
+    let output = work_dir.run_jj(["debug", "export-refs"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 2 refs, removed 2 refs, kept 0 refs.
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["debug", "export-refs", "--remove"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 0 refs, removed 2 refs, kept 0 refs.
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_debug_tree() {
     let test_env = TestEnvironment::default();
@@ -300,6 +383,103 @@ fn test_debug_operation_id() {
     ");
 }
 
+#[test]
+fn test_debug_proto_dump_restore() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["describe", "-m", "initial"]).success();
+    let op_id = work_dir
+        .run_jj(["debug", "operation", "--display", "id"])
+        .success()
+        .stdout
+        .into_raw();
+    let op_id = op_id.trim_end();
+
+    // Edit the description of the operation
+    let json = work_dir
+        .run_jj(["debug", "proto", "dump", "operation", op_id])
+        .success()
+        .stdout
+        .into_raw();
+    assert!(json.contains(r#""description": "describe commit "#));
+    let edited_json = json.replacen(
+        r#""description": "describe commit "#,
+        r#""description": "repaired describe commit "#,
+        1,
+    );
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["debug", "proto", "restore", "operation", op_id])
+            .write_stdin(edited_json)
+    });
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored operation record
+    [EOF]
+    ");
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "--ignore-working-copy",
+        "--no-graph",
+        "-n1",
+        "-Tdescription.first_line()",
+    ]);
+    assert!(output.stdout.raw().starts_with("repaired describe commit "));
+
+    // Invalid records are rejected
+    let invalid_json = json.replacen(r#""view_id": ""#, r#""view_id": "00"#, 1);
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["debug", "proto", "restore", "operation", op_id])
+            .write_stdin(invalid_json)
+    });
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Could not write object of type operation
+    Caused by: Invalid hash length (expected 64 bytes, got 65 bytes)
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Tree state and extra metadata can be restored from their dumps
+    let json = work_dir
+        .run_jj(["debug", "proto", "dump", "tree-state"])
+        .success()
+        .stdout
+        .into_raw();
+    assert!(json.contains(r#""path": "file""#));
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["debug", "proto", "restore", "tree-state"])
+            .write_stdin(json)
+    });
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored tree-state record
+    [EOF]
+    ");
+    let commit_id = work_dir
+        .run_jj(["log", "--no-graph", "-r@", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+    let json = work_dir
+        .run_jj(["debug", "proto", "dump", "extra-metadata", &commit_id])
+        .success()
+        .stdout
+        .into_raw();
+    assert!(json.contains(r#""change_id": ""#));
+    let output = work_dir.run_jj_with(|cmd| {
+        cmd.args(["debug", "proto", "restore", "extra-metadata", &commit_id])
+            .write_stdin(json)
+    });
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored extra-metadata record
+    [EOF]
+    ");
+}
+
 fn filter_index_stats(output: CommandOutput) -> CommandOutput {
     let regex = Regex::new(r"    Name: [0-9a-z]+").unwrap();
     output.normalize_stdout_with(|text| regex.replace_all(&text, "    Name: [hash]").into_owned())
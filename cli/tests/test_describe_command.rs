@@ -604,6 +604,52 @@ fn test_multiple_message_args() {
     ");
 }
 
+#[test]
+fn test_describe_message_file_and_stdin() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Comment lines and surrounding blank lines are removed
+    std::fs::write(
+        test_env.env_root().join("message"),
+        "\n\ndescription from file\nJJ: comment\n\nbody\n\n\n",
+    )
+    .unwrap();
+    work_dir
+        .run_jj(["describe", "--message-file", "../message"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    description from file
+
+    body
+    [EOF]
+    ");
+
+    // A trailing newline is added
+    work_dir
+        .run_jj_with(|cmd| {
+            cmd.args(["describe", "--stdin"])
+                .write_stdin("JJ: comment\ndescription from stdin")
+        })
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    description from stdin
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["describe", "--message-file", "missing"]);
+    insta::assert_snapshot!(
+        output.normalize_stderr_with(|s| s.split_inclusive('\n').take(1).collect()), @r"
+    ------- stderr -------
+    Error: Failed to read missing
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_describe_default_description() {
     let mut test_env = TestEnvironment::default();
@@ -841,6 +887,25 @@ fn test_describe_avoids_unc() {
     assert_eq!(edited_path, dunce::simplified(&edited_path));
 }
 
+#[test]
+fn test_describe_with_temp_dir_config() {
+    let mut test_env = TestEnvironment::default();
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"ui.temp-dir = "tmp""#);
+
+    std::fs::write(edit_script, "dump-path path").unwrap();
+    work_dir.run_jj(["describe"]).success();
+
+    // The temporary file is created in .jj/tmp, and deleted after editing
+    let edited_path =
+        PathBuf::from(std::fs::read_to_string(test_env.env_root().join("path")).unwrap());
+    let temp_dir = dunce::canonicalize(work_dir.root().join(".jj").join("tmp")).unwrap();
+    assert!(edited_path.starts_with(&temp_dir), "{edited_path:?}");
+    assert!(!edited_path.exists());
+}
+
 #[test]
 fn test_describe_with_edit_and_message_args_opens_editor() {
     let mut test_env = TestEnvironment::default();
@@ -1126,6 +1191,41 @@ fn test_add_trailer_committer() {
     "#);
 }
 
+#[cfg(unix)]
+#[test]
+fn test_describe_pre_commit_rewrite_hook() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"hooks.pre-commit-rewrite = ["sh", "-c", "! grep -q CLI"]"#);
+
+    // The hook rejects the new description, so the operation is aborted
+    let output = work_dir.run_jj(["describe", "-m", "description from CLI"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The pre-commit-rewrite hook rejected commit 7b186b4f9454 (exit status: 1)
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @"");
+
+    // New commits aren't checked
+    work_dir
+        .run_jj(["new", "-m", "new commit from CLI"])
+        .success();
+    work_dir.run_jj(["undo"]).success();
+
+    work_dir
+        .run_jj(["describe", "-m", "description from editor"])
+        .success();
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    description from editor
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"commit_id.short() ++ " " ++ description"#;
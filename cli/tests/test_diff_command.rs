@@ -14,6 +14,7 @@
 
 use indoc::indoc;
 use itertools::Itertools as _;
+use testutils::git;
 
 use crate::common::create_commit;
 use crate::common::create_commit_with_files;
@@ -551,6 +552,76 @@ fn test_diff_file_mode() {
     ");
 }
 
+#[test]
+fn test_diff_git_submodule() {
+    let test_env = TestEnvironment::default();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::init(work_dir.root());
+
+    // Add a submodule, update it, then replace it with a regular file
+    let submodule_id1 = gix::ObjectId::from_hex(&b"1".repeat(40)).unwrap();
+    let submodule_id2 = gix::ObjectId::from_hex(&b"2".repeat(40)).unwrap();
+    let blob_id = git_repo.write_blob(b"file\n").unwrap().detach();
+    let mut parents = vec![];
+    for (message, kind, id) in [
+        ("add", gix::object::tree::EntryKind::Commit, submodule_id1),
+        (
+            "update",
+            gix::object::tree::EntryKind::Commit,
+            submodule_id2,
+        ),
+        ("replace", gix::object::tree::EntryKind::Blob, blob_id),
+    ] {
+        let mut tree_editor = git_repo
+            .edit_tree(gix::ObjectId::empty_tree(git_repo.object_hash()))
+            .unwrap();
+        tree_editor.upsert("sub", kind, id).unwrap();
+        let tree_id = tree_editor.write().unwrap().detach();
+        let commit_id = git::write_commit(&git_repo, "refs/heads/main", tree_id, message, &parents);
+        parents = vec![commit_id];
+    }
+    work_dir.run_jj(["git", "init", "--git-repo=."]).success();
+
+    let output = work_dir.run_jj(["diff", "--git", "-r=description(add)"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/sub b/sub
+    new file mode 160000
+    index 0000000000..1111111111
+    --- /dev/null
+    +++ b/sub
+    @@ -0,0 +1,1 @@
+    +Subproject commit 1111111111111111111111111111111111111111
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--git", "-r=description(update)"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/sub b/sub
+    index 1111111111..2222222222 160000
+    --- a/sub
+    +++ b/sub
+    @@ -1,1 +1,1 @@
+    -Subproject commit 1111111111111111111111111111111111111111
+    +Subproject commit 2222222222222222222222222222222222222222
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--color=always", "-r=description(update)"]);
+    insta::assert_snapshot!(output, @r"
+    [38;5;3mModified Git submodule sub:[39m
+    [38;5;1m   1[39m [38;5;2m   1[39m: Git submodule checked out at [4m[38;5;1m1111111111111111111111111111111111111111[38;5;2m2222222222222222222222222222222222222222[24m[39m
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary", "-r=description(update)"]);
+    insta::assert_snapshot!(output, @r"
+    M sub
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary", "-r=description(replace)"]);
+    insta::assert_snapshot!(output, @r"
+    T sub
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_types() {
     let test_env = TestEnvironment::default();
@@ -627,6 +698,27 @@ fn test_diff_types() {
         -F foo
         [EOF]
         ");
+
+        let output = work_dir.run_jj([
+            "diff",
+            "--summary",
+            r#"--from=description("symlink")"#,
+            r#"--to=description("file")"#,
+        ]);
+        insta::assert_snapshot!(output, @r"
+        T foo
+        [EOF]
+        ");
+        let output = work_dir.run_jj([
+            "diff",
+            "--summary",
+            r#"--from=description("file")"#,
+            r#"--to=description("executable")"#,
+        ]);
+        insta::assert_snapshot!(output, @r"
+        M foo
+        [EOF]
+        ");
     }
 }
 
@@ -659,6 +751,74 @@ fn test_diff_name_only() {
     ");
 }
 
+#[test]
+fn test_diff_generated_files() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(r#"diff.generated-files = "glob:'**/*.gen'""#);
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.create_dir("sub");
+    work_dir.write_file("sub/file.gen", "generated\n");
+
+    // Generated files are collapsed in human-readable formats
+    let output = work_dir.run_jj(["diff"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    Added regular file file:
+            1: a
+    Generated file changed: sub/file.gen
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    A file
+    Generated file changed: sub/file.gen
+    [EOF]
+    ");
+
+    // Machine-readable formats include them in full
+    let output = work_dir.run_jj(["diff", "--git"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..7898192261
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,1 @@
+    +a
+    diff --git a/sub/file.gen b/sub/file.gen
+    new file mode 100644
+    index 0000000000..86d4c2dd38
+    --- /dev/null
+    +++ b/sub/file.gen
+    @@ -0,0 +1,1 @@
+    +generated
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["diff", "--name-only"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    file
+    sub/file.gen
+    [EOF]
+    ");
+
+    // Unless they are explicitly included
+    let output = work_dir.run_jj(["diff", "--summary", "--include-generated"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    A file
+    A sub/file.gen
+    [EOF]
+    ");
+
+    // The path filter also applies to generated files
+    let output = work_dir.run_jj(["diff", "--summary", "file"]);
+    insta::assert_snapshot!(output, @r"
+    A file
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_renamed_file_and_dir() {
     let test_env = TestEnvironment::default();
@@ -861,6 +1021,43 @@ fn test_diff_relative_paths() {
     ");
 }
 
+#[test]
+fn test_diff_paths_config() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.create_dir_all("dir1/subdir1");
+    work_dir.create_dir("dir2");
+    work_dir.write_file("file1", "foo1\n");
+    work_dir.write_file("dir1/subdir1/file3", "foo3\n");
+    work_dir.write_file("dir2/file4", "foo4\n");
+
+    // Forward slashes are used regardless of the platform
+    let sub_dir1 = work_dir.dir("dir1");
+    let output = sub_dir1.run_jj(["diff", "-s", "--config=ui.paths.separator=slash"]);
+    insta::assert_snapshot!(output, @r"
+    A subdir1/file3
+    A ../dir2/file4
+    A ../file1
+    [EOF]
+    ");
+
+    // Paths are relative to the workspace root
+    let output = sub_dir1.run_jj([
+        "diff",
+        "-s",
+        "--config=ui.paths.relative-to=repo",
+        "--config=ui.paths.separator=slash",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    A dir1/subdir1/file3
+    A dir2/file4
+    A file1
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_hunks() {
     let test_env = TestEnvironment::default();
@@ -2192,6 +2389,92 @@ context = 0
     ");
 }
 
+#[test]
+fn test_diff_git_function_context() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file(
+        "main.rs",
+        indoc! {r#"
+            fn main() {
+                let x = 1;
+                let y = 2;
+                println!("{x} {y}");
+            }
+
+            struct Foo {
+                a: u32,
+                b: u32,
+            }
+        "#},
+    );
+    work_dir.write_file("notes.txt", "Section A\n  one\n  two\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file(
+        "main.rs",
+        indoc! {r#"
+            fn main() {
+                let x = 1;
+                let y = 3;
+                println!("{x} {y}");
+            }
+
+            struct Foo {
+                a: u32,
+                b: u64,
+            }
+        "#},
+    );
+    work_dir.write_file("notes.txt", "Section A\n  one\n  TWO\n");
+
+    let output = work_dir.run_jj([
+        "diff",
+        "--git",
+        "--context=1",
+        "--config=diff.git.function-context=true",
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    diff --git a/main.rs b/main.rs
+    index 97413d6c9c..48686b62dc 100644
+    --- a/main.rs
+    +++ b/main.rs
+    @@ -2,3 +2,3 @@ fn main() {
+         let x = 1;
+    -    let y = 2;
+    +    let y = 3;
+         println!("{x} {y}");
+    @@ -8,3 +8,3 @@ struct Foo {
+         a: u32,
+    -    b: u32,
+    +    b: u64,
+     }
+    diff --git a/notes.txt b/notes.txt
+    index d2d7d109a8..4a444b4a7b 100644
+    --- a/notes.txt
+    +++ b/notes.txt
+    @@ -2,2 +2,2 @@ Section A
+       one
+    -  two
+    +  TWO
+    [EOF]
+    "#);
+
+    // Disabled by default
+    let output = work_dir.run_jj(["diff", "--git", "--context=0", "notes.txt"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/notes.txt b/notes.txt
+    index d2d7d109a8..4a444b4a7b 100644
+    --- a/notes.txt
+    +++ b/notes.txt
+    @@ -3,1 +3,1 @@
+    -  two
+    +  TWO
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_skipped_context_nondefault() {
     let test_env = TestEnvironment::default();
@@ -3809,3 +4092,95 @@ fn test_diff_revisions() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_diff_merge_commit() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\nc\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.run_jj(["describe", "-m", "left"]).success();
+    work_dir.write_file("file", "a1\nb\nc\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "left"])
+        .success();
+    work_dir.run_jj(["new", "@-", "-m", "right"]).success();
+    work_dir.write_file("file", "a2\nb\nc2\n");
+    work_dir.write_file("other", "other\n");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "right"])
+        .success();
+    work_dir
+        .run_jj(["new", "left", "right", "-m", "merge"])
+        .success();
+    work_dir.write_file("file", "a3\nb\nc2\n");
+
+    // By default, the merge is compared to the automatic merge of its parents
+    let output = work_dir.run_jj(["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--summary", "--first-parent"]);
+    insta::assert_snapshot!(output, @r"
+    M file
+    A other
+    [EOF]
+    ");
+
+    // Only the conflict resolution differs from both parents
+    let output = work_dir.run_jj(["diff", "--cc"]);
+    insta::assert_snapshot!(output, @r"
+    diff --cc file
+    index 977adf23f6,a301662bd8..b4a6553e2a
+    --- a/file
+    +++ b/file
+    @@@ -1,3 -1,3 +1,3 @@@
+    - a1
+     -a2
+    ++a3
+      b
+    - c
+    + c2
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--cc", "--context=0"]);
+    insta::assert_snapshot!(output, @r"
+    diff --cc file
+    index 977adf23f6,a301662bd8..b4a6553e2a
+    --- a/file
+    +++ b/file
+    @@@ -1,1 -1,1 +1,1 @@@
+    - a1
+     -a2
+    ++a3
+    [EOF]
+    ");
+
+    // A non-merge commit is compared to its only parent
+    let output = work_dir.run_jj(["diff", "--cc", "-rleft"]);
+    insta::assert_snapshot!(output, @r"
+    diff --cc file
+    index de980441c3..977adf23f6
+    --- a/file
+    +++ b/file
+    @@ -1,3 +1,3 @@
+    -a
+    +a1
+     b
+     c
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["diff", "--cc", "-rleft", "-rright"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: --cc requires a single revision
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -0,0 +1,80 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_evolve_merges_divergent_commits() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\nb\nc\n");
+    work_dir.run_jj(["commit", "-m", "target"]).success();
+    work_dir
+        .run_jj(["new", "description(target)", "-m", "edit 1"])
+        .success();
+    work_dir.write_file("file", "A\nb\nc\n");
+    work_dir
+        .run_jj(["new", "description(target)", "-m", "edit 2"])
+        .success();
+    work_dir.write_file("file", "a\nb\nC\n");
+    work_dir.run_jj(["new", "root()"]).success();
+
+    // Squash the edits into the target commit concurrently
+    let op_id = work_dir.current_operation_id();
+    work_dir
+        .run_jj([
+            "squash",
+            "--from=description('edit 1')",
+            "--into=description(target)",
+            "-u",
+        ])
+        .success();
+    work_dir
+        .run_jj([
+            "squash",
+            "--from=description('edit 2')",
+            "--into=description(target)",
+            "-u",
+            "--at-op",
+            &op_id,
+        ])
+        .success();
+    let output = work_dir.run_jj(["evolve"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    Resolved divergence of 1 changes
+      target
+    Rebased 2 descendant commits
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "-r", "description(target)", "file"]);
+    insta::assert_snapshot!(output, @r"
+    A
+    b
+    C
+    [EOF]
+    ");
+
+    // Nothing left to resolve
+    let output = work_dir.run_jj(["evolve"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No divergent changes to resolve
+    [EOF]
+    ");
+}
@@ -0,0 +1,120 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(unix)]
+
+use std::os::unix::fs::PermissionsExt as _;
+
+use indoc::indoc;
+
+use crate::common::TestEnvironment;
+
+/// Installs an executable script named `jj-<name>` into a directory added to
+/// `PATH`.
+fn add_external_command(test_env: &mut TestEnvironment, name: &str, script: &str) {
+    let bin_dir = test_env.env_root().join("bin");
+    std::fs::create_dir_all(&bin_dir).unwrap();
+    let path = bin_dir.join(format!("jj-{name}"));
+    std::fs::write(&path, script).unwrap();
+    let mut permissions = std::fs::metadata(&path).unwrap().permissions();
+    permissions.set_mode(0o755);
+    std::fs::set_permissions(&path, permissions).unwrap();
+    let old_path = std::env::var("PATH").unwrap_or_default();
+    test_env.add_env_var("PATH", format!("{}:{old_path}", bin_dir.to_str().unwrap()));
+}
+
+#[test]
+fn test_external_command() {
+    let mut test_env = TestEnvironment::default();
+    add_external_command(
+        &mut test_env,
+        "hello",
+        indoc! {r#"
+            #!/bin/sh
+            echo "args:" "$@"
+            echo "version: $JJ_EXTERNAL_COMMAND_VERSION"
+            echo "workspace:" ${JJ_WORKSPACE_ROOT##*/}
+            "$JJ_EXE" --version >/dev/null && echo "can call jj"
+        "#},
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Arguments after the command name are passed as is
+    let output = work_dir.run_jj(["hello", "--flag", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    args: --flag -r @
+    version: 1
+    workspace: repo
+    can call jj
+    [EOF]
+    ");
+
+    // Outside of a workspace
+    let output = test_env.run_jj_in(".", ["hello"]);
+    insta::assert_snapshot!(output, @r"
+    args:
+    version: 1
+    workspace:
+    can call jj
+    [EOF]
+    ");
+
+    // The workspace can be specified by --repository
+    let output = test_env.run_jj_in(".", ["-R", "repo", "hello"]);
+    insta::assert_snapshot!(output, @r"
+    args:
+    version: 1
+    workspace: repo
+    can call jj
+    [EOF]
+    ");
+
+    // Aliases can refer to external commands
+    test_env.add_config(r#"aliases.hi = ["hello", "world"]"#);
+    let output = work_dir.run_jj(["hi"]);
+    insta::assert_snapshot!(output, @r"
+    args: world
+    version: 1
+    workspace: repo
+    can call jj
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_external_command_fail_and_precedence() {
+    let mut test_env = TestEnvironment::default();
+    add_external_command(
+        &mut test_env,
+        "fail",
+        "#!/bin/sh\necho failed >&2\nexit 3\n",
+    );
+
+    // The exit status is passed on
+    let output = test_env.run_jj_in(".", ["fail"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    failed
+    [EOF]
+    [exit status: 3]
+    ");
+
+    // Built-in commands take precedence
+    add_external_command(&mut test_env, "log", "#!/bin/sh\necho external\n");
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@", "-T", r#""builtin""#]);
+    insta::assert_snapshot!(output, @"builtin[EOF]");
+}
@@ -48,6 +48,14 @@ fn test_annotate_linear() {
     kkmpptxz test.use 2001-02-03 08:05:10    2: new text from new commit
     [EOF]
     ");
+
+    // `jj blame` is a built-in alias
+    let output = work_dir.run_jj(["blame", "file.txt"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsm foo      2001-02-03 08:05:08    1: line1
+    kkmpptxz test.use 2001-02-03 08:05:10    2: new text from new commit
+    [EOF]
+    ");
 }
 
 #[test]
@@ -105,6 +105,40 @@ fn test_show() {
     ");
 }
 
+#[test]
+fn test_show_range() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "0123456789\n");
+    work_dir.write_file("file2", "abcdefghij\n");
+
+    let output = work_dir.run_jj(["file", "show", "file1", "--offset=3", "--length=4"]);
+    insta::assert_snapshot!(output, @"3456[EOF]");
+
+    // The range is applied to each file
+    let output = work_dir.run_jj(["file", "show", ".", "--offset=8"]);
+    insta::assert_snapshot!(output, @r"
+    89
+    ij
+    [EOF]
+    ");
+
+    // Out-of-range offset prints nothing
+    let output = work_dir.run_jj(["file", "show", "file1", "--offset=100"]);
+    insta::assert_snapshot!(output, @"");
+
+    // The range also applies to materialized conflicts
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file1", "a\n");
+    work_dir.run_jj(["new", "@-"]).success();
+    work_dir.write_file("file1", "b\n");
+    work_dir.run_jj(["new", "all:@-+"]).success();
+    let output = work_dir.run_jj(["file", "show", "file1", "--length=23"]);
+    insta::assert_snapshot!(output, @"<<<<<<< Conflict 1 of 1[EOF]");
+}
+
 #[cfg(unix)]
 #[test]
 fn test_show_symlink() {
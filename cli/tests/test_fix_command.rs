@@ -213,6 +213,65 @@ fn test_config_disabled_tools() {
     ");
 }
 
+#[test]
+fn test_config_selected_tools() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let formatter_path = assert_cmd::cargo::cargo_bin("fake-formatter");
+    assert!(formatter_path.is_file());
+    let formatter = to_toml_value(formatter_path.to_str().unwrap());
+    test_env.add_config(format!(
+        r###"
+        [fix.tools.tool-1]
+        command = [{formatter}, "--uppercase"]
+        patterns = ["foo"]
+
+        [fix.tools.tool-2]
+        command = [{formatter}, "--lowercase"]
+        patterns = ["bar"]
+
+        [fix.tools.tool-3]
+        enabled = false
+        command = [{formatter}, "--lowercase"]
+        patterns = ["baz"]
+        "###
+    ));
+
+    work_dir.write_file("foo", "Foo\n");
+    work_dir.write_file("bar", "Bar\n");
+    work_dir.write_file("baz", "Baz\n");
+
+    // Unknown tools are rejected
+    let output = work_dir.run_jj(["fix", "--tool=tool-1", "--tool=tool-4"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such entry in `fix.tools`: tool-4
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Selected tools are run even if disabled
+    work_dir
+        .run_jj(["fix", "--tool=tool-1", "--tool=tool-3"])
+        .success();
+    let output = work_dir.run_jj(["file", "show", "foo", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    FOO
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "bar", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    Bar
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["file", "show", "baz", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    baz
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_config_disabled_tools_warning_when_all_tools_are_disabled() {
     let test_env = TestEnvironment::default();
@@ -15,6 +15,7 @@
 use testutils::git;
 
 use crate::common::create_commit;
+use crate::common::to_toml_value;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 use crate::common::TestWorkDir;
@@ -495,6 +496,44 @@ fn test_git_fetch_from_remote_with_slashes() {
     ");
 }
 
+#[test]
+fn test_git_fetch_with_depth() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    let first_id = add_commit_to_branch(&git_repo, "main");
+    git::add_commit(
+        &git_repo,
+        "refs/heads/main",
+        "file",
+        b"content",
+        "second",
+        &[first_id],
+    );
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../origin"])
+        .success();
+
+    let output = work_dir.run_jj(["git", "fetch", "--depth=1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: main@origin [new] untracked
+    [EOF]
+    ");
+
+    // Only the tip commit is fetched
+    let template = r#"separate(" ", description.first_line(), bookmarks)"#;
+    let output = work_dir.run_jj(["log", "-T", template, "-r", "all()"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    │ ◆  second main@origin
+    ├─╯
+    ◆
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_fetch_prune_before_updating_tips() {
     let test_env = TestEnvironment::default();
@@ -753,6 +792,9 @@ fn test_git_fetch_all() {
     bookmark: b@origin      [updated] tracked
     bookmark: trunk2@origin [new] tracked
     Abandoned 2 commits that are no longer reachable.
+    Warning: The operation made 1 change divergent:
+      yostqsxw?? 6fc6fe17 b?? b@origin | b
+    Hint: Use `jj evolve` to merge the commits of each divergent change, or abandon the unneeded commits with `jj abandon <commit ID>`.
     [EOF]
     ");
     insta::assert_snapshot!(get_bookmark_output(&target_dir), @r"
@@ -942,6 +984,9 @@ fn test_git_fetch_some_of_many_bookmarks() {
     bookmark: a1@origin [updated] tracked
     bookmark: b@origin  [updated] tracked
     Abandoned 1 commits that are no longer reachable.
+    Warning: The operation made 1 change divergent:
+      yostqsxw?? 2b30dbc9 b?? b@origin | b
+    Hint: Use `jj evolve` to merge the commits of each divergent change, or abandon the unneeded commits with `jj abandon <commit ID>`.
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&target_dir), @r#"
@@ -1659,6 +1704,155 @@ fn test_git_fetch_remote_only_bookmark() {
     ");
 }
 
+#[test]
+fn test_git_fetch_tags() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = add_git_remote(&test_env, &work_dir, "origin");
+
+    // Tag the bookmark, and a commit which isn't reachable from any bookmark
+    let commit_id = git_repo
+        .find_reference("refs/heads/origin")
+        .unwrap()
+        .peel_to_id_in_place()
+        .unwrap()
+        .detach();
+    git_repo
+        .reference(
+            "refs/tags/reachable",
+            commit_id,
+            gix::refs::transaction::PreviousValue::MustNotExist,
+            "create tag",
+        )
+        .unwrap();
+    git::add_commit(
+        &git_repo,
+        "refs/tags/unreachable",
+        "file",
+        b"content",
+        "message",
+        &[],
+    );
+    let list_tags = || work_dir.run_jj(["tag", "list", "-T", r#"name ++ "\n""#]);
+
+    work_dir.run_jj(["git", "fetch", "--no-tags"]).success();
+    insta::assert_snapshot!(list_tags(), @"");
+
+    // By default, only tags pointing into the fetched history are fetched
+    work_dir.run_jj(["git", "fetch"]).success();
+    insta::assert_snapshot!(list_tags(), @r"
+    reachable
+    [EOF]
+    ");
+
+    work_dir.run_jj(["git", "fetch", "--tags"]).success();
+    insta::assert_snapshot!(list_tags(), @r"
+    reachable
+    unreachable
+    [EOF]
+    ");
+
+    // Deleted tags are only removed with --prune-tags
+    git_repo
+        .find_reference("refs/tags/unreachable")
+        .unwrap()
+        .delete()
+        .unwrap();
+    work_dir.run_jj(["git", "fetch", "--tags"]).success();
+    insta::assert_snapshot!(list_tags(), @r"
+    reachable
+    unreachable
+    [EOF]
+    ");
+    work_dir.run_jj(["git", "fetch", "--prune-tags"]).success();
+    insta::assert_snapshot!(list_tags(), @r"
+    reachable
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_fetch_prune_tags_multiple_remotes() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let origin_repo = add_git_remote(&test_env, &work_dir, "origin");
+    let upstream_repo = add_git_remote(&test_env, &work_dir, "upstream");
+    for (git_repo, tag) in [
+        (&origin_repo, "origin-tag"),
+        (&upstream_repo, "upstream-tag"),
+    ] {
+        git::add_commit(
+            git_repo,
+            &format!("refs/tags/{tag}"),
+            "file",
+            b"content",
+            "message",
+            &[],
+        );
+    }
+    let list_tags = || work_dir.run_jj(["tag", "list", "-T", r#"name ++ "\n""#]);
+
+    // Tags are kept if any of the remotes has them
+    work_dir
+        .run_jj(["git", "fetch", "--all-remotes", "--prune-tags"])
+        .success();
+    insta::assert_snapshot!(list_tags(), @r"
+    origin-tag
+    upstream-tag
+    [EOF]
+    ");
+
+    origin_repo
+        .find_reference("refs/tags/origin-tag")
+        .unwrap()
+        .delete()
+        .unwrap();
+    work_dir
+        .run_jj(["git", "fetch", "--all-remotes", "--prune-tags"])
+        .success();
+    insta::assert_snapshot!(list_tags(), @r"
+    upstream-tag
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_fetch_tags_from_config() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = add_git_remote(&test_env, &work_dir, "origin");
+    git::add_commit(
+        &git_repo,
+        "refs/tags/unreachable",
+        "file",
+        b"content",
+        "message",
+        &[],
+    );
+    let list_tags = || work_dir.run_jj(["tag", "list", "-T", r#"name ++ "\n""#]);
+
+    // The per-remote setting takes precedence
+    test_env.add_config(
+        r#"
+        git.fetch-tags = "all"
+        remotes.origin.fetch-tags = "none"
+        "#,
+    );
+    work_dir.run_jj(["git", "fetch"]).success();
+    insta::assert_snapshot!(list_tags(), @"");
+
+    work_dir
+        .run_jj(["git", "fetch", "--config=remotes.origin.fetch-tags=all"])
+        .success();
+    insta::assert_snapshot!(list_tags(), @r"
+    unreachable
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_fetch_preserve_commits_across_repos() {
     let test_env = TestEnvironment::default();
@@ -1756,3 +1950,51 @@ fn test_git_fetch_preserve_commits_across_repos() {
     [EOF]
     ");
 }
+
+#[cfg(unix)]
+#[test]
+fn test_git_fetch_post_fetch_hook() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let git_repo = add_git_remote(&test_env, &work_dir, "rem1");
+    let log_path = test_env.env_root().join("hook.log");
+    // Log the JSON input with the commit ids replaced
+    let script = format!(
+        r#"exec >> '{path}' && sed -E 's/[0-9a-f]{{40}}/<id>/g' && echo"#,
+        path = log_path.display()
+    );
+    test_env.add_config(format!(
+        "hooks.post-fetch = [\"sh\", \"-c\", {}]",
+        to_toml_value(script)
+    ));
+
+    work_dir.run_jj(["git", "fetch"]).success();
+    insta::assert_snapshot!(std::fs::read_to_string(&log_path).unwrap(), @r#"
+    {"bookmarks":[{"commits":["<id>"],"commits_truncated":false,"name":"rem1","new_targets":["<id>"],"old_targets":[],"remote":"rem1"}]}
+    "#);
+
+    // The hook isn't run if no bookmarks were updated
+    std::fs::remove_file(&log_path).unwrap();
+    work_dir.run_jj(["git", "fetch"]).success();
+    assert!(!log_path.exists());
+
+    let parent_id = git_repo
+        .find_reference("refs/heads/rem1")
+        .unwrap()
+        .peel_to_id_in_place()
+        .unwrap()
+        .detach();
+    git::add_commit(
+        &git_repo,
+        "refs/heads/rem1",
+        "file",
+        b"content",
+        "second",
+        &[parent_id],
+    );
+    work_dir.run_jj(["git", "fetch"]).success();
+    insta::assert_snapshot!(std::fs::read_to_string(&log_path).unwrap(), @r#"
+    {"bookmarks":[{"commits":["<id>"],"commits_truncated":false,"name":"rem1","new_targets":["<id>"],"old_targets":["<id>"],"remote":"rem1"}]}
+    "#);
+}
@@ -16,6 +16,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use indoc::formatdoc;
+use indoc::indoc;
 use test_case::test_case;
 use testutils::git;
 
@@ -116,6 +117,84 @@ fn test_git_init_internal_at_operation() {
     ");
 }
 
+#[test]
+fn test_git_init_internal_template() {
+    let test_env = TestEnvironment::default();
+    let template_dir = test_env.work_dir("").create_dir("template");
+    template_dir.write_file(
+        "jj-template.toml",
+        indoc! {r#"
+            config = "repo-config.toml"
+            files = "files"
+            sparse = ["src", ".gitignore"]
+            description = "Initial commit"
+        "#},
+    );
+    template_dir.write_file("repo-config.toml", "revset-aliases.'trunk()' = 'root()'\n");
+    template_dir.create_dir_all("files/src");
+    template_dir.create_dir_all("files/docs");
+    template_dir.write_file("files/.gitignore", "target/\n");
+    template_dir.write_file("files/src/lib.rs", "");
+    template_dir.write_file("files/docs/README.md", "");
+    template_dir.write_file("files/README.md", "");
+
+    // Files outside of the sparse patterns are skipped
+    let output = test_env.run_jj_in(".", ["git", "init", "repo", "--template", "template"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Warning: Skipped template files outside the sparse patterns:
+      repo/README.md
+      repo/docs/README.md
+    Working copy  (@) now at: sqpuoqvx e864350d (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm eb5e21dc Initial commit
+    Initialized repo in "repo"
+    [EOF]
+    "#);
+    let work_dir = test_env.work_dir("repo");
+    assert!(!work_dir.root().join("README.md").exists());
+    assert!(!work_dir.root().join("docs").exists());
+    assert_eq!(
+        work_dir.read_file(".jj/repo/config.toml"),
+        "revset-aliases.'trunk()' = 'root()'\n"
+    );
+
+    let output = work_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    .gitignore
+    src
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["file", "list", "-r=@-"]);
+    insta::assert_snapshot!(output, @r"
+    .gitignore
+    src/lib.rs
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["log", "-T", "description", "-r=::@"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  Initial commit
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_init_template_missing_manifest() {
+    let test_env = TestEnvironment::default();
+    test_env.work_dir("").create_dir("template");
+
+    let output = test_env.run_jj_in(".", ["git", "init", "repo", "--template", "template"]);
+    insta::assert_snapshot!(output.strip_stderr_last_line(), @r"
+    ------- stderr -------
+    Error: Failed to read repository template manifest $TEST_ENV/template/jj-template.toml
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(!test_env.env_root().join("repo").exists());
+}
+
 #[test_case(false; "full")]
 #[test_case(true; "bare")]
 fn test_git_init_external(bare: bool) {
@@ -221,6 +221,26 @@ fn test_git_push_matching_bookmark_unchanged() {
     ");
 }
 
+#[test]
+fn test_git_push_deleted_bookmark_by_default() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    work_dir
+        .run_jj(["bookmark", "delete", "bookmark1"])
+        .success();
+    work_dir.run_jj(["new", "bookmark2"]).success();
+    let output = work_dir.run_jj(["git", "push", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No bookmarks found in the default push revset: remote_bookmarks(remote=origin)..@
+    Changes to push to origin:
+      Delete bookmark bookmark1 from 9b2e76de3920
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+}
+
 /// Test that `jj git push` without arguments pushes a bookmark to the specified
 /// remote even if it's already up to date on another remote
 /// (`remote_bookmarks(remote=<remote>)..@` vs. `remote_bookmarks()..@`).
@@ -855,6 +875,7 @@ fn test_git_push_changes() {
     M file
     Working copy  (@) : yostqsxw 4b18f5ea bar
     Parent commit (@-): yqosqzyt 0f8164cd push-yostqsxwqrlt* push-yqosqzytrlsw | foo
+    Bookmark push-yostqsxwqrlt is behind push-yostqsxwqrlt@origin by 1 commit
     [EOF]
     ");
     let output = work_dir.run_jj(["git", "push", "-c=@", "-b=push-yostqsxwqrlt"]);
@@ -871,6 +892,7 @@ fn test_git_push_changes() {
     M file
     Working copy  (@) : yostqsxw 4b18f5ea bar
     Parent commit (@-): yqosqzyt 0f8164cd push-yostqsxwqrlt* push-yqosqzytrlsw | foo
+    Bookmark push-yostqsxwqrlt is behind push-yostqsxwqrlt@origin by 1 commit
     [EOF]
     ");
 
@@ -2256,6 +2278,51 @@ fn test_git_push_rejected_by_remote() {
     });
 }
 
+#[cfg(unix)]
+#[test]
+fn test_git_push_post_push_hook() {
+    let test_env = TestEnvironment::default();
+    set_up(&test_env);
+    let work_dir = test_env.work_dir("local");
+    let log_path = test_env.env_root().join("hook.log");
+    // Log the remote, and the JSON input with the commit ids replaced
+    let script = format!(
+        r#"exec >> '{path}' && echo "$JJ_REMOTE" && sed -E 's/[0-9a-f]{{40}}/<id>/g' && echo"#,
+        path = log_path.display()
+    );
+    test_env.add_config(format!(
+        "hooks.post-push = [\"sh\", \"-c\", {}]",
+        to_toml_value(script)
+    ));
+
+    work_dir
+        .run_jj(["new", "bookmark1", "-m", "new commit"])
+        .success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "my-bookmark"])
+        .success();
+    // The hook isn't run on dry-run
+    work_dir
+        .run_jj([
+            "git",
+            "push",
+            "--allow-new",
+            "-b",
+            "my-bookmark",
+            "--dry-run",
+        ])
+        .success();
+    assert!(!log_path.exists());
+
+    work_dir
+        .run_jj(["git", "push", "--allow-new", "-b", "my-bookmark"])
+        .success();
+    insta::assert_snapshot!(std::fs::read_to_string(&log_path).unwrap(), @r#"
+    origin
+    {"bookmarks":[{"commits":["<id>"],"commits_truncated":false,"name":"my-bookmark","new_targets":["<id>"],"old_targets":[],"remote":"origin"}]}
+    "#);
+}
+
 #[must_use]
 fn get_bookmark_output(work_dir: &TestWorkDir) -> CommandOutput {
     // --quiet to suppress deleted bookmarks hint
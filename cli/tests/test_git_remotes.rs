@@ -563,3 +563,75 @@ fn test_git_remote_with_global_git_remote_config() {
     	fetch = +refs/heads/*:refs/remotes/origin/*
     "#);
 }
+
+#[test]
+fn test_git_remote_prune() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-bookmark = true");
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    git::add_commit(&git_repo, "refs/heads/keep", "file", b"keep", "keep", &[]);
+    git::add_commit(&git_repo, "refs/heads/gone", "file", b"gone", "gone", &[]);
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["git", "remote", "add", "origin", "../origin"])
+        .success();
+    work_dir.run_jj(["git", "fetch"]).success();
+    let get_bookmarks = || {
+        let template = r#"separate("@", name, remote) ++ "\n""#;
+        work_dir.run_jj(["bookmark", "list", "--all-remotes", "-T", template])
+    };
+
+    // Nothing to prune
+    let output = work_dir.run_jj(["git", "remote", "prune", "origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    git_repo
+        .find_reference("refs/heads/gone")
+        .unwrap()
+        .delete()
+        .unwrap();
+
+    let output = work_dir.run_jj(["git", "remote", "prune", "origin", "--dry-run"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Would delete remote bookmarks:
+      gone@origin
+    Dry-run requested, not pruning.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmarks(), @r"
+    gone
+    gone@origin
+    keep
+    keep@origin
+    [EOF]
+    ");
+
+    // The tracked local bookmark is deleted along with the remote bookmark
+    let output = work_dir.run_jj(["git", "remote", "prune", "origin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: gone@origin [deleted] untracked
+    Abandoned 1 commits that are no longer reachable.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmarks(), @r"
+    keep
+    keep@origin
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["git", "remote", "prune", "nonexistent"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No git remote named 'nonexistent'
+    [EOF]
+    [exit status: 1]
+    ");
+}
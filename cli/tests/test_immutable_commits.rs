@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use regex::Regex;
+
 use crate::common::TestEnvironment;
 
 #[test]
@@ -176,6 +178,45 @@ fn test_immutable_heads_set_to_working_copy() {
     ");
 }
 
+#[test]
+fn test_rewrite_immutable_working_copy() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file", "a");
+    work_dir.run_jj(["describe", "-m=a"]).success();
+    // The working-copy commit becomes immutable without a new commit being
+    // created on top of it, since no transaction is committed.
+    test_env.add_config(r#"revset-aliases."immutable_heads()" = "@""#);
+    let first_line_without_hash = |s: String| {
+        let line = s.lines().next().unwrap();
+        let line = Regex::new(r"[0-9a-f]{12}").unwrap().replace(line, "<hash>");
+        format!("{line}\n")
+    };
+
+    let output = work_dir.run_jj(["commit", "-m=b"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(first_line_without_hash), @r"
+    ------- stderr -------
+    Error: Commit <hash> is immutable
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["file", "untrack", "file"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(first_line_without_hash), @r"
+    ------- stderr -------
+    Error: Commit <hash> is immutable
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["op", "restore", "--working-copy-only", "@-"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(first_line_without_hash), @r"
+    ------- stderr -------
+    Error: Commit <hash> is immutable
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_new_wc_commit_when_wc_immutable_multi_workspace() {
     let test_env = TestEnvironment::default();
@@ -0,0 +1,121 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use regex::Regex;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_import_commit() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    test_env.run_jj_in(".", ["git", "init", "source"]).success();
+    test_env.run_jj_in(".", ["git", "init", "dest"]).success();
+    let source_dir = test_env.work_dir("source");
+    let dest_dir = test_env.work_dir("dest");
+    let without_hash = |s: String| {
+        Regex::new(r"[0-9a-f]{12}")
+            .unwrap()
+            .replace_all(&s, "<hash>")
+            .into_owned()
+    };
+
+    source_dir.write_file("file", "a\nb\nc\n");
+    source_dir.run_jj(["commit", "-m", "base"]).success();
+    source_dir.write_file("file", "a\nb\nC\n");
+    source_dir.write_file("lib/x", "x\n");
+    source_dir.run_jj(["commit", "-m", "change"]).success();
+
+    dest_dir.write_file("file", "A\nb\nc\n");
+    dest_dir.run_jj(["commit", "-m", "dest base"]).success();
+
+    // The changes are merged into the destination contents
+    let output = dest_dir.run_jj([
+        "import-commit",
+        "--from-repo=../source",
+        "-r=description(change)",
+    ]);
+    insta::assert_snapshot!(output.normalize_stderr_with(without_hash), @r"
+    ------- stderr -------
+    Imported commit <hash> as change
+    [EOF]
+    ");
+    let output = dest_dir.run_jj(["file", "show", "-r=description(change)", "file"]);
+    insta::assert_snapshot!(output, @r"
+    A
+    b
+    C
+    [EOF]
+    ");
+    let output = dest_dir.run_jj(["file", "list", "-r=description(change)"]);
+    insta::assert_snapshot!(output, @r"
+    file
+    lib/x
+    [EOF]
+    ");
+
+    // Paths can be moved to another directory
+    let output = dest_dir.run_jj([
+        "import-commit",
+        "--from-repo=../source",
+        "-r=description(change)",
+        "-d=root()",
+        "--from-dir=lib",
+        "--to-dir=vendor/lib",
+    ]);
+    insta::assert_snapshot!(output.normalize_stderr_with(without_hash), @r"
+    ------- stderr -------
+    Imported commit <hash> as change
+    [EOF]
+    ");
+    let output = dest_dir.run_jj(["file", "list", "-r=description(change) & root()+"]);
+    insta::assert_snapshot!(output, @r"
+    vendor/lib/x
+    [EOF]
+    ");
+
+    // The revision is resolved in the other repository
+    let output = dest_dir.run_jj([
+        "import-commit",
+        "--from-repo=../source",
+        "-r=description('dest base')",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revset `description('dest base')` didn't resolve to any revisions
+    [EOF]
+    [exit status: 1]
+    ");
+    // The revision is resolved with the config of the other repository
+    source_dir
+        .run_jj([
+            "config",
+            "set",
+            "--repo",
+            "revset-aliases.imported",
+            "description(change)",
+        ])
+        .success();
+    let output = dest_dir.run_jj([
+        "import-commit",
+        "--from-repo=../source",
+        "-r=imported",
+        "-d=description('dest base')",
+    ]);
+    insta::assert_snapshot!(output.normalize_stderr_with(without_hash), @r"
+    ------- stderr -------
+    Imported commit <hash> as change
+    [EOF]
+    ");
+}
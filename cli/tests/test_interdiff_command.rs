@@ -75,6 +75,43 @@ fn test_interdiff_basic() {
     ");
 }
 
+#[test]
+fn test_interdiff_revision() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.run_jj(["describe", "-m=A"]).success();
+    work_dir.run_jj(["new", "root()", "-m=B"]).success();
+    work_dir.write_file("file2", "a\n");
+    work_dir.run_jj(["describe", "-m=B"]).success();
+
+    // Rebasing alone doesn't show up as a change
+    work_dir
+        .run_jj(["rebase", "-r=@", "-d=description(A)"])
+        .success();
+    let output = work_dir.run_jj(["interdiff", "-r=@"]);
+    insta::assert_snapshot!(output, @"");
+
+    work_dir.write_file("file2", "a\nb\n");
+    let output = work_dir.run_jj(["interdiff", "-r=@"]);
+    insta::assert_snapshot!(output, @r"
+    Modified regular file file2:
+       1    1: a
+            2: b
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["interdiff", "-r=root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revision zzzzzzzz 00000000 (empty) (no description set) has no previous version
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_interdiff_paths() {
     let test_env = TestEnvironment::default();
@@ -496,6 +496,48 @@ fn test_log_shortest_accessors() {
     ");
 }
 
+#[test]
+fn test_log_shortest_default_min_length() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let render = |template| work_dir.run_jj(["log", "--no-graph", "-r", "root()", "-T", template]);
+    let template = r#"
+    separate(" ",
+        change_id.shortest().prefix() ++ "[" ++ change_id.shortest().rest() ++ "]",
+        commit_id.shortest().prefix() ++ "[" ++ commit_id.shortest().rest() ++ "]",
+        commit_id.shortest(2),
+    )"#;
+
+    insta::assert_snapshot!(render(template), @"z[] 0[] 00[EOF]");
+
+    // The configured length is used if no length is specified
+    test_env.add_config("ui.shortest-id-min-length = 4");
+    insta::assert_snapshot!(render(template), @"z[zzz] 0[000] 00[EOF]");
+
+    let output = work_dir.run_jj([
+        "log",
+        "--config=ui.shortest-id-min-length=-1",
+        "-Tchange_id.shortest()",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse template: Failed to load shortest id settings
+    Caused by:
+    1:  --> 1:11
+      |
+    1 | change_id.shortest()
+      |           ^------^
+      |
+      = Failed to load shortest id settings
+    2: Invalid type or value for ui.shortest-id-min-length
+    3: invalid value: integer `-1`, expected usize
+
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_log_bad_short_prefixes() {
     let test_env = TestEnvironment::default();
@@ -877,6 +919,19 @@ fn test_log_filtered_by_path() {
     [EOF]
     ");
 
+    // patches are also limited to the matching files
+    let output = work_dir.run_jj(["log", "-T", "description", "-p", "file1"]);
+    insta::assert_snapshot!(output, @r"
+    @  second
+    │  Modified regular file file1:
+    │     1    1: foo
+    │          2: bar
+    ○  first
+    │  Added regular file file1:
+    ~          1: foo
+    [EOF]
+    ");
+
     // empty revisions are filtered out by "all()" fileset.
     let output = work_dir.run_jj(["log", "-Tdescription", "-s", "all()"]);
     insta::assert_snapshot!(output, @r"
@@ -927,6 +982,126 @@ fn test_log_filtered_by_path() {
     ");
 }
 
+#[test]
+fn test_log_follow_renames() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\nb\nc\n");
+    work_dir.run_jj(["describe", "-m", "add file1"]).success();
+    work_dir.run_jj(["new", "-m", "modify file1"]).success();
+    work_dir.write_file("file1", "a\nb\nc\nd\n");
+    work_dir.run_jj(["new", "-m", "rename"]).success();
+    work_dir.remove_file("file1");
+    work_dir.write_file("file2", "a\nb\nc\nd\n");
+    work_dir.run_jj(["new", "-m", "modify file2"]).success();
+    work_dir.write_file("file2", "a\nb\nc\nd\ne\n");
+    work_dir.run_jj(["new", "-m", "unrelated"]).success();
+    work_dir.write_file("other", "x\n");
+
+    // Without --follow, only the commits touching the new path are shown.
+    let output = work_dir.run_jj(["log", "-T", "description", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ○  modify file2
+    ○  rename
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["log", "-T", "description", "--follow", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ○  modify file2
+    ○  rename
+    │  (renamed from file1)
+    ○  modify file1
+    ○  add file1
+    │
+    ~
+    [EOF]
+    ");
+
+    // The diff is limited to the followed file at each commit.
+    let output = work_dir.run_jj(["log", "-T", "description", "-s", "--follow", "file2"]);
+    insta::assert_snapshot!(output, @r"
+    ○  modify file2
+    │  M file2
+    ○  rename
+    │  (renamed from file1)
+    │  R {file1 => file2}
+    ○  modify file1
+    │  M file1
+    ○  add file1
+    │  A file1
+    ~
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--no-graph",
+        "--follow",
+        "file2",
+        "-r",
+        "::@--",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    rename
+    (renamed from file1)
+    modify file1
+    add file1
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "log",
+        "-T",
+        "description",
+        "--follow",
+        "file2",
+        "--limit",
+        "2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ○  modify file2
+    ○  rename
+    │  (renamed from file1)
+    ~
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_follow_stops_where_file_was_added() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "old\n");
+    work_dir
+        .run_jj(["describe", "-m", "add old file"])
+        .success();
+    work_dir.run_jj(["new", "-m", "remove file"]).success();
+    work_dir.remove_file("file");
+    work_dir.run_jj(["new", "-m", "add file"]).success();
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["new", "-m", "modify file"]).success();
+    work_dir.write_file("file", "b\n");
+
+    // The history of the unrelated file with the same name isn't shown.
+    let output = work_dir.run_jj(["log", "-T", "description", "--follow", "file"]);
+    insta::assert_snapshot!(output, @r"
+    @  modify file
+    ○  add file
+    │
+    ~
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_limit() {
     let test_env = TestEnvironment::default();
@@ -1109,6 +1284,26 @@ fn test_default_revset_per_repo() {
     ");
 }
 
+#[test]
+fn test_default_revset_deprecated_name() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "foo\n");
+    work_dir.run_jj(["describe", "-m", "add a file"]).success();
+
+    // The old name is still understood, with a warning.
+    test_env.add_config(r#"ui.default-revset = "root()""#);
+    insta::assert_snapshot!(work_dir.run_jj(["log", "-T", "commit_id"]), @r"
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ------- stderr -------
+    Warning: Deprecated user-level config: ui.default-revset is renamed to revsets.log
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_multiple_revsets() {
     let test_env = TestEnvironment::default();
@@ -1149,6 +1344,82 @@ fn test_multiple_revsets() {
     ");
 }
 
+#[test]
+fn test_log_revisions_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    for name in ["foo", "bar", "baz"] {
+        work_dir.run_jj(["new", "-m", name]).success();
+        work_dir
+            .run_jj(["bookmark", "create", "-r@", name])
+            .success();
+    }
+    let foo_commit_id = work_dir
+        .run_jj(["log", "--no-graph", "-rfoo", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+    let baz_change_id = work_dir
+        .run_jj(["log", "--no-graph", "-rbaz", "-Tchange_id"])
+        .success()
+        .stdout
+        .into_raw();
+
+    // Commit and change IDs can be mixed, and combined with -r
+    std::fs::write(
+        test_env.env_root().join("ids"),
+        format!("{foo_commit_id}\n  {baz_change_id}\n"),
+    )
+    .unwrap();
+    insta::assert_snapshot!(
+        work_dir.run_jj(["log", "-T", "bookmarks", "--revisions-file", "../ids"]), @r"
+    @  baz
+    ~  (elided revisions)
+    ○  foo
+    │
+    ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        work_dir.run_jj(["log", "-T", "bookmarks", "--revisions-file", "../ids", "-rbar"]), @r"
+    @  baz
+    ○  bar
+    ○  foo
+    │
+    ~
+    [EOF]
+    ");
+
+    // IDs must be full-length, and other revset expressions aren't allowed
+    std::fs::write(test_env.env_root().join("ids"), &foo_commit_id[..12]).unwrap();
+    insta::assert_snapshot!(
+        work_dir.run_jj(["log", "-T", "bookmarks", "--revisions-file", "../ids"]), @r"
+    ------- stderr -------
+    Error: Revision `b51ab2e2c88f` doesn't exist
+    [EOF]
+    [exit status: 1]
+    ");
+    std::fs::write(test_env.env_root().join("ids"), "foo").unwrap();
+    insta::assert_snapshot!(
+        work_dir.run_jj(["log", "-T", "bookmarks", "--revisions-file", "../ids"]), @r#"
+    ------- stderr -------
+    Error: Invalid commit or change ID "foo" in ../ids
+    [EOF]
+    [exit status: 1]
+    "#);
+
+    // Unknown full IDs are reported
+    std::fs::write(test_env.env_root().join("ids"), "1".repeat(40)).unwrap();
+    insta::assert_snapshot!(
+        work_dir.run_jj(["log", "-T", "bookmarks", "--revisions-file", "../ids"]), @r"
+    ------- stderr -------
+    Error: Revision `1111111111111111111111111111111111111111` doesn't exist
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_graph_template_color() {
     // Test that color codes from a multi-line template don't span the graph lines.
@@ -390,6 +390,12 @@ fn test_op_log_template() {
     [EOF]
     ");
 
+    insta::assert_snapshot!(render(r#""[" ++ username ++ "|" ++ hostname ++ "]\n""#), @r"
+    @  [test-username|host.example.com]
+    ○  [|]
+    [EOF]
+    ");
+
     // Negative length shouldn't cause panic.
     insta::assert_snapshot!(render(r#"id.short(-1) ++ "|""#), @r"
     @  <Error: out of range integral type conversion attempted>|
@@ -423,6 +429,68 @@ fn test_op_log_template() {
     ");
 }
 
+#[test]
+fn test_op_log_bookmark_filter() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "foo"])
+        .success();
+    work_dir.run_jj(["new"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "bar"])
+        .success();
+    work_dir.run_jj(["bookmark", "set", "-r@", "foo"]).success();
+    let template = r#"tags ++ "\n""#;
+
+    let output = work_dir.run_jj(["op", "log", "-T", template, "--bookmark=foo"]);
+    insta::assert_snapshot!(output, @r"
+    @  args: jj bookmark set -r@ foo
+    ○  args: jj bookmark create -r@ foo
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "log", "-T", template, "--no-graph", "--bookmark=bar"]);
+    insta::assert_snapshot!(output, @r"
+    args: jj bookmark create -r@ bar
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--no-graph",
+        "--bookmark=glob:*",
+        "--reversed",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    args: jj bookmark create -r@ foo
+    args: jj bookmark create -r@ bar
+    args: jj bookmark set -r@ foo
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj([
+        "op",
+        "log",
+        "-T",
+        template,
+        "--bookmark=glob:*",
+        "--limit=2",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    @  args: jj bookmark set -r@ foo
+    ○  args: jj bookmark create -r@ bar
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "log", "-T", template, "--bookmark=baz"]);
+    insta::assert_snapshot!(output, @"");
+}
+
 #[test]
 fn test_op_log_builtin_templates() {
     let test_env = TestEnvironment::default();
@@ -764,6 +832,272 @@ fn test_op_abandon_ancestors() {
     ");
 }
 
+#[test]
+fn test_op_compact() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["commit", "-m", "commit 1"]).success();
+    work_dir.run_jj(["commit", "-m", "commit 2"]).success();
+
+    // No retention policy
+    let output = work_dir.run_jj(["op", "compact"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No retention policy is configured
+    Hint: Pass --max-count or --max-age-days, or set `operation.retention.max-count` or `operation.retention.max-age-days`
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Keep the two most recent operations
+    let output = work_dir.run_jj(["op", "compact", "--max-count=2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned 1 operations and reparented 2 descendant operations.
+    [EOF]
+    ");
+    let template = r#"description ++ "\n""#;
+    insta::assert_snapshot!(work_dir.run_jj(["op", "log", "--no-graph", "-T", template]), @r"
+    commit 4e0592f3dd52e7a4998a97d9a1f354e2727a856b
+    commit e8849ae12c709f2321908879bc724fdb2ab8a781
+
+    [EOF]
+    ");
+
+    // Nothing to compact
+    let output = work_dir.run_jj(["op", "compact", "--max-count=2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Age-based retention from config. The test operations are far in the
+    // past, so only the current operation is kept.
+    let output = work_dir.run_jj([
+        "op",
+        "compact",
+        "--config=operation.retention.max-age-days=30",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Abandoned 1 operations and reparented 1 descendant operations.
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.run_jj(["op", "log", "--no-graph", "-T", template]), @r"
+    commit 4e0592f3dd52e7a4998a97d9a1f354e2727a856b
+
+    [EOF]
+    ");
+
+    // The working copy is still usable after compaction.
+    insta::assert_snapshot!(work_dir.run_jj(["log", "-r", "@-", "--no-graph", "-T", "description"]), @r"
+    commit 2
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_restore_at_time() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["commit", "-m", "commit 1"]).success();
+    work_dir.run_jj(["commit", "-m", "commit 2"]).success();
+
+    // Each test command advances the timestamp by one second, so this resolves
+    // to the operation of `jj commit -m 'commit 1'`.
+    work_dir
+        .run_jj(["op", "restore", "--at", "2 seconds ago"])
+        .success();
+    let output = work_dir.run_jj([
+        "log",
+        "--no-graph",
+        "-r",
+        r#"description(glob:"commit*")"#,
+        "-T",
+        "description",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    commit 1
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "restore", "--at", "2000-01-01"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No operation found at or before 2000-01-01
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["op", "restore", "--at", "whenever"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(|s| s.lines().next().unwrap().to_owned() + "\n"), @r"
+    ------- stderr -------
+    Error: Invalid date: whenever
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = work_dir.run_jj(["op", "restore", "--at", "2 seconds ago", "@-"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(|s| s.lines().next().unwrap().to_owned() + "\n"), @r"
+    ------- stderr -------
+    error: the argument '--at <DATE>' cannot be used with '[OPERATION]'
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_op_restore_working_copy_only() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "original\n");
+    work_dir.run_jj(["commit", "-m", "commit 1"]).success();
+    work_dir.write_file("file", "modified\n");
+    work_dir.write_file("new-file", "new\n");
+    work_dir.run_jj(["describe", "-m", "wip"]).success();
+    work_dir.run_jj(["restore"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@-", "foo"])
+        .success();
+    insta::assert_snapshot!(work_dir.run_jj(["diff", "--summary"]), @"");
+
+    // Restore the contents from before `jj restore`, keeping the bookmark
+    work_dir
+        .run_jj(["op", "restore", "--working-copy-only", "@--"])
+        .success();
+    insta::assert_snapshot!(work_dir.run_jj(["diff", "--summary"]), @r"
+    M file
+    A new-file
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @"modified");
+    let template = r#"separate(" ", description.first_line(), bookmarks) ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-rroot()..@", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    wip
+    commit 1 foo
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "restore", "--working-copy-only", "--what=repo", "@-"]);
+    insta::assert_snapshot!(output.normalize_stderr_with(|s| s.lines().next().unwrap().to_owned() + "\n"), @r"
+    ------- stderr -------
+    error: the argument '--working-copy-only' cannot be used with '--what <WHAT>'
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_op_restore_working_copy_backup() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Without any backup, there is nothing to restore
+    let output = work_dir.run_jj(["op", "restore", "--working-copy-only"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No working-copy backup found in the operation log
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // `jj restore` of the working-copy commit records a backup
+    work_dir.write_file("file", "original\n");
+    work_dir.run_jj(["commit", "-m", "commit 1"]).success();
+    work_dir.write_file("file", "modified\n");
+    work_dir.run_jj(["restore"]).success();
+    let output = work_dir.run_jj(["op", "log", "-n1", "--no-graph", "-T", "tags"]);
+    insta::assert_snapshot!(output, @r"
+    args: jj restore
+    working-copy-backup.default: b1eeafbb77eb025122271f0ca13c004b487cbb20[EOF]
+    ");
+    work_dir.run_jj(["new", "-m", "unrelated"]).success();
+    let output = work_dir.run_jj(["op", "restore", "--working-copy-only"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored working-copy contents backed up by operation: f2be42b891e3 (2001-02-03 08:05:10) restore into commit b1eeafbb77eb025122271f0ca13c004b487cbb20
+    Working copy  (@) now at: royxmykx 991c2cda unrelated
+    Parent commit (@-)      : kkmpptxz c579b74e (empty) (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    modified
+    ");
+
+    // So does abandoning the working-copy commit
+    work_dir.write_file("file", "abandoned\n");
+    work_dir.run_jj(["abandon"]).success();
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    original
+    ");
+    let output = work_dir.run_jj(["op", "restore", "--working-copy-only"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored working-copy contents backed up by operation: fa043806ca15 (2001-02-03 08:05:14) abandon commit 4da3e852b9647940b110491a3bde50584a51e462
+    Working copy  (@) now at: vruxwmqv 923fecd2 (no description set)
+    Parent commit (@-)      : kkmpptxz c579b74e (empty) (no description set)
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(work_dir.read_file("file"), @r"
+    abandoned
+    ");
+
+    // Abandoning other commits doesn't
+    work_dir.run_jj(["abandon", "@-"]).success();
+    let output = work_dir.run_jj(["op", "log", "-n1", "--no-graph", "-T", "tags"]);
+    insta::assert_snapshot!(output, @r"
+    args: jj abandon @-[EOF]
+    ");
+}
+
+#[test]
+fn test_op_restore_working_copy_backup_conflicted_checkout() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "base\n");
+    work_dir.run_jj(["commit", "-m", "base"]).success();
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["describe", "-m", "left"]).success();
+    work_dir
+        .run_jj(["new", "description(base)", "-m", "right"])
+        .success();
+    work_dir.write_file("file", "b\n");
+    work_dir
+        .run_jj([
+            "new",
+            "description(left)",
+            "description(right)",
+            "-m",
+            "merge",
+        ])
+        .success();
+    let output = work_dir.run_jj(["op", "log", "-n1", "--no-graph", "-T", "tags"]);
+    insta::assert_snapshot!(output, @r"
+    args: jj new 'description(left)' 'description(right)' -m merge
+    working-copy-backup.default: 5e1b8ccd364b096b076a24d705bb49e61f2d0086[EOF]
+    ");
+
+    // Checking out a non-conflicted commit doesn't record a backup
+    work_dir.run_jj(["new", "description(base)"]).success();
+    let output = work_dir.run_jj(["op", "log", "-n1", "--no-graph", "-T", "tags"]);
+    insta::assert_snapshot!(output, @r"
+    args: jj new 'description(base)'[EOF]
+    ");
+}
+
 #[test]
 fn test_op_abandon_without_updating_working_copy() {
     let test_env = TestEnvironment::default();
@@ -927,7 +1261,7 @@ fn test_op_recover_from_bad_gc() {
         .run_jj(["op", "log", "--no-graph", r#"-Tid.short() ++ "\n""#])
         .success();
     let [head_op_id, _, _, bad_op_id] = output.stdout.raw().lines().next_array().unwrap();
-    insta::assert_snapshot!(head_op_id, @"9a34044af622");
+    insta::assert_snapshot!(head_op_id, @"d03096f2dcc5");
     insta::assert_snapshot!(bad_op_id, @"65860cfb750d");
 
     // Corrupt the repo by removing hidden but reachable commit object.
@@ -964,15 +1298,16 @@ fn test_op_recover_from_bad_gc() {
     // "op log" should still be usable.
     let output = work_dir.run_jj(["op", "log", "--ignore-working-copy", "--at-op", head_op_id]);
     insta::assert_snapshot!(output, @r"
-    @  9a34044af622 test-username@host.example.com 2001-02-03 04:05:12.000 +07:00 - 2001-02-03 04:05:12.000 +07:00
+    @  d03096f2dcc5 test-username@host.example.com 2001-02-03 04:05:12.000 +07:00 - 2001-02-03 04:05:12.000 +07:00
     │  describe commit a053bc8736064a739ab73f2c775a6ac2851bf1a3
     │  args: jj describe -m4
-    ○  c08e984b3923 test-username@host.example.com 2001-02-03 04:05:11.000 +07:00 - 2001-02-03 04:05:11.000 +07:00
+    ○  e182c4a77b2f test-username@host.example.com 2001-02-03 04:05:11.000 +07:00 - 2001-02-03 04:05:11.000 +07:00
     │  new empty commit
     │  args: jj new -m3
-    ○  9988649fbebb test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
+    ○  6599324a7d6f test-username@host.example.com 2001-02-03 04:05:10.000 +07:00 - 2001-02-03 04:05:10.000 +07:00
     │  abandon commit 4e123bae951c3216a145dbcd56d60522739d362e
     │  args: jj abandon
+    │  working-copy-backup.default: 4e123bae951c3216a145dbcd56d60522739d362e
     ○  65860cfb750d test-username@host.example.com 2001-02-03 04:05:09.000 +07:00 - 2001-02-03 04:05:09.000 +07:00
     │  describe commit 884fe9b9c65602d724c7c0f2a238d5549efbe5e6
     │  args: jj describe -m2
@@ -1052,6 +1387,92 @@ fn test_op_corrupted_operation_file() {
     ");
 }
 
+#[test]
+fn test_op_recover() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let op_heads_path = work_dir
+        .root()
+        .join(PathBuf::from_iter([".jj", "repo", "op_heads", "heads"]));
+
+    std::fs::remove_dir_all(&op_heads_path).unwrap();
+    let output = work_dir.run_jj(["op", "log"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Internal error: Corrupt repository
+    Caused by: Operation log has no heads
+    Hint: Run `jj op recover` to restore the operation heads.
+    [EOF]
+    [exit status: 255]
+    ");
+
+    let output = work_dir.run_jj(["op", "recover"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Operation heads are now:
+      8f47435a3990 add workspace 'default'
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["op", "log", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    @  add workspace 'default'
+    ○
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_wait() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let get_head_op_id = || {
+        let output = work_dir
+            .run_jj([
+                "op",
+                "log",
+                "--ignore-working-copy",
+                "--no-graph",
+                "-n1",
+                "-Tid",
+            ])
+            .success();
+        output.stdout.into_raw()
+    };
+    let old_op_id = get_head_op_id();
+
+    // Times out if no operation is recorded. The working copy isn't
+    // snapshotted by the command itself.
+    work_dir.write_file("file", "contents");
+    let output = work_dir.run_jj(["op", "wait", "--timeout=0"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Timed out waiting for a new operation
+    [EOF]
+    [exit status: 1]
+    ");
+    assert_eq!(get_head_op_id(), old_op_id);
+
+    // Returns immediately if the head has already moved past the operation
+    work_dir.run_jj(["describe", "-m", "new"]).success();
+    let new_op_id = get_head_op_id();
+    assert_ne!(new_op_id, old_op_id);
+    let output = work_dir
+        .run_jj(["op", "wait", "--from", &old_op_id])
+        .success();
+    assert_eq!(output.stdout.into_raw(), format!("{new_op_id}\n"));
+
+    let output = work_dir.run_jj(["op", "wait", "--at-op=@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: --at-op is not respected
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_op_summary_diff_template() {
     let test_env = TestEnvironment::default();
@@ -1474,6 +1895,7 @@ fn test_op_diff() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Moved 1 bookmarks to xlzxqlsl 731ab199 bookmark-1* | (empty) new commit
+    Resolved conflicts in 1 bookmark
     [EOF]
     ");
     let output = work_dir.run_jj(["op", "diff"]);
@@ -1622,7 +2044,7 @@ fn test_op_diff_patch() {
     let output = work_dir.run_jj(["op", "diff", "-p", "--git"]);
     insta::assert_snapshot!(output, @r"
     From operation: cfb8edbeae42 (2001-02-03 08:05:11) squash commits into 6b57e33cc56babbeaa6bcd6e2a296236b52ad93c
-      To operation: dd1ab16f2720 (2001-02-03 08:05:13) abandon commit 6cbd01aefe5ae05a015328311dbd63b7305b8ebe
+      To operation: 2576a2671f83 (2001-02-03 08:05:13) abandon commit 6cbd01aefe5ae05a015328311dbd63b7305b8ebe
 
     Changed commits:
     ○  + yqosqzyt c97a8573 (empty) (no description set)
@@ -2456,6 +2878,7 @@ fn test_op_show() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Moved 1 bookmarks to tlkvzzqu 8f340dd7 bookmark-1* | (empty) new commit
+    Resolved conflicts in 1 bookmark
     [EOF]
     ");
     let output = work_dir.run_jj(["op", "show"]);
@@ -2609,9 +3032,10 @@ fn test_op_show_patch() {
     ");
     let output = work_dir.run_jj(["op", "show", "-p", "--git"]);
     insta::assert_snapshot!(output, @r"
-    dd1ab16f2720 test-username@host.example.com 2001-02-03 04:05:13.000 +07:00 - 2001-02-03 04:05:13.000 +07:00
+    2576a2671f83 test-username@host.example.com 2001-02-03 04:05:13.000 +07:00 - 2001-02-03 04:05:13.000 +07:00
     abandon commit 6cbd01aefe5ae05a015328311dbd63b7305b8ebe
     args: jj abandon
+    working-copy-backup.default: 6cbd01aefe5ae05a015328311dbd63b7305b8ebe
 
     Changed commits:
     ○  + yqosqzyt c97a8573 (empty) (no description set)
@@ -2626,9 +3050,10 @@ fn test_op_show_patch() {
     // Try again with "op log".
     let output = work_dir.run_jj(["op", "log", "--git"]);
     insta::assert_snapshot!(output, @r"
-    @  dd1ab16f2720 test-username@host.example.com 2001-02-03 04:05:13.000 +07:00 - 2001-02-03 04:05:13.000 +07:00
+    @  2576a2671f83 test-username@host.example.com 2001-02-03 04:05:13.000 +07:00 - 2001-02-03 04:05:13.000 +07:00
     │  abandon commit 6cbd01aefe5ae05a015328311dbd63b7305b8ebe
     │  args: jj abandon
+    │  working-copy-backup.default: 6cbd01aefe5ae05a015328311dbd63b7305b8ebe
     │
     │  Changed commits:
     │  ○  + yqosqzyt c97a8573 (empty) (no description set)
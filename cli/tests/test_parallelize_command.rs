@@ -57,6 +57,30 @@ fn test_parallelize_no_descendants() {
     ");
 }
 
+#[test]
+fn test_parallelize_dependent_commits() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["commit", "-m=1"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["describe", "-m=2"]).success();
+
+    // The change in 2 can't be applied without 1, so 2 becomes conflicted
+    work_dir
+        .run_jj(["parallelize", "description(1)::"])
+        .success();
+    let template = r#"separate(" ", description.first_line(), if(conflict, "conflict")) ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r=~root()", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    2 conflict
+    1
+    [EOF]
+    ");
+}
+
 // Only the head commit has descendants.
 #[test]
 fn test_parallelize_with_descendants_simple() {
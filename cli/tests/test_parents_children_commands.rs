@@ -0,0 +1,80 @@
+// Copyright 2024 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_parents_children() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.run_jj(["new", "root()", "-m", "a"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "a"])
+        .success();
+    work_dir.run_jj(["new", "root()", "-m", "b"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "b"])
+        .success();
+    work_dir.run_jj(["new", "a", "b", "-m", "merge"]).success();
+    work_dir
+        .run_jj(["bookmark", "create", "-r@", "merge"])
+        .success();
+    work_dir.run_jj(["new", "-m", "c1"]).success();
+    work_dir.run_jj(["new", "merge", "-m", "c2"]).success();
+    let template = r#"description.first_line() ++ "\n""#;
+
+    // Parents are listed in commit order
+    let output = work_dir.run_jj(["parents", "-rmerge", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    a
+    b
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["parents", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    merge
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["parents", "-rroot()", "-T", template]);
+    insta::assert_snapshot!(output, @"");
+
+    // Children are listed newest first
+    let output = work_dir.run_jj(["children", "-rmerge", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    c2
+    c1
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["children", "-ra", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    merge
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["children", "-T", template]);
+    insta::assert_snapshot!(output, @"");
+
+    // JSON output
+    let output = work_dir.run_jj([
+        "parents",
+        "-rmerge",
+        "-T",
+        r#"json(self.description()) ++ "\n""#,
+    ]);
+    insta::assert_snapshot!(output, @r#"
+    "a\n"
+    "b\n"
+    [EOF]
+    "#);
+}
@@ -97,7 +97,7 @@ fn test_rebase_invalid() {
     let output = work_dir.run_jj(["rebase", "-r", "a", "-d", "a"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Error: Cannot rebase 7d980be7a1d4 onto itself
+    Error: Cannot rebase rlvkpnrz 7d980be7 a | a onto itself
     [EOF]
     [exit status: 1]
     ");
@@ -115,7 +115,7 @@ fn test_rebase_invalid() {
     let output = work_dir.run_jj(["rebase", "-s", "a", "-d", "b"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Error: Cannot rebase 7d980be7a1d4 onto descendant 123b4d91f6e5
+    Error: Cannot rebase rlvkpnrz 7d980be7 a | a onto descendant zsuskuln 123b4d91 b | b
     [EOF]
     [exit status: 1]
     ");
@@ -654,6 +654,65 @@ fn test_rebase_multiple_revisions() {
     ");
 }
 
+#[test]
+fn test_rebase_revisions_file() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+    create_commit(&work_dir, "d", &["c"]);
+    // Test the setup
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  d: c
+    ○  c: b
+    ○  b: a
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    let b_commit_id = work_dir
+        .run_jj(["log", "--no-graph", "-rb", "-Tcommit_id"])
+        .success()
+        .stdout
+        .into_raw();
+    std::fs::write(test_env.env_root().join("ids"), format!("{b_commit_id}\n")).unwrap();
+    let output = work_dir.run_jj(["rebase", "--revisions-file", "../ids", "-r", "c", "-d", "d"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 commits to destination
+    Rebased 1 descendant commits
+    Working copy  (@) now at: vruxwmqv 8f1b4598 d | d
+    Parent commit (@-)      : rlvkpnrz 7d980be7 a | a
+    Added 0 files, modified 0 files, removed 2 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    ○  c: b
+    ○  b: d
+    @  d: a
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // Cannot be combined with -s or -b
+    let output = work_dir.run_jj(["rebase", "--revisions-file", "../ids", "-s", "c", "-d", "a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--revisions-file <PATH>' cannot be used with '--source <REVSETS>'
+
+    Usage: jj rebase --revisions-file <PATH> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_rebase_revision_onto_descendant() {
     let test_env = TestEnvironment::default();
@@ -2725,7 +2784,8 @@ fn test_rebase_skip_emptied() {
     [EOF]
     ");
 
-    let output = work_dir.run_jj(["rebase", "-d=b", "--skip-emptied"]);
+    // `--skip-empty` is an alias of `--skip-emptied`
+    let output = work_dir.run_jj(["rebase", "-d=b", "--skip-empty"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Rebased 2 commits to destination
@@ -3054,6 +3114,47 @@ fn test_rebase_skip_duplicate_divergent() {
     ");
 }
 
+#[test]
+fn test_rebase_rewrite_confirmation_non_interactive() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("ui.rewrite-confirmation-threshold = 1");
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "c", &["b"]);
+
+    // The prompt can't be answered, so the rebase is aborted
+    let output = work_dir.run_jj(["rebase", "-s", "b", "-d", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: This operation would rewrite more than 1 commits (`ui.rewrite-confirmation-threshold`).
+    Do you want to continue? (yN): n
+    Error: Aborted rewriting commits
+    Hint: Pass --yes to skip this confirmation, or raise `ui.rewrite-confirmation-threshold`.
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir), @r"
+    @  c: b
+    ○  b: a
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["rebase", "-s", "b", "-d", "root()", "--yes"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 commits to destination
+    Working copy  (@) now at: royxmykx 50b89726 c | c
+    Parent commit (@-)      : zsuskuln 5c6d09da b | b
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = "bookmarks ++ surround(': ', '', parents.map(|c| c.bookmarks()))";
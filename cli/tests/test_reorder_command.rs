@@ -0,0 +1,207 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::create_commit;
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+#[test]
+fn test_reorder() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    for n in 1..4 {
+        work_dir.run_jj(["commit", &format!("-m{n}")]).success();
+    }
+    work_dir.run_jj(["describe", "-m=4"]).success();
+    insta::assert_snapshot!(get_log_output(&work_dir, "all()"), @r"
+    @  4 parents: 3
+    ○  3 parents: 2
+    ○  2 parents: 1
+    ○  1 parents:
+    ◆  parents:
+    [EOF]
+    ");
+
+    // The descendants of the stack stay on top of it
+    let output = work_dir.run_jj([
+        "reorder",
+        "--order",
+        "description(3)",
+        "description(1)",
+        "description(2)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Reordered 3 commits
+    Rebased 1 descendant commits
+    Working copy  (@) now at: 4
+    Parent commit (@-)      : 2
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir, "all()"), @r"
+    @  4 parents: 2
+    ○  2 parents: 1
+    ○  1 parents: 3
+    ○  3 parents:
+    ◆  parents:
+    [EOF]
+    ");
+
+    // Reordering into the current order is a no-op
+    let output = work_dir.run_jj(["reorder", "-o", "description(1)", "description(2)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_reorder_with_side_branch() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit(&work_dir, "a", &[]);
+    create_commit(&work_dir, "b", &["a"]);
+    create_commit(&work_dir, "side", &["b"]);
+    create_commit(&work_dir, "c", &["b"]);
+
+    // The side branch follows the revision it's based on
+    let output = work_dir.run_jj(["reorder", "-o", "b", "a", "c"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Reordered 3 commits
+    Rebased 1 descendant commits
+    Working copy  (@) now at: c
+    Parent commit (@-)      : a
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir, "::c"), @r"
+    @  c parents: a
+    ○  a parents: b
+    ○  b parents:
+    ◆  parents:
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir, "side"), @r"
+    ○  side parents: b
+    │
+    ~
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_reorder_conflicts() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+
+    for n in 1..4 {
+        work_dir.write_file("file", format!("{n}\n"));
+        work_dir.run_jj(["commit", &format!("-m{n}")]).success();
+    }
+
+    // Moving the revision that adds the file to the top conflicts the
+    // revisions that modify it, which is reported before rewriting
+    let output = work_dir.run_jj([
+        "reorder",
+        "-o",
+        "description(2)",
+        "description(3)",
+        "description(1)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: The new order will cause conflicts in these revisions:
+      2
+      3
+    Reordered 3 commits
+    Rebased 1 descendant commits
+    Working copy  (@) now at: 
+    Parent commit (@-)      : 1
+    New conflicts appeared in 2 commits:
+      3
+      2
+    Hint: To resolve the conflicts, start by creating a commit on top of
+    the first conflicted commit:
+      jj new rlvkpnrz
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you can inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&work_dir, "::@"), @r"
+    @  parents: 1
+    ○  1 parents: 3
+    ×  3 parents: 2
+    ×  2 parents:
+    ◆  parents:
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_reorder_invalid_order() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    for n in 1..4 {
+        work_dir.run_jj(["commit", &format!("-m{n}")]).success();
+    }
+
+    // Not all revisions of the stack are listed
+    let output = work_dir.run_jj([
+        "reorder",
+        "-r",
+        "description(1)::description(3)",
+        "-o",
+        "description(2)",
+        "description(1)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Each revision of the stack must be listed exactly once in the new order
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // A revision is listed twice
+    let output = work_dir.run_jj(["reorder", "-o", "description(1)", "description(1)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Each revision of the stack must be listed exactly once in the new order
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[must_use]
+fn get_log_output(work_dir: &TestWorkDir, revisions: &str) -> CommandOutput {
+    let template = r#"
+    separate(" ",
+        description.first_line(),
+        "parents:",
+        parents.map(|c|c.description().first_line())
+    )"#;
+    work_dir.run_jj(["log", "-T", template, "-r", revisions])
+}
@@ -245,3 +245,75 @@ fn test_report_conflicts_with_resolving_conflicts_hint_disabled() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_report_new_divergence() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m=A"]).success();
+    let hidden_id = work_dir
+        .run_jj(["log", "--no-graph", "-r=@", "-T=commit_id"])
+        .success()
+        .stdout
+        .into_raw();
+    work_dir.run_jj(["describe", "-m=A2"]).success();
+
+    // Rewriting a hidden predecessor makes the change divergent
+    let output = work_dir.run_jj(["describe", &format!("-r={hidden_id}"), "-m=A3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: The operation made 1 change divergent:
+      A3
+    Hint: Use `jj evolve` to merge the commits of each divergent change, or abandon the unneeded commits with `jj abandon <commit ID>`.
+    [EOF]
+    ");
+
+    // Already divergent changes aren't reported again
+    let output = work_dir.run_jj(["describe", "-r=description(A3)", "-m=A4"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_report_new_orphans() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(r#"templates.commit_summary = 'description.first_line()'"#);
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.run_jj(["describe", "-m=A"]).success();
+    let hidden_id = work_dir
+        .run_jj(["log", "--no-graph", "-r=@", "-T=commit_id"])
+        .success()
+        .stdout
+        .into_raw();
+    work_dir.run_jj(["describe", "-m=A2"]).success();
+
+    // Creating a commit on top of a rewritten commit makes it an orphan
+    let output = work_dir.run_jj(["new", &hidden_id, "-m=B"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy  (@) now at: B
+    Parent commit (@-)      : A
+    Warning: The operation made 1 change divergent:
+      A2
+    Hint: Use `jj evolve` to merge the commits of each divergent change, or abandon the unneeded commits with `jj abandon <commit ID>`.
+    Warning: The operation added 1 commits on top of hidden commits:
+      B
+    Hint: The hidden commits were rewritten or abandoned. If their changes are now divergent, `jj evolve` merges them and rebases the new commits. Otherwise, use `jj rebase -s <commit ID> -d <destination>` to move the new commits.
+    [EOF]
+    ");
+
+    // Reviving the orphan along with its parent doesn't report it again
+    work_dir.run_jj(["abandon", "description(B)"]).success();
+    let output = work_dir.run_jj(["undo"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Undid operation: 707b0519789b (2001-02-03 08:05:12) abandon commit 7ac8344dab21356b404f5dc19d7aba9edb715340
+    Working copy  (@) now at: B
+    Parent commit (@-)      : A
+    [EOF]
+    ");
+}
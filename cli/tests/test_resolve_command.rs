@@ -542,6 +542,49 @@ fn check_resolve_produces_input_file(
     }
 }
 
+#[test]
+fn test_leftover_conflict_markers() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    create_commit_with_files(&work_dir, "base", &[], &[("file", "base\n")]);
+    create_commit_with_files(&work_dir, "a", &["base"], &[("file", "a\n")]);
+    create_commit_with_files(&work_dir, "b", &["base"], &[("file", "b\n")]);
+    create_commit_with_files(&work_dir, "conflict", &["a", "b"], &[]);
+
+    // The conflict is resolved, but the start marker was left behind
+    work_dir.write_file("file", "<<<<<<< Conflict 1 of 1\nresolution\n");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file
+    [EOF]
+    ------- stderr -------
+    Warning: Some resolved files still contain conflict markers:
+      file
+    [EOF]
+    ");
+    let output = work_dir.run_jj(["resolve", "--list"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No conflicts found at this revision
+    [EOF]
+    [exit status: 2]
+    ");
+
+    // The warning can be disabled
+    work_dir.write_file("file", "<<<<<<< Conflict 1 of 1\nresolution 2\n");
+    let output = work_dir.run_jj([
+        "file",
+        "list",
+        "--config=snapshot.detect-conflict-markers=false",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    file
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_normal_conflict_input_files() {
     let mut test_env = TestEnvironment::default();
@@ -354,6 +354,24 @@ fn test_revert_description_template() {
     "#);
 }
 
+#[test]
+fn test_revert_trailers() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(r#"templates.commit_trailers = '"Signed-off-by: " ++ committer.email()'"#);
+    let work_dir = test_env.work_dir("repo");
+    create_commit_with_files(&work_dir, "a", &[], &[("a", "a\n")]);
+
+    work_dir.run_jj(["revert", "-r@", "-d@"]).success();
+    let template = r#"description.first_line() ++ "\n" ++ trailers.map(|t| t.key() ++ ": " ++ t.value()).join("\n") ++ "\n""#;
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@+", "-T", template]);
+    insta::assert_snapshot!(output, @r#"
+    Revert "a"
+    Signed-off-by: test.user@example.com
+    [EOF]
+    "#);
+}
+
 #[must_use]
 fn get_log_output(work_dir: &TestWorkDir) -> CommandOutput {
     let template = r#"commit_id.short() ++ " " ++ description"#;
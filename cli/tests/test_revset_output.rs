@@ -409,6 +409,34 @@ fn test_function_name_hint() {
     [exit status: 1]
     ");
 
+    // Renamed functions should suggest the new names
+    insta::assert_snapshot!(evaluate(r#"branches(glob:"release-*")"#), @r#"
+    ------- stderr -------
+    Error: Failed to parse revset: Function `branches` doesn't exist
+    Caused by:  --> 1:1
+      |
+    1 | branches(glob:"release-*")
+      | ^------^
+      |
+      = Function `branches` doesn't exist
+    Hint: Did you mean `ancestors`, `bookmarks`, `reachable`?
+    [EOF]
+    [exit status: 1]
+    "#);
+    insta::assert_snapshot!(evaluate("remote_branches()"), @r"
+    ------- stderr -------
+    Error: Failed to parse revset: Function `remote_branches` doesn't exist
+    Caused by:  --> 1:1
+      |
+    1 | remote_branches()
+      | ^-------------^
+      |
+      = Function `remote_branches` doesn't exist
+    Hint: Did you mean `remote_bookmarks`, `untracked_remote_bookmarks`?
+    [EOF]
+    [exit status: 1]
+    ");
+
     // Both builtin function and function alias should be suggested
     insta::assert_snapshot!(evaluate("author_()"), @r"
     ------- stderr -------
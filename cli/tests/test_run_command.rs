@@ -0,0 +1,128 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+use crate::common::TestWorkDir;
+
+fn create_commits(work_dir: &TestWorkDir) {
+    work_dir.run_jj(["describe", "-mc1"]).success();
+    work_dir.write_file("file", "1\n");
+    for i in 2..=3 {
+        work_dir.run_jj(["new", &format!("-mc{i}")]).success();
+        work_dir.write_file("file", format!("{i}\n"));
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+    create_commits(&work_dir);
+
+    // The command runs on each revision, parents first
+    let output = work_dir.run_jj(["run", "cat file", "-r", "description(c1)::"]);
+    insta::assert_snapshot!(output, @r"
+    1
+    2
+    3
+    [EOF]
+    ------- stderr -------
+    Command succeeded on c1
+    Command succeeded on c2
+    Command succeeded on c3
+    [EOF]
+    ");
+
+    // The working copy isn't touched
+    let output = work_dir.run_jj(["run", "rm file", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Command succeeded on c3
+    [EOF]
+    ");
+    assert_eq!(work_dir.read_file("file"), "3\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_failed() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+    create_commits(&work_dir);
+
+    let output = work_dir.run_jj([
+        "run",
+        "test \"$(cat file)\" != 2",
+        "-r",
+        "description(c1)::",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Command succeeded on c1
+    Command failed on c2 (exit status: 1)
+    Command succeeded on c3
+    Error: Command failed on 1 of 3 revisions
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_rewrite() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+    let work_dir = test_env.work_dir("repo");
+    create_commits(&work_dir);
+
+    // Changes are only recorded for the revisions the command succeeded on
+    let output = work_dir.run_jj([
+        "run",
+        "--rewrite",
+        "echo new > new; test \"$(cat file)\" != 2",
+        "-r",
+        "description(c1) | description(c2)",
+    ]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Command succeeded on c1
+    Command failed on c2 (exit status: 1)
+    Rewrote 1 commits
+    Rebased 2 descendant commits
+    Working copy  (@) now at: c3
+    Parent commit (@-)      : c2
+    Added 1 files, modified 0 files, removed 0 files
+    Error: Command failed on 1 of 2 revisions
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = work_dir.run_jj(["log", "-T", "description.first_line()", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    @  c3
+    │  M file
+    ○  c2
+    │  M file
+    ○  c1
+    │  A file
+    │  A new
+    ◆
+    [EOF]
+    ");
+}
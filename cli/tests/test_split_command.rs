@@ -753,6 +753,146 @@ fn test_split_empty() {
     ");
 }
 
+#[test]
+fn test_split_by_config() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.create_dir("docs");
+    work_dir.create_dir("lib");
+    work_dir.write_file("docs/file", "docs\n");
+    work_dir.write_file("lib/file", "lib\n");
+    work_dir.write_file("other", "other\n");
+    work_dir.run_jj(["describe", "-m", "original"]).success();
+
+    // No groups are configured
+    let output = work_dir.run_jj(["split", "--by-config"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: No `split.groups` are configured
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    test_env.add_config(
+        r#"
+        [split.groups.1-docs]
+        patterns = ["docs"]
+        [split.groups.2-lib]
+        patterns = ["lib"]
+        description = "lib: update library"
+        [split.groups.3-unused]
+        patterns = ["unused"]
+        "#,
+    );
+    work_dir.run_jj(["split", "--by-config"]).success();
+    let output = work_dir.run_jj(["log", "-T", "description", "--summary"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    @  original
+    │  A other
+    ○  lib: update library
+    │  A lib/file
+    ○  1-docs: original
+    │  A docs/file
+    ◆
+    [EOF]
+    ");
+
+    // Nothing in the revision matches any group
+    let output = work_dir.run_jj(["split", "--by-config"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes in the revision match any of the `split.groups`
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_split_by_config_group_by() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.create_dir_all("docs");
+    work_dir.create_dir_all("lib/a/b");
+    work_dir.create_dir_all("lib/c");
+    work_dir.write_file("lib/OWNERS", "");
+    work_dir.write_file("lib/a/OWNERS", "");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("docs/file", "docs\n");
+    work_dir.write_file("lib/a/b/file", "a\n");
+    work_dir.write_file("lib/c/file", "c\n");
+    work_dir.write_file("other", "other\n");
+    work_dir.run_jj(["describe", "-m", "original"]).success();
+
+    test_env.add_config(r#"split.group-by = "top-level-directory""#);
+    work_dir.run_jj(["split", "--by-config"]).success();
+    let output = work_dir.run_jj(["log", "-T", "description", "--summary"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    @  original
+    │  A other
+    ○  lib: original
+    │  A lib/a/b/file
+    │  A lib/c/file
+    ○  docs: original
+    │  A docs/file
+    ○
+    │  A lib/OWNERS
+    │  A lib/a/OWNERS
+    ◆
+    [EOF]
+    ");
+
+    test_env.add_config(r#"split.group-by = "owners""#);
+    work_dir.run_jj(["undo"]).success();
+    work_dir.run_jj(["split", "--by-config"]).success();
+    let output = work_dir.run_jj(["log", "-T", "description", "--summary"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    @  original
+    │  A docs/file
+    │  A other
+    ○  lib/a: original
+    │  A lib/a/b/file
+    ○  lib: original
+    │  A lib/c/file
+    ○
+    │  A lib/OWNERS
+    │  A lib/a/OWNERS
+    ◆
+    [EOF]
+    ");
+
+    // Nothing in the revision is covered by an OWNERS file
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("other", "changed\n");
+    let output = work_dir.run_jj(["split", "--by-config"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes in the revision are covered by an `OWNERS` file
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_split_builtin_editor_without_terminal() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    work_dir.write_file("file1", "foo\n");
+
+    // The builtin diff editor fails cleanly when it can't draw its UI
+    let output = work_dir.run_jj(["split", "--tool=:builtin"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to edit diff
+    Caused by: The builtin difftool/mergetool requires an interactive terminal
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_split_message_editor_avoids_unc() {
     let mut test_env = TestEnvironment::default();
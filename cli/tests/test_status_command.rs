@@ -110,6 +110,38 @@ fn test_status_filtered() {
     ");
 }
 
+#[test]
+fn test_status_generated_files() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(r#"templates.commit_summary = '"-"'"#);
+    test_env.add_config(r#"diff.generated-files = "glob:'*.gen'""#);
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file", "file");
+    work_dir.write_file("file.gen", "generated");
+
+    let output = work_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    A file
+    Generated file changed: file.gen
+    Working copy  (@) : -
+    Parent commit (@-): -
+    [EOF]
+    ");
+
+    let output = work_dir.run_jj(["status", "--include-generated"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    A file
+    A file.gen
+    Working copy  (@) : -
+    Parent commit (@-): -
+    [EOF]
+    ");
+}
+
 // See <https://github.com/jj-vcs/jj/issues/3108>
 // See <https://github.com/jj-vcs/jj/issues/4147>
 #[test]
@@ -470,3 +502,78 @@ fn test_status_untracked_files() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_status_tracked_bookmarks() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config("git.auto-local-bookmark = true");
+    test_env.add_config("templates.commit_summary = 'description.first_line()'");
+
+    test_env.run_jj_in(".", ["git", "init", "remote"]).success();
+    let remote_dir = test_env.work_dir("remote");
+    remote_dir.run_jj(["describe", "-m=A"]).success();
+    remote_dir.run_jj(["new", "-m=B"]).success();
+    remote_dir
+        .run_jj(["bookmark", "create", "-r@", "main"])
+        .success();
+    remote_dir.run_jj(["new"]).success();
+    remote_dir.run_jj(["git", "export"]).success();
+
+    let mut remote_git_path = remote_dir.root().to_owned();
+    remote_git_path.extend([".jj", "repo", "store", "git"]);
+    test_env
+        .run_jj_in(
+            ".",
+            ["git", "clone", remote_git_path.to_str().unwrap(), "local"],
+        )
+        .success();
+    let local_dir = test_env.work_dir("local");
+
+    // Synced bookmarks aren't reported
+    local_dir.run_jj(["new", "main", "-m=C"]).success();
+    let output = local_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : C
+    Parent commit (@-): B
+    [EOF]
+    ");
+
+    local_dir
+        .run_jj(["bookmark", "set", "main", "-r@"])
+        .success();
+    let output = local_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : C
+    Parent commit (@-): B
+    Bookmark main is ahead of main@origin by 1 commit
+    [EOF]
+    ");
+
+    local_dir.run_jj(["new", "main@origin-", "-m=E"]).success();
+    local_dir
+        .run_jj(["bookmark", "set", "main", "--allow-backwards", "-r@-"])
+        .success();
+    let output = local_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : E
+    Parent commit (@-): A
+    Bookmark main is behind main@origin by 1 commit
+    [EOF]
+    ");
+
+    local_dir.run_jj(["describe", "-m=D"]).success();
+    local_dir
+        .run_jj(["bookmark", "set", "main", "-r@"])
+        .success();
+    let output = local_dir.run_jj(["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy  (@) : D
+    Parent commit (@-): A
+    Bookmark main has diverged from main@origin (ahead by 1 commit, behind by 1 commit)
+    [EOF]
+    ");
+}
@@ -96,7 +96,7 @@ fn test_templater_parse_error() {
       |                    ^^
       |
       = Keyword `se` doesn't exist
-    Hint: Did you mean `s`, `self`?
+    Hint: Did you mean `closest_tag`, `closest_tag_distance`, `s`, `self`?
     [EOF]
     [exit status: 1]
     "#);
@@ -455,6 +455,34 @@ fn test_templater_alias_override() {
     insta::assert_snapshot!(output, @"arg[EOF]");
 }
 
+#[test]
+fn test_templater_alias_in_templates_table() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+
+    // Named templates are defined in [template-aliases], not in [templates]
+    test_env.add_config("templates.oneline = 'commit_id.short()'");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@-", "-Toneline"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to parse template: Keyword `oneline` doesn't exist
+    Caused by:  --> 1:1
+      |
+    1 | oneline
+      | ^-----^
+      |
+      = Keyword `oneline` doesn't exist
+    Hint: `templates.oneline` is a command setting, not a template alias. Define `template-aliases.oneline` to use it as a named template.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    test_env.add_config("template-aliases.oneline = 'commit_id.short()'");
+    let output = work_dir.run_jj(["log", "--no-graph", "-r@-", "-Toneline"]);
+    insta::assert_snapshot!(output, @"000000000000[EOF]");
+}
+
 #[test]
 fn test_templater_bad_alias_decl() {
     let test_env = TestEnvironment::default();
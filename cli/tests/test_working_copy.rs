@@ -15,6 +15,7 @@
 use indoc::indoc;
 use regex::Regex;
 
+use crate::common::to_toml_value;
 use crate::common::TestEnvironment;
 
 #[test]
@@ -115,6 +116,48 @@ fn test_snapshot_large_file() {
     ");
 }
 
+#[test]
+fn test_snapshot_report() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("ui.report-snapshot = true");
+    let work_dir = test_env.work_dir("repo");
+
+    work_dir.write_file("file1", "a\n");
+    work_dir.write_file("file2", "b\n");
+    let output = work_dir.run_jj(["log", "-r@", "-T", "commit_id.short()"]);
+    insta::assert_snapshot!(output, @r"
+    @  112d5fc7d2af
+    │
+    ~
+    [EOF]
+    ------- stderr -------
+    Snapshotted working copy into 112d5fc7d2af: added 2 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+
+    work_dir.write_file("file1", "a2\n");
+    work_dir.remove_file("file2");
+    work_dir.write_file("file3", "c\n");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    file3
+    [EOF]
+    ------- stderr -------
+    Snapshotted working copy into 7b10533d20e1: added 1 file, modified 1 file, removed 1 file
+    [EOF]
+    ");
+
+    // Nothing is reported if there were no changes
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    file3
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_snapshot_large_file_restore() {
     let test_env = TestEnvironment::default();
@@ -365,7 +408,7 @@ fn test_conflict_marker_length_stored_in_working_copy() {
     // Working copy should contain conflict marker length
     let output = work_dir.run_jj(["debug", "local-working-copy"]);
     insta::assert_snapshot!(output.normalize_stdout_with(redact_output), @r#"
-    Current operation: OperationId("da3b34243efe5ea04830cd2211b5be79444fbc2ef23681361fd2f551ebb86772bff21695da95b72388306e028bf04c6d76db10bf4cbd3a08eb34bf744c8900c7")
+    Current operation: OperationId("95f78107fc97e7742217d41371aa274951481c0a3f809667cc2d588b93e28c3ee99fe4c79c29224d0ab98acf85d09e343d5aa4679f4d1af69b1cb9913056c6d9")
     Current tree: Merge(Conflicted([TreeId("381273b50cf73f8c81b3f1502ee89e9bbd6c1518"), TreeId("771f3d31c4588ea40a8864b2a981749888e596c2"), TreeId("f56b8223da0dab22b03b8323ced4946329aeb4e0")]))
     Normal { <executable> }           249 <timestamp> Some(MaterializedConflictData { conflict_marker_len: 11 }) "file"
     [EOF]
@@ -428,7 +471,7 @@ fn test_conflict_marker_length_stored_in_working_copy() {
     // Working copy should still contain conflict marker length
     let output = work_dir.run_jj(["debug", "local-working-copy"]);
     insta::assert_snapshot!(output.normalize_stdout_with(redact_output), @r#"
-    Current operation: OperationId("85725298062bdfe1d00333e7b3c5af27891e8e59acb236e8499b5712699cf77f91e3b3664e3433771b096fe781113bfe4cf1b88887aae02af733ba40963d5015")
+    Current operation: OperationId("5dafd1e67f5953a4436c7c7ce3588c93473ad0bc3289ebcfc5544571d6b016fe36873653746ff5da556daad4dd4149b5ab8e658df3988618e123c59ae4797a2f")
     Current tree: Merge(Conflicted([TreeId("381273b50cf73f8c81b3f1502ee89e9bbd6c1518"), TreeId("771f3d31c4588ea40a8864b2a981749888e596c2"), TreeId("3329c18c95f7b7a55c278c2259e9c4ce711fae59")]))
     Normal { <executable> }           289 <timestamp> Some(MaterializedConflictData { conflict_marker_len: 11 }) "file"
     [EOF]
@@ -463,9 +506,68 @@ fn test_conflict_marker_length_stored_in_working_copy() {
     // working copy
     let output = work_dir.run_jj(["debug", "local-working-copy"]);
     insta::assert_snapshot!(output.normalize_stdout_with(redact_output), @r#"
-    Current operation: OperationId("683acb91a6165a95b02bcc8ea2133982ba6f244ec006634447e074ccc5a3c4df0bd955e4f628a406059edaa30e9c5af88f3fd06b0c5e9e48df93556da6fe410c")
+    Current operation: OperationId("3f34728092d2702ecc5a4819f253fbec2a7c1e56595302ab0b08f0a885b37550c4ed11bce85db969fa348b8115e0407a6b797f456a7e23d42dee3803cdd52fd2")
     Current tree: Merge(Resolved(TreeId("6120567b3cb2472d549753ed3e4b84183d52a650")))
     Normal { <executable> }           130 <timestamp> None "file"
     [EOF]
     "#);
 }
+
+#[cfg(unix)]
+#[test]
+fn test_working_copy_hooks() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    let log_path = test_env.env_root().join("hook.log");
+    let script = format!(
+        r#"echo "$JJ_HOOK" >> '{path}' && cat >> '{path}'"#,
+        path = log_path.display()
+    );
+    let script = to_toml_value(script);
+    test_env.add_config(format!(
+        r#"
+        hooks.post-checkout = ["sh", "-c", {script}]
+        hooks.post-snapshot = ["sh", "-c", {script}]
+        "#
+    ));
+
+    work_dir.create_dir("dir");
+    work_dir.write_file("dir/file", "a\n");
+    work_dir.write_file("file", "a\n");
+    work_dir.run_jj(["new"]).success();
+    work_dir.write_file("file", "b\n");
+    work_dir.run_jj(["prev", "--edit"]).success();
+    // Nothing changed, so no hooks are run.
+    work_dir.run_jj(["status"]).success();
+
+    insta::assert_snapshot!(std::fs::read_to_string(&log_path).unwrap(), @r"
+    post-snapshot
+    dir/file
+    file
+    post-snapshot
+    file
+    post-checkout
+    file
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_working_copy_hook_failure() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let work_dir = test_env.work_dir("repo");
+    test_env.add_config(r#"hooks.post-snapshot = ["sh", "-c", "exit 1"]"#);
+
+    // A failing hook is reported, but the command still succeeds.
+    work_dir.write_file("file", "a\n");
+    let output = work_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file
+    [EOF]
+    ------- stderr -------
+    Warning: The post-snapshot hook exited with exit status: 1
+    [EOF]
+    ");
+}
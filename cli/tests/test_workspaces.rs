@@ -125,6 +125,33 @@ fn test_workspaces_sparse_patterns() {
         .success();
     let output = ws6_dir.run_jj(["sparse", "list"]);
     insta::assert_snapshot!(output, @"");
+    // --sparse-add behavior
+    let ws7_dir = test_env.work_dir("ws7");
+    let ws8_dir = test_env.work_dir("ws8");
+    ws3_dir
+        .run_jj(["workspace", "add", "--sparse-add=baz", "../ws7"])
+        .success();
+    let output = ws7_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    bar
+    baz
+    foo
+    [EOF]
+    ");
+    ws3_dir
+        .run_jj([
+            "workspace",
+            "add",
+            "--sparse-patterns=empty",
+            "--sparse-add=baz",
+            "../ws8",
+        ])
+        .success();
+    let output = ws8_dir.run_jj(["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    baz
+    [EOF]
+    ");
 }
 
 /// Test adding a second workspace while the current workspace is editing a
@@ -417,6 +444,47 @@ fn test_workspaces_add_workspace_from_subdir() {
     ");
 }
 
+/// Test adding a workspace in an existing directory
+#[test]
+fn test_workspaces_add_workspace_in_existing_dir() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+    test_env.work_dir("").create_dir("secondary");
+    let non_empty_dir = test_env.work_dir("").create_dir("non-empty");
+    non_empty_dir.write_file("file", "contents");
+
+    main_dir.write_file("file", "contents");
+    main_dir.run_jj(["commit", "-m", "initial"]).success();
+
+    let output = main_dir.run_jj(["workspace", "list"]);
+    insta::assert_snapshot!(output, @r"
+    default: rlvkpnrz 504e3d8c (empty) (no description set)
+    [EOF]
+    ");
+
+    // An empty directory can be used as the destination
+    let output = main_dir.run_jj(["workspace", "add", "--name", "second", "../secondary"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r#"
+    ------- stderr -------
+    Created workspace in "../secondary"
+    Working copy  (@) now at: rzvqmyuk bcc858e1 (empty) (no description set)
+    Parent commit (@-)      : qpvuntsm 7b22a8cb initial
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+
+    // A non-empty directory is rejected
+    let output = main_dir.run_jj(["workspace", "add", "../non-empty"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Workspace already exists
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(!non_empty_dir.root().join(".jj").exists());
+}
+
 #[test]
 fn test_workspaces_add_workspace_in_current_workspace() {
     let test_env = TestEnvironment::default();
@@ -483,6 +551,9 @@ fn test_workspaces_add_workspace_in_current_workspace() {
 fn test_workspaces_conflicting_edits() {
     let test_env = TestEnvironment::default();
     test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    test_env.add_config(
+        r#"templates.commit_summary = 'separate(" ", change_id.shortest(8), if(conflict, "(conflict)"), if(empty, "(empty)"))'"#,
+    );
     let main_dir = test_env.work_dir("main");
     let secondary_dir = test_env.work_dir("secondary");
 
@@ -511,8 +582,8 @@ fn test_workspaces_conflicting_edits() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Rebased 1 descendant commits
-    Working copy  (@) now at: mzvwutvl 3a9b690d (empty) (no description set)
-    Parent commit (@-)      : qpvuntsm b853f7c8 (no description set)
+    Working copy  (@) now at: mzvwutvl (empty)
+    Parent commit (@-)      : qpvuntsm
     [EOF]
     ");
 
@@ -544,40 +615,46 @@ fn test_workspaces_conflicting_edits() {
     [EOF]
     [exit status: 1]
     ");
-    // It was detected that the working copy is now stale.
-    // Since there was an uncommitted change in the working copy, it should
-    // have been committed first (causing divergence)
+    // It was detected that the working copy is now stale. The uncommitted
+    // change in the working copy is snapshotted, and then merged into the
+    // rewritten working-copy commit instead of being left in a divergent commit.
     let output = secondary_dir.run_jj(["workspace", "update-stale"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Concurrent modification detected, resolving automatically.
     Rebased 1 descendant commits onto commits rewritten by other operation
-    Working copy  (@) now at: pmmvwywv?? 90f3d42e (empty) (no description set)
-    Parent commit (@-)      : qpvuntsm b853f7c8 (no description set)
+    Working copy  (@) now at: pmmvwywv (conflict)
+    Parent commit (@-)      : qpvuntsm
     Added 0 files, modified 1 files, removed 0 files
-    Updated working copy to fresh commit 90f3d42e0bff
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Updated working copy to fresh commit pmmvwywv (conflict)
     [EOF]
     ");
-    insta::assert_snapshot!(get_log_output(&secondary_dir),
-    @r"
-    @  90f3d42e0bff secondary@ (divergent)
-    │ ×  de7155dbea42 (divergent)
-    ├─╯
-    │ ○  3a9b690d6e67 default@
-    ├─╯
-    ○  b853f7c8b006
-    ◆  000000000000
+    insta::assert_snapshot!(secondary_dir.read_file("file"), @r"
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -contents
+    +changed in main
+    +++++++ Contents of side #2
+    changed in second
+    >>>>>>> Conflict 1 of 1 ends
+    ");
+    let output = secondary_dir.run_jj(["workspace", "update-stale"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Attempted recovery, but the working copy is not stale
     [EOF]
     ");
-    // The stale working copy should have been resolved by the previous command
-    insta::assert_snapshot!(get_log_output(&secondary_dir), @r"
-    @  90f3d42e0bff secondary@ (divergent)
-    │ ×  de7155dbea42 (divergent)
-    ├─╯
-    │ ○  3a9b690d6e67 default@
+    let template =
+        r#"separate(" ", change_id.shortest(8), working_copies, if(divergent, "(divergent)"))"#;
+    let output = secondary_dir.run_jj(["log", "-T", template, "-r", "all()"]);
+    insta::assert_snapshot!(output, @r"
+    @  pmmvwywv secondary@
+    │ ○  mzvwutvl default@
     ├─╯
-    ○  b853f7c8b006
-    ◆  000000000000
+    ○  qpvuntsm
+    ◆  zzzzzzzz
     [EOF]
     ");
 }
@@ -643,7 +720,7 @@ fn test_workspaces_updated_by_other() {
     Working copy  (@) now at: pmmvwywv 90f3d42e (empty) (no description set)
     Parent commit (@-)      : qpvuntsm b853f7c8 (no description set)
     Added 0 files, modified 1 files, removed 0 files
-    Updated working copy to fresh commit 90f3d42e0bff
+    Updated working copy to fresh commit pmmvwywv 90f3d42e (empty) (no description set)
     [EOF]
     ");
     insta::assert_snapshot!(get_log_output(&secondary_dir),
@@ -715,7 +792,7 @@ fn test_workspaces_updated_by_other_automatic() {
     Working copy  (@) now at: pmmvwywv 90f3d42e (empty) (no description set)
     Parent commit (@-)      : qpvuntsm b853f7c8 (no description set)
     Added 0 files, modified 1 files, removed 0 files
-    Updated working copy to fresh commit 90f3d42e0bff
+    Updated working copy to fresh commit pmmvwywv 90f3d42e (empty) (no description set)
     [EOF]
     ");
 
@@ -939,6 +1016,41 @@ fn test_workspaces_update_stale_noop() {
     ");
 }
 
+/// Test "update-stale" when the working-copy operation was rewritten
+#[test]
+fn test_workspaces_update_stale_sibling_operation() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_dir = test_env.work_dir("main");
+
+    main_dir.write_file("file", "contents");
+    main_dir.run_jj(["commit", "-m", "commit 1"]).success();
+    main_dir.run_jj(["commit", "-m", "commit 2"]).success();
+
+    // Rewrite the operation the working copy was updated to, then update the
+    // working-copy commit on top of the rewritten operation.
+    main_dir
+        .run_jj(["op", "abandon", "@-", "--ignore-working-copy"])
+        .success();
+    main_dir
+        .run_jj(["new", "root()", "--ignore-working-copy"])
+        .success();
+
+    let output = main_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The working copy was updated at operation ca39fe8d59f6, which is not an ancestor of the current operation c3ac15a6fda2.
+    Hint: Run `jj workspace update-stale` to update it.
+    See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy for more information.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    main_dir.run_jj(["workspace", "update-stale"]).success();
+    let output = main_dir.run_jj(["file", "list"]);
+    insta::assert_snapshot!(output, @"");
+}
+
 /// Test "update-stale" in a dirty, but not stale working copy.
 #[test]
 fn test_workspaces_update_stale_snapshot() {
@@ -26,16 +26,51 @@ fn main() -> Result<()> {
     let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
     let protos_dir = root.join("src").join("protos");
 
-    prost_build::Config::new()
+    // Records of these packages can be dumped as and restored from JSON by
+    // `jj debug proto`. Bytes fields are represented as hex strings.
+    let serde_packages = [".git_store", ".op_store", ".working_copy"];
+    let hex_fields = [
+        ".git_store.Commit.change_id",
+        ".git_store.Commit.predecessors",
+        ".git_store.Commit.root_tree",
+        ".op_store.CommitPredecessors.commit_id",
+        ".op_store.CommitPredecessors.predecessor_ids",
+        ".op_store.GitRef.commit_id",
+        ".op_store.Operation.parents",
+        ".op_store.Operation.view_id",
+        ".op_store.RefConflict.Term.value",
+        ".op_store.RefConflictLegacy.adds",
+        ".op_store.RefConflictLegacy.removes",
+        ".op_store.RefTarget.value.commit_id",
+        ".op_store.View.git_head_legacy",
+        ".op_store.View.head_ids",
+        ".op_store.View.wc_commit_id",
+        ".op_store.View.wc_commit_ids",
+        ".working_copy.Checkout.operation_id",
+        ".working_copy.FileState.conflict_id",
+        ".working_copy.TreeState.legacy_tree_id",
+        ".working_copy.TreeState.tree_ids",
+    ];
+
+    let mut config = prost_build::Config::new();
+    config
         .out_dir(&protos_dir)
         .include_file("mod.rs")
         // For old protoc versions. 3.12.4 needs this, but 3.21.12 doesn't.
-        .protoc_arg("--experimental_allow_proto3_optional")
-        .compile_protos(
-            &input
-                .into_iter()
-                .map(|x| protos_dir.join(x))
-                .collect::<Vec<_>>(),
-            &[protos_dir],
-        )
+        .protoc_arg("--experimental_allow_proto3_optional");
+    for package in serde_packages {
+        config
+            .type_attribute(package, "#[derive(serde::Serialize, serde::Deserialize)]")
+            .message_attribute(package, "#[serde(default, deny_unknown_fields)]");
+    }
+    for field in hex_fields {
+        config.field_attribute(field, r#"#[serde(with = "crate::hex_util::serde_hex")]"#);
+    }
+    config.compile_protos(
+        &input
+            .into_iter()
+            .map(|x| protos_dir.join(x))
+            .collect::<Vec<_>>(),
+        &[protos_dir],
+    )
 }
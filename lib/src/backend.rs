@@ -414,6 +414,7 @@ impl Tree {
         self.entries.keys().map(|name| name.as_ref())
     }
 
+    /// Iterates over the entries in the order of their names.
     pub fn entries(&self) -> TreeEntriesNonRecursiveIterator<'_> {
         TreeEntriesNonRecursiveIterator {
             iter: self.entries.iter(),
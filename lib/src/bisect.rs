@@ -0,0 +1,164 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bisection of commit history to find the first bad commit.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use itertools::Itertools as _;
+use thiserror::Error;
+
+use crate::backend::BackendError;
+use crate::backend::CommitId;
+use crate::commit::Commit;
+use crate::repo::Repo;
+use crate::revset::ResolvedRevsetExpression;
+use crate::revset::RevsetEvaluationError;
+use crate::revset::RevsetExpression;
+
+/// Result of evaluating a commit during bisection.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Evaluation {
+    /// The commit doesn't have the property being searched for.
+    Good,
+    /// The commit has the property being searched for.
+    Bad,
+    /// The commit couldn't be evaluated.
+    Skip,
+}
+
+/// What to do next in a bisection.
+#[derive(Clone, Debug)]
+pub enum NextStep {
+    /// The given commit should be evaluated.
+    Evaluate(Commit),
+    /// The bisection is done.
+    Done(BisectionResult),
+}
+
+/// Outcome of a finished bisection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BisectionResult {
+    /// The first bad commit was found. There may be multiple if the bad
+    /// commits have no common first bad ancestor, e.g. in merged branches.
+    Found(Vec<Commit>),
+    /// Skipped commits prevented the bisection from narrowing down the first
+    /// bad commit. It's one of the given commits.
+    Indeterminate(Vec<Commit>),
+}
+
+/// Error that may occur during bisection.
+#[derive(Debug, Error)]
+pub enum BisectionError {
+    /// Error while contacting the Backend.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    /// Error resolving commit ancestry.
+    #[error(transparent)]
+    RevsetEvaluation(#[from] RevsetEvaluationError),
+}
+
+/// Finds the first bad commit between good and bad commits.
+///
+/// The candidates are the commits that are ancestors of a bad commit but not
+/// ancestors of a good commit. Each step picks the commit in the middle of the
+/// remaining candidates in the index order, which halves the candidates in
+/// linear history.
+pub struct Bisector<'repo> {
+    repo: &'repo dyn Repo,
+    good_commits: HashSet<CommitId>,
+    bad_commits: HashSet<CommitId>,
+    skipped_commits: HashSet<CommitId>,
+}
+
+impl<'repo> Bisector<'repo> {
+    /// Starts a bisection with the given known good and bad commits.
+    pub fn new(
+        repo: &'repo dyn Repo,
+        good_commits: impl IntoIterator<Item = CommitId>,
+        bad_commits: impl IntoIterator<Item = CommitId>,
+    ) -> Self {
+        Self {
+            repo,
+            good_commits: good_commits.into_iter().collect(),
+            bad_commits: bad_commits.into_iter().collect(),
+            skipped_commits: HashSet::new(),
+        }
+    }
+
+    /// Records the result of evaluating the commit.
+    pub fn mark(&mut self, id: CommitId, evaluation: Evaluation) {
+        let set = match evaluation {
+            Evaluation::Good => &mut self.good_commits,
+            Evaluation::Bad => &mut self.bad_commits,
+            Evaluation::Skip => &mut self.skipped_commits,
+        };
+        set.insert(id);
+    }
+
+    /// Commits known to be good.
+    pub fn good_commits(&self) -> &HashSet<CommitId> {
+        &self.good_commits
+    }
+
+    /// Commits known to be bad.
+    pub fn bad_commits(&self) -> &HashSet<CommitId> {
+        &self.bad_commits
+    }
+
+    /// Commits that couldn't be evaluated.
+    pub fn skipped_commits(&self) -> &HashSet<CommitId> {
+        &self.skipped_commits
+    }
+
+    /// Determines the next commit to evaluate, or the result if there are no
+    /// commits left to evaluate.
+    pub fn next_step(&self) -> Result<NextStep, BisectionError> {
+        let good = commits_expression(&self.good_commits);
+        let bad = commits_expression(&self.bad_commits);
+        let skipped = commits_expression(&self.skipped_commits);
+        let candidates = good.range(&bad);
+        let first_bad = candidates.intersection(&bad).roots();
+        let to_evaluate = candidates.minus(&first_bad.descendants()).minus(&skipped);
+        let to_evaluate_ids: Vec<_> = to_evaluate.evaluate(self.repo)?.iter().try_collect()?;
+        if let Some(id) = to_evaluate_ids.get(to_evaluate_ids.len() / 2) {
+            let commit = self.repo.store().get_commit(id)?;
+            return Ok(NextStep::Evaluate(commit));
+        }
+
+        // Skipped commits between the good commits and the first bad commits
+        // may be the actual first bad commits.
+        let possible_first_bad = candidates
+            .intersection(&bad.union(&skipped))
+            .minus(&first_bad.children().descendants());
+        let commits: Vec<_> = possible_first_bad
+            .evaluate(self.repo)?
+            .iter()
+            .map(|id| Ok::<_, BisectionError>(self.repo.store().get_commit(&id?)?))
+            .try_collect()?;
+        let is_determinate = commits
+            .iter()
+            .all(|commit| self.bad_commits.contains(commit.id()));
+        if is_determinate {
+            Ok(NextStep::Done(BisectionResult::Found(commits)))
+        } else {
+            Ok(NextStep::Done(BisectionResult::Indeterminate(commits)))
+        }
+    }
+}
+
+fn commits_expression(ids: &HashSet<CommitId>) -> Rc<ResolvedRevsetExpression> {
+    RevsetExpression::commits(ids.iter().cloned().collect())
+}
@@ -16,6 +16,8 @@
 
 use std::sync::Arc;
 
+use blake2::Blake2b512;
+use digest::Digest as _;
 use pollster::FutureExt as _;
 
 use crate::backend;
@@ -27,6 +29,7 @@ use crate::backend::MergedTreeId;
 use crate::backend::Signature;
 use crate::commit::is_backend_commit_empty;
 use crate::commit::Commit;
+use crate::content_hash::ContentHash as _;
 use crate::repo::MutableRepo;
 use crate::repo::Repo;
 use crate::settings::JJRng;
@@ -164,6 +167,10 @@ pub struct DetachedCommitBuilder {
     commit: backend::Commit,
     rewrite_source: Option<Commit>,
     sign_settings: SignSettings,
+    deterministic_change_ids: bool,
+    /// Whether to replace the change id with one derived from the commit
+    /// content when the commit is written.
+    derive_change_id: bool,
 }
 
 impl DetachedCommitBuilder {
@@ -195,6 +202,8 @@ impl DetachedCommitBuilder {
             commit,
             rewrite_source: None,
             sign_settings: settings.sign_settings(),
+            deterministic_change_ids: settings.deterministic_change_ids(),
+            derive_change_id: settings.deterministic_change_ids(),
         }
     }
 
@@ -238,6 +247,8 @@ impl DetachedCommitBuilder {
             rng: settings.get_rng(),
             rewrite_source: Some(predecessor.clone()),
             sign_settings: settings.sign_settings(),
+            deterministic_change_ids: settings.deterministic_change_ids(),
+            derive_change_id: false,
         }
     }
 
@@ -289,11 +300,17 @@ impl DetachedCommitBuilder {
 
     pub fn set_change_id(&mut self, change_id: ChangeId) -> &mut Self {
         self.commit.change_id = change_id;
+        self.derive_change_id = false;
         self
     }
 
+    /// Assigns a new change id to the commit.
+    ///
+    /// If `core.deterministic-change-ids` is enabled, the change id will be
+    /// derived from the commit content when the commit is written.
     pub fn generate_new_change_id(&mut self) -> &mut Self {
         self.commit.change_id = self.rng.new_change_id(self.store.change_id_length());
+        self.derive_change_id = self.deterministic_change_ids;
         self
     }
 
@@ -349,7 +366,10 @@ impl DetachedCommitBuilder {
     }
 
     /// Writes new commit and makes it visible in the `mut_repo`.
-    pub fn write(self, mut_repo: &mut MutableRepo) -> BackendResult<Commit> {
+    pub fn write(mut self, mut_repo: &mut MutableRepo) -> BackendResult<Commit> {
+        if self.derive_change_id {
+            self.commit.change_id = derive_change_id(&self.commit, self.store.change_id_length());
+        }
         let predecessors = self.commit.predecessors.clone();
         let commit = write_to_store(&self.store, self.commit, &self.sign_settings)?;
         // FIXME: Google's index.has_id() always returns true.
@@ -376,7 +396,11 @@ impl DetachedCommitBuilder {
     /// This does not consume the builder, so you can reuse the current
     /// configuration to create another commit later.
     pub fn write_hidden(&self) -> BackendResult<Commit> {
-        write_to_store(&self.store, self.commit.clone(), &self.sign_settings)
+        let mut commit = self.commit.clone();
+        if self.derive_change_id {
+            commit.change_id = derive_change_id(&commit, self.store.change_id_length());
+        }
+        write_to_store(&self.store, commit, &self.sign_settings)
     }
 
     /// Records the old commit as abandoned in the `mut_repo`.
@@ -396,6 +420,22 @@ impl DetachedCommitBuilder {
     }
 }
 
+/// Derives a change id from the content of a new commit.
+///
+/// Only the content is hashed, so the same commit gets the same change id
+/// regardless of who commits it and in which repo. Identical commits are told
+/// apart by their parents and predecessors, e.g. a duplicate of an existing
+/// commit gets a change id different from the original.
+fn derive_change_id(commit: &backend::Commit, length: usize) -> ChangeId {
+    let mut hasher = Blake2b512::default();
+    commit.parents.hash(&mut hasher);
+    commit.predecessors.hash(&mut hasher);
+    commit.root_tree.hash(&mut hasher);
+    commit.description.hash(&mut hasher);
+    commit.author.hash(&mut hasher);
+    ChangeId::new(hasher.finalize()[..length].to_vec())
+}
+
 fn write_to_store(
     store: &Arc<Store>,
     mut commit: backend::Commit,
@@ -60,8 +60,12 @@ pub struct ConfigResolutionContext<'a> {
 #[derive(Clone, Debug, Default, serde::Deserialize)]
 #[serde(default, rename_all = "kebab-case")]
 struct ScopeCondition {
-    /// Paths to match the repository path prefix.
+    /// Paths to match the repository path prefix. Entries prefixed with
+    /// `glob:` are moved to `repository_globs` when the paths are expanded.
     pub repositories: Option<Vec<PathBuf>>,
+    /// Glob patterns to match the repository path or any of its ancestors.
+    #[serde(skip)]
+    pub repository_globs: Vec<glob::Pattern>,
     /// Commands to match. Subcommands are matched space-separated.
     /// - `--when.commands = ["foo"]` -> matches "foo", "foo bar", "foo bar baz"
     /// - `--when.commands = ["foo bar"]` -> matches "foo bar", "foo bar baz",
@@ -89,11 +93,33 @@ impl ScopeCondition {
                 *path = new_path;
             }
         }
+        if let Some(repositories) = &mut self.repositories {
+            let mut prefixes = Vec::with_capacity(repositories.len());
+            for path in repositories.drain(..) {
+                match path.to_str().and_then(|s| s.strip_prefix("glob:")) {
+                    Some(pattern) => {
+                        let pattern = match expand_home(Path::new(pattern), context.home_dir)? {
+                            Some(new_path) => new_path
+                                .into_os_string()
+                                .into_string()
+                                .map_err(|_| "Invalid glob pattern (not valid UTF-8)")?,
+                            None => pattern.to_owned(),
+                        };
+                        let pattern = glob::Pattern::new(&pattern)
+                            .map_err(|_| "Invalid glob pattern in --when.repositories")?;
+                        self.repository_globs.push(pattern);
+                    }
+                    None => prefixes.push(path),
+                }
+            }
+            *repositories = prefixes;
+        }
         Ok(self)
     }
 
     fn matches(&self, context: &ConfigResolutionContext) -> bool {
-        matches_path_prefix(self.repositories.as_deref(), context.repo_path)
+        (matches_path_prefix(self.repositories.as_deref(), context.repo_path)
+            || matches_path_glob(&self.repository_globs, context.repo_path))
             && matches_command(self.commands.as_deref(), context.command)
     }
 }
@@ -116,6 +142,23 @@ fn matches_path_prefix(candidates: Option<&[PathBuf]>, actual: Option<&Path>) ->
     }
 }
 
+fn matches_path_glob(patterns: &[glob::Pattern], actual: Option<&Path>) -> bool {
+    // Don't let wildcards match the ".jj" directory of the repository itself.
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        require_literal_leading_dot: true,
+        ..Default::default()
+    };
+    let Some(actual) = actual else {
+        return false;
+    };
+    actual.ancestors().any(|path| {
+        patterns
+            .iter()
+            .any(|pattern| pattern.matches_path_with(path, options))
+    })
+}
+
 fn matches_command(candidates: Option<&[String]>, actual: Option<&str>) -> bool {
     match (candidates, actual) {
         (Some(candidates), Some(actual)) => candidates.iter().any(|candidate| {
@@ -443,6 +486,7 @@ mod tests {
     fn test_condition_repo_path() {
         let condition = ScopeCondition {
             repositories: Some(["/foo", "/bar"].map(PathBuf::from).into()),
+            repository_globs: vec![],
             commands: None,
         };
 
@@ -482,6 +526,7 @@ mod tests {
     fn test_condition_repo_path_windows() {
         let condition = ScopeCondition {
             repositories: Some(["c:/foo", r"d:\bar/baz"].map(PathBuf::from).into()),
+            repository_globs: vec![],
             commands: None,
         };
 
@@ -638,6 +683,64 @@ mod tests {
         insta::assert_snapshot!(resolved_config.layers()[1].data, @"a = 'a #1 baz'");
     }
 
+    #[test]
+    fn test_resolve_repo_path_glob() {
+        let mut source_config = StackedConfig::empty();
+        source_config.add_layer(new_user_layer(indoc! {"
+            a = 'a #0'
+            [[--scope]]
+            --when.repositories = ['glob:~/work/*']
+            a = 'a #0.1 work'
+            [[--scope]]
+            --when.repositories = ['/foo', 'glob:/bar/**/repo']
+            a = 'a #0.2 foo|bar'
+        "}));
+
+        let resolve_at = |repo_path: &str| {
+            let context = ConfigResolutionContext {
+                home_dir: Some(Path::new("/home/dir")),
+                repo_path: Some(Path::new(repo_path)),
+                command: None,
+            };
+            let resolved_config = resolve(&source_config, &context).unwrap();
+            resolved_config
+                .layers()
+                .iter()
+                .map(|layer| layer.data.get("a").unwrap().as_str().unwrap().to_owned())
+                .collect_vec()
+        };
+        assert_eq!(resolve_at("/home/dir/other/.jj/repo"), ["a #0"]);
+        assert_eq!(resolve_at("/home/dir/work/.jj/repo"), ["a #0"]);
+        assert_eq!(
+            resolve_at("/home/dir/work/proj/.jj/repo"),
+            ["a #0", "a #0.1 work"]
+        );
+        assert_eq!(
+            resolve_at("/home/dir/work/proj/sub/.jj/repo"),
+            ["a #0", "a #0.1 work"]
+        );
+        assert_eq!(resolve_at("/foo/.jj/repo"), ["a #0", "a #0.2 foo|bar"]);
+        assert_eq!(resolve_at("/bar/x/y/repo"), ["a #0", "a #0.2 foo|bar"]);
+        assert_eq!(resolve_at("/bar/x/y"), ["a #0"]);
+    }
+
+    #[test]
+    fn test_resolve_repo_path_invalid_glob() {
+        let mut source_config = StackedConfig::empty();
+        source_config.add_layer(new_user_layer(indoc! {"
+            --when.repositories = ['glob:/foo/[']
+        "}));
+        let context = ConfigResolutionContext {
+            home_dir: None,
+            repo_path: Some(Path::new("/foo/.jj/repo")),
+            command: None,
+        };
+        assert_matches!(
+            resolve(&source_config, &context),
+            Err(ConfigGetError::Type { name, .. }) if name == "--when"
+        );
+    }
+
     #[test]
     fn test_resolve_command() {
         let mut source_config = StackedConfig::empty();
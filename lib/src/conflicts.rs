@@ -800,6 +800,21 @@ pub fn parse_conflict(
     }
 }
 
+/// Returns true if the input contains any conflict start or end marker lines
+/// which are at least as long as the expected length.
+///
+/// This can be used to detect conflict markers which were left behind after
+/// the rest of a conflict was resolved, and which `parse_conflict()` would
+/// therefore treat as regular content.
+pub fn has_conflict_markers(input: &[u8], expected_marker_len: usize) -> bool {
+    input.lines_with_terminator().any(|line| {
+        matches!(
+            parse_conflict_marker(line, expected_marker_len),
+            Some(ConflictMarkerLineChar::ConflictStart | ConflictMarkerLineChar::ConflictEnd)
+        )
+    })
+}
+
 /// This method handles parsing both JJ-style and Git-style conflict markers,
 /// meaning that switching conflict marker styles won't prevent existing files
 /// with other conflict marker styles from being parsed successfully. The
@@ -0,0 +1,218 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bloom filters of the paths changed by each commit.
+//!
+//! Like the changed-path filters in Git's commit-graph, these let path-limited
+//! queries skip commits without diffing their trees. A filter may report false
+//! positives, but never false negatives.
+
+use std::collections::BTreeSet;
+
+use blake2::Blake2b512;
+use digest::Digest as _;
+use futures::StreamExt as _;
+use pollster::FutureExt as _;
+
+use crate::backend::BackendResult;
+use crate::backend::CommitId;
+use crate::commit::Commit;
+use crate::fileset::FilePattern;
+use crate::fileset::FilesetExpression;
+use crate::matchers::EverythingMatcher;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+
+/// Commits changing more paths than this don't get a filter. The filter would
+/// be large, and path-limited queries would likely match such commits anyway.
+const MAX_CHANGED_PATHS: usize = 512;
+
+/// Number of filter bits per changed path. Together with `NUM_HASHES`, this
+/// gives a false positive rate of about 1%.
+const BITS_PER_PATH: usize = 10;
+
+/// Number of bits set per path.
+const NUM_HASHES: u64 = 7;
+
+/// Borrowed bloom filter of the paths changed by a commit.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ChangedPathFilter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ChangedPathFilter<'a> {
+    /// Wraps serialized filter `data`. Returns `None` if the data is empty,
+    /// which means the filter wasn't computed.
+    pub fn from_bytes(data: &'a [u8]) -> Option<Self> {
+        (!data.is_empty()).then_some(ChangedPathFilter { data })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns false if `path` (a file or directory) definitely wasn't changed.
+    pub fn maybe_contains(&self, path: &RepoPath) -> bool {
+        let num_bits = self.data.len() as u64 * 8;
+        bit_indices(path, num_bits).all(|bit| self.data[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+}
+
+/// Computes the filter of the paths changed by `commit` relative to its
+/// parent. Returns empty data if no filter should be stored because the
+/// commit is a merge or changes too many paths.
+pub(super) fn compute_changed_path_filter(commit: &Commit) -> BackendResult<Vec<u8>> {
+    let [parent_id] = commit.parent_ids() else {
+        return Ok(vec![]);
+    };
+    let Some(paths) = collect_changed_paths(commit, parent_id)? else {
+        return Ok(vec![]);
+    };
+    Ok(build_filter(&paths))
+}
+
+fn collect_changed_paths(
+    commit: &Commit,
+    parent_id: &CommitId,
+) -> BackendResult<Option<BTreeSet<RepoPathBuf>>> {
+    let store = commit.store();
+    let from_tree = store.get_commit(parent_id)?.tree()?;
+    let to_tree = commit.tree()?;
+    let mut tree_diff = from_tree.diff_stream(&to_tree, &EverythingMatcher);
+    let mut paths = BTreeSet::new();
+    async {
+        while let Some(entry) = tree_diff.next().await {
+            entry.values?;
+            // Directories are added too so prefix queries can be answered.
+            for path in entry.path.ancestors().filter(|path| !path.is_root()) {
+                if !paths.insert(path.to_owned()) {
+                    break;
+                }
+            }
+            if paths.len() > MAX_CHANGED_PATHS {
+                return Ok(None);
+            }
+        }
+        Ok(Some(paths))
+    }
+    .block_on()
+}
+
+fn build_filter(paths: &BTreeSet<RepoPathBuf>) -> Vec<u8> {
+    // At least one byte so that a commit with no changes gets a filter.
+    let num_bytes = (paths.len() * BITS_PER_PATH).div_ceil(8).max(1);
+    let mut data = vec![0; num_bytes];
+    let num_bits = num_bytes as u64 * 8;
+    for path in paths {
+        for bit in bit_indices(path, num_bits) {
+            data[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+    data
+}
+
+fn bit_indices(path: &RepoPath, num_bits: u64) -> impl Iterator<Item = usize> {
+    let digest = Blake2b512::digest(path.as_internal_file_string().as_bytes());
+    let h1 = u64::from_le_bytes(digest[0..8].try_into().unwrap());
+    let h2 = u64::from_le_bytes(digest[8..16].try_into().unwrap());
+    (0..NUM_HASHES).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits) as usize)
+}
+
+/// Returns paths of which at least one must have changed for a diff to match
+/// `expr`. Returns `None` if there's no such set of paths.
+pub(super) fn required_changed_paths(expr: &FilesetExpression) -> Option<Vec<RepoPathBuf>> {
+    match expr {
+        FilesetExpression::None => Some(vec![]),
+        FilesetExpression::All => None,
+        FilesetExpression::Pattern(pattern) => {
+            let path = match pattern {
+                FilePattern::FilePath(path) | FilePattern::PrefixPath(path) => path,
+                FilePattern::FileGlob { dir, .. } => dir,
+            };
+            (!path.is_root()).then(|| vec![path.clone()])
+        }
+        FilesetExpression::UnionAll(exprs) => {
+            let paths = exprs
+                .iter()
+                .map(required_changed_paths)
+                .collect::<Option<Vec<_>>>()?;
+            Some(paths.concat())
+        }
+        FilesetExpression::Intersection(expr1, expr2) => {
+            required_changed_paths(expr1).or_else(|| required_changed_paths(expr2))
+        }
+        FilesetExpression::Difference(expr1, _) => required_changed_paths(expr1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo_path(value: &str) -> &RepoPath {
+        RepoPath::from_internal_string(value).unwrap()
+    }
+
+    #[test]
+    fn test_filter_contains_inserted_paths() {
+        let paths: BTreeSet<_> = ["dir", "dir/file", "other"]
+            .into_iter()
+            .map(|value| repo_path(value).to_owned())
+            .collect();
+        let data = build_filter(&paths);
+        let filter = ChangedPathFilter::from_bytes(&data).unwrap();
+        for path in &paths {
+            assert!(filter.maybe_contains(path));
+        }
+        assert!(!filter.maybe_contains(repo_path("dir/unchanged")));
+    }
+
+    #[test]
+    fn test_filter_of_unchanged_commit() {
+        let data = build_filter(&BTreeSet::new());
+        let filter = ChangedPathFilter::from_bytes(&data).unwrap();
+        assert!(!filter.maybe_contains(repo_path("file")));
+        assert!(ChangedPathFilter::from_bytes(&[]).is_none());
+    }
+
+    #[test]
+    fn test_required_changed_paths() {
+        let file = || FilesetExpression::file_path(repo_path("dir/file").to_owned());
+        let prefix = || FilesetExpression::prefix_path(repo_path("other").to_owned());
+        assert_eq!(
+            required_changed_paths(&FilesetExpression::union_all(vec![file(), prefix()])),
+            Some(vec![
+                repo_path("dir/file").to_owned(),
+                repo_path("other").to_owned(),
+            ])
+        );
+        assert_eq!(
+            required_changed_paths(&file().difference(prefix())),
+            Some(vec![repo_path("dir/file").to_owned()])
+        );
+        assert_eq!(
+            required_changed_paths(&FilesetExpression::all().intersection(prefix())),
+            Some(vec![repo_path("other").to_owned()])
+        );
+        assert_eq!(
+            required_changed_paths(&FilesetExpression::none()),
+            Some(vec![])
+        );
+        assert_eq!(required_changed_paths(&FilesetExpression::all()), None);
+        assert_eq!(
+            required_changed_paths(&FilesetExpression::prefix_path(RepoPathBuf::root())),
+            None
+        );
+    }
+}
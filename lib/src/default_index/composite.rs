@@ -29,6 +29,7 @@ use itertools::Itertools as _;
 use ref_cast::ref_cast_custom;
 use ref_cast::RefCastCustom;
 
+use super::changed_path::ChangedPathFilter;
 use super::entry::IndexEntry;
 use super::entry::IndexPosition;
 use super::entry::LocalPosition;
@@ -82,6 +83,10 @@ pub(super) trait IndexSegment: Send + Sync {
         prefix: &HexPrefix,
     ) -> PrefixResolution<(ChangeId, SmallLocalPositionsVec)>;
 
+    /// Returns the local positions of the entries associated with the given
+    /// `change_id`, sorted in ascending order.
+    fn change_id_to_positions(&self, change_id: &ChangeId) -> Option<SmallLocalPositionsVec>;
+
     fn generation_number(&self, local_pos: LocalPosition) -> u32;
 
     fn commit_id(&self, local_pos: LocalPosition) -> CommitId;
@@ -91,6 +96,12 @@ pub(super) trait IndexSegment: Send + Sync {
     fn num_parents(&self, local_pos: LocalPosition) -> u32;
 
     fn parent_positions(&self, local_pos: LocalPosition) -> SmallIndexPositionsVec;
+
+    fn changed_path_filter(&self, local_pos: LocalPosition) -> Option<ChangedPathFilter<'_>>;
+
+    /// Returns the local positions of the entries which have the given global
+    /// `parent_pos` as a parent, sorted in ascending order.
+    fn child_positions(&self, parent_pos: IndexPosition) -> SmallLocalPositionsVec;
 }
 
 pub(super) type DynIndexSegment = dyn IndexSegment;
@@ -288,6 +299,51 @@ impl CompositeIndex {
             })
     }
 
+    /// Looks up the entries associated with the given `change_id`. The
+    /// returned entries may be hidden.
+    ///
+    /// The returned index positions are sorted in ascending order.
+    pub(super) fn resolve_change_id(&self, change_id: &ChangeId) -> SmallIndexPositionsVec {
+        let mut positions = SmallIndexPositionsVec::new();
+        // Parent segments contain smaller positions, so insert them first.
+        for segment in self.ancestor_index_segments() {
+            if let Some(local_positions) = segment.change_id_to_positions(change_id) {
+                let num_parent_commits = segment.num_parent_commits();
+                positions.insert_many(
+                    0,
+                    local_positions
+                        .into_iter()
+                        .map(|LocalPosition(pos)| IndexPosition(pos + num_parent_commits)),
+                );
+            }
+        }
+        positions
+    }
+
+    /// Looks up the entries which have the entry at `pos` as a parent. The
+    /// returned entries may be hidden.
+    ///
+    /// The returned index positions are sorted in ascending order.
+    pub(super) fn children_positions(&self, pos: IndexPosition) -> SmallIndexPositionsVec {
+        let mut positions = SmallIndexPositionsVec::new();
+        // Children are indexed after the parent, so segments which end before
+        // the parent can't contain them. Parent segments contain smaller
+        // positions, so insert them first.
+        for segment in self.ancestor_index_segments().take_while(|segment| {
+            segment.num_parent_commits() + segment.num_local_commits() > pos.0
+        }) {
+            let num_parent_commits = segment.num_parent_commits();
+            positions.insert_many(
+                0,
+                segment
+                    .child_positions(pos)
+                    .into_iter()
+                    .map(|LocalPosition(pos)| IndexPosition(pos + num_parent_commits)),
+            );
+        }
+        positions
+    }
+
     pub(super) fn is_ancestor_pos(
         &self,
         ancestor_pos: IndexPosition,
@@ -586,6 +642,20 @@ impl<I: AsCompositeIndex + Send + Sync> ChangeIdIndex for ChangeIdIndexImpl<I> {
         }
     }
 
+    fn resolve_change_id(&self, change_id: &ChangeId) -> Option<Vec<CommitId>> {
+        let index = self.index.as_composite();
+        let positions = index.resolve_change_id(change_id);
+        let first_pos = *positions.first()?;
+        let mut reachable_set = self.reachable_set.lock().unwrap();
+        reachable_set.visit_until(index, first_pos);
+        let reachable_commit_ids = positions
+            .iter()
+            .filter(|&&pos| reachable_set.contains(pos))
+            .map(|&pos| index.entry_by_pos(pos).commit_id())
+            .collect_vec();
+        (!reachable_commit_ids.is_empty()).then_some(reachable_commit_ids)
+    }
+
     // Calculates the shortest prefix length of the given `change_id` among all
     // IDs, including hidden entries.
     //
@@ -21,6 +21,7 @@ use std::hash::Hasher;
 
 use smallvec::SmallVec;
 
+use super::changed_path::ChangedPathFilter;
 use super::composite::CompositeIndex;
 use super::composite::DynIndexSegment;
 use crate::backend::ChangeId;
@@ -120,4 +121,10 @@ impl<'a> IndexEntry<'a> {
             .into_iter()
             .map(move |pos| composite.entry_by_pos(pos))
     }
+
+    /// Returns the bloom filter of the paths changed from the parent, if
+    /// available.
+    pub(super) fn changed_path_filter(&self) -> Option<ChangedPathFilter<'a>> {
+        self.source.changed_path_filter(self.local_pos)
+    }
 }
@@ -21,6 +21,7 @@
 
 #![allow(missing_docs)]
 
+mod changed_path;
 mod composite;
 mod entry;
 mod mutable;
@@ -279,6 +280,25 @@ mod tests {
             entry_5.parents().nth(1).unwrap().position(),
             IndexPosition(2)
         );
+        // Children can be looked up across segments
+        assert_eq!(
+            index.children_positions(IndexPosition(0)),
+            smallvec_inline![IndexPosition(1), IndexPosition(2)]
+        );
+        assert_eq!(
+            index.children_positions(IndexPosition(1)),
+            smallvec_inline![IndexPosition(4)]
+        );
+        assert_eq!(
+            index.children_positions(IndexPosition(2)),
+            smallvec_inline![IndexPosition(3), IndexPosition(5)]
+        );
+        assert!(index.children_positions(IndexPosition(3)).is_empty());
+        assert_eq!(
+            index.children_positions(IndexPosition(4)),
+            smallvec_inline![IndexPosition(5)]
+        );
+        assert!(index.children_positions(IndexPosition(5)).is_empty());
     }
 
     #[test_case(false; "in memory")]
@@ -770,6 +790,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_change_id() {
+        let temp_dir = new_temp_dir();
+        let mut new_commit_id = commit_id_generator();
+        let local_positions_vec = |positions: &[u32]| -> SmallLocalPositionsVec {
+            positions.iter().copied().map(LocalPosition).collect()
+        };
+        let index_positions_vec = |positions: &[u32]| -> SmallIndexPositionsVec {
+            positions.iter().copied().map(IndexPosition).collect()
+        };
+
+        let id_0 = ChangeId::from_hex("00000001");
+        let id_1 = ChangeId::from_hex("00999999");
+        let id_2 = ChangeId::from_hex("05548888");
+        let id_3 = ChangeId::from_hex("05544444");
+
+        let mut mutable_segment = MutableIndexSegment::full(16, 4);
+        mutable_segment.add_commit_data(new_commit_id(), id_0.clone(), &[]);
+        mutable_segment.add_commit_data(new_commit_id(), id_1.clone(), &[]);
+        mutable_segment.add_commit_data(new_commit_id(), id_1.clone(), &[]);
+        mutable_segment.add_commit_data(new_commit_id(), id_2.clone(), &[]);
+
+        let initial_file = mutable_segment.save_in(temp_dir.path()).unwrap();
+        mutable_segment = MutableIndexSegment::incremental(initial_file.clone());
+
+        mutable_segment.add_commit_data(new_commit_id(), id_3.clone(), &[]);
+        mutable_segment.add_commit_data(new_commit_id(), id_1.clone(), &[]);
+
+        // Local lookup
+        assert_eq!(
+            initial_file.change_id_to_positions(&id_1),
+            Some(local_positions_vec(&[1, 2]))
+        );
+        assert_eq!(
+            initial_file.change_id_to_positions(&id_2),
+            Some(local_positions_vec(&[3]))
+        );
+        assert_eq!(initial_file.change_id_to_positions(&id_3), None);
+        assert_eq!(
+            mutable_segment.change_id_to_positions(&id_1),
+            Some(local_positions_vec(&[1]))
+        );
+        assert_eq!(mutable_segment.change_id_to_positions(&id_0), None);
+
+        // Global lookup
+        let index = mutable_segment.as_composite();
+        assert_eq!(index.resolve_change_id(&id_0), index_positions_vec(&[0]));
+        assert_eq!(
+            index.resolve_change_id(&id_1),
+            index_positions_vec(&[1, 2, 5])
+        );
+        assert_eq!(index.resolve_change_id(&id_3), index_positions_vec(&[4]));
+        // Prefix of an existing id isn't matched
+        assert_eq!(
+            index.resolve_change_id(&ChangeId::from_hex("0554")),
+            index_positions_vec(&[])
+        );
+    }
+
     #[test]
     fn neighbor_change_ids() {
         let temp_dir = new_temp_dir();
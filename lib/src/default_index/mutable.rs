@@ -31,6 +31,8 @@ use smallvec::smallvec;
 use smallvec::SmallVec;
 use tempfile::NamedTempFile;
 
+use super::changed_path::compute_changed_path_filter;
+use super::changed_path::ChangedPathFilter;
 use super::composite::AsCompositeIndex;
 use super::composite::ChangeIdIndexImpl;
 use super::composite::CompositeIndex;
@@ -44,6 +46,7 @@ use super::readonly::DefaultReadonlyIndex;
 use super::readonly::ReadonlyIndexSegment;
 use super::readonly::INDEX_SEGMENT_FILE_FORMAT_VERSION;
 use super::readonly::OVERFLOW_FLAG;
+use crate::backend::BackendResult;
 use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::commit::Commit;
@@ -69,6 +72,7 @@ struct MutableGraphEntry {
     change_id: ChangeId,
     generation_number: u32,
     parent_positions: SmallIndexPositionsVec,
+    changed_path_filter: Vec<u8>,
 }
 
 pub(super) struct MutableIndexSegment {
@@ -79,6 +83,8 @@ pub(super) struct MutableIndexSegment {
     graph: Vec<MutableGraphEntry>,
     commit_lookup: BTreeMap<CommitId, LocalPosition>,
     change_lookup: BTreeMap<ChangeId, SmallLocalPositionsVec>,
+    children_lookup: BTreeMap<IndexPosition, SmallLocalPositionsVec>,
+    changed_path_filters: bool,
 }
 
 impl MutableIndexSegment {
@@ -91,6 +97,8 @@ impl MutableIndexSegment {
             graph: vec![],
             commit_lookup: BTreeMap::new(),
             change_lookup: BTreeMap::new(),
+            children_lookup: BTreeMap::new(),
+            changed_path_filters: false,
         }
     }
 
@@ -106,6 +114,8 @@ impl MutableIndexSegment {
             graph: vec![],
             commit_lookup: BTreeMap::new(),
             change_lookup: BTreeMap::new(),
+            children_lookup: BTreeMap::new(),
+            changed_path_filters: false,
         }
     }
 
@@ -113,19 +123,41 @@ impl MutableIndexSegment {
         CompositeIndex::new(self)
     }
 
-    pub(super) fn add_commit(&mut self, commit: &Commit) {
-        self.add_commit_data(
+    pub(super) fn add_commit(&mut self, commit: &Commit) -> BackendResult<()> {
+        // Don't compute the filter if the commit is already indexed.
+        if self.as_composite().has_id(commit.id()) {
+            return Ok(());
+        }
+        let changed_path_filter = if self.changed_path_filters {
+            compute_changed_path_filter(commit)?
+        } else {
+            vec![]
+        };
+        self.add_commit_data_with_filter(
             commit.id().clone(),
             commit.change_id().clone(),
             commit.parent_ids(),
+            changed_path_filter,
         );
+        Ok(())
     }
 
+    #[cfg(test)]
     pub(super) fn add_commit_data(
         &mut self,
         commit_id: CommitId,
         change_id: ChangeId,
         parent_ids: &[CommitId],
+    ) {
+        self.add_commit_data_with_filter(commit_id, change_id, parent_ids, vec![]);
+    }
+
+    fn add_commit_data_with_filter(
+        &mut self,
+        commit_id: CommitId,
+        change_id: ChangeId,
+        parent_ids: &[CommitId],
+        changed_path_filter: Vec<u8>,
     ) {
         if self.as_composite().has_id(&commit_id) {
             return;
@@ -135,6 +167,7 @@ impl MutableIndexSegment {
             change_id,
             generation_number: 0,
             parent_positions: SmallVec::new(),
+            changed_path_filter,
         };
         for parent_id in parent_ids {
             let parent_entry = self
@@ -155,6 +188,13 @@ impl MutableIndexSegment {
             // positions are inherently sorted
             .and_modify(|positions| positions.push(local_pos))
             .or_insert(smallvec![local_pos]);
+        for &parent_pos in &entry.parent_positions {
+            self.children_lookup
+                .entry(parent_pos)
+                // positions are inherently sorted
+                .or_default()
+                .push(local_pos);
+        }
         self.graph.push(entry);
     }
 
@@ -163,7 +203,15 @@ impl MutableIndexSegment {
         for pos in other_segment.num_parent_commits()..other.num_commits() {
             let entry = other.entry_by_pos(IndexPosition(pos));
             let parent_ids = entry.parents().map(|entry| entry.commit_id()).collect_vec();
-            self.add_commit_data(entry.commit_id(), entry.change_id(), &parent_ids);
+            let changed_path_filter = entry
+                .changed_path_filter()
+                .map_or_else(Vec::new, |filter| filter.as_bytes().to_vec());
+            self.add_commit_data_with_filter(
+                entry.commit_id(),
+                entry.change_id(),
+                &parent_ids,
+                changed_path_filter,
+            );
         }
     }
 
@@ -229,6 +277,14 @@ impl MutableIndexSegment {
         buf.extend(0_u32.to_le_bytes());
         let change_overflow_offset = buf.len();
         buf.extend(0_u32.to_le_bytes());
+        let num_changed_path_bytes: usize = self
+            .graph
+            .iter()
+            .map(|entry| entry.changed_path_filter.len())
+            .sum();
+        buf.extend(u32::try_from(num_changed_path_bytes).unwrap().to_le_bytes());
+        let num_child_edges: usize = self.children_lookup.values().map(|v| v.len()).sum();
+        buf.extend(u32::try_from(num_child_edges).unwrap().to_le_bytes());
 
         // Positions of change ids in the sorted table
         let change_id_pos_map: HashMap<&ChangeId, u32> = self
@@ -313,6 +369,22 @@ impl MutableIndexSegment {
         for LocalPosition(pos) in change_overflow {
             buf.extend(pos.to_le_bytes());
         }
+
+        let mut changed_path_end = 0_u32;
+        for entry in &self.graph {
+            changed_path_end += u32::try_from(entry.changed_path_filter.len()).unwrap();
+            buf.extend(changed_path_end.to_le_bytes());
+        }
+        for entry in &self.graph {
+            buf.extend_from_slice(&entry.changed_path_filter);
+        }
+
+        for (IndexPosition(parent_pos), child_positions) in &self.children_lookup {
+            for LocalPosition(child_pos) in child_positions {
+                buf.extend(parent_pos.to_le_bytes());
+                buf.extend(child_pos.to_le_bytes());
+            }
+        }
     }
 
     /// If the MutableIndex has more than half the commits of its parent
@@ -431,6 +503,10 @@ impl IndexSegment for MutableIndexSegment {
             .map(|(id, positions)| (id.clone(), positions.clone()))
     }
 
+    fn change_id_to_positions(&self, change_id: &ChangeId) -> Option<SmallLocalPositionsVec> {
+        self.change_lookup.get(change_id).cloned()
+    }
+
     fn generation_number(&self, local_pos: LocalPosition) -> u32 {
         self.graph[local_pos.0 as usize].generation_number
     }
@@ -454,6 +530,17 @@ impl IndexSegment for MutableIndexSegment {
     fn parent_positions(&self, local_pos: LocalPosition) -> SmallIndexPositionsVec {
         self.graph[local_pos.0 as usize].parent_positions.clone()
     }
+
+    fn changed_path_filter(&self, local_pos: LocalPosition) -> Option<ChangedPathFilter<'_>> {
+        ChangedPathFilter::from_bytes(&self.graph[local_pos.0 as usize].changed_path_filter)
+    }
+
+    fn child_positions(&self, parent_pos: IndexPosition) -> SmallLocalPositionsVec {
+        self.children_lookup
+            .get(&parent_pos)
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 /// In-memory mutable records for the on-disk commit index backend.
@@ -470,6 +557,17 @@ impl DefaultMutableIndex {
         DefaultMutableIndex(mutable_segment)
     }
 
+    /// Sets whether the changed-path filters should be computed for commits
+    /// added by [`MutableIndex::add_commit()`].
+    pub(super) fn with_changed_path_filters(mut self, enabled: bool) -> Self {
+        self.0.changed_path_filters = enabled;
+        self
+    }
+
+    pub(super) fn add_commit(&mut self, commit: &Commit) -> BackendResult<()> {
+        self.0.add_commit(commit)
+    }
+
     #[cfg(test)]
     pub(crate) fn add_commit_data(
         &mut self,
@@ -555,8 +653,10 @@ impl MutableIndex for DefaultMutableIndex {
         Box::new(ChangeIdIndexImpl::new(self, heads))
     }
 
-    fn add_commit(&mut self, commit: &Commit) {
-        self.0.add_commit(commit);
+    fn add_commit(&mut self, commit: &Commit) -> Result<(), IndexError> {
+        self.0
+            .add_commit(commit)
+            .map_err(|err| IndexError(err.into()))
     }
 
     fn merge_in(&mut self, other: &dyn ReadonlyIndex) {
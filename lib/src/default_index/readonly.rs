@@ -21,12 +21,18 @@ use std::fmt::Formatter;
 use std::fs::File;
 use std::io;
 use std::io::Read;
+use std::io::Seek as _;
+use std::io::SeekFrom;
+use std::ops::Range;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::OnceLock;
 
 use smallvec::smallvec;
 use thiserror::Error;
 
+use super::changed_path::ChangedPathFilter;
 use super::composite::AsCompositeIndex;
 use super::composite::ChangeIdIndexImpl;
 use super::composite::CompositeIndex;
@@ -104,7 +110,7 @@ impl ReadonlyIndexLoadError {
 }
 
 /// Current format version of the index segment file.
-pub(crate) const INDEX_SEGMENT_FILE_FORMAT_VERSION: u32 = 6;
+pub(crate) const INDEX_SEGMENT_FILE_FORMAT_VERSION: u32 = 8;
 
 /// If set, the value is stored in the overflow table.
 pub(crate) const OVERFLOW_FLAG: u32 = 0x8000_0000;
@@ -186,6 +192,8 @@ impl CommitGraphEntry<'_> {
 /// u32: number of local change ids
 /// u32: number of overflow parent entries
 /// u32: number of overflow change id positions
+/// u32: number of changed-path filter bytes
+/// u32: number of child edges
 /// for each entry, in some topological order with parents first:
 ///   u32: generation number
 ///   if number of parents <= 2:
@@ -211,6 +219,15 @@ impl CommitGraphEntry<'_> {
 ///   u32: global index position
 /// for each overflow change id entry:
 ///   u32: local position in the graph entries table
+/// for each entry, in the graph entries order:
+///   u32: end offset of the changed-path filter (the start offset is the end
+///        offset of the previous entry, or 0)
+/// for each entry, in the graph entries order:
+///   <length number of bytes>: bloom filter of the paths changed from the
+///                             parent (empty if not computed)
+/// for each parent-child edge of the entries, sorted by parent then child:
+///   u32: global index position of the parent
+///   u32: local position of the child in the graph entries table
 /// ```
 ///
 /// Note that u32 fields are 4-byte aligned so long as the parent file name
@@ -228,13 +245,92 @@ pub(super) struct ReadonlyIndexSegment {
     num_local_commits: u32,
     num_local_change_ids: u32,
     num_change_overflow_entries: u32,
+    num_child_edges: u32,
     // Base data offsets in bytes:
     commit_lookup_base: usize,
     change_id_table_base: usize,
     change_pos_table_base: usize,
     parent_overflow_base: usize,
     change_overflow_base: usize,
-    data: Vec<u8>,
+    changed_path_pos_base: usize,
+    changed_path_data_base: usize,
+    child_edge_base: usize,
+    data: SegmentData,
+}
+
+/// Table in the local entries of segment file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SegmentTable {
+    Graph,
+    CommitLookup,
+    ChangeId,
+    ChangePos,
+    ParentOverflow,
+    ChangeOverflow,
+    ChangedPathPos,
+    ChangedPathData,
+    ChildEdge,
+}
+
+const NUM_SEGMENT_TABLES: usize = 9;
+
+/// Number of table records (or commits for the changed-path data table) read
+/// at once from lazily-loaded segments.
+const LAZY_PAGE_RECORDS: usize = 1024;
+
+/// Local entries of segment file.
+enum SegmentData {
+    /// All tables are read into memory when the segment is loaded.
+    Loaded(Vec<u8>),
+    /// Pages of tables are read from the file when first accessed.
+    Lazy(Box<LazySegmentData>),
+}
+
+/// Pages of a table, each of which is filled on first access.
+type LazyTablePages = Box<[OnceLock<Box<[u8]>>]>;
+
+struct LazySegmentData {
+    /// Path to the segment file, which is reopened for each read.
+    path: PathBuf,
+    /// File offset of the local entries.
+    offset: u64,
+    pages: [LazyTablePages; NUM_SEGMENT_TABLES],
+}
+
+impl LazySegmentData {
+    fn read(&self, range: Range<usize>) -> io::Result<Box<[u8]>> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.offset + range.start as u64))?;
+        let mut buf = vec![0; range.len()].into_boxed_slice();
+        file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Returns the `page_index`-th page of the `table`, reading the data
+    /// `range` if the page isn't loaded yet.
+    fn page(
+        &self,
+        table: SegmentTable,
+        page_index: usize,
+        range: Range<usize>,
+    ) -> io::Result<&[u8]> {
+        let cell = &self.pages[table as usize][page_index];
+        if let Some(page) = cell.get() {
+            return Ok(page);
+        }
+        let page = self.read(range)?;
+        Ok(cell.get_or_init(|| page))
+    }
+}
+
+/// Numbers of the local entries stored in the segment file header.
+struct LocalEntriesHeader {
+    num_local_commits: u32,
+    num_local_change_ids: u32,
+    num_parent_overflow_entries: u32,
+    num_change_overflow_entries: u32,
+    num_changed_path_bytes: u32,
+    num_child_edges: u32,
 }
 
 impl Debug for ReadonlyIndexSegment {
@@ -248,24 +344,29 @@ impl Debug for ReadonlyIndexSegment {
 
 impl ReadonlyIndexSegment {
     /// Loads both parent segments and local entries from the given file `name`.
+    ///
+    /// If `lazy` is true, the tables of the local entries are read from the
+    /// file when they're first accessed.
     pub(super) fn load(
         dir: &Path,
         name: String,
         commit_id_length: usize,
         change_id_length: usize,
+        lazy: bool,
     ) -> Result<Arc<ReadonlyIndexSegment>, ReadonlyIndexLoadError> {
-        let mut file = File::open(dir.join(&name))
+        let file = File::open(dir.join(&name))
             .map_err(|err| ReadonlyIndexLoadError::from_io_err(&name, err))?;
-        Self::load_from(&mut file, dir, name, commit_id_length, change_id_length)
+        Self::load_from(file, dir, name, commit_id_length, change_id_length, lazy)
     }
 
     /// Loads both parent segments and local entries from the given `file`.
-    pub(super) fn load_from(
-        file: &mut dyn Read,
+    fn load_from(
+        mut file: File,
         dir: &Path,
         name: String,
         commit_id_length: usize,
         change_id_length: usize,
+        lazy: bool,
     ) -> Result<Arc<ReadonlyIndexSegment>, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err(&name, err);
         let read_u32 = |file: &mut dyn Read| {
@@ -273,14 +374,14 @@ impl ReadonlyIndexSegment {
             file.read_exact(&mut buf).map_err(from_io_err)?;
             Ok(u32::from_le_bytes(buf))
         };
-        let format_version = read_u32(file)?;
+        let format_version = read_u32(&mut file)?;
         if format_version != INDEX_SEGMENT_FILE_FORMAT_VERSION {
             return Err(ReadonlyIndexLoadError::UnexpectedVersion {
                 found_version: format_version,
                 expected_version: INDEX_SEGMENT_FILE_FORMAT_VERSION,
             });
         }
-        let parent_filename_len = read_u32(file)?;
+        let parent_filename_len = read_u32(&mut file)?;
         let maybe_parent_file = if parent_filename_len > 0 {
             let mut parent_filename_bytes = vec![0; parent_filename_len as usize];
             file.read_exact(&mut parent_filename_bytes)
@@ -293,17 +394,38 @@ impl ReadonlyIndexSegment {
                 parent_filename,
                 commit_id_length,
                 change_id_length,
+                lazy,
             )?;
             Some(parent_file)
         } else {
             None
         };
-        Self::load_with_parent_file(
-            file,
+        if !lazy {
+            return Self::load_with_parent_file(
+                &mut file,
+                name,
+                maybe_parent_file,
+                commit_id_length,
+                change_id_length,
+            );
+        }
+        let header = Self::read_local_entries_header(&mut file, &name)?;
+        let offset = file.stream_position().map_err(from_io_err)?;
+        let file_len = file.metadata().map_err(from_io_err)?.len();
+        let data_len = file_len.saturating_sub(offset) as usize;
+        let data = SegmentData::Lazy(Box::new(LazySegmentData {
+            path: dir.join(&name),
+            offset,
+            pages: Default::default(),
+        }));
+        Self::new(
             name,
             maybe_parent_file,
             commit_id_length,
             change_id_length,
+            header,
+            data,
+            data_len,
         )
     }
 
@@ -316,21 +438,62 @@ impl ReadonlyIndexSegment {
         commit_id_length: usize,
         change_id_length: usize,
     ) -> Result<Arc<ReadonlyIndexSegment>, ReadonlyIndexLoadError> {
-        let from_io_err = |err| ReadonlyIndexLoadError::from_io_err(&name, err);
+        let header = Self::read_local_entries_header(file, &name)?;
+        let mut data = vec![];
+        file.read_to_end(&mut data)
+            .map_err(|err| ReadonlyIndexLoadError::from_io_err(&name, err))?;
+        let data_len = data.len();
+        Self::new(
+            name,
+            parent_file,
+            commit_id_length,
+            change_id_length,
+            header,
+            SegmentData::Loaded(data),
+            data_len,
+        )
+    }
+
+    fn read_local_entries_header(
+        file: &mut dyn Read,
+        name: &str,
+    ) -> Result<LocalEntriesHeader, ReadonlyIndexLoadError> {
         let read_u32 = |file: &mut dyn Read| {
             let mut buf = [0; 4];
-            file.read_exact(&mut buf).map_err(from_io_err)?;
+            file.read_exact(&mut buf)
+                .map_err(|err| ReadonlyIndexLoadError::from_io_err(name, err))?;
             Ok(u32::from_le_bytes(buf))
         };
+        Ok(LocalEntriesHeader {
+            num_local_commits: read_u32(file)?,
+            num_local_change_ids: read_u32(file)?,
+            num_parent_overflow_entries: read_u32(file)?,
+            num_change_overflow_entries: read_u32(file)?,
+            num_changed_path_bytes: read_u32(file)?,
+            num_child_edges: read_u32(file)?,
+        })
+    }
+
+    fn new(
+        name: String,
+        parent_file: Option<Arc<ReadonlyIndexSegment>>,
+        commit_id_length: usize,
+        change_id_length: usize,
+        header: LocalEntriesHeader,
+        data: SegmentData,
+        data_len: usize,
+    ) -> Result<Arc<ReadonlyIndexSegment>, ReadonlyIndexLoadError> {
+        let LocalEntriesHeader {
+            num_local_commits,
+            num_local_change_ids,
+            num_parent_overflow_entries,
+            num_change_overflow_entries,
+            num_changed_path_bytes,
+            num_child_edges,
+        } = header;
         let num_parent_commits = parent_file
             .as_ref()
             .map_or(0, |segment| segment.as_composite().num_commits());
-        let num_local_commits = read_u32(file)?;
-        let num_local_change_ids = read_u32(file)?;
-        let num_parent_overflow_entries = read_u32(file)?;
-        let num_change_overflow_entries = read_u32(file)?;
-        let mut data = vec![];
-        file.read_to_end(&mut data).map_err(from_io_err)?;
 
         let commit_graph_entry_size = CommitGraphEntry::size(commit_id_length);
         let graph_size = (num_local_commits as usize) * commit_graph_entry_size;
@@ -339,6 +502,9 @@ impl ReadonlyIndexSegment {
         let change_pos_table_size = (num_local_change_ids as usize) * 4;
         let parent_overflow_size = (num_parent_overflow_entries as usize) * 4;
         let change_overflow_size = (num_change_overflow_entries as usize) * 4;
+        let changed_path_pos_size = (num_local_commits as usize) * 4;
+        let changed_path_data_size = num_changed_path_bytes as usize;
+        let child_edge_size = (num_child_edges as usize) * 8;
 
         let graph_base = 0;
         let commit_lookup_base = graph_base + graph_size;
@@ -346,15 +512,39 @@ impl ReadonlyIndexSegment {
         let change_pos_table_base = change_id_table_base + change_id_table_size;
         let parent_overflow_base = change_pos_table_base + change_pos_table_size;
         let change_overflow_base = parent_overflow_base + parent_overflow_size;
-        let expected_size = change_overflow_base + change_overflow_size;
+        let changed_path_pos_base = change_overflow_base + change_overflow_size;
+        let changed_path_data_base = changed_path_pos_base + changed_path_pos_size;
+        let child_edge_base = changed_path_data_base + changed_path_data_size;
+        let expected_size = child_edge_base + child_edge_size;
 
-        if data.len() != expected_size {
+        if data_len != expected_size {
             return Err(ReadonlyIndexLoadError::invalid_data(
                 name,
                 "unexpected data length",
             ));
         }
 
+        let mut data = data;
+        if let SegmentData::Lazy(lazy) = &mut data {
+            let num_pages = |num_records: usize| num_records.div_ceil(LAZY_PAGE_RECORDS);
+            let table_records = [
+                num_local_commits as usize,
+                num_local_commits as usize,
+                num_local_change_ids as usize,
+                num_local_change_ids as usize,
+                num_parent_overflow_entries as usize,
+                num_change_overflow_entries as usize,
+                num_local_commits as usize,
+                num_local_commits as usize,
+                num_child_edges as usize,
+            ];
+            lazy.pages = table_records.map(|num_records| {
+                (0..num_pages(num_records))
+                    .map(|_| OnceLock::new())
+                    .collect()
+            });
+        }
+
         Ok(Arc::new(ReadonlyIndexSegment {
             parent_file,
             num_parent_commits,
@@ -364,11 +554,15 @@ impl ReadonlyIndexSegment {
             num_local_commits,
             num_local_change_ids,
             num_change_overflow_entries,
+            num_child_edges,
             commit_lookup_base,
             change_id_table_base,
             change_pos_table_base,
             parent_overflow_base,
             change_overflow_base,
+            changed_path_pos_base,
+            changed_path_data_base,
+            child_edge_base,
             data,
         }))
     }
@@ -389,19 +583,111 @@ impl ReadonlyIndexSegment {
         self.change_id_length
     }
 
+    fn table_range(&self, table: SegmentTable) -> Range<usize> {
+        match table {
+            SegmentTable::Graph => 0..self.commit_lookup_base,
+            SegmentTable::CommitLookup => self.commit_lookup_base..self.change_id_table_base,
+            SegmentTable::ChangeId => self.change_id_table_base..self.change_pos_table_base,
+            SegmentTable::ChangePos => self.change_pos_table_base..self.parent_overflow_base,
+            SegmentTable::ParentOverflow => self.parent_overflow_base..self.change_overflow_base,
+            SegmentTable::ChangeOverflow => self.change_overflow_base..self.changed_path_pos_base,
+            SegmentTable::ChangedPathPos => self.changed_path_pos_base..self.changed_path_data_base,
+            SegmentTable::ChangedPathData => self.changed_path_data_base..self.child_edge_base,
+            SegmentTable::ChildEdge => {
+                let child_edge_size = (self.num_child_edges as usize) * 8;
+                self.child_edge_base..self.child_edge_base + child_edge_size
+            }
+        }
+    }
+
+    fn record_size(&self, table: SegmentTable) -> usize {
+        match table {
+            SegmentTable::Graph => CommitGraphEntry::size(self.commit_id_length),
+            SegmentTable::ChangeId => self.change_id_length,
+            SegmentTable::ChildEdge => 8,
+            SegmentTable::CommitLookup
+            | SegmentTable::ChangePos
+            | SegmentTable::ParentOverflow
+            | SegmentTable::ChangeOverflow
+            | SegmentTable::ChangedPathPos => 4,
+            // Variable-length filters, addressed by byte
+            SegmentTable::ChangedPathData => 1,
+        }
+    }
+
+    /// Returns the bytes of the `index`-th record of the fixed-size `table`.
+    fn record(&self, table: SegmentTable, index: usize) -> &[u8] {
+        let size = self.record_size(table);
+        let range = self.table_range(table);
+        match &self.data {
+            SegmentData::Loaded(data) => &data[range][index * size..][..size],
+            SegmentData::Lazy(lazy) => {
+                let page_index = index / LAZY_PAGE_RECORDS;
+                let page_start = range.start + page_index * LAZY_PAGE_RECORDS * size;
+                let page_end = (page_start + LAZY_PAGE_RECORDS * size).min(range.end);
+                let page = self.lazy_page(lazy, table, page_index, page_start..page_end);
+                &page[(index % LAZY_PAGE_RECORDS) * size..][..size]
+            }
+        }
+    }
+
+    fn record_u32(&self, table: SegmentTable, index: usize) -> u32 {
+        u32::from_le_bytes(self.record(table, index).try_into().unwrap())
+    }
+
+    /// Returns the serialized changed-path filter of the commit at
+    /// `local_pos`, or `None` if the recorded range is invalid.
+    fn changed_path_data(&self, local_pos: LocalPosition) -> Option<&[u8]> {
+        let start_of = |local_pos: usize| match local_pos.checked_sub(1) {
+            Some(prev_pos) => self.changed_path_end(LocalPosition(prev_pos as u32)),
+            None => 0,
+        };
+        let start = start_of(local_pos.0 as usize);
+        let end = self.changed_path_end(local_pos);
+        let range = self.table_range(SegmentTable::ChangedPathData);
+        match &self.data {
+            SegmentData::Loaded(data) => data[range].get(start..end),
+            SegmentData::Lazy(lazy) => {
+                // Pages are aligned to commits so a filter never spans pages.
+                let page_index = local_pos.0 as usize / LAZY_PAGE_RECORDS;
+                let first_pos = page_index * LAZY_PAGE_RECORDS;
+                let last_pos = (first_pos + LAZY_PAGE_RECORDS).min(self.num_local_commits as usize);
+                let page_start = start_of(first_pos);
+                let page_end = start_of(last_pos);
+                if page_start > page_end || page_end > range.len() {
+                    return None;
+                }
+                let page_range = range.start + page_start..range.start + page_end;
+                let page =
+                    self.lazy_page(lazy, SegmentTable::ChangedPathData, page_index, page_range);
+                page.get(start.checked_sub(page_start)?..end.checked_sub(page_start)?)
+            }
+        }
+    }
+
+    fn lazy_page<'a>(
+        &self,
+        lazy: &'a LazySegmentData,
+        table: SegmentTable,
+        page_index: usize,
+        range: Range<usize>,
+    ) -> &'a [u8] {
+        // The file size was checked when the segment was loaded, and index
+        // files are never modified. A read error means the file was removed or
+        // damaged, which can't be reported through the infallible index API.
+        lazy.page(table, page_index, range)
+            .map_err(|err| ReadonlyIndexLoadError::from_io_err(&self.name, err))
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
     fn graph_entry(&self, local_pos: LocalPosition) -> CommitGraphEntry<'_> {
-        let table = &self.data[..self.commit_lookup_base];
-        let entry_size = CommitGraphEntry::size(self.commit_id_length);
-        let offset = (local_pos.0 as usize) * entry_size;
         CommitGraphEntry {
-            data: &table[offset..][..entry_size],
+            data: self.record(SegmentTable::Graph, local_pos.0 as usize),
         }
     }
 
     fn commit_lookup_pos(&self, lookup_pos: u32) -> LocalPosition {
-        let table = &self.data[self.commit_lookup_base..self.change_id_table_base];
-        let offset = (lookup_pos as usize) * 4;
-        LocalPosition(u32::from_le_bytes(table[offset..][..4].try_into().unwrap()))
+        LocalPosition(self.record_u32(SegmentTable::CommitLookup, lookup_pos as usize))
     }
 
     fn change_lookup_id(&self, lookup_pos: u32) -> ChangeId {
@@ -410,24 +696,16 @@ impl ReadonlyIndexSegment {
 
     // might be better to add borrowed version of ChangeId
     fn change_lookup_id_bytes(&self, lookup_pos: u32) -> &[u8] {
-        let table = &self.data[self.change_id_table_base..self.change_pos_table_base];
-        let offset = (lookup_pos as usize) * self.change_id_length;
-        &table[offset..][..self.change_id_length]
+        self.record(SegmentTable::ChangeId, lookup_pos as usize)
     }
 
     fn change_lookup_pos(&self, lookup_pos: u32) -> ChangeLocalPosition {
-        let table = &self.data[self.change_pos_table_base..self.parent_overflow_base];
-        let offset = (lookup_pos as usize) * 4;
-        ChangeLocalPosition(u32::from_le_bytes(table[offset..][..4].try_into().unwrap()))
+        ChangeLocalPosition(self.record_u32(SegmentTable::ChangePos, lookup_pos as usize))
     }
 
     fn overflow_parents(&self, overflow_pos: u32, num_parents: u32) -> SmallIndexPositionsVec {
-        let table = &self.data[self.parent_overflow_base..self.change_overflow_base];
-        let offset = (overflow_pos as usize) * 4;
-        let size = (num_parents as usize) * 4;
-        table[offset..][..size]
-            .chunks_exact(4)
-            .map(|chunk| IndexPosition(u32::from_le_bytes(chunk.try_into().unwrap())))
+        (overflow_pos..overflow_pos + num_parents)
+            .map(|pos| IndexPosition(self.record_u32(SegmentTable::ParentOverflow, pos as usize)))
             .collect()
     }
 
@@ -436,11 +714,48 @@ impl ReadonlyIndexSegment {
         &self,
         overflow_pos: u32,
     ) -> impl Iterator<Item = LocalPosition> + use<'_> {
-        let table = &self.data[self.change_overflow_base..];
-        let offset = (overflow_pos as usize) * 4;
-        table[offset..]
-            .chunks_exact(4)
-            .map(|chunk| LocalPosition(u32::from_le_bytes(chunk.try_into().unwrap())))
+        (overflow_pos..self.num_change_overflow_entries)
+            .map(|pos| LocalPosition(self.record_u32(SegmentTable::ChangeOverflow, pos as usize)))
+    }
+
+    fn changed_path_end(&self, local_pos: LocalPosition) -> usize {
+        self.record_u32(SegmentTable::ChangedPathPos, local_pos.0 as usize) as usize
+    }
+
+    fn child_edge(&self, edge_pos: u32) -> (IndexPosition, LocalPosition) {
+        let record = self.record(SegmentTable::ChildEdge, edge_pos as usize);
+        let parent_pos = u32::from_le_bytes(record[..4].try_into().unwrap());
+        let child_pos = u32::from_le_bytes(record[4..].try_into().unwrap());
+        (IndexPosition(parent_pos), LocalPosition(child_pos))
+    }
+
+    /// Returns the graph entry positions associated with the change id at the
+    /// `lookup_pos`.
+    fn change_positions(&self, lookup_pos: u32) -> SmallLocalPositionsVec {
+        let change_pos = self.change_lookup_pos(lookup_pos);
+        if let Some(local_pos) = change_pos.as_inlined() {
+            smallvec![local_pos]
+        } else {
+            let overflow_pos = change_pos.as_overflow().unwrap();
+            // Collect commits having the same change id. For cache
+            // locality, it might be better to look for the next few
+            // change id positions to determine the size.
+            let positions: SmallLocalPositionsVec = self
+                .overflow_changes_from(overflow_pos)
+                .take_while(|&local_pos| {
+                    let entry = self.graph_entry(local_pos);
+                    entry.change_id_lookup_pos() == lookup_pos
+                })
+                .collect();
+            debug_assert_eq!(
+                overflow_pos + u32::try_from(positions.len()).unwrap(),
+                (lookup_pos + 1..self.num_local_change_ids)
+                    .find_map(|lookup_pos| self.change_lookup_pos(lookup_pos).as_overflow())
+                    .unwrap_or(self.num_change_overflow_entries),
+                "all overflow positions to the next change id should be collected"
+            );
+            positions
+        }
     }
 
     /// Binary searches commit id by `prefix`. Returns the lookup position.
@@ -520,32 +835,13 @@ impl IndexSegment for ReadonlyIndexSegment {
     ) -> PrefixResolution<(ChangeId, SmallLocalPositionsVec)> {
         self.change_id_byte_prefix_to_lookup_pos(prefix.min_prefix_bytes())
             .prefix_matches(prefix, |pos| self.change_lookup_id(pos))
-            .map(|(id, lookup_pos)| {
-                let change_pos = self.change_lookup_pos(lookup_pos);
-                if let Some(local_pos) = change_pos.as_inlined() {
-                    (id, smallvec![local_pos])
-                } else {
-                    let overflow_pos = change_pos.as_overflow().unwrap();
-                    // Collect commits having the same change id. For cache
-                    // locality, it might be better to look for the next few
-                    // change id positions to determine the size.
-                    let positions: SmallLocalPositionsVec = self
-                        .overflow_changes_from(overflow_pos)
-                        .take_while(|&local_pos| {
-                            let entry = self.graph_entry(local_pos);
-                            entry.change_id_lookup_pos() == lookup_pos
-                        })
-                        .collect();
-                    debug_assert_eq!(
-                        overflow_pos + u32::try_from(positions.len()).unwrap(),
-                        (lookup_pos + 1..self.num_local_change_ids)
-                            .find_map(|lookup_pos| self.change_lookup_pos(lookup_pos).as_overflow())
-                            .unwrap_or(self.num_change_overflow_entries),
-                        "all overflow positions to the next change id should be collected"
-                    );
-                    (id, positions)
-                }
-            })
+            .map(|(id, lookup_pos)| (id, self.change_positions(lookup_pos)))
+    }
+
+    fn change_id_to_positions(&self, change_id: &ChangeId) -> Option<SmallLocalPositionsVec> {
+        self.change_id_byte_prefix_to_lookup_pos(change_id.as_bytes())
+            .ok()
+            .map(|lookup_pos| self.change_positions(lookup_pos))
     }
 
     fn generation_number(&self, local_pos: LocalPosition) -> u32 {
@@ -587,25 +883,58 @@ impl IndexSegment for ReadonlyIndexSegment {
             self.overflow_parents(overflow_pos, num_parents)
         }
     }
+
+    fn changed_path_filter(&self, local_pos: LocalPosition) -> Option<ChangedPathFilter<'_>> {
+        ChangedPathFilter::from_bytes(self.changed_path_data(local_pos)?)
+    }
+
+    fn child_positions(&self, parent_pos: IndexPosition) -> SmallLocalPositionsVec {
+        // (parent_pos, 0) sorts first among the edges of the parent, so the
+        // lookup position points to the first edge if there's any.
+        let start = binary_search_pos_by(self.num_child_edges, |edge_pos| {
+            self.child_edge(edge_pos)
+                .cmp(&(parent_pos, LocalPosition(0)))
+        })
+        .result
+        .unwrap_or_else(|pos| pos);
+        (start..self.num_child_edges)
+            .map(|edge_pos| self.child_edge(edge_pos))
+            .take_while(|&(pos, _)| pos == parent_pos)
+            .map(|(_, child_pos)| child_pos)
+            .collect()
+    }
 }
 
 /// Commit index backend which stores data on local disk.
 #[derive(Clone, Debug)]
-pub struct DefaultReadonlyIndex(Arc<ReadonlyIndexSegment>);
+pub struct DefaultReadonlyIndex {
+    segment: Arc<ReadonlyIndexSegment>,
+    changed_path_filters: bool,
+}
 
 impl DefaultReadonlyIndex {
     pub(super) fn from_segment(segment: Arc<ReadonlyIndexSegment>) -> Self {
-        DefaultReadonlyIndex(segment)
+        DefaultReadonlyIndex {
+            segment,
+            changed_path_filters: false,
+        }
+    }
+
+    /// Sets whether the changed-path filters should be computed for commits
+    /// added to the mutable index.
+    pub(super) fn with_changed_path_filters(mut self, enabled: bool) -> Self {
+        self.changed_path_filters = enabled;
+        self
     }
 
     pub(super) fn as_segment(&self) -> &Arc<ReadonlyIndexSegment> {
-        &self.0
+        &self.segment
     }
 }
 
 impl AsCompositeIndex for DefaultReadonlyIndex {
     fn as_composite(&self) -> &CompositeIndex {
-        self.0.as_composite()
+        self.segment.as_composite()
     }
 }
 
@@ -670,7 +999,10 @@ impl ReadonlyIndex for DefaultReadonlyIndex {
     }
 
     fn start_modification(&self) -> Box<dyn MutableIndex> {
-        Box::new(DefaultMutableIndex::incremental(self.0.clone()))
+        Box::new(
+            DefaultMutableIndex::incremental(self.segment.clone())
+                .with_changed_path_filters(self.changed_path_filters),
+        )
     }
 }
 
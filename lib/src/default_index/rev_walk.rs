@@ -22,6 +22,7 @@ use std::iter::Fuse;
 use std::iter::FusedIterator;
 use std::ops::Range;
 
+use itertools::Itertools as _;
 use smallvec::SmallVec;
 
 use super::composite::CompositeIndex;
@@ -386,15 +387,60 @@ impl<'a> RevWalkBuilder<'a> {
         self.ancestors_with_min_pos(min_pos)
     }
 
-    /// Fully consumes ancestors and walks back from the `root_positions`.
+    /// Collects entries which are descendants of the `root_positions` and
+    /// ancestors of the wanted heads, in order of descending index position.
+    ///
+    /// The entries are looked up by following the reverse edges stored in the
+    /// index, so the cost is proportional to the number of descendants of the
+    /// roots rather than the distance between the heads and the roots.
+    fn descendant_candidates(
+        self,
+        root_positions: impl IntoIterator<Item = IndexPosition>,
+    ) -> Vec<IndexPosition> {
+        if !self.unwanted.is_empty() {
+            // The reverse edges can't tell whether an entry is an ancestor of
+            // the unwanted roots.
+            return self.ancestors_until_roots(root_positions).collect();
+        }
+        let index = self.index;
+        let mut children_map: HashMap<IndexPosition, SmallIndexPositionsVec> = HashMap::new();
+        let mut work = root_positions.into_iter().collect_vec();
+        while let Some(pos) = work.pop() {
+            if children_map.contains_key(&pos) {
+                continue;
+            }
+            let children = index.children_positions(pos);
+            work.extend(children.iter().copied());
+            children_map.insert(pos, children);
+        }
+
+        let wanted_positions: HashSet<_> = self.wanted.into_iter().collect();
+        let mut positions = children_map.keys().copied().collect_vec();
+        positions.sort_unstable_by_key(|&pos| Reverse(pos));
+        // Children precede their parents in this order, so an entry is an
+        // ancestor of the heads if any of its children has been kept.
+        let mut kept_positions = HashSet::new();
+        positions.retain(|pos| {
+            let keep = wanted_positions.contains(pos)
+                || children_map[pos]
+                    .iter()
+                    .any(|child_pos| kept_positions.contains(child_pos));
+            if keep {
+                kept_positions.insert(*pos);
+            }
+            keep
+        });
+        positions
+    }
+
+    /// Walks descendants of the `root_positions` which are also ancestors of
+    /// the wanted heads.
     ///
     /// The returned iterator yields entries in order of ascending index
     /// position.
     pub fn descendants(self, root_positions: HashSet<IndexPosition>) -> RevWalkDescendants<'a> {
         let index = self.index;
-        let candidate_positions = self
-            .ancestors_until_roots(root_positions.iter().copied())
-            .collect();
+        let candidate_positions = self.descendant_candidates(root_positions.iter().copied());
         RevWalkBorrowedIndexIter {
             index,
             walk: RevWalkDescendantsImpl {
@@ -405,8 +451,8 @@ impl<'a> RevWalkBuilder<'a> {
         }
     }
 
-    /// Fully consumes ancestors and walks back from the `root_positions` within
-    /// the `generation_range`.
+    /// Walks descendants of the `root_positions` within the
+    /// `generation_range`.
     ///
     /// A generation number counts from the roots.
     ///
@@ -418,7 +464,7 @@ impl<'a> RevWalkBuilder<'a> {
         generation_range: Range<u32>,
     ) -> RevWalkDescendantsGenerationRange {
         let index = self.index;
-        let positions = self.ancestors_until_roots(root_positions.iter().copied());
+        let positions = self.descendant_candidates(root_positions.iter().copied());
         let descendants_index = RevWalkDescendantsIndex::build(index, positions);
 
         let mut wanted_queue = RevWalkQueue::with_min_pos(Reverse(IndexPosition::MAX));
@@ -32,6 +32,8 @@ use futures::StreamExt as _;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
 
+use super::changed_path::required_changed_paths;
+use super::entry::IndexEntry;
 use super::rev_walk::EagerRevWalk;
 use super::rev_walk::PeekableRevWalk;
 use super::rev_walk::RevWalk;
@@ -57,6 +59,7 @@ use crate::merge::Merge;
 use crate::merged_tree::resolve_file_values;
 use crate::object_id::ObjectId as _;
 use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
 use crate::revset::ResolvedExpression;
 use crate::revset::ResolvedPredicateExpression;
 use crate::revset::Revset;
@@ -862,18 +865,17 @@ impl EvaluationContext<'_> {
                 let builder =
                     RevWalkBuilder::new(index).wanted_heads(head_positions.try_collect()?);
                 if generation_from_roots == &(1..2) {
-                    let root_positions: HashSet<_> = root_positions.try_collect()?;
+                    let root_positions: Vec<_> = root_positions.try_collect()?;
+                    let child_positions: HashSet<_> = root_positions
+                        .iter()
+                        .flat_map(|&pos| index.children_positions(pos))
+                        .collect();
                     let walk = builder
-                        .ancestors_until_roots(root_positions.iter().copied())
+                        .ancestors_until_roots(child_positions.iter().copied())
                         .detach();
                     let candidates = RevWalkRevset { walk };
-                    let predicate = as_pure_predicate_fn(move |index, pos| {
-                        Ok(index
-                            .entry_by_pos(pos)
-                            .parent_positions()
-                            .iter()
-                            .any(|parent_pos| root_positions.contains(parent_pos)))
-                    });
+                    let predicate =
+                        as_pure_predicate_fn(move |_index, pos| Ok(child_positions.contains(&pos)));
                     // TODO: Suppose heads include all visible heads, ToPredicateFn version can be
                     // optimized to only test the predicate()
                     Ok(Box::new(FilterRevset {
@@ -1006,6 +1008,42 @@ impl EvaluationContext<'_> {
                 let candidate_set = self.evaluate(candidates)?;
                 Ok(Box::new(self.take_latest_revset(&*candidate_set, *count)?))
             }
+            ResolvedExpression::Closest { candidates, heads } => {
+                let head_positions: Vec<_> = self
+                    .evaluate(heads)?
+                    .positions()
+                    .attach(index)
+                    .try_collect()?;
+                let ancestor_positions: HashSet<_> = RevWalkBuilder::new(index)
+                    .wanted_heads(head_positions.clone())
+                    .ancestors()
+                    .collect();
+                let candidate_positions: Vec<_> = self
+                    .evaluate(candidates)?
+                    .positions()
+                    .attach(index)
+                    .filter_ok(|pos| ancestor_positions.contains(pos))
+                    .try_collect()?;
+                // A candidate that is an ancestor of another candidate is
+                // farther away, so only the heads have to be measured. The
+                // distance is the number of ancestors of the heads that aren't
+                // ancestors of the candidate. Ties are broken by picking the
+                // latest candidate.
+                let positions = index
+                    .heads_pos(candidate_positions)
+                    .into_iter()
+                    .min_by_key(|&pos| {
+                        let distance = RevWalkBuilder::new(index)
+                            .wanted_heads(head_positions.clone())
+                            .unwanted_roots(vec![pos])
+                            .ancestors()
+                            .count();
+                        (distance, Reverse(pos))
+                    })
+                    .into_iter()
+                    .collect();
+                Ok(Box::new(EagerRevset { positions }))
+            }
             ResolvedExpression::Coalesce(expression1, expression2) => {
                 let set1 = self.evaluate(expression1)?;
                 if set1.positions().attach(index).next().is_some() {
@@ -1258,8 +1296,13 @@ fn build_predicate_fn(
         }
         RevsetFilterPredicate::File(expr) => {
             let matcher: Rc<dyn Matcher> = expr.to_matcher().into();
+            let required_paths: Option<Rc<[RepoPathBuf]>> =
+                required_changed_paths(expr).map(Rc::from);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_have_changed_any(&entry, required_paths.as_deref()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(has_diff_from_parent(&store, index, &commit, &*matcher).block_on()?)
             })
@@ -1267,8 +1310,13 @@ fn build_predicate_fn(
         RevsetFilterPredicate::DiffContains { text, files } => {
             let text_pattern = text.clone();
             let files_matcher: Rc<dyn Matcher> = files.to_matcher().into();
+            let required_paths: Option<Rc<[RepoPathBuf]>> =
+                required_changed_paths(files).map(Rc::from);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_have_changed_any(&entry, required_paths.as_deref()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(
                     matches_diff_from_parent(
@@ -1287,6 +1335,22 @@ fn build_predicate_fn(
             let commit = store.get_commit(&entry.commit_id())?;
             Ok(commit.has_conflict()?)
         }),
+        RevsetFilterPredicate::HasConflictIn(expr) => {
+            let matcher: Rc<dyn Matcher> = expr.to_matcher().into();
+            box_pure_predicate_fn(move |index, pos| {
+                let entry = index.entry_by_pos(pos);
+                let commit = store.get_commit(&entry.commit_id())?;
+                if !commit.has_conflict()? {
+                    return Ok(false);
+                }
+                for (path, _) in commit.tree()?.conflicts() {
+                    if matcher.matches(&path) {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            })
+        }
         RevsetFilterPredicate::Signed => box_pure_predicate_fn(move |index, pos| {
             let entry = index.entry_by_pos(pos);
             let commit = store.get_commit(&entry.commit_id())?;
@@ -1303,6 +1367,15 @@ fn build_predicate_fn(
     }
 }
 
+/// Returns false if the changed-path filter of `entry` rules out changes to all
+/// of the `paths`. Returns true if there's no filter or no `paths` to test.
+fn may_have_changed_any(entry: &IndexEntry, paths: Option<&[RepoPathBuf]>) -> bool {
+    let (Some(paths), Some(filter)) = (paths, entry.changed_path_filter()) else {
+        return true;
+    };
+    paths.iter().any(|path| filter.maybe_contains(path))
+}
+
 async fn has_diff_from_parent(
     store: &Arc<Store>,
     index: &CompositeIndex,
@@ -1434,6 +1507,8 @@ async fn to_file_content(
     expect(clippy::cloned_ref_to_slice_refs, reason = "makes tests more readable")
 )]
 mod tests {
+    use std::cell::Cell;
+
     use indoc::indoc;
 
     use super::*;
@@ -1564,6 +1639,46 @@ mod tests {
         assert!(p(index, get_pos(&id_0)).unwrap());
     }
 
+    #[test]
+    fn test_revset_intersection_is_lazy() {
+        let mut new_change_id = change_id_generator();
+        let mut index = DefaultMutableIndex::full(3, 16);
+        let ids = (0..10)
+            .map(|i| CommitId::try_from_hex(&i.to_string().repeat(6)).unwrap())
+            .collect_vec();
+        index.add_commit_data(ids[0].clone(), new_change_id(), &[]);
+        for (parent_id, id) in ids.iter().tuple_windows() {
+            index.add_commit_data(id.clone(), new_change_id(), &[parent_id.clone()]);
+        }
+
+        let index = index.as_composite();
+        let get_pos = |id: &CommitId| index.commit_id_to_pos(id).unwrap();
+        let num_walked = Cell::new(0);
+        let ancestors = RevWalkRevset {
+            walk: RevWalkBuilder::new(index)
+                .wanted_heads(vec![get_pos(&ids[9])])
+                .ancestors()
+                .detach()
+                .map(|_index, pos| {
+                    num_walked.set(num_walked.get() + 1);
+                    pos
+                }),
+        };
+        let set = IntersectionRevset {
+            set1: ancestors,
+            set2: EagerRevset {
+                positions: vec![get_pos(&ids[8])],
+            },
+        };
+        assert_eq!(
+            try_collect_vec(set.positions().attach(index)).unwrap(),
+            vec![get_pos(&ids[8])]
+        );
+        // The ancestors aren't walked past the smaller set (plus one peeked
+        // entry.)
+        assert_eq!(num_walked.get(), 3);
+    }
+
     #[test]
     fn test_revset_combinator_error_propagation() {
         let mut new_change_id = change_id_generator();
@@ -48,6 +48,8 @@ use crate::index::IndexStore;
 use crate::index::IndexWriteError;
 use crate::index::MutableIndex;
 use crate::index::ReadonlyIndex;
+use crate::metrics;
+use crate::metrics::DurationTimer;
 use crate::object_id::ObjectId as _;
 use crate::op_store::OpStoreError;
 use crate::op_store::OperationId;
@@ -94,6 +96,8 @@ pub enum DefaultIndexStoreError {
 #[derive(Debug)]
 pub struct DefaultIndexStore {
     dir: PathBuf,
+    lazy_load: bool,
+    changed_path_filters: bool,
 }
 
 impl DefaultIndexStore {
@@ -104,6 +108,8 @@ impl DefaultIndexStore {
     pub fn init(dir: &Path) -> Result<Self, DefaultIndexStoreInitError> {
         let store = DefaultIndexStore {
             dir: dir.to_owned(),
+            lazy_load: false,
+            changed_path_filters: false,
         };
         store.ensure_base_dirs()?;
         Ok(store)
@@ -112,9 +118,31 @@ impl DefaultIndexStore {
     pub fn load(dir: &Path) -> DefaultIndexStore {
         DefaultIndexStore {
             dir: dir.to_owned(),
+            lazy_load: false,
+            changed_path_filters: false,
         }
     }
 
+    /// Sets whether the tables of index segment files should be read when
+    /// they're first accessed instead of when the segments are loaded.
+    ///
+    /// This reduces the cost of loading a large index for commands that look
+    /// up only a few commits, but the segment files are kept open.
+    pub fn with_lazy_load(mut self, lazy_load: bool) -> Self {
+        self.lazy_load = lazy_load;
+        self
+    }
+
+    /// Sets whether bloom filters of the paths changed by each commit should
+    /// be stored in the index.
+    ///
+    /// The filters speed up path-limited revsets such as `files()`, but
+    /// indexing a commit requires diffing its tree against the parent.
+    pub fn with_changed_path_filters(mut self, changed_path_filters: bool) -> Self {
+        self.changed_path_filters = changed_path_filters;
+        self
+    }
+
     pub fn reinit(&self) -> Result<(), DefaultIndexStoreInitError> {
         // Create base directories in case the store was initialized by old jj.
         self.ensure_base_dirs()?;
@@ -165,6 +193,7 @@ impl DefaultIndexStore {
             index_file_id_hex,
             commit_id_length,
             change_id_length,
+            self.lazy_load,
         )
         .map_err(DefaultIndexStoreError::LoadIndex)
     }
@@ -179,7 +208,8 @@ impl DefaultIndexStore {
         store: &Arc<Store>,
     ) -> Result<DefaultReadonlyIndex, DefaultIndexStoreError> {
         let index_segment = self.build_index_segments_at_operation(operation, store)?;
-        Ok(DefaultReadonlyIndex::from_segment(index_segment))
+        Ok(DefaultReadonlyIndex::from_segment(index_segment)
+            .with_changed_path_filters(self.changed_path_filters))
     }
 
     #[tracing::instrument(skip(self, store))]
@@ -189,6 +219,7 @@ impl DefaultIndexStore {
         store: &Arc<Store>,
     ) -> Result<Arc<ReadonlyIndexSegment>, DefaultIndexStoreError> {
         tracing::info!("scanning operations to index");
+        metrics::increment_counter(metrics::INDEX_STORE_BUILDS);
         let operations_dir = self.operations_dir();
         let commit_id_length = store.commit_id_length();
         let change_id_length = store.change_id_length();
@@ -232,7 +263,8 @@ impl DefaultIndexStore {
         match &parent_op {
             None => {
                 maybe_parent_file = None;
-                mutable_index = DefaultMutableIndex::full(commit_id_length, change_id_length);
+                mutable_index = DefaultMutableIndex::full(commit_id_length, change_id_length)
+                    .with_changed_path_filters(self.changed_path_filters);
             }
             Some(op) => {
                 let parent_file = self.load_index_segments_at_operation(
@@ -241,7 +273,8 @@ impl DefaultIndexStore {
                     change_id_length,
                 )?;
                 maybe_parent_file = Some(parent_file.clone());
-                mutable_index = DefaultMutableIndex::incremental(parent_file);
+                mutable_index = DefaultMutableIndex::incremental(parent_file)
+                    .with_changed_path_filters(self.changed_path_filters);
             }
         }
 
@@ -312,8 +345,13 @@ impl DefaultIndexStore {
             },
             |_| panic!("graph has cycle"),
         )?;
-        for (CommitByCommitterTimestamp(commit), _) in commits.iter().rev() {
-            mutable_index.add_commit(commit);
+        for (CommitByCommitterTimestamp(commit), op_id) in commits.iter().rev() {
+            mutable_index.add_commit(commit).map_err(|source| {
+                DefaultIndexStoreError::IndexCommits {
+                    op_id: op_id.clone(),
+                    source,
+                }
+            })?;
         }
 
         let index_file = self.save_mutable_index(mutable_index, operation.id())?;
@@ -371,6 +409,7 @@ impl IndexStore for DefaultIndexStore {
         op: &Operation,
         store: &Arc<Store>,
     ) -> Result<Box<dyn ReadonlyIndex>, IndexReadError> {
+        let _timer = DurationTimer::start(metrics::INDEX_STORE_LOAD_DURATION);
         let index_segment = match self.load_index_segments_at_operation(
             op.id(),
             store.commit_id_length(),
@@ -404,7 +443,10 @@ impl IndexStore for DefaultIndexStore {
             result => result,
         }
         .map_err(|err| IndexReadError(err.into()))?;
-        Ok(Box::new(DefaultReadonlyIndex::from_segment(index_segment)))
+        Ok(Box::new(
+            DefaultReadonlyIndex::from_segment(index_segment)
+                .with_changed_path_filters(self.changed_path_filters),
+        ))
     }
 
     fn write_index(
@@ -419,6 +461,9 @@ impl IndexStore for DefaultIndexStore {
         let index_segment = self
             .save_mutable_index(*index, op.id())
             .map_err(|err| IndexWriteError(err.into()))?;
-        Ok(Box::new(DefaultReadonlyIndex::from_segment(index_segment)))
+        Ok(Box::new(
+            DefaultReadonlyIndex::from_segment(index_segment)
+                .with_changed_path_filters(self.changed_path_filters),
+        ))
     }
 }
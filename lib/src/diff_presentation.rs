@@ -0,0 +1,334 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structured line-based diffs of file contents.
+//!
+//! This is the data the unified diff renderer prints, exposed so that other
+//! frontends can present the same hunks without parsing formatted output.
+
+use std::borrow::Borrow;
+use std::mem;
+use std::ops::Range;
+
+use bstr::BStr;
+use itertools::Itertools as _;
+
+use crate::diff::find_line_ranges;
+use crate::diff::CompareBytesExactly;
+use crate::diff::CompareBytesIgnoreAllWhitespace;
+use crate::diff::CompareBytesIgnoreWhitespaceAmount;
+use crate::diff::Diff;
+use crate::diff::DiffHunk;
+use crate::diff::DiffHunkKind;
+
+/// Options for line-based comparison.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LineDiffOptions {
+    /// How equivalence of lines is tested.
+    pub compare_mode: LineCompareMode,
+    // TODO: add --ignore-blank-lines, etc. which aren't mutually exclusive.
+}
+
+/// How equivalence of lines is tested.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LineCompareMode {
+    /// Compares lines literally.
+    #[default]
+    Exact,
+    /// Compares lines ignoring any whitespace occurrences.
+    IgnoreAllSpace,
+    /// Compares lines ignoring changes in whitespace amount.
+    IgnoreSpaceChange,
+}
+
+/// Compares `inputs` line by line.
+pub fn diff_by_line<'input, T: AsRef<[u8]> + ?Sized + 'input>(
+    inputs: impl IntoIterator<Item = &'input T>,
+    options: &LineDiffOptions,
+) -> Diff<'input> {
+    // TODO: If we add --ignore-blank-lines, its tokenizer will have to attach
+    // blank lines to the preceding range. Maybe it can also be implemented as a
+    // post-process (similar to refine_changed_regions()) that expands unchanged
+    // regions across blank lines.
+    match options.compare_mode {
+        LineCompareMode::Exact => {
+            Diff::for_tokenizer(inputs, find_line_ranges, CompareBytesExactly)
+        }
+        LineCompareMode::IgnoreAllSpace => {
+            Diff::for_tokenizer(inputs, find_line_ranges, CompareBytesIgnoreAllWhitespace)
+        }
+        LineCompareMode::IgnoreSpaceChange => {
+            Diff::for_tokenizer(inputs, find_line_ranges, CompareBytesIgnoreWhitespaceAmount)
+        }
+    }
+}
+
+/// Kind of a line in a unified diff hunk.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiffLineType {
+    /// Line present on both sides.
+    Context,
+    /// Line present only on the left side.
+    Removed,
+    /// Line present only on the right side.
+    Added,
+}
+
+/// Whether a token within a line is changed or not.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiffTokenType {
+    /// Token present on both sides.
+    Matching,
+    /// Token changed on this side.
+    Different,
+}
+
+/// Tokens of a single line, including the trailing newline if any.
+pub type DiffTokenVec<'content> = Vec<(DiffTokenType, &'content [u8])>;
+
+/// Hunk of a unified diff: changed lines surrounded by context lines.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnifiedDiffHunk<'content> {
+    /// 0-based range of lines in the left content.
+    pub left_line_range: Range<usize>,
+    /// 0-based range of lines in the right content.
+    pub right_line_range: Range<usize>,
+    /// Lines in display order, split into word-level tokens.
+    pub lines: Vec<(DiffLineType, DiffTokenVec<'content>)>,
+}
+
+impl<'content> UnifiedDiffHunk<'content> {
+    fn extend_context_lines(&mut self, lines: impl IntoIterator<Item = &'content [u8]>) {
+        let old_len = self.lines.len();
+        self.lines.extend(lines.into_iter().map(|line| {
+            let tokens = vec![(DiffTokenType::Matching, line)];
+            (DiffLineType::Context, tokens)
+        }));
+        self.left_line_range.end += self.lines.len() - old_len;
+        self.right_line_range.end += self.lines.len() - old_len;
+    }
+
+    fn extend_removed_lines(&mut self, lines: impl IntoIterator<Item = DiffTokenVec<'content>>) {
+        let old_len = self.lines.len();
+        self.lines
+            .extend(lines.into_iter().map(|line| (DiffLineType::Removed, line)));
+        self.left_line_range.end += self.lines.len() - old_len;
+    }
+
+    fn extend_added_lines(&mut self, lines: impl IntoIterator<Item = DiffTokenVec<'content>>) {
+        let old_len = self.lines.len();
+        self.lines
+            .extend(lines.into_iter().map(|line| (DiffLineType::Added, line)));
+        self.right_line_range.end += self.lines.len() - old_len;
+    }
+}
+
+/// Computes unified diff hunks between `[left, right]` contents, with up to
+/// `context` lines of context around each change.
+pub fn unified_diff_hunks<'content>(
+    contents: [&'content BStr; 2],
+    context: usize,
+    options: &LineDiffOptions,
+) -> Vec<UnifiedDiffHunk<'content>> {
+    let mut hunks = vec![];
+    let mut current_hunk = UnifiedDiffHunk {
+        left_line_range: 0..0,
+        right_line_range: 0..0,
+        lines: vec![],
+    };
+    let diff = diff_by_line(contents, options);
+    let mut diff_hunks = diff.hunks().peekable();
+    while let Some(hunk) = diff_hunks.next() {
+        match hunk.kind {
+            DiffHunkKind::Matching => {
+                // Just use the right (i.e. new) content. We could count the
+                // number of skipped lines separately, but the number of the
+                // context lines should match the displayed content.
+                let [_, right] = hunk.contents[..].try_into().unwrap();
+                let mut lines = right.split_inclusive(|b| *b == b'\n').fuse();
+                if !current_hunk.lines.is_empty() {
+                    // The previous hunk line should be either removed/added.
+                    current_hunk.extend_context_lines(lines.by_ref().take(context));
+                }
+                let before_lines = if diff_hunks.peek().is_some() {
+                    lines.by_ref().rev().take(context).collect()
+                } else {
+                    vec![] // No more hunks
+                };
+                let num_skip_lines = lines.count();
+                if num_skip_lines > 0 {
+                    let left_start = current_hunk.left_line_range.end + num_skip_lines;
+                    let right_start = current_hunk.right_line_range.end + num_skip_lines;
+                    if !current_hunk.lines.is_empty() {
+                        hunks.push(current_hunk);
+                    }
+                    current_hunk = UnifiedDiffHunk {
+                        left_line_range: left_start..left_start,
+                        right_line_range: right_start..right_start,
+                        lines: vec![],
+                    };
+                }
+                // The next hunk should be of DiffHunk::Different type if any.
+                current_hunk.extend_context_lines(before_lines.into_iter().rev());
+            }
+            DiffHunkKind::Different => {
+                let [left_lines, right_lines] =
+                    unzip_diff_hunks_to_lines(Diff::by_word(hunk.contents).hunks());
+                current_hunk.extend_removed_lines(left_lines);
+                current_hunk.extend_added_lines(right_lines);
+            }
+        }
+    }
+    if !current_hunk.lines.is_empty() {
+        hunks.push(current_hunk);
+    }
+    hunks
+}
+
+/// Splits `[left, right]` hunk pairs into `[left_lines, right_lines]`.
+pub fn unzip_diff_hunks_to_lines<'content, I>(diff_hunks: I) -> [Vec<DiffTokenVec<'content>>; 2]
+where
+    I: IntoIterator,
+    I::Item: Borrow<DiffHunk<'content>>,
+{
+    let mut left_lines: Vec<DiffTokenVec<'content>> = vec![];
+    let mut right_lines: Vec<DiffTokenVec<'content>> = vec![];
+    let mut left_tokens: DiffTokenVec<'content> = vec![];
+    let mut right_tokens: DiffTokenVec<'content> = vec![];
+
+    for hunk in diff_hunks {
+        let hunk = hunk.borrow();
+        match hunk.kind {
+            DiffHunkKind::Matching => {
+                // TODO: add support for unmatched contexts
+                debug_assert!(hunk.contents.iter().all_equal());
+                for token in hunk.contents[0].split_inclusive(|b| *b == b'\n') {
+                    left_tokens.push((DiffTokenType::Matching, token));
+                    right_tokens.push((DiffTokenType::Matching, token));
+                    if token.ends_with(b"\n") {
+                        left_lines.push(mem::take(&mut left_tokens));
+                        right_lines.push(mem::take(&mut right_tokens));
+                    }
+                }
+            }
+            DiffHunkKind::Different => {
+                let [left, right] = hunk.contents[..]
+                    .try_into()
+                    .expect("hunk should have exactly two inputs");
+                for token in left.split_inclusive(|b| *b == b'\n') {
+                    left_tokens.push((DiffTokenType::Different, token));
+                    if token.ends_with(b"\n") {
+                        left_lines.push(mem::take(&mut left_tokens));
+                    }
+                }
+                for token in right.split_inclusive(|b| *b == b'\n') {
+                    right_tokens.push((DiffTokenType::Different, token));
+                    if token.ends_with(b"\n") {
+                        right_lines.push(mem::take(&mut right_tokens));
+                    }
+                }
+            }
+        }
+    }
+
+    if !left_tokens.is_empty() {
+        left_lines.push(left_tokens);
+    }
+    if !right_tokens.is_empty() {
+        right_lines.push(right_tokens);
+    }
+    [left_lines, right_lines]
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::ByteSlice as _;
+
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_hunks_single_change() {
+        let left = b"a\nb\nc\nd\ne\n".as_bstr();
+        let right = b"a\nb\nC\nd\ne\n".as_bstr();
+        let hunks = unified_diff_hunks([left, right], 1, &LineDiffOptions::default());
+        assert_eq!(
+            hunks,
+            vec![UnifiedDiffHunk {
+                left_line_range: 1..4,
+                right_line_range: 1..4,
+                lines: vec![
+                    (
+                        DiffLineType::Context,
+                        vec![(DiffTokenType::Matching, &b"b\n"[..])]
+                    ),
+                    (
+                        DiffLineType::Removed,
+                        vec![
+                            (DiffTokenType::Different, &b"c"[..]),
+                            (DiffTokenType::Matching, &b"\n"[..]),
+                        ],
+                    ),
+                    (
+                        DiffLineType::Added,
+                        vec![
+                            (DiffTokenType::Different, &b"C"[..]),
+                            (DiffTokenType::Matching, &b"\n"[..]),
+                        ],
+                    ),
+                    (
+                        DiffLineType::Context,
+                        vec![(DiffTokenType::Matching, &b"d\n"[..])]
+                    ),
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_hunks_separate_changes() {
+        let left = b"a\nb\nc\nd\ne\n".as_bstr();
+        let right = b"b\nc\nd\ne\nf\n".as_bstr();
+        let hunks = unified_diff_hunks([left, right], 0, &LineDiffOptions::default());
+        let ranges = hunks
+            .iter()
+            .map(|hunk| (hunk.left_line_range.clone(), hunk.right_line_range.clone()))
+            .collect_vec();
+        assert_eq!(ranges, vec![(0..1, 0..0), (5..5, 4..5)]);
+        let line_types = hunks
+            .iter()
+            .map(|hunk| {
+                hunk.lines
+                    .iter()
+                    .map(|(line_type, _)| *line_type)
+                    .collect_vec()
+            })
+            .collect_vec();
+        assert_eq!(
+            line_types,
+            vec![vec![DiffLineType::Removed], vec![DiffLineType::Added]]
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_hunks_ignore_whitespace() {
+        let left = b"a\nb c\n".as_bstr();
+        let right = b"a\nb  c\n".as_bstr();
+        let options = LineDiffOptions {
+            compare_mode: LineCompareMode::IgnoreSpaceChange,
+        };
+        assert_eq!(unified_diff_hunks([left, right], 3, &options), vec![]);
+        let options = LineDiffOptions::default();
+        assert_eq!(unified_diff_hunks([left, right], 3, &options).len(), 1);
+    }
+}
@@ -35,6 +35,7 @@ use tokio::io::AsyncReadExt as _;
 use tokio::io::ReadBuf;
 
 pub use self::platform::check_symlink_support;
+pub use self::platform::is_owned_by_current_user;
 pub use self::platform::try_symlink;
 
 #[derive(Debug, Error)]
@@ -242,6 +243,39 @@ pub async fn copy_async_to_sync<R: AsyncRead, W: Write + ?Sized>(
     }
 }
 
+/// Like [`copy_async_to_sync()`], but skips the first `offset` bytes, and
+/// copies at most `length` bytes after that. The skipped bytes are still read
+/// from `reader`.
+pub async fn copy_async_range_to_sync<R: AsyncRead, W: Write + ?Sized>(
+    reader: R,
+    writer: &mut W,
+    offset: u64,
+    length: Option<u64>,
+) -> io::Result<usize> {
+    let mut buf = vec![0; 16 << 10];
+    let mut total_written_bytes = 0;
+    let mut bytes_to_skip = offset;
+    let mut bytes_to_write = length.unwrap_or(u64::MAX);
+
+    let mut reader = std::pin::pin!(reader);
+    while bytes_to_write > 0 {
+        let read_bytes = reader.read(&mut buf).await?;
+        if read_bytes == 0 {
+            break;
+        }
+        let skipped_bytes = read_bytes.min(usize::try_from(bytes_to_skip).unwrap_or(usize::MAX));
+        bytes_to_skip -= skipped_bytes as u64;
+        let chunk = &buf[skipped_bytes..read_bytes];
+        let chunk = &chunk[..chunk
+            .len()
+            .min(usize::try_from(bytes_to_write).unwrap_or(usize::MAX))];
+        writer.write_all(chunk)?;
+        bytes_to_write -= chunk.len() as u64;
+        total_written_bytes += chunk.len();
+    }
+    Ok(total_written_bytes)
+}
+
 /// `AsyncRead`` implementation backed by a `Read`. It is not actually async;
 /// the goal is simply to avoid reading the full contents from the `Read` into
 /// memory.
@@ -275,6 +309,7 @@ mod platform {
     use std::io;
     use std::os::unix::ffi::OsStrExt as _;
     use std::os::unix::fs::symlink;
+    use std::os::unix::fs::MetadataExt as _;
     use std::path::Path;
 
     pub type BadOsStrEncoding = Infallible;
@@ -295,6 +330,12 @@ mod platform {
     pub fn try_symlink<P: AsRef<Path>, Q: AsRef<Path>>(original: P, link: Q) -> io::Result<()> {
         symlink(original, link)
     }
+
+    /// Returns whether the file is owned by the effective user of this process.
+    pub fn is_owned_by_current_user(path: &Path) -> io::Result<bool> {
+        let metadata = path.metadata()?;
+        Ok(metadata.uid() == rustix::process::geteuid().as_raw())
+    }
 }
 
 #[cfg(windows)]
@@ -329,6 +370,12 @@ mod platform {
 
         symlink_file(original, link)
     }
+
+    /// File ownership isn't checked on Windows.
+    pub fn is_owned_by_current_user(path: &Path) -> io::Result<bool> {
+        path.metadata()?;
+        Ok(true)
+    }
 }
 
 #[cfg_attr(unix, allow(dead_code))]
@@ -430,6 +477,13 @@ mod tests {
         assert!(persist_content_addressed_temp_file(temp_file, target).is_ok());
     }
 
+    #[test]
+    fn test_is_owned_by_current_user() {
+        let temp_dir = new_temp_dir();
+        assert!(is_owned_by_current_user(temp_dir.path()).unwrap());
+        assert!(is_owned_by_current_user(&temp_dir.path().join("missing")).is_err());
+    }
+
     #[test_case(false ; "existing file open")]
     #[test_case(true ; "existing file closed")]
     fn test_persist_target_exists(existing_file_closed: bool) {
@@ -470,6 +524,27 @@ mod tests {
         assert_eq!(output, input);
     }
 
+    #[test]
+    fn test_copy_async_range_to_sync() {
+        // More than 1 buffer worth of data
+        let input = (0..100u8).cycle().take(40000).collect_vec();
+        let copy_range = |offset, length| {
+            let mut output = vec![];
+            let result = copy_async_range_to_sync(Cursor::new(&input), &mut output, offset, length)
+                .block_on();
+            assert_eq!(result.unwrap(), output.len());
+            output
+        };
+
+        assert_eq!(copy_range(0, None), input);
+        assert_eq!(copy_range(3, Some(2)), [3, 4]);
+        assert_eq!(copy_range(20000, Some(30000)), input[20000..]);
+        assert_eq!(copy_range(16383, Some(16386)), input[16383..32769]);
+        assert_eq!(copy_range(40000, None), b"");
+        assert_eq!(copy_range(50000, Some(1)), b"");
+        assert_eq!(copy_range(0, Some(0)), b"");
+    }
+
     #[test]
     fn test_blocking_async_reader() {
         let input = b"hello";
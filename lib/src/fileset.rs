@@ -498,6 +498,7 @@ mod tests {
     use std::path::PathBuf;
 
     use super::*;
+    use crate::repo_path::UiPathStyle;
 
     fn repo_path_buf(value: impl Into<String>) -> RepoPathBuf {
         RepoPathBuf::from_internal_string(value).unwrap()
@@ -528,6 +529,7 @@ mod tests {
         let path_converter = RepoPathUiConverter::Fs {
             cwd: PathBuf::from("/ws/cur"),
             base: PathBuf::from("/ws"),
+            style: UiPathStyle::default(),
         };
         let parse = |text| parse_maybe_bare(&mut FilesetDiagnostics::new(), text, &path_converter);
 
@@ -573,6 +575,7 @@ mod tests {
             // meta character in cwd path shouldn't be expanded
             cwd: PathBuf::from("/ws/cur*"),
             base: PathBuf::from("/ws"),
+            style: UiPathStyle::default(),
         };
         let parse = |text| parse_maybe_bare(&mut FilesetDiagnostics::new(), text, &path_converter);
 
@@ -746,6 +749,7 @@ mod tests {
         let path_converter = RepoPathUiConverter::Fs {
             cwd: PathBuf::from("/ws/cur"),
             base: PathBuf::from("/ws"),
+            style: UiPathStyle::default(),
         };
         let parse = |text| parse_maybe_bare(&mut FilesetDiagnostics::new(), text, &path_converter);
 
@@ -774,6 +778,7 @@ mod tests {
         let path_converter = RepoPathUiConverter::Fs {
             cwd: PathBuf::from("/ws/cur"),
             base: PathBuf::from("/ws"),
+            style: UiPathStyle::default(),
         };
         let parse = |text| parse_maybe_bare(&mut FilesetDiagnostics::new(), text, &path_converter);
 
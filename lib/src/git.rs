@@ -20,8 +20,10 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::default::Default;
 use std::fs::File;
+use std::iter;
 use std::num::NonZeroU32;
 use std::path::PathBuf;
+use std::slice;
 use std::str;
 use std::sync::Arc;
 
@@ -46,10 +48,13 @@ use crate::matchers::EverythingMatcher;
 use crate::merged_tree::MergedTree;
 use crate::merged_tree::TreeDiffEntry;
 use crate::object_id::ObjectId as _;
+use crate::op_store::OpStoreError;
+use crate::op_store::OperationId;
 use crate::op_store::RefTarget;
 use crate::op_store::RefTargetOptionExt as _;
 use crate::op_store::RemoteRef;
 use crate::op_store::RemoteRefState;
+use crate::op_walk;
 use crate::ref_name::GitRefName;
 use crate::ref_name::GitRefNameBuf;
 use crate::ref_name::RefName;
@@ -60,6 +65,7 @@ use crate::ref_name::RemoteRefSymbol;
 use crate::ref_name::RemoteRefSymbolBuf;
 use crate::refs::BookmarkPushUpdate;
 use crate::repo::MutableRepo;
+use crate::repo::ReadonlyRepo;
 use crate::repo::Repo;
 use crate::repo_path::RepoPath;
 use crate::revset::RevsetExpression;
@@ -1227,6 +1233,164 @@ fn update_git_head(
     Ok(())
 }
 
+/// Git ref namespace where [`export_backup_refs()`] writes refs.
+pub const BACKUP_REF_NAMESPACE: &str = "refs/jj/backup/";
+
+#[derive(Debug, Error)]
+pub enum GitBackupRefsError {
+    #[error(transparent)]
+    OpStore(#[from] OpStoreError),
+    #[error(transparent)]
+    Git(Box<dyn std::error::Error + Send + Sync>),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+}
+
+impl GitBackupRefsError {
+    fn from_git(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        GitBackupRefsError::Git(source.into())
+    }
+}
+
+/// Describes changes made by [`export_backup_refs()`] or
+/// [`remove_backup_refs()`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GitBackupRefsStats {
+    /// Number of refs created.
+    pub added_count: usize,
+    /// Number of refs deleted.
+    pub removed_count: usize,
+    /// Number of existing refs which were up to date.
+    pub kept_count: usize,
+}
+
+/// Writes Git refs for all commits referenced by the operation history of the
+/// `repo`, so standard Git tools (e.g. backup or mirroring tools) can see every
+/// commit `jj` needs.
+///
+/// Commits referenced by an operation's view or recorded as a commit
+/// predecessor are backed up. A single ref is written for each of these commits
+/// which isn't an ancestor of another one, named
+/// `refs/jj/backup/<operation id>/<commit id>` after the oldest operation
+/// referencing the commit. Existing backup refs which are no longer needed are
+/// removed.
+pub fn export_backup_refs(repo: &ReadonlyRepo) -> Result<GitBackupRefsStats, GitBackupRefsError> {
+    let git_repo = get_git_repo(repo.store())?;
+    let root_commit_id = repo.store().root_commit_id();
+    // Operations are visited from the newest, so the oldest operation wins.
+    let mut commit_op_ids: HashMap<CommitId, OperationId> = HashMap::new();
+    for op in op_walk::walk_ancestors(slice::from_ref(repo.operation())) {
+        let op = op?;
+        let view = op.view()?;
+        let predecessor_ids = op
+            .store_operation()
+            .commit_predecessors
+            .iter()
+            .flatten()
+            .flat_map(|(id, predecessor_ids)| iter::once(id).chain(predecessor_ids));
+        for id in itertools::chain(view.all_referenced_commit_ids(), predecessor_ids) {
+            if id != root_commit_id {
+                commit_op_ids.insert(id.clone(), op.id().clone());
+            }
+        }
+    }
+    // Ancestors are reachable from the refs of their descendants. Commits
+    // missing from the index get refs of their own.
+    let index = repo.index();
+    let (indexed_ids, unindexed_ids): (Vec<_>, Vec<_>) =
+        commit_op_ids.keys().partition(|id| index.has_id(id));
+    let head_ids = index
+        .heads(&mut indexed_ids.into_iter())
+        .map_err(GitBackupRefsError::from_git)?;
+    let mut new_refs: HashMap<String, gix::ObjectId> = itertools::chain(&head_ids, unindexed_ids)
+        .map(|id| {
+            let op_id = &commit_op_ids[id];
+            let name = format!("{BACKUP_REF_NAMESPACE}{}/{}", op_id.hex(), id.hex());
+            (name, gix::ObjectId::from_bytes_or_panic(id.as_bytes()))
+        })
+        .collect();
+
+    let mut stats = GitBackupRefsStats::default();
+    let mut ref_edits = Vec::new();
+    for git_ref in existing_backup_refs(&git_repo)? {
+        let name = git_ref.name.as_bstr().to_string();
+        let up_to_date = git_ref.target.try_id().is_some_and(|oid| {
+            new_refs
+                .get(&name)
+                .is_some_and(|new_oid| new_oid.as_bytes() == oid.as_bytes())
+        });
+        if up_to_date {
+            new_refs.remove(&name);
+            stats.kept_count += 1;
+        } else {
+            ref_edits.push(to_backup_ref_deletion(git_ref));
+            stats.removed_count += 1;
+        }
+    }
+    for (name, oid) in new_refs {
+        ref_edits.push(gix::refs::transaction::RefEdit {
+            change: gix::refs::transaction::Change::Update {
+                log: gix::refs::transaction::LogChange {
+                    message: "backup by jj".into(),
+                    ..Default::default()
+                },
+                expected: gix::refs::transaction::PreviousValue::Any,
+                new: gix::refs::Target::Object(oid),
+            },
+            name: name.try_into().map_err(GitBackupRefsError::from_git)?,
+            deref: false,
+        });
+        stats.added_count += 1;
+    }
+    git_repo
+        .edit_references(ref_edits)
+        .map_err(GitBackupRefsError::from_git)?;
+    Ok(stats)
+}
+
+/// Removes all refs written by [`export_backup_refs()`].
+pub fn remove_backup_refs(store: &Store) -> Result<GitBackupRefsStats, GitBackupRefsError> {
+    let git_repo = get_git_repo(store)?;
+    let ref_edits = existing_backup_refs(&git_repo)?
+        .into_iter()
+        .map(to_backup_ref_deletion)
+        .collect_vec();
+    let stats = GitBackupRefsStats {
+        removed_count: ref_edits.len(),
+        ..Default::default()
+    };
+    git_repo
+        .edit_references(ref_edits)
+        .map_err(GitBackupRefsError::from_git)?;
+    Ok(stats)
+}
+
+fn existing_backup_refs(
+    git_repo: &gix::Repository,
+) -> Result<Vec<gix::refs::Reference>, GitBackupRefsError> {
+    let git_references = git_repo
+        .references()
+        .map_err(GitBackupRefsError::from_git)?;
+    let backup_refs: Vec<_> = git_references
+        .prefixed(BACKUP_REF_NAMESPACE)
+        .map_err(GitBackupRefsError::from_git)?
+        .map_ok(|git_ref| git_ref.detach())
+        .try_collect()
+        .map_err(GitBackupRefsError::Git)?;
+    Ok(backup_refs)
+}
+
+fn to_backup_ref_deletion(git_ref: gix::refs::Reference) -> gix::refs::transaction::RefEdit {
+    gix::refs::transaction::RefEdit {
+        change: gix::refs::transaction::Change::Delete {
+            expected: gix::refs::transaction::PreviousValue::ExistingMustMatch(git_ref.target),
+            log: gix::refs::transaction::RefLog::AndReference,
+        },
+        name: git_ref.name,
+        deref: false,
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum GitResetHeadError {
     #[error(transparent)]
@@ -2093,9 +2257,24 @@ pub enum GitFetchError {
     Subprocess(#[from] GitSubprocessError),
 }
 
+/// Describes which tags should be fetched by [`GitFetch::fetch()`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitFetchTags {
+    /// Fetches tags pointing into the fetched history, which is Git's default.
+    /// The `remote.<name>.tagOpt` setting of Git is respected.
+    #[default]
+    Reachable,
+    /// Fetches all tags.
+    All,
+    /// Doesn't fetch tags, nor import them into the view.
+    None,
+}
+
 struct FetchedBranches {
     remote: RemoteNameBuf,
     branches: Vec<StringPattern>,
+    tags: GitFetchTags,
 }
 
 fn expand_fetch_refspecs(
@@ -2160,6 +2339,7 @@ impl<'a> GitFetch<'a> {
         &mut self,
         remote_name: &RemoteName,
         branch_names: &[StringPattern],
+        tags: GitFetchTags,
         mut callbacks: RemoteCallbacks<'_>,
         depth: Option<NonZeroU32>,
     ) -> Result<(), GitFetchError> {
@@ -2189,10 +2369,13 @@ impl<'a> GitFetch<'a> {
         //
         // even more unfortunately, git errors out one refspec at a time,
         // meaning that the below cycle runs in O(#failed refspecs)
-        while let Some(failing_refspec) =
-            self.git_ctx
-                .spawn_fetch(remote_name, &remaining_refspecs, &mut callbacks, depth)?
-        {
+        while let Some(failing_refspec) = self.git_ctx.spawn_fetch(
+            remote_name,
+            &remaining_refspecs,
+            tags,
+            &mut callbacks,
+            depth,
+        )? {
             tracing::debug!(failing_refspec, "failed to fetch ref");
             remaining_refspecs.retain(|r| r.source.as_ref() != Some(&failing_refspec));
 
@@ -2211,10 +2394,110 @@ impl<'a> GitFetch<'a> {
         self.fetched.push(FetchedBranches {
             remote: remote_name.to_owned(),
             branches: branch_names.to_vec(),
+            tags,
+        });
+        Ok(())
+    }
+
+    /// Lists the remote-tracking bookmarks of the remote which no longer
+    /// exist on the remote. The remote is queried without fetching objects.
+    #[tracing::instrument(skip(self))]
+    pub fn find_stale_remote_bookmarks(
+        &self,
+        remote_name: &RemoteName,
+    ) -> Result<Vec<RefNameBuf>, GitFetchError> {
+        validate_remote_name(remote_name)?;
+        if self
+            .git_repo
+            .try_find_remote(remote_name.as_str())
+            .is_none()
+        {
+            return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+        }
+        let remote_branches: HashSet<RefNameBuf> = self
+            .git_ctx
+            .spawn_ls_remote_branches(remote_name)?
+            .into_iter()
+            .collect();
+        let stale_bookmarks = self
+            .mut_repo
+            .view()
+            .remote_bookmarks(remote_name)
+            .filter(|(_, remote_ref)| remote_ref.is_present())
+            .map(|(name, _)| name)
+            .filter(|&name| !remote_branches.contains(name))
+            .map(ToOwned::to_owned)
+            .collect_vec();
+        tracing::debug!(?stale_bookmarks);
+        Ok(stale_bookmarks)
+    }
+
+    /// Deletes the remote-tracking branches of the given `bookmark_names` from
+    /// the local git repo, as `fetch()` does for branches deleted on the
+    /// remote.
+    ///
+    /// The deletions can be subsequently imported into the `jj` repo by calling
+    /// `import_refs()`.
+    #[tracing::instrument(skip(self))]
+    pub fn prune(
+        &mut self,
+        remote_name: &RemoteName,
+        bookmark_names: &[RefNameBuf],
+    ) -> Result<(), GitFetchError> {
+        validate_remote_name(remote_name)?;
+        let branches_to_prune = bookmark_names
+            .iter()
+            .map(|name| {
+                format!(
+                    "{remote_name}/{branch_name}",
+                    remote_name = remote_name.as_str(),
+                    branch_name = name.as_str()
+                )
+            })
+            .collect_vec();
+        self.git_ctx.spawn_branch_prune(&branches_to_prune)?;
+        self.fetched.push(FetchedBranches {
+            remote: remote_name.to_owned(),
+            branches: bookmark_names
+                .iter()
+                .map(|name| StringPattern::exact(name.as_str()))
+                .collect(),
+            tags: GitFetchTags::None,
         });
         Ok(())
     }
 
+    /// Deletes the local tags which exist on none of the given remotes.
+    ///
+    /// Unlike `git fetch --prune-tags`, which only looks at a single remote,
+    /// tags are kept if any of the `remote_names` has them. The deletions can
+    /// be subsequently imported into the `jj` repo by calling `import_refs()`
+    /// if tags were fetched.
+    #[tracing::instrument(skip(self))]
+    pub fn prune_tags(&mut self, remote_names: &[&RemoteName]) -> Result<(), GitFetchError> {
+        let mut remote_tags = HashSet::new();
+        for &remote_name in remote_names {
+            validate_remote_name(remote_name)?;
+            if self
+                .git_repo
+                .try_find_remote(remote_name.as_str())
+                .is_none()
+            {
+                return Err(GitFetchError::NoSuchRemote(remote_name.to_owned()));
+            }
+            remote_tags.extend(self.git_ctx.spawn_ls_remote_tags(remote_name)?);
+        }
+        let tags_to_delete = self
+            .git_ctx
+            .spawn_list_tags()?
+            .into_iter()
+            .filter(|name| !remote_tags.contains(name))
+            .collect_vec();
+        tracing::debug!(?tags_to_delete);
+        self.git_ctx.spawn_tag_delete(&tags_to_delete)?;
+        Ok(())
+    }
+
     /// Queries remote for the default branch name.
     #[tracing::instrument(skip(self))]
     pub fn get_default_branch(
@@ -2235,7 +2518,8 @@ impl<'a> GitFetch<'a> {
 
     /// Import the previously fetched remote-tracking branches into the jj repo
     /// and update jj's local branches. We also import local tags since remote
-    /// tags should have been merged by Git.
+    /// tags should have been merged by Git, unless tags were fetched from none
+    /// of the remotes.
     ///
     /// Clears all yet-to-be-imported {branch_names, remote_name} pairs after
     /// the import. If `fetch()` has not been called since the last time
@@ -2258,7 +2542,10 @@ impl<'a> GitFetch<'a> {
                                 .iter()
                                 .any(|pattern| pattern.matches(symbol.name.as_str()))
                         }),
-                    GitRefKind::Tag => true,
+                    GitRefKind::Tag => self
+                        .fetched
+                        .iter()
+                        .any(|fetched| fetched.tags != GitFetchTags::None),
                 },
             )?;
 
@@ -95,6 +95,7 @@ use crate::stacked_table::ReadonlyTable;
 use crate::stacked_table::TableSegment as _;
 use crate::stacked_table::TableStore;
 use crate::stacked_table::TableStoreError;
+use crate::trailer;
 
 const HASH_LENGTH: usize = 20;
 const CHANGE_ID_LENGTH: usize = 16;
@@ -104,6 +105,9 @@ const CONFLICT_SUFFIX: &str = ".jjconflict";
 
 pub const JJ_TREES_COMMIT_HEADER: &str = "jj:trees";
 pub const CHANGE_ID_COMMIT_HEADER: &str = "change-id";
+/// Commit message trailer used to preserve the change id through Git tools
+/// that drop non-standard commit headers.
+pub const CHANGE_ID_COMMIT_TRAILER: &str = "JJ-Change-Id";
 
 #[derive(Debug, Error)]
 pub enum GitBackendInitError {
@@ -180,6 +184,7 @@ pub struct GitBackend {
     cached_extra_metadata: Mutex<Option<Arc<ReadonlyTable>>>,
     git_executable: PathBuf,
     write_change_id_header: bool,
+    write_change_id_trailer: bool,
 }
 
 impl GitBackend {
@@ -206,6 +211,7 @@ impl GitBackend {
             cached_extra_metadata: Mutex::new(None),
             git_executable: git_settings.executable_path,
             write_change_id_header: git_settings.write_change_id_header,
+            write_change_id_trailer: git_settings.write_change_id_trailer,
         }
     }
 
@@ -393,6 +399,52 @@ impl GitBackend {
         Ok(())
     }
 
+    /// Reads the extra metadata record of the commit without converting it.
+    ///
+    /// Returns `None` if the commit has no extra metadata.
+    pub fn read_extra_metadata_proto(
+        &self,
+        id: &CommitId,
+    ) -> BackendResult<Option<crate::protos::git_store::Commit>> {
+        validate_git_object_id(id)?;
+        let table = self
+            .extra_metadata_store
+            .get_head()
+            .map_err(GitBackendError::ReadMetadata)?;
+        let Some(extras) = table.get_value(id.as_bytes()) else {
+            return Ok(None);
+        };
+        let proto = crate::protos::git_store::Commit::decode(extras)
+            .map_err(|err| to_read_object_err(err, id))?;
+        Ok(Some(proto))
+    }
+
+    /// Replaces the extra metadata record of the existing commit.
+    ///
+    /// This is meant for repairing corrupted records. The record is validated
+    /// so it can be loaded along with the Git commit.
+    pub fn write_extra_metadata_proto(
+        &self,
+        id: &CommitId,
+        proto: &crate::protos::git_store::Commit,
+    ) -> BackendResult<()> {
+        let git_commit_id = validate_git_object_id(id)?;
+        validate_extras_proto(proto).map_err(|message| BackendError::WriteObject {
+            object_type: "commit",
+            source: message.into(),
+        })?;
+        let locked_repo = self.lock_git_repo();
+        locked_repo
+            .find_object(git_commit_id)
+            .map_err(|err| map_not_found_err(err, id))?
+            .try_into_commit()
+            .map_err(|err| to_read_object_err(err, id))?;
+        let (table, table_lock) = self.read_extra_metadata_table_locked()?;
+        let mut mut_table = table.start_mutation();
+        mut_table.add_entry(id.to_bytes(), proto.encode_to_vec());
+        self.save_extra_metadata_table(mut_table, &table_lock)
+    }
+
     /// Imports the given commits and ancestors from the backing Git repo.
     ///
     /// The `head_ids` may contain commits that have already been imported, but
@@ -550,13 +602,29 @@ fn commit_from_git_without_root_parent(
         .try_to_commit_ref()
         .map_err(|err| to_read_object_err(err, id))?;
 
+    // Use lossy conversion as commit message with "mojibake" is still better than
+    // nothing.
+    // TODO: what should we do with commit.encoding?
+    let message = String::from_utf8_lossy(commit.message);
     // If the git header has a change-id field, we attempt to convert that to a
-    // valid JJ Change Id
-    let change_id = commit
+    // valid JJ Change Id.
+    let header_change_id = commit
         .extra_headers()
         .find(CHANGE_ID_COMMIT_HEADER)
         .and_then(ChangeId::try_from_reverse_hex)
-        .filter(|val| val.as_bytes().len() == CHANGE_ID_LENGTH)
+        .filter(|val| val.as_bytes().len() == CHANGE_ID_LENGTH);
+    // Otherwise, fall back to the change-id trailer. A trailer which disagrees
+    // with the header wasn't added by us, so it's kept in the description.
+    let (description, trailer_change_id) = match split_change_id_trailer(&message) {
+        Some((description, change_id))
+            if header_change_id.as_ref().is_none_or(|id| *id == change_id) =>
+        {
+            (description, Some(change_id))
+        }
+        _ => (message.into_owned(), None),
+    };
+    let change_id = header_change_id
+        .or(trailer_change_id)
         .unwrap_or_else(|| change_id_from_git_commit_id(id));
 
     // shallow commits don't have parents their parents actually fetched, so we
@@ -586,10 +654,6 @@ fn commit_from_git_without_root_parent(
                 MergedTreeId::Legacy(tree_id)
             }
         });
-    // Use lossy conversion as commit message with "mojibake" is still better than
-    // nothing.
-    // TODO: what should we do with commit.encoding?
-    let description = String::from_utf8_lossy(commit.message).into_owned();
     let author = signature_from_git(commit.author());
     let committer = signature_from_git(commit.committer());
 
@@ -626,6 +690,53 @@ fn commit_from_git_without_root_parent(
     })
 }
 
+/// Appends the change-id trailer to the description. If the description
+/// already ends with a trailer paragraph, the trailer is added to it.
+fn append_change_id_trailer(description: &str, change_id: &ChangeId) -> String {
+    let trailer = format!("{CHANGE_ID_COMMIT_TRAILER}: {}\n", change_id.reverse_hex());
+    if description.is_empty() {
+        return trailer;
+    }
+    let mut message = description.to_owned();
+    if !message.ends_with('\n') {
+        message.push('\n');
+    }
+    if trailer::parse_description_trailers(&message).is_empty() {
+        message.push('\n');
+    }
+    message.push_str(&trailer);
+    message
+}
+
+/// Splits the change-id trailer added by [`append_change_id_trailer()`] from
+/// the commit message. Returns `None` if the message doesn't end with a valid
+/// change-id trailer, or if the message isn't formatted the way
+/// [`append_change_id_trailer()`] would format it.
+fn split_change_id_trailer(message: &str) -> Option<(String, ChangeId)> {
+    let body = message.strip_suffix('\n')?;
+    let (rest, last_line) = match body.rsplit_once('\n') {
+        Some((rest, line)) => (Some(rest), line),
+        None => (None, body),
+    };
+    let value = last_line
+        .strip_prefix(CHANGE_ID_COMMIT_TRAILER)?
+        .strip_prefix(": ")?;
+    let change_id = ChangeId::try_from_reverse_hex(value)
+        .filter(|val| val.as_bytes().len() == CHANGE_ID_LENGTH)?;
+    let description = match rest {
+        None => String::new(),
+        // Drop the blank line separating the trailer paragraph if there is one.
+        Some(rest) => match rest.strip_suffix('\n') {
+            Some(stripped) => format!("{stripped}\n"),
+            None => format!("{rest}\n"),
+        },
+    };
+    // A trailer written by hand may be formatted differently, and must be
+    // preserved.
+    (append_change_id_trailer(&description, &change_id) == message)
+        .then_some((description, change_id))
+}
+
 fn change_id_from_git_commit_id(id: &CommitId) -> ChangeId {
     // We reverse the bits of the commit id to create the change id. We don't
     // want to use the first bytes unmodified because then it would be ambiguous
@@ -712,6 +823,27 @@ fn serialize_extras(commit: &Commit) -> Vec<u8> {
     proto.encode_to_vec()
 }
 
+/// Checks that the extras record can be loaded by [`deserialize_extras()`].
+fn validate_extras_proto(proto: &crate::protos::git_store::Commit) -> Result<(), String> {
+    if proto.change_id.len() != CHANGE_ID_LENGTH {
+        return Err(format!(
+            "Invalid change id length (expected {CHANGE_ID_LENGTH} bytes, got {} bytes)",
+            proto.change_id.len()
+        ));
+    }
+    if proto.root_tree.len() % 2 == 0 && !proto.root_tree.is_empty() {
+        return Err("Even number of root tree ids".to_owned());
+    }
+    let mut ids = itertools::chain(&proto.root_tree, &proto.predecessors);
+    if let Some(id) = ids.find(|id| id.len() != HASH_LENGTH) {
+        return Err(format!(
+            "Invalid hash length (expected {HASH_LENGTH} bytes, got {} bytes)",
+            id.len()
+        ));
+    }
+    Ok(())
+}
+
 fn deserialize_extras(commit: &mut Commit, bytes: &[u8]) {
     let proto = crate::protos::git_store::Commit::decode(bytes).unwrap();
     if !proto.change_id.is_empty() {
@@ -1264,7 +1396,11 @@ impl Backend for GitBackend {
         };
         let author = signature_to_git(&contents.author);
         let mut committer = signature_to_git(&contents.committer);
-        let message = &contents.description;
+        let message = if self.write_change_id_trailer {
+            append_change_id_trailer(&contents.description, &contents.change_id)
+        } else {
+            contents.description.clone()
+        };
         if contents.parents.is_empty() {
             return Err(BackendError::Other(
                 "Cannot write a commit with no parents".into(),
@@ -1313,7 +1449,7 @@ impl Backend for GitBackend {
         let (table, table_lock) = self.read_extra_metadata_table_locked()?;
         let id = loop {
             let mut commit = gix::objs::Commit {
-                message: message.to_owned().into(),
+                message: message.clone().into(),
                 tree: git_tree_id,
                 author: author.clone(),
                 committer: committer.clone(),
@@ -1625,6 +1761,8 @@ mod tests {
     use pollster::FutureExt as _;
 
     use super::*;
+    use crate::config::ConfigLayer;
+    use crate::config::ConfigSource;
     use crate::config::StackedConfig;
     use crate::content_hash::blake2b_hash;
     use crate::tests::new_temp_dir;
@@ -1974,6 +2112,137 @@ mod tests {
         );
     }
 
+    #[test]
+    fn round_trip_change_id_via_git_trailer() {
+        let mut config = StackedConfig::with_defaults();
+        config.add_layer(
+            ConfigLayer::parse(
+                ConfigSource::User,
+                "git.write-change-id-header = false\ngit.write-change-id-trailer = true",
+            )
+            .unwrap(),
+        );
+        let settings = UserSettings::from_config(config).unwrap();
+        let temp_dir = new_temp_dir();
+
+        let store_path = temp_dir.path().join("store");
+        fs::create_dir(&store_path).unwrap();
+        let git_repo_path = temp_dir.path().join("git");
+        let git_repo = git_init(git_repo_path);
+
+        let backend = GitBackend::init_external(&settings, &store_path, git_repo.path()).unwrap();
+        let original_change_id = ChangeId::from_hex("1111eeee1111eeee1111eeee1111eeee");
+        let descriptions = [
+            "",
+            "initial\n",
+            "initial\n\nbody\n",
+            "initial\n\nSigned-off-by: Someone <someone@example.com>\n",
+        ];
+        for (i, description) in descriptions.into_iter().enumerate() {
+            let commit = Commit {
+                parents: vec![backend.root_commit_id().clone()],
+                predecessors: vec![],
+                root_tree: MergedTreeId::Legacy(backend.empty_tree_id().clone()),
+                change_id: original_change_id.clone(),
+                description: description.to_string(),
+                author: create_signature(),
+                committer: create_signature(),
+                secure_sig: None,
+            };
+            let (commit_id, _commit) = backend.write_commit(commit, None).block_on().unwrap();
+            let git_commit = git_repo.find_commit(git_id(&commit_id)).unwrap();
+            assert!(git_commit
+                .message_raw()
+                .unwrap()
+                .ends_with(b"JJ-Change-Id: yyyyllllyyyyllllyyyyllllyyyyllll\n"));
+
+            // The change id must be derived from the trailer when the extra
+            // metadata isn't available, and the trailer must not leak into the
+            // description.
+            let empty_store_path = temp_dir.path().join(format!("empty_store{i}"));
+            fs::create_dir(&empty_store_path).unwrap();
+            let no_extra_backend =
+                GitBackend::init_external(&settings, &empty_store_path, git_repo.path()).unwrap();
+            let no_extra_commit = no_extra_backend.read_commit(&commit_id).block_on().unwrap();
+            assert_eq!(no_extra_commit.change_id, original_change_id);
+            assert_eq!(no_extra_commit.description, description);
+        }
+    }
+
+    #[test]
+    fn split_change_id_trailer_added_by_us() {
+        let change_id = ChangeId::from_hex("1111eeee1111eeee1111eeee1111eeee");
+        let trailer = "JJ-Change-Id: yyyyllllyyyyllllyyyyllllyyyyllll";
+        for description in [
+            "",
+            "initial\n",
+            "initial\n\nSigned-off-by: X <x@example.com>\n",
+        ] {
+            let message = append_change_id_trailer(description, &change_id);
+            assert_eq!(
+                split_change_id_trailer(&message),
+                Some((description.to_owned(), change_id.clone()))
+            );
+        }
+
+        // Trailers written in a different format are kept
+        let message = format!("initial\n{trailer}\n");
+        assert_eq!(split_change_id_trailer(&message), None);
+        let message = format!("initial\n\n{trailer}\n\n");
+        assert_eq!(split_change_id_trailer(&message), None);
+        let message = "initial\n\nJJ-Change-Id:  yyyyllllyyyyllllyyyyllllyyyyllll\n";
+        assert_eq!(split_change_id_trailer(message), None);
+        let message = format!("initial\n\nSigned-off-by: X <x@example.com>\n\n{trailer}\n");
+        assert_eq!(split_change_id_trailer(&message), None);
+        // Invalid change ids are kept
+        let message = "initial\n\nJJ-Change-Id: yyyy\n";
+        assert_eq!(split_change_id_trailer(message), None);
+    }
+
+    #[test]
+    fn read_change_id_trailer_disagreeing_with_header() {
+        let settings = user_settings();
+        let temp_dir = new_temp_dir();
+        let store_path = temp_dir.path();
+        let git_repo_path = temp_dir.path().join("git");
+        let git_repo = git_init(git_repo_path);
+        let backend = GitBackend::init_external(&settings, store_path, git_repo.path()).unwrap();
+
+        // A trailer which doesn't match the change-id header wasn't added by
+        // us, so it's part of the description.
+        let message = "initial\n\nJJ-Change-Id: yyyyllllyyyyllllyyyyllllyyyyllll\n";
+        let signature = gix::actor::Signature {
+            name: GIT_USER.into(),
+            email: GIT_EMAIL.into(),
+            time: gix::date::Time::now_utc(),
+        };
+        let empty_tree_id =
+            gix::ObjectId::from_hex(b"4b825dc642cb6eb9a060e54bf8d69288fbee4904").unwrap();
+        let git_commit_id = git_repo
+            .write_object(&gix::objs::Commit {
+                tree: empty_tree_id,
+                parents: smallvec::SmallVec::new(),
+                author: signature.clone(),
+                committer: signature,
+                encoding: None,
+                message: message.into(),
+                extra_headers: vec![(
+                    CHANGE_ID_COMMIT_HEADER.into(),
+                    "zzzzkkkkzzzzkkkkzzzzkkkkzzzzkkkk".into(),
+                )],
+            })
+            .unwrap();
+        let commit = backend
+            .read_commit(&CommitId::from_bytes(git_commit_id.as_bytes()))
+            .block_on()
+            .unwrap();
+        assert_eq!(
+            commit.change_id.reverse_hex(),
+            "zzzzkkkkzzzzkkkkzzzzkkkkzzzzkkkk"
+        );
+        assert_eq!(commit.description, message);
+    }
+
     #[test]
     fn read_empty_string_placeholder() {
         let git_signature1 = gix::actor::Signature {
@@ -2248,6 +2517,52 @@ mod tests {
         assert!(git_ref_ids.iter().any(|id| *id == git_id(&commit_id)));
     }
 
+    #[test]
+    fn read_write_extra_metadata_proto() {
+        let settings = user_settings();
+        let temp_dir = new_temp_dir();
+        let backend = GitBackend::init_internal(&settings, temp_dir.path()).unwrap();
+        let signature = Signature {
+            name: "Someone".to_string(),
+            email: "someone@example.com".to_string(),
+            timestamp: Timestamp {
+                timestamp: MillisSinceEpoch(0),
+                tz_offset: 0,
+            },
+        };
+        let commit = Commit {
+            parents: vec![backend.root_commit_id().clone()],
+            predecessors: vec![],
+            root_tree: MergedTreeId::Legacy(backend.empty_tree_id().clone()),
+            change_id: ChangeId::new(vec![42; 16]),
+            description: "initial".to_string(),
+            author: signature.clone(),
+            committer: signature,
+            secure_sig: None,
+        };
+        let commit_id = backend.write_commit(commit, None).block_on().unwrap().0;
+
+        let mut proto = backend
+            .read_extra_metadata_proto(&commit_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(proto.change_id, vec![42; 16]);
+        proto.change_id = vec![43; 16];
+        backend
+            .write_extra_metadata_proto(&commit_id, &proto)
+            .unwrap();
+        let commit = backend.read_commit(&commit_id).block_on().unwrap();
+        assert_eq!(commit.change_id, ChangeId::new(vec![43; 16]));
+
+        // Invalid records are rejected
+        proto.change_id = vec![44; 3];
+        assert!(backend
+            .write_extra_metadata_proto(&commit_id, &proto)
+            .is_err());
+        let commit = backend.read_commit(&commit_id).block_on().unwrap();
+        assert_eq!(commit.change_id, ChangeId::new(vec![43; 16]));
+    }
+
     #[test]
     fn import_head_commits_duplicates() {
         let settings = user_settings();
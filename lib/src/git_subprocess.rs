@@ -28,6 +28,7 @@ use bstr::ByteSlice as _;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::git::GitFetchTags;
 use crate::git::GitPushStats;
 use crate::git::Progress;
 use crate::git::RefSpec;
@@ -159,6 +160,7 @@ impl<'a> GitSubprocessContext<'a> {
         &self,
         remote_name: &RemoteName,
         refspecs: &[RefSpec],
+        tags: GitFetchTags,
         callbacks: &mut RemoteCallbacks<'_>,
         depth: Option<NonZeroU32>,
     ) -> Result<Option<String>, GitSubprocessError> {
@@ -173,6 +175,15 @@ impl<'a> GitSubprocessContext<'a> {
         if callbacks.progress.is_some() {
             command.arg("--progress");
         }
+        match tags {
+            GitFetchTags::Reachable => {}
+            GitFetchTags::All => {
+                command.arg("--tags");
+            }
+            GitFetchTags::None => {
+                command.arg("--no-tags");
+            }
+        }
         if let Some(d) = depth {
             command.arg(format!("--depth={d}"));
         }
@@ -228,6 +239,82 @@ impl<'a> GitSubprocessContext<'a> {
         Ok(maybe_branch.map(Into::into))
     }
 
+    /// Lists the branches of the remote without fetching them:
+    ///
+    /// `git ls-remote --heads <remote_name>`
+    pub(crate) fn spawn_ls_remote_branches(
+        &self,
+        remote_name: &RemoteName,
+    ) -> Result<Vec<RefNameBuf>, GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        command.args(["ls-remote", "--heads", "--", remote_name.as_str()]);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+
+        let output = parse_git_ls_remote_output(output)?;
+        parse_git_ls_remote_branches(&output.stdout)
+    }
+
+    /// Lists the tags of the remote without fetching them:
+    ///
+    /// `git ls-remote --tags <remote_name>`
+    pub(crate) fn spawn_ls_remote_tags(
+        &self,
+        remote_name: &RemoteName,
+    ) -> Result<Vec<RefNameBuf>, GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        command.args(["ls-remote", "--tags", "--", remote_name.as_str()]);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+
+        let output = parse_git_ls_remote_output(output)?;
+        parse_git_ls_remote_tags(&output.stdout)
+    }
+
+    /// Lists the local tags:
+    ///
+    /// `git for-each-ref --format=%(refname:lstrip=2) refs/tags/`
+    pub(crate) fn spawn_list_tags(&self) -> Result<Vec<RefNameBuf>, GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        command.args(["for-each-ref", "--format=%(refname:lstrip=2)", "refs/tags/"]);
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        output
+            .stdout
+            .lines()
+            .map(|name| {
+                name.to_str().map(RefNameBuf::from).map_err(|e| {
+                    GitSubprocessError::External(format!(
+                        "git for-each-ref output is not utf-8: {e:?}"
+                    ))
+                })
+            })
+            .try_collect()
+    }
+
+    /// Deletes particular local tags
+    pub(crate) fn spawn_tag_delete(
+        &self,
+        tags_to_delete: &[RefNameBuf],
+    ) -> Result<(), GitSubprocessError> {
+        if tags_to_delete.is_empty() {
+            return Ok(());
+        }
+        tracing::debug!(?tags_to_delete, "deleting tags");
+        let mut command = self.create_command();
+        command.stdout(Stdio::null());
+        command.args(["tag", "--delete", "--"]);
+        command.args(tags_to_delete.iter().map(|name| name.as_str()));
+        let output = wait_with_output(self.spawn_cmd(command)?)?;
+        if !output.status.success() {
+            return Err(external_git_error(&output.stderr));
+        }
+        Ok(())
+    }
+
     /// Push references to git
     ///
     /// All pushes are forced, using --force-with-lease to perform a test&set
@@ -438,6 +525,52 @@ fn parse_git_remote_show_default_branch(
         .map(|b| b.map(|x| x.to_string()))
 }
 
+fn parse_git_ls_remote_output(output: Output) -> Result<Output, GitSubprocessError> {
+    if output.status.success() {
+        return Ok(output);
+    }
+
+    // There are some git errors we want to parse out
+    if let Some(option) = parse_unknown_option(&output.stderr) {
+        return Err(GitSubprocessError::UnsupportedGitOption(option));
+    }
+
+    if let Some(remote) = parse_no_such_remote(&output.stderr) {
+        return Err(GitSubprocessError::NoSuchRepository(remote));
+    }
+
+    Err(external_git_error(&output.stderr))
+}
+
+// git ls-remote outputs one `<oid>\t<ref>` line per ref
+fn parse_git_ls_remote_tags(stdout: &[u8]) -> Result<Vec<RefNameBuf>, GitSubprocessError> {
+    stdout
+        .lines()
+        .filter_map(|line| line.split_once_str("\t"))
+        .filter_map(|(_oid, full_name)| full_name.strip_prefix(b"refs/tags/"))
+        // Annotated tags are also listed with the peeled commit
+        .filter(|name| !name.ends_with(b"^{}"))
+        .map(|name| {
+            name.to_str().map(RefNameBuf::from).map_err(|e| {
+                GitSubprocessError::External(format!("git ls-remote output is not utf-8: {e:?}"))
+            })
+        })
+        .try_collect()
+}
+
+fn parse_git_ls_remote_branches(stdout: &[u8]) -> Result<Vec<RefNameBuf>, GitSubprocessError> {
+    stdout
+        .lines()
+        .filter_map(|line| line.split_once_str("\t"))
+        .filter_map(|(_oid, full_name)| full_name.strip_prefix(b"refs/heads/"))
+        .map(|name| {
+            name.to_str().map(RefNameBuf::from).map_err(|e| {
+                GitSubprocessError::External(format!("git ls-remote output is not utf-8: {e:?}"))
+            })
+        })
+        .try_collect()
+}
+
 // git-push porcelain has the following format (per line)
 // `<flag>\t<from>:<to>\t<summary> (<reason>)`
 //
@@ -783,6 +916,34 @@ and the repository exists. "###;
 Done";
     const SAMPLE_OK_STDERR: &[u8] = b"";
 
+    #[test]
+    fn test_parse_git_ls_remote_branches() {
+        let stdout = b"\
+0123456789012345678901234567890123456789\trefs/heads/main
+0123456789012345678901234567890123456789\trefs/heads/feature/a
+0123456789012345678901234567890123456789\trefs/tags/v1.0
+";
+        assert_eq!(
+            parse_git_ls_remote_branches(stdout).unwrap(),
+            [RefNameBuf::from("main"), RefNameBuf::from("feature/a")]
+        );
+        assert!(parse_git_ls_remote_branches(b"").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_git_ls_remote_tags() {
+        let stdout = b"\
+0123456789012345678901234567890123456789\trefs/tags/v1.0
+0123456789012345678901234567890123456789\trefs/tags/v1.0^{}
+0123456789012345678901234567890123456789\trefs/tags/v2.0
+";
+        assert_eq!(
+            parse_git_ls_remote_tags(stdout).unwrap(),
+            [RefNameBuf::from("v1.0"), RefNameBuf::from("v2.0")]
+        );
+        assert!(parse_git_ls_remote_tags(b"").unwrap().is_empty());
+    }
+
     #[test]
     fn test_parse_no_such_remote() {
         assert_eq!(
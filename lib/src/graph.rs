@@ -76,11 +76,15 @@ fn reachable_targets<N>(edges: &[GraphEdge<N>]) -> impl DoubleEndedIterator<Item
 }
 
 /// Creates new graph in which nodes and edges are reversed.
-pub fn reverse_graph<N, ID: Clone + Eq + Hash, E>(
+///
+/// The input graph is buffered entirely since the first node of the reversed
+/// graph is the last input node, and the edges of a reversed node point to
+/// input nodes that came before it.
+pub fn reverse_graph<N, ID: Clone + Eq + Hash, E, F: Fn(&N) -> &ID>(
     input: impl Iterator<Item = Result<GraphNode<N, ID>, E>>,
-    as_id: impl Fn(&N) -> &ID,
-) -> Result<Vec<GraphNode<N, ID>>, E> {
-    let mut entries = vec![];
+    as_id: F,
+) -> Result<ReverseGraphIterator<N, ID, F>, E> {
+    let mut nodes = vec![];
     let mut reverse_edges: HashMap<ID, Vec<GraphEdge<ID>>> = HashMap::new();
     for item in input {
         let (node, edges) = item?;
@@ -90,15 +94,51 @@ pub fn reverse_graph<N, ID: Clone + Eq + Hash, E>(
                 edge_type,
             });
         }
-        entries.push(node);
+        nodes.push(node);
     }
+    Ok(ReverseGraphIterator {
+        nodes,
+        reverse_edges,
+        as_id,
+    })
+}
+
+/// Iterator over the nodes of a reversed graph. See [`reverse_graph()`].
+#[derive(Clone, Debug)]
+pub struct ReverseGraphIterator<N, ID, F> {
+    /// Input nodes, the last of which is emitted first.
+    nodes: Vec<N>,
+    /// Reversed edges keyed by the node they originate from.
+    reverse_edges: HashMap<ID, Vec<GraphEdge<ID>>>,
+    as_id: F,
+}
 
-    let mut items = vec![];
-    for node in entries.into_iter().rev() {
-        let edges = reverse_edges.remove(as_id(&node)).unwrap_or_default();
-        items.push((node, edges));
+impl<N, ID, F> Iterator for ReverseGraphIterator<N, ID, F>
+where
+    ID: Eq + Hash,
+    F: Fn(&N) -> &ID,
+{
+    type Item = GraphNode<N, ID>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.pop()?;
+        let edges = self
+            .reverse_edges
+            .remove((self.as_id)(&node))
+            .unwrap_or_default();
+        Some((node, edges))
     }
-    Ok(items)
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.nodes.len(), Some(self.nodes.len()))
+    }
+}
+
+impl<N, ID, F> ExactSizeIterator for ReverseGraphIterator<N, ID, F>
+where
+    ID: Eq + Hash,
+    F: Fn(&N) -> &ID,
+{
 }
 
 /// Graph iterator adapter to group topological branches.
@@ -424,6 +464,49 @@ mod tests {
         ");
     }
 
+    #[test]
+    fn test_reverse_graph() {
+        let graph = [
+            ('D', vec![direct('C'), indirect('B')]),
+            ('C', vec![direct('A')]),
+            ('B', vec![missing('X')]),
+            ('A', vec![]),
+        ]
+        .map(Ok::<_, Infallible>);
+        let reversed = reverse_graph(graph.iter().cloned(), |c| c).unwrap();
+        insta::assert_snapshot!(format_graph(reversed.map(Ok)), @r"
+        A  direct(C)
+        │
+        │ B  indirect(D)
+        │ ╷
+        C ╷  direct(D)
+        ├─╯
+        D
+        ");
+
+        // The reversed nodes are emitted one by one. Edges to missing nodes
+        // are dropped since there's no node to attach them to.
+        let mut iter = reverse_graph(graph.iter().cloned(), |c| c).unwrap();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next().unwrap(), ('A', vec![direct('C')]));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next().unwrap(), ('B', vec![indirect('D')]));
+        assert_eq!(iter.next().unwrap(), ('C', vec![direct('D')]));
+        assert_eq!(iter.next().unwrap(), ('D', vec![]));
+        assert!(iter.next().is_none());
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn test_reverse_graph_error() {
+        let graph = [
+            Ok(('B', vec![direct('A')])),
+            Err("error"),
+            Ok(('A', vec![])),
+        ];
+        assert_eq!(reverse_graph(graph.into_iter(), |c| c).err(), Some("error"));
+    }
+
     type TopoGrouped<N, I> = TopoGroupedGraphIterator<N, N, I, fn(&N) -> &N>;
 
     fn topo_grouped<I, E>(graph_iter: I) -> TopoGrouped<char, I::IntoIter>
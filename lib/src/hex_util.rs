@@ -113,6 +113,102 @@ pub fn common_hex_len(bytes_a: &[u8], bytes_b: &[u8]) -> usize {
         .unwrap_or_else(|| bytes_a.len().min(bytes_b.len()) * 2)
 }
 
+/// Serde helpers to represent `bytes` fields of protobuf messages as hex
+/// strings.
+///
+/// This is used by the generated protobuf types through
+/// `#[serde(with = "crate::hex_util::serde_hex")]`.
+pub(crate) mod serde_hex {
+    use std::collections::HashMap;
+
+    use serde::de::DeserializeOwned;
+    use serde::de::Error as _;
+    use serde::Deserialize as _;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    use super::decode_hex;
+    use super::encode_hex;
+
+    /// Bytes container which can be converted from/to hex representation.
+    pub trait HexBytes: Sized {
+        type Hex: Serialize + DeserializeOwned;
+
+        fn to_hex(&self) -> Self::Hex;
+        fn from_hex(hex: Self::Hex) -> Option<Self>;
+    }
+
+    impl HexBytes for Vec<u8> {
+        type Hex = String;
+
+        fn to_hex(&self) -> Self::Hex {
+            encode_hex(self)
+        }
+
+        fn from_hex(hex: Self::Hex) -> Option<Self> {
+            decode_hex(hex)
+        }
+    }
+
+    impl HexBytes for Option<Vec<u8>> {
+        type Hex = Option<String>;
+
+        fn to_hex(&self) -> Self::Hex {
+            self.as_deref().map(encode_hex)
+        }
+
+        fn from_hex(hex: Self::Hex) -> Option<Self> {
+            match hex {
+                Some(hex) => Some(Some(decode_hex(hex)?)),
+                None => Some(None),
+            }
+        }
+    }
+
+    impl HexBytes for Vec<Vec<u8>> {
+        type Hex = Vec<String>;
+
+        fn to_hex(&self) -> Self::Hex {
+            self.iter().map(|bytes| encode_hex(bytes)).collect()
+        }
+
+        fn from_hex(hex: Self::Hex) -> Option<Self> {
+            hex.into_iter().map(decode_hex).collect()
+        }
+    }
+
+    impl HexBytes for HashMap<String, Vec<u8>> {
+        type Hex = HashMap<String, String>;
+
+        fn to_hex(&self) -> Self::Hex {
+            self.iter()
+                .map(|(key, bytes)| (key.clone(), encode_hex(bytes)))
+                .collect()
+        }
+
+        fn from_hex(hex: Self::Hex) -> Option<Self> {
+            hex.into_iter()
+                .map(|(key, hex)| Some((key, decode_hex(hex)?)))
+                .collect()
+        }
+    }
+
+    pub fn serialize<T: HexBytes, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.to_hex().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: HexBytes, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let hex = T::Hex::deserialize(deserializer)?;
+        T::from_hex(hex).ok_or_else(|| D::Error::custom("invalid hex string"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,170 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builds a tree from a subset of the changes between two trees.
+//!
+//! This is the tree-building part of partial operations such as `jj split -i`,
+//! independent of the UI used to select the changes.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use bstr::BString;
+use thiserror::Error;
+
+use crate::backend::BackendError;
+use crate::backend::MergedTreeId;
+use crate::backend::TreeValue;
+use crate::conflicts::materialize_tree_value;
+use crate::conflicts::MaterializedFileValue;
+use crate::conflicts::MaterializedTreeValue;
+use crate::diff::Diff;
+use crate::diff::DiffHunkKind;
+use crate::merge::Merge;
+use crate::merged_tree::MergedTree;
+use crate::merged_tree::MergedTreeBuilder;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+
+/// Changes to select from a single file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FileSelection {
+    /// Selects the right side of the file as is, including changes to the file
+    /// type and executable bit.
+    All,
+    /// Selects the changed hunks at the given indices.
+    ///
+    /// The indices count the [`DiffHunkKind::Different`] hunks of
+    /// [`Diff::by_line()`] between the left and right file contents, starting
+    /// at 0. Indices out of range are ignored. Both sides must be regular
+    /// files or absent.
+    Hunks(BTreeSet<usize>),
+}
+
+/// Error building a tree from selected hunks.
+#[derive(Debug, Error)]
+pub enum HunkSelectionError {
+    /// Error while contacting the Backend.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    /// Hunks were selected in a path which isn't a regular file.
+    #[error("Cannot select hunks in {path}: {reason}", path = path.as_internal_file_string())]
+    UnsupportedFile {
+        /// Path of the file.
+        path: RepoPathBuf,
+        /// Why the hunks can't be selected.
+        reason: String,
+    },
+}
+
+/// Builds a tree by applying the `selection` of changes between `left_tree`
+/// and `right_tree` to `left_tree`.
+///
+/// Paths not in `selection` keep their contents in `left_tree`.
+pub async fn select_hunks(
+    left_tree: &MergedTree,
+    right_tree: &MergedTree,
+    selection: &BTreeMap<RepoPathBuf, FileSelection>,
+) -> Result<MergedTreeId, HunkSelectionError> {
+    let store = left_tree.store();
+    let mut tree_builder = MergedTreeBuilder::new(left_tree.id().clone());
+    for (path, file_selection) in selection {
+        let right_value = right_tree.path_value_async(path).await?;
+        let hunk_indices = match file_selection {
+            FileSelection::All => {
+                tree_builder.set_or_remove(path.clone(), right_value);
+                continue;
+            }
+            FileSelection::Hunks(hunk_indices) => hunk_indices,
+        };
+        let left_value = left_tree.path_value_async(path).await?;
+        let left_absent = left_value.is_absent();
+        let right_absent = right_value.is_absent();
+        let left_file =
+            to_file_value(path, materialize_tree_value(store, path, left_value).await?)?;
+        let right_file = to_file_value(
+            path,
+            materialize_tree_value(store, path, right_value).await?,
+        )?;
+        let (left_text, right_text, file_template) = match (left_file, right_file) {
+            (Some(mut left), Some(mut right)) => (
+                left.read_all(path).await?,
+                right.read_all(path).await?,
+                left,
+            ),
+            (Some(mut left), None) => (left.read_all(path).await?, vec![], left),
+            (None, Some(mut right)) => (vec![], right.read_all(path).await?, right),
+            (None, None) => continue,
+        };
+
+        let diff = Diff::by_line([&left_text, &right_text]);
+        let mut new_text = BString::default();
+        let mut num_changed = 0;
+        let mut num_selected = 0;
+        for hunk in diff.hunks() {
+            let side = match hunk.kind {
+                DiffHunkKind::Matching => 0,
+                DiffHunkKind::Different => {
+                    let selected = hunk_indices.contains(&num_changed);
+                    num_changed += 1;
+                    if selected {
+                        num_selected += 1;
+                        1
+                    } else {
+                        0
+                    }
+                }
+            };
+            new_text.extend_from_slice(hunk.contents[side]);
+        }
+
+        // An added file stays absent if nothing was selected, and a deleted
+        // file is removed only if everything was selected.
+        let new_value = if (left_absent && num_selected == 0)
+            || (right_absent && num_selected == num_changed)
+        {
+            Merge::absent()
+        } else {
+            let id = store.write_file(path, &mut new_text.as_slice()).await?;
+            Merge::normal(TreeValue::File {
+                id,
+                executable: file_template.executable,
+                copy_id: file_template.copy_id,
+            })
+        };
+        tree_builder.set_or_remove(path.clone(), new_value);
+    }
+    Ok(tree_builder.write_tree(store)?)
+}
+
+fn to_file_value(
+    path: &RepoPath,
+    value: MaterializedTreeValue,
+) -> Result<Option<MaterializedFileValue>, HunkSelectionError> {
+    let reason = match value {
+        MaterializedTreeValue::Absent => return Ok(None),
+        MaterializedTreeValue::File(file) => return Ok(Some(file)),
+        MaterializedTreeValue::AccessDenied(err) => format!("Access is denied: {err}"),
+        MaterializedTreeValue::Symlink { .. } => "Is a symlink".to_owned(),
+        MaterializedTreeValue::FileConflict(_) | MaterializedTreeValue::OtherConflict { .. } => {
+            "Is a conflict".to_owned()
+        }
+        MaterializedTreeValue::GitSubmodule(_) => "Is a Git submodule".to_owned(),
+        MaterializedTreeValue::Tree(_) => "Is a directory".to_owned(),
+    };
+    Err(HunkSelectionError::UnsupportedFile {
+        path: path.to_owned(),
+        reason,
+    })
+}
@@ -193,6 +193,8 @@ impl IdPrefixIndex<'_> {
         disambiguate_prefix_with_refs(repo.view(), &commit_id.to_string(), len)
     }
 
+    /// Returns the shortest length of a prefix of `commit_id` that is unique
+    /// among commit IDs, without accounting for refs that would shadow it.
     pub fn shortest_commit_prefix_len_exact(&self, repo: &dyn Repo, commit_id: &CommitId) -> usize {
         if let Some(indexes) = self.indexes {
             if let Some(lookup) = indexes
@@ -242,7 +244,9 @@ impl IdPrefixIndex<'_> {
         disambiguate_prefix_with_refs(repo.view(), &change_id.to_string(), len)
     }
 
-    fn shortest_change_prefix_len_exact(&self, repo: &dyn Repo, change_id: &ChangeId) -> usize {
+    /// Returns the shortest length of a prefix of `change_id` that is unique
+    /// among change IDs, without accounting for refs that would shadow it.
+    pub fn shortest_change_prefix_len_exact(&self, repo: &dyn Repo, change_id: &ChangeId) -> usize {
         if let Some(indexes) = self.indexes {
             if let Some(lookup) = indexes
                 .change_index
@@ -160,7 +160,7 @@ pub trait MutableIndex {
         heads: &mut dyn Iterator<Item = &CommitId>,
     ) -> Box<dyn ChangeIdIndex + '_>;
 
-    fn add_commit(&mut self, commit: &Commit);
+    fn add_commit(&mut self, commit: &Commit) -> Result<(), IndexError>;
 
     fn merge_in(&mut self, other: &dyn ReadonlyIndex);
 }
@@ -173,6 +173,18 @@ pub trait ChangeIdIndex: Send + Sync {
     /// The order of the returned commit IDs is unspecified.
     fn resolve_prefix(&self, prefix: &HexPrefix) -> PrefixResolution<Vec<CommitId>>;
 
+    /// Looks up the visible commit IDs associated with the given `change_id`.
+    /// Returns `None` if there are no such commits.
+    ///
+    /// The order of the returned commit IDs is unspecified.
+    fn resolve_change_id(&self, change_id: &ChangeId) -> Option<Vec<CommitId>> {
+        match self.resolve_prefix(&HexPrefix::from_id(change_id)) {
+            PrefixResolution::NoMatch => None,
+            PrefixResolution::SingleMatch(entries) => Some(entries),
+            PrefixResolution::AmbiguousMatch => panic!("complete change_id should be unambiguous"),
+        }
+    }
+
     /// This function returns the shortest length of a prefix of `key` that
     /// disambiguates it from every other key in the index.
     ///
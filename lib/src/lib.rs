@@ -31,6 +31,7 @@ pub mod content_hash;
 pub mod absorb;
 pub mod annotate;
 pub mod backend;
+pub mod bisect;
 pub mod commit;
 pub mod commit_builder;
 pub mod config;
@@ -41,6 +42,7 @@ pub mod dag_walk;
 pub mod default_index;
 pub mod default_submodule_store;
 pub mod diff;
+pub mod diff_presentation;
 pub mod dsl_util;
 pub mod evolution;
 pub mod extensions_map;
@@ -74,6 +76,7 @@ pub mod gitignore;
 pub mod gpg_signing;
 pub mod graph;
 pub mod hex_util;
+pub mod hunk_selection;
 pub mod id_prefix;
 pub mod index;
 pub mod local_working_copy;
@@ -81,7 +84,9 @@ pub mod lock;
 pub mod matchers;
 pub mod merge;
 pub mod merged_tree;
+pub mod metrics;
 pub mod object_id;
+pub mod obsolescence;
 pub mod op_heads_store;
 pub mod op_store;
 pub mod op_walk;
@@ -108,6 +113,7 @@ pub mod simple_op_store;
 pub mod ssh_signing;
 pub mod stacked_table;
 pub mod store;
+pub mod store_migration;
 pub mod str_util;
 pub mod submodule_store;
 #[cfg(feature = "testing")]
@@ -117,6 +123,7 @@ pub mod trailer;
 pub mod transaction;
 pub mod tree;
 pub mod tree_builder;
+pub mod tree_merge_cache;
 pub mod union_find;
 pub mod view;
 pub mod working_copy;
@@ -54,6 +54,7 @@ use rayon::prelude::ParallelIterator as _;
 use tempfile::NamedTempFile;
 use thiserror::Error;
 use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt as _;
 use tracing::instrument;
 use tracing::trace_span;
 
@@ -97,6 +98,8 @@ use crate::merge::MergedTreeValue;
 use crate::merged_tree::MergedTree;
 use crate::merged_tree::MergedTreeBuilder;
 use crate::merged_tree::TreeDiffEntry;
+use crate::metrics;
+use crate::metrics::DurationTimer;
 use crate::object_id::ObjectId as _;
 use crate::op_store::OperationId;
 use crate::ref_name::WorkspaceName;
@@ -111,6 +114,7 @@ use crate::working_copy::CheckoutOptions;
 use crate::working_copy::CheckoutStats;
 use crate::working_copy::LockedWorkingCopy;
 use crate::working_copy::ResetError;
+use crate::working_copy::SnapshotChangeReason;
 use crate::working_copy::SnapshotError;
 use crate::working_copy::SnapshotOptions;
 use crate::working_copy::SnapshotProgress;
@@ -119,6 +123,7 @@ use crate::working_copy::UntrackedReason;
 use crate::working_copy::WorkingCopy;
 use crate::working_copy::WorkingCopyFactory;
 use crate::working_copy::WorkingCopyStateError;
+use crate::working_copy::EMPTY_DIRECTORY_MARKER;
 
 /// On-disk state of file executable bit.
 // TODO: maybe better to preserve the executable bit on all platforms, and
@@ -533,6 +538,43 @@ fn is_file_state_entries_proto_unique_and_sorted(
         .all(|(path1, path2)| path1 < path2)
 }
 
+/// Checks that the tree state record can be loaded without panicking.
+fn validate_tree_state_proto(proto: &crate::protos::working_copy::TreeState) -> Result<(), String> {
+    if proto.tree_ids.is_empty() {
+        if proto.legacy_tree_id.is_empty() {
+            return Err("No tree id".to_owned());
+        }
+    } else if proto.tree_ids.len() % 2 == 0 {
+        return Err("Even number of tree ids".to_owned());
+    }
+    for entry in &proto.file_states {
+        RepoPath::from_internal_string(&entry.path)
+            .map_err(|err| format!("Invalid file state path {:?}: {err}", entry.path))?;
+    }
+    let paths_unique = proto
+        .file_states
+        .iter()
+        .map(|entry| RepoPath::from_internal_string(&entry.path).unwrap())
+        .sorted()
+        .tuple_windows()
+        .all(|(path1, path2)| path1 < path2);
+    if !paths_unique {
+        return Err("Duplicate file state paths".to_owned());
+    }
+    if proto.is_file_states_sorted
+        && !is_file_state_entries_proto_unique_and_sorted(&proto.file_states)
+    {
+        return Err("File states are not sorted".to_owned());
+    }
+    if let Some(sparse_patterns) = &proto.sparse_patterns {
+        for prefix in &sparse_patterns.prefixes {
+            RepoPathBuf::from_internal_string(prefix)
+                .map_err(|err| format!("Invalid sparse pattern {prefix:?}: {err}"))?;
+        }
+    }
+    Ok(())
+}
+
 fn sparse_patterns_from_proto(
     proto: Option<&crate::protos::working_copy::SparsePatterns>,
 ) -> Vec<RepoPathBuf> {
@@ -610,6 +652,22 @@ fn create_parent_dirs(
     Ok(Some(file_path))
 }
 
+/// Returns true if `path` is the [`EMPTY_DIRECTORY_MARKER`] of a directory.
+fn is_empty_directory_marker(path: &RepoPath) -> bool {
+    path.split()
+        .is_some_and(|(_, name)| name.as_internal_str() == EMPTY_DIRECTORY_MARKER)
+}
+
+/// Removes empty directories from `dir` up to the root of the working copy.
+fn remove_empty_dirs(mut dir: &Path) {
+    loop {
+        if fs::remove_dir(dir).is_err() {
+            break;
+        }
+        dir = dir.parent().unwrap();
+    }
+}
+
 /// Removes existing file named `disk_path` if any. Returns `Ok(true)` if the
 /// file was there and got removed, meaning that new file can be safely created.
 ///
@@ -765,6 +823,8 @@ pub enum TreeStateError {
     WriteTreeState { path: PathBuf, source: io::Error },
     #[error("Persisting tree state to file {path}")]
     PersistTreeState { path: PathBuf, source: io::Error },
+    #[error("Invalid tree state for {path}: {message}")]
+    InvalidTreeState { path: PathBuf, message: String },
     #[error("Filesystem monitor error")]
     Fsmonitor(#[source] Box<dyn Error + Send + Sync>),
 }
@@ -839,6 +899,58 @@ impl TreeState {
         Ok(wc)
     }
 
+    /// Reads the stored record of the tree state at `state_path` without
+    /// converting it.
+    pub fn read_proto(
+        state_path: &Path,
+    ) -> Result<crate::protos::working_copy::TreeState, TreeStateError> {
+        let tree_state_path = state_path.join("tree_state");
+        let buf = fs::read(&tree_state_path).map_err(|err| TreeStateError::ReadTreeState {
+            path: tree_state_path.clone(),
+            source: err,
+        })?;
+        crate::protos::working_copy::TreeState::decode(&*buf).map_err(|err| {
+            TreeStateError::DecodeTreeState {
+                path: tree_state_path,
+                source: err,
+            }
+        })
+    }
+
+    /// Replaces the stored record of the tree state at `state_path`.
+    ///
+    /// This is meant for repairing corrupted records. The record is validated
+    /// so it can be loaded by [`TreeState::load()`].
+    pub fn write_proto(
+        state_path: &Path,
+        proto: &crate::protos::working_copy::TreeState,
+    ) -> Result<(), TreeStateError> {
+        let tree_state_path = state_path.join("tree_state");
+        validate_tree_state_proto(proto).map_err(|message| TreeStateError::InvalidTreeState {
+            path: tree_state_path.clone(),
+            message,
+        })?;
+        let mut temp_file =
+            NamedTempFile::new_in(state_path).map_err(|err| TreeStateError::WriteTreeState {
+                path: state_path.to_owned(),
+                source: err,
+            })?;
+        temp_file
+            .as_file_mut()
+            .write_all(&proto.encode_to_vec())
+            .map_err(|err| TreeStateError::WriteTreeState {
+                path: state_path.to_owned(),
+                source: err,
+            })?;
+        temp_file.persist(&tree_state_path).map_err(
+            |tempfile::PersistError { error, file: _ }| TreeStateError::PersistTreeState {
+                path: tree_state_path.clone(),
+                source: error,
+            },
+        )?;
+        Ok(())
+    }
+
     fn update_own_mtime(&mut self) {
         if let Ok(metadata) = self.state_path.join("tree_state").symlink_metadata() {
             self.own_mtime = mtime_from_metadata(&metadata);
@@ -979,6 +1091,7 @@ impl TreeState {
         &mut self,
         options: &SnapshotOptions,
     ) -> Result<(bool, SnapshotStats), SnapshotError> {
+        let _timer = DurationTimer::start(metrics::WORKING_COPY_SNAPSHOT_DURATION);
         let &SnapshotOptions {
             ref base_ignores,
             ref fsmonitor_settings,
@@ -986,6 +1099,9 @@ impl TreeState {
             start_tracking_matcher,
             max_new_file_size,
             conflict_marker_style,
+            track_empty_directories,
+            detect_conflict_markers,
+            dry_run,
         } = options;
 
         let sparse_matcher = self.sparse_matcher();
@@ -1012,6 +1128,8 @@ impl TreeState {
         let (file_states_tx, file_states_rx) = channel();
         let (untracked_paths_tx, untracked_paths_rx) = channel();
         let (deleted_files_tx, deleted_files_rx) = channel();
+        let (conflict_marker_paths_tx, conflict_marker_paths_rx) = channel();
+        let (changed_paths_tx, changed_paths_rx) = channel();
 
         trace_span!("traverse filesystem").in_scope(|| -> Result<(), SnapshotError> {
             let snapshotter = FileSnapshotter {
@@ -1024,10 +1142,14 @@ impl TreeState {
                 file_states_tx,
                 untracked_paths_tx,
                 deleted_files_tx,
+                conflict_marker_paths_tx,
+                changed_paths_tx: dry_run.then_some(changed_paths_tx),
                 error: OnceLock::new(),
                 progress,
                 max_new_file_size,
                 conflict_marker_style,
+                track_empty_directories,
+                detect_conflict_markers,
             };
             let directory_to_visit = DirectoryToVisit {
                 dir: RepoPathBuf::root(),
@@ -1044,18 +1166,45 @@ impl TreeState {
             snapshotter.into_result()
         })?;
 
-        let stats = SnapshotStats {
+        let mut stats = SnapshotStats {
             untracked_paths: untracked_paths_rx.into_iter().collect(),
+            conflict_marker_paths: conflict_marker_paths_rx.into_iter().sorted().collect(),
+            ..SnapshotStats::default()
         };
+        if dry_run {
+            stats.changed_paths = changed_paths_rx.into_iter().collect();
+            for path in deleted_files_rx {
+                stats
+                    .changed_paths
+                    .insert(path, SnapshotChangeReason::Removed);
+            }
+            for reason in stats.changed_paths.values() {
+                match reason {
+                    SnapshotChangeReason::NewFile => stats.added_files += 1,
+                    SnapshotChangeReason::Removed => stats.removed_files += 1,
+                    _ => stats.modified_files += 1,
+                }
+            }
+            return Ok((false, stats));
+        }
         let mut tree_builder = MergedTreeBuilder::new(self.tree_id.clone());
         trace_span!("process tree entries").in_scope(|| {
+            let file_states = self.file_states.all();
             for (path, tree_values) in &tree_entries_rx {
+                // Tree entries are only sent if the value changed, so a tracked
+                // path must have been modified.
+                if file_states.contains_path(&path) {
+                    stats.modified_files += 1;
+                } else {
+                    stats.added_files += 1;
+                }
                 tree_builder.set_or_remove(path, tree_values);
             }
         });
         let deleted_files = trace_span!("process deleted tree entries").in_scope(|| {
             let deleted_files = HashSet::from_iter(deleted_files_rx);
             is_dirty |= !deleted_files.is_empty();
+            stats.removed_files = deleted_files.len() as u32;
             for file in &deleted_files {
                 tree_builder.set_or_remove(file.clone(), Merge::absent());
             }
@@ -1171,10 +1320,16 @@ struct FileSnapshotter<'a> {
     file_states_tx: Sender<(RepoPathBuf, FileState)>,
     untracked_paths_tx: Sender<(RepoPathBuf, UntrackedReason)>,
     deleted_files_tx: Sender<RepoPathBuf>,
+    conflict_marker_paths_tx: Sender<RepoPathBuf>,
+    // Set only in dry-run mode, in which nothing is sent to the other channels
+    // except for untracked and deleted files.
+    changed_paths_tx: Option<Sender<(RepoPathBuf, SnapshotChangeReason)>>,
     error: OnceLock<SnapshotError>,
     progress: Option<&'a SnapshotProgress<'a>>,
     max_new_file_size: u64,
     conflict_marker_style: ConflictMarkerStyle,
+    track_empty_directories: bool,
+    detect_conflict_markers: bool,
 }
 
 impl FileSnapshotter<'_> {
@@ -1239,11 +1394,62 @@ impl FileSnapshotter<'_> {
                 Err(err) => Err(err),
             })
             .collect::<Result<_, _>>()?;
-        let present_entries = PresentDirEntries { dirs, files };
+        let mut present_entries = PresentDirEntries { dirs, files };
+        self.process_empty_directory(&dir, &git_ignore, file_states, &mut present_entries)?;
         self.emit_deleted_files(&dir, file_states, &present_entries);
         Ok(())
     }
 
+    /// Keeps or starts tracking the [`EMPTY_DIRECTORY_MARKER`] of `dir` if the
+    /// directory has no other tracked entries. The marker doesn't exist on
+    /// disk, so it's added to the `present_entries` if it should be kept.
+    fn process_empty_directory(
+        &self,
+        dir: &RepoPath,
+        git_ignore: &GitIgnoreFile,
+        file_states: FileStates<'_>,
+        present_entries: &mut PresentDirEntries,
+    ) -> Result<(), SnapshotError> {
+        if !self.track_empty_directories
+            || dir.is_root()
+            || !present_entries.dirs.is_empty()
+            || !present_entries.files.is_empty()
+        {
+            return Ok(());
+        }
+        let name = RepoPathComponent::new(EMPTY_DIRECTORY_MARKER).unwrap();
+        if file_states.get_at(dir, name).is_some() {
+            present_entries
+                .files
+                .insert(EMPTY_DIRECTORY_MARKER.to_owned());
+            return Ok(());
+        }
+        let path = dir.join(name);
+        if !self.matcher.matches(&path)
+            || !self.start_tracking_matcher.matches(&path)
+            || git_ignore.matches(path.as_internal_file_string())
+        {
+            return Ok(());
+        }
+        if let Some(changed_paths_tx) = &self.changed_paths_tx {
+            changed_paths_tx
+                .send((path, SnapshotChangeReason::NewFile))
+                .ok();
+            return Ok(());
+        }
+        let id = self.store().write_file(&path, &mut &b""[..]).block_on()?;
+        let tree_value = Merge::normal(TreeValue::File {
+            id,
+            executable: false,
+            copy_id: CopyId::placeholder(),
+        });
+        self.tree_entries_tx.send((path.clone(), tree_value)).ok();
+        self.file_states_tx
+            .send((path, FileState::placeholder()))
+            .ok();
+        Ok(())
+    }
+
     fn process_dir_entry<'scope>(
         &'scope self,
         dir: &RepoPath,
@@ -1352,6 +1558,17 @@ impl FileSnapshotter<'_> {
                 continue;
             }
             let disk_path = tracked_path.to_fs_path(&self.tree_state.working_copy_path)?;
+            if self.track_empty_directories
+                && is_empty_directory_marker(tracked_path)
+                && !disk_path.exists()
+            {
+                // The marker which isn't materialized as a file is kept as
+                // long as the directory exists.
+                if !disk_path.parent().unwrap().is_dir() {
+                    self.deleted_files_tx.send(tracked_path.to_owned()).ok();
+                }
+                continue;
+            }
             let metadata = match disk_path.symlink_metadata() {
                 Ok(metadata) => Some(metadata),
                 Err(err) if err.kind() == io::ErrorKind::NotFound => None,
@@ -1383,6 +1600,18 @@ impl FileSnapshotter<'_> {
         maybe_current_file_state: Option<&FileState>,
         mut new_file_state: FileState,
     ) -> Result<(), SnapshotError> {
+        if let Some(changed_paths_tx) = &self.changed_paths_tx {
+            let reason = match maybe_current_file_state {
+                None => Some(SnapshotChangeReason::NewFile),
+                Some(current_file_state) => {
+                    self.find_change_reason(&path, disk_path, current_file_state, &new_file_state)?
+                }
+            };
+            if let Some(reason) = reason {
+                changed_paths_tx.send((path, reason)).ok();
+            }
+            return Ok(());
+        }
         let update = self.get_updated_tree_value(
             &path,
             disk_path,
@@ -1405,6 +1634,86 @@ impl FileSnapshotter<'_> {
         Ok(())
     }
 
+    /// Tells why the tracked file at `disk_path` would be changed in the tree,
+    /// or returns `None` if it wouldn't. The file contents are compared with
+    /// the current tree without writing anything to the store.
+    fn find_change_reason(
+        &self,
+        repo_path: &RepoPath,
+        disk_path: &Path,
+        current_file_state: &FileState,
+        new_file_state: &FileState,
+    ) -> Result<Option<SnapshotChangeReason>, SnapshotError> {
+        let reason = if current_file_state.file_type != new_file_state.file_type {
+            SnapshotChangeReason::FileTypeChanged
+        } else if current_file_state.size != new_file_state.size {
+            SnapshotChangeReason::SizeChanged
+        } else if current_file_state.mtime != new_file_state.mtime {
+            SnapshotChangeReason::MtimeChanged
+        } else if current_file_state.mtime >= self.tree_state.own_mtime {
+            SnapshotChangeReason::AmbiguousMtime
+        } else {
+            return Ok(None);
+        };
+        let is_unchanged = match (
+            &new_file_state.file_type,
+            self.current_tree.path_value(repo_path)?.as_normal(),
+        ) {
+            (
+                FileType::Normal { executable },
+                Some(TreeValue::File {
+                    id,
+                    executable: old_executable,
+                    copy_id: _,
+                }),
+            ) => {
+                executable.unwrap_or_else(|| *old_executable) == *old_executable
+                    && self.file_content_matches(repo_path, disk_path, id)?
+            }
+            (FileType::Symlink, Some(TreeValue::Symlink(id)))
+                if self.tree_state.symlink_support =>
+            {
+                let target = fs::read_link(disk_path).map_err(|err| SnapshotError::Other {
+                    message: format!("Failed to read symlink {}", disk_path.display()),
+                    err: err.into(),
+                })?;
+                let old_target = self.store().read_symlink(repo_path, id).block_on()?;
+                target.to_str() == Some(old_target.as_str())
+            }
+            // Conflicts and files of different types in the tree are
+            // considered changed.
+            _ => false,
+        };
+        Ok((!is_unchanged).then_some(reason))
+    }
+
+    fn file_content_matches(
+        &self,
+        repo_path: &RepoPath,
+        disk_path: &Path,
+        id: &FileId,
+    ) -> Result<bool, SnapshotError> {
+        let content = fs::read(disk_path).map_err(|err| SnapshotError::Other {
+            message: format!("Failed to open file {}", disk_path.display()),
+            err: err.into(),
+        })?;
+        let old_content = async {
+            let mut reader = self.store().read_file(repo_path, id).await?;
+            let mut old_content = vec![];
+            reader
+                .read_to_end(&mut old_content)
+                .await
+                .map_err(|err| BackendError::ReadFile {
+                    path: repo_path.to_owned(),
+                    id: id.clone(),
+                    source: err.into(),
+                })?;
+            Ok::<_, BackendError>(old_content)
+        }
+        .block_on()?;
+        Ok(content == old_content)
+    }
+
     /// Emits file paths that don't exist in the `present_entries`.
     fn emit_deleted_files(
         &self,
@@ -1558,19 +1867,30 @@ impl FileSnapshotter<'_> {
                 message: format!("Failed to open file {}", disk_path.display()),
                 err: err.into(),
             })?;
+            let conflict_marker_len = materialized_conflict_data
+                .map_or(MIN_CONFLICT_MARKER_LEN, |data| {
+                    data.conflict_marker_len as usize
+                });
             let new_file_ids = conflicts::update_from_content(
                 &old_file_ids,
                 self.store(),
                 repo_path,
                 &content,
                 self.conflict_marker_style,
-                materialized_conflict_data.map_or(MIN_CONFLICT_MARKER_LEN, |data| {
-                    data.conflict_marker_len as usize
-                }),
+                conflict_marker_len,
             )
             .await?;
             match new_file_ids.into_resolved() {
                 Ok(file_id) => {
+                    // The conflict is considered resolved, but markers may have
+                    // been left behind by mistake.
+                    if self.detect_conflict_markers
+                        && conflicts::has_conflict_markers(&content, conflict_marker_len)
+                    {
+                        self.conflict_marker_paths_tx
+                            .send(repo_path.to_owned())
+                            .ok();
+                    }
                     // On Windows, we preserve the executable bit from the merged trees.
                     let executable = executable.unwrap_or_else(|| {
                         if let Some(merge) = current_tree_values.to_executable_merge() {
@@ -1754,12 +2074,7 @@ impl TreeState {
             other => CheckoutError::InternalBackendError(other),
         })?;
         let stats = self
-            .update(
-                &old_tree,
-                new_tree,
-                self.sparse_matcher().as_ref(),
-                options.conflict_marker_style,
-            )
+            .update(&old_tree, new_tree, self.sparse_matcher().as_ref(), options)
             .block_on()?;
         self.tree_id = new_tree.id();
         Ok(stats)
@@ -1782,20 +2097,10 @@ impl TreeState {
         let removed_matcher = DifferenceMatcher::new(&old_matcher, &new_matcher);
         let empty_tree = MergedTree::resolved(Tree::empty(self.store.clone(), RepoPathBuf::root()));
         let added_stats = self
-            .update(
-                &empty_tree,
-                &tree,
-                &added_matcher,
-                options.conflict_marker_style,
-            )
+            .update(&empty_tree, &tree, &added_matcher, options)
             .block_on()?;
         let removed_stats = self
-            .update(
-                &tree,
-                &empty_tree,
-                &removed_matcher,
-                options.conflict_marker_style,
-            )
+            .update(&tree, &empty_tree, &removed_matcher, options)
             .block_on()?;
         self.sparse_patterns = sparse_patterns;
         assert_eq!(added_stats.updated_files, 0);
@@ -1816,8 +2121,12 @@ impl TreeState {
         old_tree: &MergedTree,
         new_tree: &MergedTree,
         matcher: &dyn Matcher,
-        conflict_marker_style: ConflictMarkerStyle,
+        options: &CheckoutOptions,
     ) -> Result<CheckoutStats, CheckoutError> {
+        let &CheckoutOptions {
+            conflict_marker_style,
+            track_empty_directories,
+        } = options;
         // TODO: maybe it's better not include the skipped counts in the "intended"
         // counts
         let mut stats = CheckoutStats {
@@ -1841,7 +2150,7 @@ impl TreeState {
             })
             .buffered(self.store.concurrency().max(1));
         while let Some((path, data)) = diff_stream.next().await {
-            let (before, after) = data?;
+            let (before, mut after) = data?;
             if after.is_absent() {
                 stats.removed_files += 1;
             } else if before.is_absent() {
@@ -1873,6 +2182,22 @@ impl TreeState {
                 stats.skipped_files += 1;
                 continue;
             };
+            // An empty marker of an empty directory is materialized as the
+            // directory itself, which was created above. Markers with content
+            // are written as normal files.
+            if track_empty_directories && is_empty_directory_marker(&path) {
+                if let MaterializedTreeValue::File(file) = &mut after {
+                    let contents = file.read_all(&path).await?;
+                    if contents.is_empty() {
+                        if before.is_present() {
+                            remove_old_file(&disk_path)?;
+                        }
+                        changed_file_states.push((path, FileState::placeholder()));
+                        continue;
+                    }
+                    file.reader = Box::pin(io::Cursor::new(contents));
+                }
+            }
             // If the path was present, check reserved path first and delete it.
             let present_file_deleted = before.is_present() && remove_old_file(&disk_path)?;
             // If not, create temporary file to test the path validity.
@@ -1885,13 +2210,7 @@ impl TreeState {
             // TODO: Check that the file has not changed before overwriting/removing it.
             let file_state = match after {
                 MaterializedTreeValue::Absent | MaterializedTreeValue::AccessDenied(_) => {
-                    let mut parent_dir = disk_path.parent().unwrap();
-                    loop {
-                        if fs::remove_dir(parent_dir).is_err() {
-                            break;
-                        }
-                        parent_dir = parent_dir.parent().unwrap();
-                    }
+                    remove_empty_dirs(disk_path.parent().unwrap());
                     deleted_files.insert(path);
                     continue;
                 }
@@ -20,6 +20,8 @@ use std::time::Duration;
 use tracing::instrument;
 
 use super::FileLockError;
+use crate::metrics;
+use crate::metrics::DurationTimer;
 
 pub struct FileLock {
     path: PathBuf,
@@ -59,6 +61,7 @@ impl Iterator for BackoffIterator {
 #[cfg_attr(unix, allow(dead_code))]
 impl FileLock {
     pub fn lock(path: PathBuf) -> Result<FileLock, FileLockError> {
+        let _timer = DurationTimer::start(metrics::LOCK_WAIT_DURATION);
         let mut options = OpenOptions::new();
         options.create_new(true);
         options.write(true);
@@ -93,6 +96,28 @@ impl FileLock {
             }
         }
     }
+
+    /// Takes the lock if it isn't held by anyone else. Returns `None` instead
+    /// of waiting otherwise.
+    pub fn try_lock(path: PathBuf) -> Result<Option<FileLock>, FileLockError> {
+        let mut options = OpenOptions::new();
+        options.create_new(true);
+        options.write(true);
+        match options.open(&path) {
+            Ok(file) => Ok(Some(FileLock { path, _file: file })),
+            Err(err)
+                if err.kind() == std::io::ErrorKind::AlreadyExists
+                    || (cfg!(windows) && err.kind() == std::io::ErrorKind::PermissionDenied) =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(FileLockError {
+                message: "Failed to create lock file",
+                path,
+                err,
+            }),
+        }
+    }
 }
 
 impl Drop for FileLock {
@@ -21,6 +21,8 @@ use rustix::fs::FlockOperation;
 use tracing::instrument;
 
 use super::FileLockError;
+use crate::metrics;
+use crate::metrics::DurationTimer;
 
 pub struct FileLock {
     path: PathBuf,
@@ -29,6 +31,21 @@ pub struct FileLock {
 
 impl FileLock {
     pub fn lock(path: PathBuf) -> Result<FileLock, FileLockError> {
+        let _timer = DurationTimer::start(metrics::LOCK_WAIT_DURATION);
+        let lock = Self::acquire(path, FlockOperation::LockExclusive)?;
+        Ok(lock.expect("blocking flock() should wait for the lock"))
+    }
+
+    /// Takes the lock if it isn't held by anyone else. Returns `None` instead
+    /// of waiting otherwise.
+    pub fn try_lock(path: PathBuf) -> Result<Option<FileLock>, FileLockError> {
+        Self::acquire(path, FlockOperation::NonBlockingLockExclusive)
+    }
+
+    fn acquire(
+        path: PathBuf,
+        operation: FlockOperation,
+    ) -> Result<Option<FileLock>, FileLockError> {
         loop {
             // Create lockfile, or open pre-existing one
             let file = File::create(&path).map_err(|err| FileLockError {
@@ -37,13 +54,17 @@ impl FileLock {
                 err,
             })?;
             // If the lock was already held, wait for it to be released
-            rustix::fs::flock(&file, FlockOperation::LockExclusive).map_err(|errno| {
-                FileLockError {
-                    message: "Failed to lock lock file",
-                    path: path.clone(),
-                    err: errno.into(),
+            match rustix::fs::flock(&file, operation) {
+                Ok(()) => {}
+                Err(rustix::io::Errno::WOULDBLOCK) => return Ok(None),
+                Err(errno) => {
+                    return Err(FileLockError {
+                        message: "Failed to lock lock file",
+                        path: path.clone(),
+                        err: errno.into(),
+                    });
                 }
-            })?;
+            }
 
             match rustix::fs::fstat(&file) {
                 Ok(stat) => {
@@ -72,7 +93,7 @@ impl FileLock {
                 }
             }
 
-            return Ok(Self { path, file });
+            return Ok(Some(Self { path, file }));
         }
     }
 }
@@ -50,6 +50,7 @@ use crate::merge::Merge;
 use crate::merge::MergeBuilder;
 use crate::merge::MergedTreeVal;
 use crate::merge::MergedTreeValue;
+use crate::metrics;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::repo_path::RepoPathComponent;
@@ -159,7 +160,35 @@ impl MergedTree {
 
     /// Tries to resolve any conflicts, resolving any conflicts that can be
     /// automatically resolved and leaving the rest unresolved.
+    ///
+    /// The results of root tree merges are memoized in the store's
+    /// [`TreeMergeCache`](crate::tree_merge_cache::TreeMergeCache) if any.
     pub fn resolve(&self) -> BackendResult<MergedTree> {
+        let cache = self
+            .store()
+            .tree_merge_cache()
+            .filter(|_| !self.trees.is_resolved() && self.dir().is_root());
+        let Some(cache) = cache else {
+            return self.resolve_uncached();
+        };
+        let input_ids = self.trees.map(|tree| tree.id().clone());
+        if let Some(output_ids) = cache.get(&input_ids) {
+            // The cached trees may have been garbage-collected.
+            match self.store().get_root_tree(&MergedTreeId::Merge(output_ids)) {
+                Ok(tree) => {
+                    metrics::increment_counter(metrics::TREE_MERGE_CACHE_HITS);
+                    return Ok(tree);
+                }
+                Err(err) => tracing::debug!(?err, "failed to read cached tree merge result"),
+            }
+        }
+        metrics::increment_counter(metrics::TREE_MERGE_CACHE_MISSES);
+        let resolved = self.resolve_uncached()?;
+        cache.insert(input_ids, resolved.trees.map(|tree| tree.id().clone()));
+        Ok(resolved)
+    }
+
+    fn resolve_uncached(&self) -> BackendResult<MergedTree> {
         let merged = merge_trees(&self.trees).block_on()?;
         // If the result can be resolved, then `merge_trees()` above would have returned
         // a resolved merge. However, that function will always preserve the arity of
@@ -279,6 +308,11 @@ impl MergedTree {
     /// (i.e. the subdirectory was replaced by symlink in one side of the
     /// conflict), then the entry for `foo` itself will be emitted, but no
     /// entries from inside `foo/` from either of the trees will be.
+    ///
+    /// Entries are emitted in the order of their paths, which are compared
+    /// component by component. This order doesn't depend on the backend, so
+    /// `foo/bar` comes before `foo.txt` even though Git sorts trees
+    /// differently.
     pub fn entries(&self) -> TreeEntriesIterator<'static> {
         self.entries_matching(&EverythingMatcher)
     }
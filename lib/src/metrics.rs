@@ -0,0 +1,108 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hooks for embedders to collect metrics about repository operations.
+//!
+//! Nothing is recorded unless a [`MetricsSink`] is installed by
+//! [`set_metrics_sink()`]. The sink can forward the measurements to e.g. a
+//! Prometheus or StatsD exporter.
+
+use std::fmt::Debug;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Number of commits found in the store's cache.
+pub const STORE_COMMIT_CACHE_HITS: &str = "store.commit.cache_hits";
+/// Number of commits read from the backend.
+pub const STORE_COMMIT_READS: &str = "store.commit.reads";
+/// Time spent reading a commit from the backend.
+pub const STORE_COMMIT_READ_DURATION: &str = "store.commit.read_duration";
+/// Number of trees found in the store's cache.
+pub const STORE_TREE_CACHE_HITS: &str = "store.tree.cache_hits";
+/// Number of trees read from the backend.
+pub const STORE_TREE_READS: &str = "store.tree.reads";
+/// Time spent reading a tree from the backend.
+pub const STORE_TREE_READ_DURATION: &str = "store.tree.read_duration";
+/// Number of tree merges found in the tree merge cache.
+pub const TREE_MERGE_CACHE_HITS: &str = "tree_merge_cache.hits";
+/// Number of tree merges not found in the tree merge cache.
+pub const TREE_MERGE_CACHE_MISSES: &str = "tree_merge_cache.misses";
+/// Number of files read from the backend.
+pub const STORE_FILE_READS: &str = "store.file.reads";
+/// Time spent loading (and building if needed) the index at an operation.
+pub const INDEX_STORE_LOAD_DURATION: &str = "index_store.load_duration";
+/// Number of times the index had to be built from the operation log.
+pub const INDEX_STORE_BUILDS: &str = "index_store.builds";
+/// Time spent waiting for a file lock.
+pub const LOCK_WAIT_DURATION: &str = "lock.wait_duration";
+/// Time spent snapshotting the working copy.
+pub const WORKING_COPY_SNAPSHOT_DURATION: &str = "working_copy.snapshot_duration";
+
+/// Receives metrics recorded by the library.
+///
+/// The metric names are the constants defined in this module. Implementations
+/// should be cheap, since they are called on hot paths such as object reads.
+pub trait MetricsSink: Debug + Send + Sync {
+    /// Adds `value` to the counter `name`.
+    fn add_to_counter(&self, name: &'static str, value: u64);
+
+    /// Records a sample of the duration histogram `name`.
+    fn record_duration(&self, name: &'static str, duration: Duration);
+}
+
+static METRICS_SINK: OnceLock<Box<dyn MetricsSink>> = OnceLock::new();
+
+/// Installs the process-wide metrics sink.
+///
+/// The sink can only be installed once. If a sink is already installed, the
+/// given sink is returned back as an error.
+pub fn set_metrics_sink(sink: Box<dyn MetricsSink>) -> Result<(), Box<dyn MetricsSink>> {
+    METRICS_SINK.set(sink)
+}
+
+fn metrics_sink() -> Option<&'static dyn MetricsSink> {
+    METRICS_SINK.get().map(|sink| sink.as_ref())
+}
+
+/// Increments the counter `name` by one.
+pub(crate) fn increment_counter(name: &'static str) {
+    if let Some(sink) = metrics_sink() {
+        sink.add_to_counter(name, 1);
+    }
+}
+
+/// Records the time elapsed between its creation and drop as a sample of the
+/// duration histogram.
+#[must_use]
+pub(crate) struct DurationTimer {
+    name: &'static str,
+    // None if no sink is installed, to avoid querying the clock needlessly.
+    start: Option<Instant>,
+}
+
+impl DurationTimer {
+    pub fn start(name: &'static str) -> Self {
+        let start = metrics_sink().map(|_| Instant::now());
+        Self { name, start }
+    }
+}
+
+impl Drop for DurationTimer {
+    fn drop(&mut self) {
+        if let (Some(sink), Some(start)) = (metrics_sink(), self.start) {
+            sink.record_duration(self.name, start.elapsed());
+        }
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Translation between predecessor records and Mercurial obsolescence markers.
+//!
+//! Markers are expressed in terms of jj commit ids. Mapping them to Mercurial
+//! node ids, and remembering which markers were already exchanged, is left to
+//! the caller.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use itertools::Itertools as _;
+use thiserror::Error;
+
+use crate::backend::BackendError;
+use crate::backend::CommitId;
+use crate::backend::Timestamp;
+use crate::op_store::OpStoreError;
+use crate::repo::MutableRepo;
+use crate::repo::ReadonlyRepo;
+use crate::repo::Repo as _;
+use crate::revset::RevsetEvaluationError;
+use crate::revset::RevsetExpression;
+
+/// Record that a commit was rewritten into other commits, or pruned.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ObsMarker {
+    /// The commit that was rewritten or pruned.
+    pub predecessor: CommitId,
+    /// The commits replacing the predecessor. Empty if it was pruned.
+    pub successors: Vec<CommitId>,
+    /// When the rewrite happened.
+    pub date: Timestamp,
+    /// Who did the rewrite.
+    pub user: String,
+}
+
+/// Error that may occur while translating obsolescence markers.
+#[derive(Debug, Error)]
+pub enum ObsMarkerError {
+    /// Error while reading commits.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    /// Error while reading the parent operations.
+    #[error(transparent)]
+    OpStore(#[from] OpStoreError),
+    /// Error while computing the hidden commits.
+    #[error(transparent)]
+    RevsetEvaluation(#[from] RevsetEvaluationError),
+}
+
+/// Returns markers for the rewrites done by the operation `repo` is loaded at.
+///
+/// Each predecessor recorded by the operation gets one marker listing all the
+/// commits it was rewritten into. Commits hidden by the operation without a
+/// successor get a prune marker. Operations written by jj < 0.30 don't record
+/// predecessors and produce no markers.
+pub fn export_markers(repo: &ReadonlyRepo) -> Result<Vec<ObsMarker>, ObsMarkerError> {
+    let op = repo.operation();
+    let Some(commit_predecessors) = &op.store_operation().commit_predecessors else {
+        return Ok(vec![]);
+    };
+    let mut successors: BTreeMap<CommitId, Vec<CommitId>> = BTreeMap::new();
+    for (new_id, old_ids) in commit_predecessors {
+        for old_id in old_ids {
+            successors
+                .entry(old_id.clone())
+                .or_default()
+                .push(new_id.clone());
+        }
+    }
+
+    let mut old_heads = vec![];
+    for parent in op.parents() {
+        old_heads.extend(parent?.view()?.heads().iter().cloned());
+    }
+    let new_heads = repo.view().heads().iter().cloned().collect_vec();
+    let hidden = RevsetExpression::commits(old_heads)
+        .ancestors()
+        .minus(&RevsetExpression::commits(new_heads).ancestors())
+        .evaluate(repo)?;
+    for commit_id in hidden.iter() {
+        successors.entry(commit_id?).or_default();
+    }
+
+    let metadata = op.metadata();
+    let markers = successors
+        .into_iter()
+        .map(|(predecessor, successors)| ObsMarker {
+            predecessor,
+            successors,
+            date: metadata.time.end,
+            user: metadata.username.clone(),
+        })
+        .collect();
+    Ok(markers)
+}
+
+/// Records the rewrites described by `markers` in `mut_repo`.
+///
+/// Markers for commits that aren't visible are ignored. If several markers
+/// share a predecessor, it is rewritten into all their successors, which makes
+/// the change divergent. A prune marker only abandons the predecessor if no
+/// other marker rewrites it. The successors get the predecessors of all their
+/// markers recorded, so `jj evolog` shows where they came from. The caller is
+/// responsible for rebasing descendants afterwards.
+pub fn import_markers(
+    mut_repo: &mut MutableRepo,
+    markers: &[ObsMarker],
+) -> Result<(), ObsMarkerError> {
+    let mut successors: BTreeMap<&CommitId, Vec<&CommitId>> = BTreeMap::new();
+    for marker in markers {
+        let entry = successors.entry(&marker.predecessor).or_default();
+        for successor in &marker.successors {
+            if !entry.contains(&successor) {
+                entry.push(successor);
+            }
+        }
+    }
+
+    let known_ids = successors
+        .keys()
+        .filter(|id| mut_repo.index().has_id(id))
+        .map(|&id| id.clone())
+        .collect_vec();
+    let visible: HashSet<_> = RevsetExpression::commits(known_ids)
+        .intersection(&RevsetExpression::visible_heads().ancestors())
+        .evaluate(mut_repo)?
+        .iter()
+        .try_collect()?;
+
+    let mut predecessors: BTreeMap<CommitId, Vec<CommitId>> = BTreeMap::new();
+    for (old_id, new_ids) in successors {
+        if !visible.contains(old_id) || old_id == mut_repo.store().root_commit_id() {
+            continue;
+        }
+        match new_ids.as_slice() {
+            [] => {
+                let old_commit = mut_repo.store().get_commit(old_id)?;
+                mut_repo.record_abandoned_commit(&old_commit);
+            }
+            [new_id] => mut_repo.set_rewritten_commit(old_id.clone(), (*new_id).clone()),
+            _ => mut_repo.set_divergent_rewrite(old_id.clone(), new_ids.iter().copied().cloned()),
+        }
+        for new_id in new_ids {
+            predecessors
+                .entry(new_id.clone())
+                .or_default()
+                .push(old_id.clone());
+        }
+    }
+    for (new_id, old_ids) in predecessors {
+        let mut all_old_ids = mut_repo
+            .commit_predecessors()
+            .get(&new_id)
+            .cloned()
+            .unwrap_or_default();
+        for old_id in old_ids {
+            if !all_old_ids.contains(&old_id) {
+                all_old_ids.push(old_id);
+            }
+        }
+        mut_repo.set_predecessors(new_id, all_old_ids);
+    }
+    Ok(())
+}
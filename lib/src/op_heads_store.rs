@@ -17,7 +17,12 @@
 use std::any::Any;
 use std::collections::HashSet;
 use std::fmt::Debug;
+use std::io;
+use std::path::Path;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
 
 use itertools::Itertools as _;
 use thiserror::Error;
@@ -71,6 +76,12 @@ pub trait OpHeadsStore: Send + Sync + Debug {
     /// operations. It is not needed for correctness; implementations are free
     /// to return a type that doesn't hold a lock.
     fn lock(&self) -> Result<Box<dyn OpHeadsStoreLock + '_>, OpHeadsStoreError>;
+
+    /// Takes the lock if it's immediately available. Returns `None` if the
+    /// lock is held by another process, or if the implementation can't tell.
+    fn try_lock(&self) -> Result<Option<Box<dyn OpHeadsStoreLock + '_>>, OpHeadsStoreError> {
+        Ok(None)
+    }
 }
 
 // Given an OpHeadsStore, fetch and resolve its op heads down to one under a
@@ -96,6 +107,30 @@ where
         return Ok(Operation::new(op_store.clone(), operation_id, operation));
     }
 
+    // While another process is publishing an operation, the heads may contain
+    // both the new operation and its parents. Since operations are immutable
+    // once written, the new operation can be loaded without waiting for the
+    // writer to release the lock. The stale parents are only removed if the
+    // lock can be taken without waiting, in case they were left behind by an
+    // interrupted process. Otherwise, they are left for the next writer.
+    if op_heads.len() > 1 {
+        let op_heads = read_operations(op_store, &op_heads)?;
+        let op_head_ids_before = op_heads.iter().map(|op| op.id().clone()).collect_vec();
+        if let [op_head] = &*remove_ancestor_operations(op_heads)? {
+            if let Some(_lock) = op_heads_store.try_lock()? {
+                let current_op_head_ids = op_heads_store.get_op_heads()?;
+                if current_op_head_ids.contains(op_head.id()) {
+                    let ancestor_op_head_ids = op_head_ids_before
+                        .into_iter()
+                        .filter(|id| id != op_head.id() && current_op_head_ids.contains(id))
+                        .collect_vec();
+                    op_heads_store.update_op_heads(&ancestor_op_head_ids, op_head.id())?;
+                }
+            }
+            return Ok(op_head.clone());
+        }
+    }
+
     // There are no/multiple heads. We take a lock, then check if there are
     // still no/multiple heads (it's likely that another process was in the
     // process of deleting on of them). If there are still multiple heads, we
@@ -117,28 +152,16 @@ where
         return Ok(Operation::new(op_store.clone(), op_head_id, op_head));
     }
 
-    let op_heads: Vec<_> = op_head_ids
-        .iter()
-        .map(|op_id: &OperationId| -> Result<Operation, OpStoreError> {
-            let data = op_store.read_operation(op_id)?;
-            Ok(Operation::new(op_store.clone(), op_id.clone(), data))
-        })
-        .try_collect()?;
+    let op_heads = read_operations(op_store, &op_head_ids)?;
     // Remove ancestors so we don't create merge operation with an operation and its
     // ancestor
     let op_head_ids_before: HashSet<_> = op_heads.iter().map(|op| op.id().clone()).collect();
-    let filtered_op_heads = dag_walk::heads_ok(
-        op_heads.into_iter().map(Ok),
-        |op: &Operation| op.id().clone(),
-        |op: &Operation| op.parents().collect_vec(),
-    )?;
-    let op_head_ids_after: HashSet<_> =
-        filtered_op_heads.iter().map(|op| op.id().clone()).collect();
+    let mut op_heads = remove_ancestor_operations(op_heads)?;
+    let op_head_ids_after: HashSet<_> = op_heads.iter().map(|op| op.id().clone()).collect();
     let ancestor_op_heads = op_head_ids_before
         .difference(&op_head_ids_after)
         .cloned()
         .collect_vec();
-    let mut op_heads = filtered_op_heads.into_iter().collect_vec();
 
     // Return without creating a merge operation
     if let [op_head] = &*op_heads {
@@ -153,3 +176,222 @@ where
     op_heads_store.update_op_heads(&old_op_heads, new_op.id())?;
     Ok(new_op)
 }
+
+/// Result of [`recover_op_heads()`].
+#[derive(Debug)]
+pub struct RecoveredOpHeads {
+    /// The new op heads, sorted by id.
+    pub head_ids: Vec<OperationId>,
+    /// Operations that couldn't be read. They were skipped, so they can't
+    /// become heads, and their parents may have become heads instead.
+    pub unreadable_ops: Vec<(OperationId, OpStoreError)>,
+}
+
+/// Makes the operations that aren't a parent of any other operation in
+/// `op_store` the op heads, and removes op heads that aren't such operations.
+///
+/// This recovers a repo whose op heads were lost or point to missing
+/// operations. Since unreachable operations are kept until they're garbage
+/// collected, operations abandoned by `jj op abandon` may become heads again.
+/// Operations that can't be read are skipped and reported in the result.
+pub fn recover_op_heads<E>(
+    op_heads_store: &dyn OpHeadsStore,
+    op_store: &Arc<dyn OpStore>,
+) -> Result<RecoveredOpHeads, E>
+where
+    E: From<OpHeadsStoreError> + From<OpStoreError>,
+{
+    let _lock = op_heads_store.lock()?;
+    let mut op_ids = op_store.all_operation_ids()?;
+    let mut parent_ids = HashSet::new();
+    let mut unreadable_ops = vec![];
+    op_ids.retain(|op_id| match op_store.read_operation(op_id) {
+        Ok(data) => {
+            parent_ids.extend(data.parents);
+            true
+        }
+        Err(err) => {
+            unreadable_ops.push((op_id.clone(), err));
+            false
+        }
+    });
+    let mut new_head_ids = op_ids
+        .into_iter()
+        .filter(|id| !parent_ids.contains(id))
+        .collect_vec();
+    if new_head_ids.is_empty() {
+        new_head_ids.push(op_store.root_operation_id().clone());
+    }
+    new_head_ids.sort();
+
+    let old_head_ids = op_heads_store.get_op_heads()?;
+    for new_id in &new_head_ids {
+        if !old_head_ids.contains(new_id) {
+            op_heads_store.update_op_heads(&[], new_id)?;
+        }
+    }
+    let stale_head_ids = old_head_ids
+        .into_iter()
+        .filter(|id| !new_head_ids.contains(id))
+        .collect_vec();
+    if !stale_head_ids.is_empty() {
+        op_heads_store.update_op_heads(&stale_head_ids, &new_head_ids[0])?;
+    }
+    Ok(RecoveredOpHeads {
+        head_ids: new_head_ids,
+        unreadable_ops,
+    })
+}
+
+fn read_operations(
+    op_store: &Arc<dyn OpStore>,
+    op_ids: &[OperationId],
+) -> Result<Vec<Operation>, OpStoreError> {
+    op_ids
+        .iter()
+        .map(|op_id| {
+            let data = op_store.read_operation(op_id)?;
+            Ok(Operation::new(op_store.clone(), op_id.clone(), data))
+        })
+        .try_collect()
+}
+
+fn remove_ancestor_operations(ops: Vec<Operation>) -> Result<Vec<Operation>, OpStoreError> {
+    let heads = dag_walk::heads_ok(
+        ops.into_iter().map(Ok),
+        |op: &Operation| op.id().clone(),
+        |op: &Operation| op.parents().collect_vec(),
+    )?;
+    Ok(heads.into_iter().collect())
+}
+
+/// Blocks until the op heads differ from `old_op_head_ids`, and returns the
+/// new op heads.
+///
+/// `last_operation_path` is the file that [`OperationFileNotifier`] replaces
+/// whenever an operation is published. On Linux, this sleeps until the file
+/// changes. On other platforms, or if the file can't be watched, the op heads
+/// are polled with exponential backoff. Once they have changed, the op heads
+/// lock is taken and released so that a concurrent process has finished
+/// updating the heads. Returns `None` if the `timeout` elapses first.
+///
+/// [`OperationFileNotifier`]: crate::transaction::OperationFileNotifier
+pub fn wait_for_op_heads_change(
+    op_heads_store: &dyn OpHeadsStore,
+    old_op_head_ids: &[OperationId],
+    last_operation_path: &Path,
+    timeout: Option<Duration>,
+) -> Result<Option<Vec<OperationId>>, OpHeadsStoreError> {
+    const INITIAL_DELAY: Duration = Duration::from_millis(10);
+    const MAX_DELAY: Duration = Duration::from_millis(500);
+    let old_op_head_ids: HashSet<&OperationId> = old_op_head_ids.iter().collect();
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    // Start watching before reading the op heads so that no change is missed.
+    let mut watcher = OperationFileWatcher::new(last_operation_path)
+        .inspect_err(|err| {
+            tracing::info!(
+                ?err,
+                "cannot watch last operation file, polling op heads instead"
+            );
+        })
+        .ok();
+    let mut delay = INITIAL_DELAY;
+    loop {
+        let op_head_ids = op_heads_store.get_op_heads()?;
+        // The heads may be transiently empty if the store doesn't support
+        // atomic updates. See resolve_op_heads().
+        if !op_head_ids.is_empty() && op_head_ids.iter().collect::<HashSet<_>>() != old_op_head_ids
+        {
+            drop(op_heads_store.lock()?);
+            return op_heads_store.get_op_heads().map(Some);
+        }
+        let now = Instant::now();
+        let time_left = match deadline {
+            Some(deadline) if deadline <= now => return Ok(None),
+            Some(deadline) => Some(deadline - now),
+            None => None,
+        };
+        if let Some(active_watcher) = &mut watcher {
+            if let Err(err) = active_watcher.wait(time_left) {
+                tracing::warn!(
+                    ?err,
+                    "failed to watch last operation file, polling op heads"
+                );
+                watcher = None;
+            }
+        } else {
+            thread::sleep(time_left.map_or(delay, |time_left| delay.min(time_left)));
+            delay = (delay * 2).min(MAX_DELAY);
+        }
+    }
+}
+
+/// Watches the directory of the last operation file with inotify.
+#[cfg(target_os = "linux")]
+struct OperationFileWatcher {
+    inotify: rustix::fd::OwnedFd,
+}
+
+#[cfg(target_os = "linux")]
+impl OperationFileWatcher {
+    fn new(path: &Path) -> io::Result<Self> {
+        use rustix::fs::inotify;
+
+        let dir = path.parent().ok_or(io::ErrorKind::InvalidInput)?;
+        // Publishers only update the file if it exists.
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        // The file is replaced by renaming a temporary file over it, so the
+        // directory is watched instead of the file itself.
+        let inotify =
+            inotify::init(inotify::CreateFlags::CLOEXEC | inotify::CreateFlags::NONBLOCK)?;
+        inotify::add_watch(
+            &inotify,
+            dir,
+            inotify::WatchFlags::MOVED_TO | inotify::WatchFlags::CLOSE_WRITE,
+        )?;
+        Ok(OperationFileWatcher { inotify })
+    }
+
+    /// Blocks until something in the directory changes or the `timeout`
+    /// elapses. Spurious wakeups are possible.
+    fn wait(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+        use rustix::event::PollFd;
+        use rustix::event::PollFlags;
+        use rustix::event::Timespec;
+        use rustix::io::Errno;
+
+        // A timeout too large to be represented is as good as none.
+        let timeout = timeout.and_then(|timeout| Timespec::try_from(timeout).ok());
+        let mut fds = [PollFd::new(&self.inotify, PollFlags::IN)];
+        match rustix::event::poll(&mut fds, timeout.as_ref()) {
+            Ok(_) | Err(Errno::INTR) => {}
+            Err(err) => return Err(err.into()),
+        }
+        // Discard the pending events. The caller re-reads the op heads anyway.
+        let mut buf = [0; 4096];
+        loop {
+            match rustix::io::read(&self.inotify, &mut buf) {
+                Ok(0) | Err(Errno::AGAIN) => return Ok(()),
+                Ok(_) | Err(Errno::INTR) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+struct OperationFileWatcher;
+
+#[cfg(not(target_os = "linux"))]
+impl OperationFileWatcher {
+    fn new(_path: &Path) -> io::Result<Self> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    fn wait(&mut self, _timeout: Option<Duration>) -> io::Result<()> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
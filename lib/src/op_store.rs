@@ -16,8 +16,8 @@
 
 use std::any::Any;
 use std::collections::BTreeMap;
-use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt;
 use std::fmt::Debug;
 use std::iter;
 use std::time::SystemTime;
@@ -243,11 +243,55 @@ pub struct BookmarkTarget<'a> {
     pub remote_refs: Vec<(&'a RemoteName, &'a RemoteRef)>,
 }
 
+impl<'a> BookmarkTarget<'a> {
+    /// Iterates over `(remote_name, remote_ref)` pairs tracked by the local
+    /// bookmark.
+    pub fn tracked_remote_refs(&self) -> impl Iterator<Item = (&'a RemoteName, &'a RemoteRef)> {
+        self.remote_refs
+            .clone()
+            .into_iter()
+            .filter(|(_, remote_ref)| remote_ref.is_tracked())
+    }
+
+    /// Iterates over tracked remotes whose target differs from the local
+    /// target.
+    pub fn diverged_remotes(&self) -> impl Iterator<Item = &'a RemoteName> {
+        let local_target = self.local_target;
+        self.tracked_remote_refs()
+            .filter(move |(_, remote_ref)| remote_ref.target != *local_target)
+            .map(|(remote_name, _)| remote_name)
+    }
+
+    /// Returns true if all tracked remote refs point to the local target.
+    pub fn is_synced(&self) -> bool {
+        self.diverged_remotes().next().is_none()
+    }
+
+    /// Returns true if the local target or any of the remote targets is
+    /// conflicted.
+    pub fn has_conflict(&self) -> bool {
+        self.local_target.has_conflict()
+            || self
+                .remote_refs
+                .iter()
+                .any(|(_, remote_ref)| remote_ref.target.has_conflict())
+    }
+
+    /// Returns true if the local bookmark has been deleted, but is still
+    /// present on some tracked remotes.
+    pub fn is_deleted_locally(&self) -> bool {
+        self.local_target.is_absent()
+            && self
+                .tracked_remote_refs()
+                .any(|(_, remote_ref)| remote_ref.is_present())
+    }
+}
+
 /// Represents the way the repo looks at a given time, just like how a Tree
 /// object represents how the file system looks at a given time.
-#[derive(ContentHash, PartialEq, Eq, Clone, Debug)]
+#[derive(ContentHash, PartialEq, Eq, Clone)]
 pub struct View {
-    /// All head commits
+    /// All head commits, in no particular order.
     pub head_ids: HashSet<CommitId>,
     pub local_bookmarks: BTreeMap<RefNameBuf, RefTarget>,
     pub tags: BTreeMap<RefNameBuf, RefTarget>,
@@ -263,6 +307,21 @@ pub struct View {
     pub wc_commit_ids: BTreeMap<WorkspaceNameBuf, CommitId>,
 }
 
+// The heads are printed in sorted order so the output is stable.
+impl fmt::Debug for View {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("View")
+            .field("head_ids", &self.head_ids.iter().sorted().collect_vec())
+            .field("local_bookmarks", &self.local_bookmarks)
+            .field("tags", &self.tags)
+            .field("remote_views", &self.remote_views)
+            .field("git_refs", &self.git_refs)
+            .field("git_head", &self.git_head)
+            .field("wc_commit_ids", &self.wc_commit_ids)
+            .finish()
+    }
+}
+
 impl View {
     /// Creates new truly empty view.
     ///
@@ -413,7 +472,7 @@ impl Operation {
             hostname: "".to_string(),
             username: "".to_string(),
             is_snapshot: false,
-            tags: HashMap::new(),
+            tags: BTreeMap::new(),
         };
         Operation {
             view_id: root_view_id,
@@ -438,7 +497,8 @@ pub struct OperationMetadata {
     /// Whether this operation represents a pure snapshotting of the working
     /// copy.
     pub is_snapshot: bool,
-    pub tags: HashMap<String, String>,
+    /// Arbitrary key-value pairs, ordered by key.
+    pub tags: BTreeMap<String, String>,
 }
 
 /// Data to be loaded into the root operation/view.
@@ -469,6 +529,10 @@ pub enum OpStoreError {
     },
     #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
+    /// A valid operation attempted, but failed because it isn't supported by
+    /// the particular op store.
+    #[error("{0}")]
+    Unsupported(String),
 }
 
 pub type OpStoreResult<T> = Result<T, OpStoreError>;
@@ -494,6 +558,18 @@ pub trait OpStore: Send + Sync + Debug {
         prefix: &HexPrefix,
     ) -> OpStoreResult<PrefixResolution<OperationId>>;
 
+    /// Returns the IDs of all operations in the store, excluding the root
+    /// operation. The order is unspecified.
+    ///
+    /// Op stores that can't enumerate operations may return
+    /// `OpStoreError::Unsupported`.
+    fn all_operation_ids(&self) -> OpStoreResult<Vec<OperationId>> {
+        Err(OpStoreError::Unsupported(format!(
+            "Listing operations is not supported by the {} op store",
+            self.name()
+        )))
+    }
+
     /// Prunes unreachable operations and views.
     ///
     /// All operations and views reachable from the `head_ids` won't be
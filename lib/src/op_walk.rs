@@ -23,6 +23,7 @@ use std::sync::Arc;
 use itertools::Itertools as _;
 use thiserror::Error;
 
+use crate::backend::MillisSinceEpoch;
 use crate::dag_walk;
 use crate::object_id::HexPrefix;
 use crate::object_id::PrefixResolution;
@@ -328,6 +329,81 @@ fn collect_ancestors_until_roots(
     items
 }
 
+/// Finds the latest operation which ended at or before the given `time`,
+/// walking back from `head_ops`.
+///
+/// Returns `None` if all operations (except the root operation) ended after
+/// `time`.
+pub fn find_op_at_time(
+    head_ops: &[Operation],
+    time: MillisSinceEpoch,
+) -> OpStoreResult<Option<Operation>> {
+    for op in walk_ancestors(head_ops) {
+        let op = op?;
+        if op.parent_ids().is_empty() {
+            continue;
+        }
+        if op.metadata().time.end.timestamp <= time {
+            return Ok(Some(op));
+        }
+    }
+    Ok(None)
+}
+
+/// Retention policy for the operation history.
+#[derive(Clone, Debug, Default)]
+pub struct OpRetentionPolicy {
+    /// Maximum number of operations to keep, counted from the head operations.
+    pub max_count: Option<usize>,
+    /// Operations which ended before this time are expired.
+    pub min_end_time: Option<MillisSinceEpoch>,
+}
+
+impl OpRetentionPolicy {
+    /// Returns true if this policy doesn't expire any operations.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_count.is_none() && self.min_end_time.is_none()
+    }
+}
+
+/// Finds the newest operations which fall outside of the retention `policy`.
+///
+/// The head operations and the root operation are never expired. Ancestors of
+/// the returned operations are implicitly expired, so the result can be passed
+/// as `root_ops` to `reparent_range()` to compact the history onto the root
+/// operation. The oldest retained operations then serve as the baseline views.
+pub fn find_expired_ops(
+    head_ops: &[Operation],
+    policy: &OpRetentionPolicy,
+) -> OpStoreResult<Vec<Operation>> {
+    if policy.is_unlimited() {
+        return Ok(vec![]);
+    }
+    let head_ids: HashSet<&OperationId> = head_ops.iter().map(|op| op.id()).collect();
+    let mut expired_ops = Vec::new();
+    // Ancestors of expired operations, which don't have to be listed.
+    let mut covered_ids = HashSet::new();
+    for (index, op) in walk_ancestors(head_ops).enumerate() {
+        let op = op?;
+        if covered_ids.contains(op.id()) {
+            covered_ids.extend(op.parent_ids().iter().cloned());
+            continue;
+        }
+        if head_ids.contains(op.id()) || op.parent_ids().is_empty() {
+            continue;
+        }
+        let too_many = policy.max_count.is_some_and(|max| index >= max);
+        let too_old = policy
+            .min_end_time
+            .is_some_and(|time| op.metadata().time.end.timestamp < time);
+        if too_many || too_old {
+            covered_ids.extend(op.parent_ids().iter().cloned());
+            expired_ops.push(op);
+        }
+    }
+    Ok(expired_ops)
+}
+
 /// Stats about `reparent_range()`.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ReparentStats {
@@ -1,13 +1,18 @@
 // This file is @generated by prost-build.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Commit {
     #[prost(bytes = "vec", repeated, tag = "2")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub predecessors: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
     #[prost(bytes = "vec", tag = "4")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub change_id: ::prost::alloc::vec::Vec<u8>,
     /// Alternating positive and negative terms. Set only for conflicts.
     /// Resolved trees are stored in the git commit
     #[prost(bytes = "vec", repeated, tag = "1")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub root_tree: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
     /// TODO(#1624): delete when we assume that all commits use this format
     #[prost(bool, tag = "10")]
@@ -1,13 +1,19 @@
 // This file is @generated by prost-build.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RefConflictLegacy {
     #[deprecated]
     #[prost(bytes = "vec", repeated, tag = "1")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub removes: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
     #[deprecated]
     #[prost(bytes = "vec", repeated, tag = "2")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub adds: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RefConflict {
     #[prost(message, repeated, tag = "1")]
@@ -17,12 +23,17 @@ pub struct RefConflict {
 }
 /// Nested message and enum types in `RefConflict`.
 pub mod ref_conflict {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(default, deny_unknown_fields)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Term {
         #[prost(bytes = "vec", optional, tag = "1")]
+        #[serde(with = "crate::hex_util::serde_hex")]
         pub value: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
     }
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RefTarget {
     /// New `RefConflict` type represents both `commit_id` and `conflict_legacy`.
@@ -32,9 +43,11 @@ pub struct RefTarget {
 /// Nested message and enum types in `RefTarget`.
 pub mod ref_target {
     /// New `RefConflict` type represents both `commit_id` and `conflict_legacy`.
+    #[derive(serde::Serialize, serde::Deserialize)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
     pub enum Value {
         #[prost(bytes, tag = "1")]
+        #[serde(with = "crate::hex_util::serde_hex")]
         CommitId(::prost::alloc::vec::Vec<u8>),
         #[prost(message, tag = "2")]
         ConflictLegacy(super::RefConflictLegacy),
@@ -42,6 +55,8 @@ pub mod ref_target {
         Conflict(super::RefConflict),
     }
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RemoteBookmark {
     #[prost(string, tag = "1")]
@@ -52,6 +67,8 @@ pub struct RemoteBookmark {
     #[prost(enumeration = "RemoteRefState", optional, tag = "3")]
     pub state: ::core::option::Option<i32>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Bookmark {
     #[prost(string, tag = "1")]
@@ -66,6 +83,8 @@ pub struct Bookmark {
     #[prost(message, repeated, tag = "3")]
     pub remote_bookmarks: ::prost::alloc::vec::Vec<RemoteBookmark>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GitRef {
     #[prost(string, tag = "1")]
@@ -74,10 +93,13 @@ pub struct GitRef {
     /// type). New GitRefs have (only) the target field.
     /// TODO: Delete support for the old format.
     #[prost(bytes = "vec", tag = "2")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub commit_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "3")]
     pub target: ::core::option::Option<RefTarget>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Tag {
     #[prost(string, tag = "1")]
@@ -85,14 +107,19 @@ pub struct Tag {
     #[prost(message, optional, tag = "2")]
     pub target: ::core::option::Option<RefTarget>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct View {
     #[prost(bytes = "vec", repeated, tag = "1")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub head_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
     #[deprecated]
     #[prost(bytes = "vec", tag = "2")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub wc_commit_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(map = "string, bytes", tag = "8")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub wc_commit_ids: ::std::collections::HashMap<
         ::prost::alloc::string::String,
         ::prost::alloc::vec::Vec<u8>,
@@ -109,15 +136,20 @@ pub struct View {
     /// TODO: Delete support for the old format.
     #[deprecated]
     #[prost(bytes = "vec", tag = "7")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub git_head_legacy: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "9")]
     pub git_head: ::core::option::Option<RefTarget>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Operation {
     #[prost(bytes = "vec", tag = "1")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub view_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", repeated, tag = "2")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub parents: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
     #[prost(message, optional, tag = "3")]
     pub metadata: ::core::option::Option<OperationMetadata>,
@@ -129,6 +161,8 @@ pub struct Operation {
     pub stores_commit_predecessors: bool,
 }
 /// TODO: Share with store.proto? Do we even need the timezone here?
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct Timestamp {
     #[prost(int64, tag = "1")]
@@ -136,6 +170,8 @@ pub struct Timestamp {
     #[prost(int32, tag = "2")]
     pub tz_offset: i32,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OperationMetadata {
     #[prost(message, optional, tag = "1")]
@@ -156,13 +192,18 @@ pub struct OperationMetadata {
         ::prost::alloc::string::String,
     >,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CommitPredecessors {
     #[prost(bytes = "vec", tag = "1")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub commit_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", repeated, tag = "2")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub predecessor_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum RemoteRefState {
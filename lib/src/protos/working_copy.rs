@@ -1,10 +1,14 @@
 // This file is @generated by prost-build.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct MaterializedConflictData {
     /// TODO: maybe we should store num_sides here as well
     #[prost(uint32, tag = "1")]
     pub conflict_marker_len: u32,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FileState {
     #[prost(int64, tag = "1")]
@@ -16,10 +20,13 @@ pub struct FileState {
     /// Set only if file_type is Conflict
     #[deprecated]
     #[prost(bytes = "vec", tag = "4")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub conflict_id: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "5")]
     pub materialized_conflict_data: ::core::option::Option<MaterializedConflictData>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FileStateEntry {
     #[prost(string, tag = "1")]
@@ -27,18 +34,24 @@ pub struct FileStateEntry {
     #[prost(message, optional, tag = "2")]
     pub state: ::core::option::Option<FileState>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SparsePatterns {
     #[prost(string, repeated, tag = "1")]
     pub prefixes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TreeState {
     #[prost(bytes = "vec", tag = "1")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub legacy_tree_id: ::prost::alloc::vec::Vec<u8>,
     /// Alternating positive and negative terms if there's a conflict, otherwise a
     /// single (positive) value
     #[prost(bytes = "vec", repeated, tag = "5")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub tree_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
     #[prost(message, repeated, tag = "2")]
     pub file_states: ::prost::alloc::vec::Vec<FileStateEntry>,
@@ -49,6 +62,8 @@ pub struct TreeState {
     #[prost(message, optional, tag = "4")]
     pub watchman_clock: ::core::option::Option<WatchmanClock>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WatchmanClock {
     #[prost(oneof = "watchman_clock::WatchmanClock", tags = "1, 2")]
@@ -56,6 +71,7 @@ pub struct WatchmanClock {
 }
 /// Nested message and enum types in `WatchmanClock`.
 pub mod watchman_clock {
+    #[derive(serde::Serialize, serde::Deserialize)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
     pub enum WatchmanClock {
         #[prost(string, tag = "1")]
@@ -64,10 +80,13 @@ pub mod watchman_clock {
         UnixTimestamp(i64),
     }
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Checkout {
     /// The operation at which the working copy was updated.
     #[prost(bytes = "vec", tag = "2")]
+    #[serde(with = "crate::hex_util::serde_hex")]
     pub operation_id: ::prost::alloc::vec::Vec<u8>,
     /// An identifier for this workspace. It is used for looking up the current
     /// working-copy commit in the repo view. Currently a human-readable name.
@@ -76,6 +95,7 @@ pub struct Checkout {
     #[prost(string, tag = "3")]
     pub workspace_name: ::prost::alloc::string::String,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum FileType {
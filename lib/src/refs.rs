@@ -15,6 +15,7 @@
 #![allow(missing_docs)]
 
 use itertools::EitherOrBoth;
+use itertools::Itertools as _;
 
 use crate::backend::CommitId;
 use crate::index::Index;
@@ -22,6 +23,9 @@ use crate::merge::trivial_merge;
 use crate::merge::Merge;
 use crate::op_store::RefTarget;
 use crate::op_store::RemoteRef;
+use crate::repo::Repo;
+use crate::revset::walk_revs;
+use crate::revset::RevsetEvaluationError;
 
 /// Compares `refs1` and `refs2` targets, yields entry if they differ.
 ///
@@ -226,6 +230,44 @@ pub fn classify_bookmark_push_action(targets: LocalAndRemoteRef) -> BookmarkPush
     }
 }
 
+/// Numbers of commits that differ between two ref targets, as
+/// `(lower, upper)` bounds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AheadBehindCounts {
+    /// Commits reachable from the target but not from the base.
+    pub ahead: (usize, Option<usize>),
+    /// Commits reachable from the base but not from the target.
+    pub behind: (usize, Option<usize>),
+}
+
+impl AheadBehindCounts {
+    /// Returns true if the target and the base are known to point to the same
+    /// history.
+    pub fn is_synced(&self) -> bool {
+        self.ahead == (0, Some(0)) && self.behind == (0, Some(0))
+    }
+}
+
+/// Counts commits `target` is ahead of and behind `base`, typically a local
+/// bookmark and the remote bookmark it tracks.
+pub fn count_ahead_behind(
+    repo: &dyn Repo,
+    target: &RefTarget,
+    base: &RefTarget,
+) -> Result<AheadBehindCounts, RevsetEvaluationError> {
+    if target == base {
+        return Ok(AheadBehindCounts {
+            ahead: (0, Some(0)),
+            behind: (0, Some(0)),
+        });
+    }
+    let target_ids = target.added_ids().cloned().collect_vec();
+    let base_ids = base.added_ids().cloned().collect_vec();
+    let ahead = walk_revs(repo, &target_ids, &base_ids)?.count_estimate()?;
+    let behind = walk_revs(repo, &base_ids, &target_ids)?.count_estimate()?;
+    Ok(AheadBehindCounts { ahead, behind })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
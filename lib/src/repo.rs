@@ -43,6 +43,7 @@ use crate::commit::Commit;
 use crate::commit::CommitByCommitterTimestamp;
 use crate::commit_builder::CommitBuilder;
 use crate::commit_builder::DetachedCommitBuilder;
+use crate::config::ConfigGetError;
 use crate::dag_walk;
 use crate::default_index::DefaultIndexStore;
 use crate::default_index::DefaultMutableIndex;
@@ -101,9 +102,19 @@ use crate::simple_backend::SimpleBackend;
 use crate::simple_op_heads_store::SimpleOpHeadsStore;
 use crate::simple_op_store::SimpleOpStore;
 use crate::store::Store;
+use crate::store_migration::default_store_migrations;
+use crate::store_migration::StoreMigrationError;
+use crate::store_migration::StoreMigrationFn;
+use crate::store_migration::StoreMigrations;
+use crate::store_migration::REPO_STORE_DIRS;
 use crate::submodule_store::SubmoduleStore;
+use crate::transaction::OperationFileNotifier;
 use crate::transaction::Transaction;
 use crate::transaction::TransactionCommitError;
+use crate::transaction::TransactionObserver;
+use crate::transaction::TransactionObservers;
+use crate::transaction::LAST_OPERATION_FILE_NAME;
+use crate::tree_merge_cache::TreeMergeCache;
 use crate::view::RenameWorkspaceError;
 use crate::view::View;
 
@@ -164,6 +175,16 @@ pub enum RepoInitError {
     Path(#[from] PathError),
 }
 
+/// Returns the tree merge cache of the repo at `repo_path` if it's enabled by
+/// `core.tree-merge-cache`.
+fn load_tree_merge_cache(
+    settings: &UserSettings,
+    repo_path: &Path,
+) -> Result<Option<TreeMergeCache>, ConfigGetError> {
+    let enabled = settings.get_bool("core.tree-merge-cache")?;
+    Ok(enabled.then(|| TreeMergeCache::new(repo_path.join("tree_merge_cache"))))
+}
+
 impl ReadonlyRepo {
     pub fn default_op_store_initializer() -> &'static OpStoreInitializer<'static> {
         &|_settings, store_path, root_data| {
@@ -176,7 +197,14 @@ impl ReadonlyRepo {
     }
 
     pub fn default_index_store_initializer() -> &'static IndexStoreInitializer<'static> {
-        &|_settings, store_path| Ok(Box::new(DefaultIndexStore::init(store_path)?))
+        &|settings, store_path| {
+            let changed_path_filters = settings
+                .get_bool("index.changed-path-filters")
+                .map_err(|err| BackendInitError(err.into()))?;
+            let store = DefaultIndexStore::init(store_path)?
+                .with_changed_path_filters(changed_path_filters);
+            Ok(Box::new(store))
+        }
     }
 
     pub fn default_submodule_store_initializer() -> &'static SubmoduleStoreInitializer<'static> {
@@ -201,7 +229,9 @@ impl ReadonlyRepo {
         let backend = backend_initializer(settings, &store_path)?;
         let backend_path = store_path.join("type");
         fs::write(&backend_path, backend.name()).context(&backend_path)?;
-        let store = Store::new(backend, signer);
+        let tree_merge_cache = load_tree_merge_cache(settings, &repo_path)
+            .map_err(|err| BackendInitError(err.into()))?;
+        let store = Store::new_with_tree_merge_cache(backend, signer, tree_merge_cache);
 
         let op_store_path = repo_path.join("op_store");
         fs::create_dir(&op_store_path).context(&op_store_path)?;
@@ -236,6 +266,8 @@ impl ReadonlyRepo {
             .context(&submodule_store_type_path)?;
         let submodule_store = Arc::from(submodule_store);
 
+        default_store_migrations().write_format_versions(&repo_path, &REPO_STORE_DIRS)?;
+
         let loader = RepoLoader {
             settings: settings.clone(),
             store,
@@ -243,6 +275,7 @@ impl ReadonlyRepo {
             op_heads_store,
             index_store,
             submodule_store,
+            observers: TransactionObservers::default(),
         };
 
         let root_operation = loader.root_operation();
@@ -344,6 +377,10 @@ impl Repo for ReadonlyRepo {
         self.loader.submodule_store()
     }
 
+    fn resolve_change_id(&self, change_id: &ChangeId) -> Option<Vec<CommitId>> {
+        self.change_id_index().resolve_change_id(change_id)
+    }
+
     fn resolve_change_id_prefix(&self, prefix: &HexPrefix) -> PrefixResolution<Vec<CommitId>> {
         self.change_id_index().resolve_prefix(prefix)
     }
@@ -397,6 +434,7 @@ pub struct StoreFactories {
     op_heads_store_factories: HashMap<String, OpHeadsStoreFactory>,
     index_store_factories: HashMap<String, IndexStoreFactory>,
     submodule_store_factories: HashMap<String, SubmoduleStoreFactory>,
+    migrations: StoreMigrations,
 }
 
 impl Default for StoreFactories {
@@ -444,7 +482,18 @@ impl Default for StoreFactories {
         // Index
         factories.add_index_store(
             DefaultIndexStore::name(),
-            Box::new(|_settings, store_path| Ok(Box::new(DefaultIndexStore::load(store_path)))),
+            Box::new(|settings, store_path| {
+                let lazy_load = settings
+                    .get_bool("index.lazy-load")
+                    .map_err(|err| BackendLoadError(err.into()))?;
+                let changed_path_filters = settings
+                    .get_bool("index.changed-path-filters")
+                    .map_err(|err| BackendLoadError(err.into()))?;
+                let store = DefaultIndexStore::load(store_path)
+                    .with_lazy_load(lazy_load)
+                    .with_changed_path_filters(changed_path_filters);
+                Ok(Box::new(store))
+            }),
         );
 
         // SubmoduleStores
@@ -453,6 +502,9 @@ impl Default for StoreFactories {
             Box::new(|_settings, store_path| Ok(Box::new(DefaultSubmoduleStore::load(store_path)))),
         );
 
+        // Migrations
+        factories.migrations.merge(default_store_migrations());
+
         factories
     }
 }
@@ -473,6 +525,8 @@ pub enum StoreLoadError {
     Backend(#[from] BackendLoadError),
     #[error(transparent)]
     Signing(#[from] SignInitError),
+    #[error(transparent)]
+    Migration(#[from] StoreMigrationError),
 }
 
 impl StoreFactories {
@@ -483,6 +537,7 @@ impl StoreFactories {
             op_heads_store_factories: HashMap::new(),
             index_store_factories: HashMap::new(),
             submodule_store_factories: HashMap::new(),
+            migrations: StoreMigrations::new(),
         }
     }
 
@@ -493,6 +548,7 @@ impl StoreFactories {
             op_heads_store_factories,
             index_store_factories,
             submodule_store_factories,
+            migrations,
         } = ext;
 
         merge_factories_map(&mut self.backend_factories, backend_factories);
@@ -503,6 +559,7 @@ impl StoreFactories {
             &mut self.submodule_store_factories,
             submodule_store_factories,
         );
+        self.migrations.merge(migrations);
     }
 
     pub fn add_backend(&mut self, name: &str, factory: BackendFactory) {
@@ -606,6 +663,38 @@ impl StoreFactories {
 
         Ok(submodule_store_factory(settings, store_path)?)
     }
+
+    /// Registers a migration of the `store` directory (e.g. `"index"`) from
+    /// its latest registered format version to the next one. The migration
+    /// may only write the `rewritten_paths` relative to the store directory.
+    pub fn add_migration(
+        &mut self,
+        store: &str,
+        rewritten_paths: &[&str],
+        migration: StoreMigrationFn,
+    ) {
+        self.migrations.add(store, rewritten_paths, migration);
+    }
+
+    /// Runs pending format migrations of the stores in `repo_path`. Fails if
+    /// any store was written in a format newer than this binary supports.
+    pub fn migrate_stores(
+        &self,
+        settings: &UserSettings,
+        repo_path: &Path,
+    ) -> Result<(), StoreLoadError> {
+        Ok(self.migrations.migrate(settings, repo_path)?)
+    }
+
+    /// Runs pending format migrations of the working copy state in the
+    /// workspace's `jj_dir`.
+    pub fn migrate_workspace_stores(
+        &self,
+        settings: &UserSettings,
+        jj_dir: &Path,
+    ) -> Result<(), StoreLoadError> {
+        Ok(self.migrations.migrate_workspace(settings, jj_dir)?)
+    }
 }
 
 pub fn read_store_type(
@@ -644,6 +733,7 @@ pub struct RepoLoader {
     op_heads_store: Arc<dyn OpHeadsStore>,
     index_store: Arc<dyn IndexStore>,
     submodule_store: Arc<dyn SubmoduleStore>,
+    observers: TransactionObservers,
 }
 
 impl RepoLoader {
@@ -662,6 +752,7 @@ impl RepoLoader {
             op_heads_store,
             index_store,
             submodule_store,
+            observers: TransactionObservers::default(),
         }
     }
 
@@ -673,9 +764,12 @@ impl RepoLoader {
         repo_path: &Path,
         store_factories: &StoreFactories,
     ) -> Result<Self, StoreLoadError> {
-        let store = Store::new(
+        store_factories.migrate_stores(settings, repo_path)?;
+        let store = Store::new_with_tree_merge_cache(
             store_factories.load_backend(settings, &repo_path.join("store"))?,
             Signer::from_settings(settings)?,
+            load_tree_merge_cache(settings, repo_path)
+                .map_err(|err| BackendLoadError(err.into()))?,
         );
         let root_op_data = RootOperationData {
             root_commit_id: store.root_commit_id().clone(),
@@ -692,6 +786,10 @@ impl RepoLoader {
         let submodule_store = Arc::from(
             store_factories.load_submodule_store(settings, &repo_path.join("submodule_store"))?,
         );
+        let observers = TransactionObservers::default();
+        observers.add(Arc::new(OperationFileNotifier::new(
+            repo_path.join(LAST_OPERATION_FILE_NAME),
+        )));
         Ok(Self {
             settings: settings.clone(),
             store,
@@ -699,6 +797,7 @@ impl RepoLoader {
             op_heads_store,
             index_store,
             submodule_store,
+            observers,
         })
     }
 
@@ -726,6 +825,18 @@ impl RepoLoader {
         &self.submodule_store
     }
 
+    /// Registers an observer to be notified when a transaction is published.
+    ///
+    /// The observer is shared by all clones of this loader, and all repos
+    /// loaded from it.
+    pub fn add_transaction_observer(&self, observer: Arc<dyn TransactionObserver>) {
+        self.observers.add(observer);
+    }
+
+    pub(crate) fn transaction_observers(&self) -> &TransactionObservers {
+        &self.observers
+    }
+
     pub fn load_at_head(&self) -> Result<Arc<ReadonlyRepo>, RepoLoaderError> {
         let op = op_heads_store::resolve_op_heads(
             self.op_heads_store.as_ref(),
@@ -939,6 +1050,15 @@ impl MutableRepo {
         // `self.rewritten_commits`
     }
 
+    /// Returns the commits written in this transaction, mapped to their
+    /// predecessors. Newly created commits have no predecessors.
+    ///
+    /// Commits which were later rewritten or abandoned within the transaction
+    /// are included.
+    pub fn commit_predecessors(&self) -> &BTreeMap<CommitId, Vec<CommitId>> {
+        &self.commit_predecessors
+    }
+
     pub(crate) fn set_predecessors(&mut self, id: CommitId, predecessors: Vec<CommitId>) {
         self.commit_predecessors.insert(id, predecessors);
     }
@@ -1395,6 +1515,10 @@ impl MutableRepo {
     /// be recursively rebased onto the new version of their parents.
     /// Returns the number of rebased descendants.
     ///
+    /// Merge commits keep their parents in the same order. Parents that weren't
+    /// rewritten are kept as is, and the changes the merge made on top of its
+    /// merged parents are reapplied on top of the new merged parents.
+    ///
     /// All rebased descendant commits will be preserved even if they were
     /// emptied following the rebase operation. To customize the rebase
     /// behavior, use [`MutableRepo::rebase_descendants_with_options`].
@@ -1587,7 +1711,9 @@ impl MutableRepo {
                     .iter()
                     .all(|parent_id| current_heads.contains(parent_id)) =>
             {
-                self.index.add_commit(head);
+                self.index
+                    .add_commit(head)
+                    .map_err(|err| BackendError::Other(err.into()))?;
                 self.view.get_mut().add_head(head.id());
                 for parent_id in head.parent_ids() {
                     self.view.get_mut().remove_head(parent_id);
@@ -1613,7 +1739,9 @@ impl MutableRepo {
                     |_| panic!("graph has cycle"),
                 )?;
                 for CommitByCommitterTimestamp(missing_commit) in missing_commits.iter().rev() {
-                    self.index.add_commit(missing_commit);
+                    self.index
+                        .add_commit(missing_commit)
+                        .map_err(|err| BackendError::Other(err.into()))?;
                 }
                 for head in heads {
                     self.view.get_mut().add_head(head.id());
@@ -1926,6 +2054,11 @@ impl Repo for MutableRepo {
         self.base_repo.submodule_store()
     }
 
+    fn resolve_change_id(&self, change_id: &ChangeId) -> Option<Vec<CommitId>> {
+        let change_id_index = self.index.change_id_index(&mut self.view().heads().iter());
+        change_id_index.resolve_change_id(change_id)
+    }
+
     fn resolve_change_id_prefix(&self, prefix: &HexPrefix) -> PrefixResolution<Vec<CommitId>> {
         let change_id_index = self.index.change_id_index(&mut self.view().heads().iter());
         change_id_index.resolve_prefix(prefix)
@@ -584,16 +584,70 @@ pub enum UiPathParseError {
     Fs(FsPathParseError),
 }
 
+/// Directory which paths displayed to the user are relative to.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UiPathBase {
+    /// Paths are relative to the current working directory.
+    #[default]
+    Cwd,
+    /// Paths are relative to the workspace root.
+    Repo,
+}
+
+/// Separator between path components displayed to the user.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UiPathSeparator {
+    /// The platform's separator, which is `\\` on Windows.
+    #[default]
+    Native,
+    /// Forward slash on all platforms.
+    Slash,
+}
+
+/// Describes how paths are formatted for display.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UiPathStyle {
+    /// Directory which paths are relative to.
+    pub relative_to: UiPathBase,
+    /// Separator between path components.
+    pub separator: UiPathSeparator,
+}
+
+impl UiPathStyle {
+    fn separator_str(&self) -> &'static str {
+        match self.separator {
+            UiPathSeparator::Native => std::path::MAIN_SEPARATOR_STR,
+            UiPathSeparator::Slash => "/",
+        }
+    }
+
+    fn format_path(&self, path: &Path) -> String {
+        let formatted = path.display().to_string();
+        match self.separator {
+            UiPathSeparator::Native => formatted,
+            UiPathSeparator::Slash => formatted.replace(std::path::MAIN_SEPARATOR, "/"),
+        }
+    }
+}
+
 /// Converts `RepoPath`s to and from plain strings as displayed to the user
 /// (e.g. relative to CWD).
 #[derive(Debug, Clone)]
 pub enum RepoPathUiConverter {
     /// Variant for a local file system. Paths are interpreted relative to `cwd`
-    /// with the repo rooted in `base`.
+    /// with the repo rooted in `base`, and are displayed as specified by
+    /// `style`.
     ///
     /// The `cwd` and `base` paths are supposed to be absolute and normalized in
     /// the same manner.
-    Fs { cwd: PathBuf, base: PathBuf },
+    Fs {
+        cwd: PathBuf,
+        base: PathBuf,
+        style: UiPathStyle,
+    },
     // TODO: Add a no-op variant that uses the internal `RepoPath` representation. Can be useful
     // on a server.
 }
@@ -602,10 +656,8 @@ impl RepoPathUiConverter {
     /// Format a path for display in the UI.
     pub fn format_file_path(&self, file: &RepoPath) -> String {
         match self {
-            RepoPathUiConverter::Fs { cwd, base } => {
-                file_util::relative_path(cwd, &file.to_fs_path_unchecked(base))
-                    .display()
-                    .to_string()
+            RepoPathUiConverter::Fs { cwd, base, style } => {
+                style.format_path(&relative_fs_path(cwd, base, style, file))
             }
         }
     }
@@ -621,9 +673,9 @@ impl RepoPathUiConverter {
         }
         let mut formatted = String::new();
         match self {
-            RepoPathUiConverter::Fs { cwd, base } => {
-                let source_path = file_util::relative_path(cwd, &source.to_fs_path_unchecked(base));
-                let target_path = file_util::relative_path(cwd, &target.to_fs_path_unchecked(base));
+            RepoPathUiConverter::Fs { cwd, base, style } => {
+                let source_path = relative_fs_path(cwd, base, style, source);
+                let target_path = relative_fs_path(cwd, base, style, target);
 
                 let source_components = source_path.components().collect_vec();
                 let target_components = target_path.components().collect_vec();
@@ -649,13 +701,12 @@ impl RepoPathUiConverter {
                     .min(source_components.len().saturating_sub(1))
                     .min(target_components.len().saturating_sub(1));
 
-                fn format_components(c: &[std::path::Component]) -> String {
-                    c.iter().collect::<PathBuf>().display().to_string()
-                }
+                let format_components =
+                    |c: &[std::path::Component]| style.format_path(&c.iter().collect::<PathBuf>());
 
                 if prefix_count > 0 {
                     formatted.push_str(&format_components(&source_components[0..prefix_count]));
-                    formatted.push_str(std::path::MAIN_SEPARATOR_STR);
+                    formatted.push_str(style.separator_str());
                 }
                 formatted.push('{');
                 formatted.push_str(&format_components(
@@ -669,7 +720,7 @@ impl RepoPathUiConverter {
                 ));
                 formatted.push('}');
                 if suffix_count > 0 {
-                    formatted.push_str(std::path::MAIN_SEPARATOR_STR);
+                    formatted.push_str(style.separator_str());
                     formatted.push_str(&format_components(
                         &source_components[source_components.len() - suffix_count..],
                     ));
@@ -685,13 +736,23 @@ impl RepoPathUiConverter {
     /// where relative paths are interpreted as relative to.
     pub fn parse_file_path(&self, input: &str) -> Result<RepoPathBuf, UiPathParseError> {
         match self {
-            RepoPathUiConverter::Fs { cwd, base } => {
-                RepoPathBuf::parse_fs_path(cwd, base, input).map_err(UiPathParseError::Fs)
-            }
+            RepoPathUiConverter::Fs {
+                cwd,
+                base,
+                style: _,
+            } => RepoPathBuf::parse_fs_path(cwd, base, input).map_err(UiPathParseError::Fs),
         }
     }
 }
 
+fn relative_fs_path(cwd: &Path, base: &Path, style: &UiPathStyle, file: &RepoPath) -> PathBuf {
+    let from = match style.relative_to {
+        UiPathBase::Cwd => cwd,
+        UiPathBase::Repo => base,
+    };
+    file_util::relative_path(from, &file.to_fs_path_unchecked(base))
+}
+
 #[cfg(test)]
 mod tests {
     use std::panic;
@@ -1151,11 +1212,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_path_with_style() {
+        let base = PathBuf::from("ws");
+        let converter = |relative_to, separator| RepoPathUiConverter::Fs {
+            cwd: base.join("dir"),
+            base: base.clone(),
+            style: UiPathStyle {
+                relative_to,
+                separator,
+            },
+        };
+
+        let ui = converter(UiPathBase::Cwd, UiPathSeparator::Slash);
+        assert_eq!(ui.format_file_path(repo_path("dir/file")), "file");
+        assert_eq!(ui.format_file_path(repo_path("sub/file")), "../sub/file");
+        assert_eq!(
+            ui.format_copied_path(repo_path("sub/a/file"), repo_path("sub/b/file")),
+            "../sub/{a => b}/file"
+        );
+
+        let ui = converter(UiPathBase::Repo, UiPathSeparator::Slash);
+        assert_eq!(ui.format_file_path(repo_path("dir/file")), "dir/file");
+        assert_eq!(ui.format_file_path(repo_path("sub/file")), "sub/file");
+        assert_eq!(
+            ui.format_copied_path(repo_path("sub/a/file"), repo_path("sub/b/file")),
+            "sub/{a => b}/file"
+        );
+
+        let ui = converter(UiPathBase::Repo, UiPathSeparator::Native);
+        assert_eq!(
+            ui.format_file_path(repo_path("sub/file")),
+            Path::new("sub").join("file").display().to_string()
+        );
+
+        // Paths are still parsed relative to cwd
+        assert_eq!(*ui.parse_file_path("file").unwrap(), *repo_path("dir/file"));
+    }
+
     #[test]
     fn test_format_copied_path() {
         let ui = RepoPathUiConverter::Fs {
             cwd: PathBuf::from("."),
             base: PathBuf::from("."),
+            style: UiPathStyle::default(),
         };
 
         let format = |before, after| {
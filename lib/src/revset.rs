@@ -19,6 +19,7 @@ use std::collections::hash_map;
 use std::collections::HashMap;
 use std::convert::Infallible;
 use std::fmt;
+use std::iter;
 use std::ops::ControlFlow;
 use std::ops::Range;
 use std::rc::Rc;
@@ -41,6 +42,7 @@ use crate::graph::GraphNode;
 use crate::id_prefix::IdPrefixContext;
 use crate::id_prefix::IdPrefixIndex;
 use crate::object_id::HexPrefix;
+use crate::object_id::ObjectId as _;
 use crate::object_id::PrefixResolution;
 use crate::op_store::RemoteRefState;
 use crate::op_walk;
@@ -136,6 +138,11 @@ pub enum RevsetCommitRef {
     RemoteSymbol(RemoteRefSymbolBuf),
     ChangeId(HexPrefix),
     CommitId(HexPrefix),
+    /// Commits with the exact ids. It's an error if any of them doesn't exist.
+    ExactIds {
+        commit_ids: Vec<CommitId>,
+        change_ids: Vec<ChangeId>,
+    },
     Bookmarks(StringPattern),
     RemoteBookmarks {
         bookmark_pattern: StringPattern,
@@ -184,6 +191,8 @@ pub enum RevsetFilterPredicate {
     },
     /// Commits with conflicts
     HasConflict,
+    /// Commits with conflicts at the paths specified by the fileset.
+    HasConflictIn(FilesetExpression),
     /// Commits that are cryptographically signed.
     Signed,
     /// Custom predicates provided by extensions
@@ -277,6 +286,12 @@ pub enum RevsetExpression<St: ExpressionState> {
         candidates: Rc<Self>,
         count: usize,
     },
+    /// Ancestor of `heads` in `candidates` that has the fewest commits between
+    /// it and `heads`.
+    Closest {
+        candidates: Rc<Self>,
+        heads: Rc<Self>,
+    },
     Filter(RevsetFilterPredicate),
     /// Marker for subtree that should be intersected as filter.
     AsFilter(Rc<Self>),
@@ -378,6 +393,14 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
         Rc::new(Self::CommitRef(commit_ref))
     }
 
+    pub fn exact_ids(commit_ids: Vec<CommitId>, change_ids: Vec<ChangeId>) -> Rc<Self> {
+        let commit_ref = RevsetCommitRef::ExactIds {
+            commit_ids,
+            change_ids,
+        };
+        Rc::new(Self::CommitRef(commit_ref))
+    }
+
     pub fn bookmarks(pattern: StringPattern) -> Rc<Self> {
         Rc::new(Self::CommitRef(RevsetCommitRef::Bookmarks(pattern)))
     }
@@ -479,6 +502,15 @@ impl<St: ExpressionState> RevsetExpression<St> {
         Rc::new(Self::ForkPoint(self.clone()))
     }
 
+    /// Ancestor of `heads` in `self` that is the closest to `heads`, like
+    /// `git describe` picks a tag.
+    pub fn closest(self: &Rc<Self>, heads: &Rc<Self>) -> Rc<Self> {
+        Rc::new(Self::Closest {
+            candidates: self.clone(),
+            heads: heads.clone(),
+        })
+    }
+
     /// Filter all commits by `predicate` in `self`.
     pub fn filtered(self: &Rc<Self>, predicate: RevsetFilterPredicate) -> Rc<Self> {
         self.intersection(&Self::filter(predicate))
@@ -671,6 +703,10 @@ pub enum ResolvedExpression {
         candidates: Box<Self>,
         count: usize,
     },
+    Closest {
+        candidates: Box<Self>,
+        heads: Box<Self>,
+    },
     Coalesce(Box<Self>, Box<Self>),
     Union(Box<Self>, Box<Self>),
     /// Intersects `candidates` with `predicate` by filtering.
@@ -689,6 +725,16 @@ pub type RevsetFunction = fn(
     &LoweringContext,
 ) -> Result<Rc<UserRevsetExpression>, RevsetParseError>;
 
+/// Functions which were removed in favor of the new names. These are only
+/// used to suggest the new names.
+const RENAMED_FUNCTIONS: &[(&str, &str)] = &[
+    ("branches", "bookmarks"),
+    ("local_branches", "bookmarks"),
+    ("remote_branches", "remote_bookmarks"),
+    ("tracked_remote_branches", "tracked_remote_bookmarks"),
+    ("untracked_remote_branches", "untracked_remote_bookmarks"),
+];
+
 static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy::new(|| {
     // Not using maplit::hashmap!{} or custom declarative macro here because
     // code completion inside macro is quite restricted.
@@ -794,6 +840,30 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         })?;
         Ok(RevsetExpression::commit_id_prefix(prefix))
     });
+    map.insert("commits", |diagnostics, function, _context| {
+        let ([], args) = function.expect_some_arguments()?;
+        let mut commit_ids = vec![];
+        let mut change_ids = vec![];
+        for arg in args {
+            revset_parser::catch_aliases(diagnostics, arg, |_diagnostics, arg| {
+                let value = revset_parser::expect_string_literal("commit or change ID", arg)?;
+                if !value.is_empty() {
+                    if let Some(id) = CommitId::try_from_hex(value) {
+                        commit_ids.push(id);
+                        return Ok(());
+                    } else if let Some(id) = ChangeId::try_from_reverse_hex(value) {
+                        change_ids.push(id);
+                        return Ok(());
+                    }
+                }
+                Err(RevsetParseError::expression(
+                    "Invalid commit or change ID",
+                    arg.span,
+                ))
+            })?;
+        }
+        Ok(RevsetExpression::exact_ids(commit_ids, change_ids))
+    });
     map.insert("bookmarks", |diagnostics, function, _context| {
         let ([], [opt_arg]) = function.expect_arguments()?;
         let pattern = if let Some(arg) = opt_arg {
@@ -850,6 +920,12 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         let expression = lower_expression(diagnostics, expression_arg, context)?;
         Ok(RevsetExpression::fork_point(&expression))
     });
+    map.insert("closest", |diagnostics, function, context| {
+        let [candidates_arg, heads_arg] = function.expect_exact_arguments()?;
+        let candidates = lower_expression(diagnostics, candidates_arg, context)?;
+        let heads = lower_expression(diagnostics, heads_arg, context)?;
+        Ok(candidates.closest(&heads))
+    });
     map.insert("merges", |_diagnostics, function, _context| {
         function.expect_no_arguments()?;
         Ok(RevsetExpression::filter(
@@ -890,11 +966,12 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         Ok(RevsetExpression::filter(predicate))
     });
     map.insert("author_date", |diagnostics, function, context| {
-        let [arg] = function.expect_exact_arguments()?;
-        let pattern = expect_date_pattern(diagnostics, arg, context.date_pattern_context())?;
-        Ok(RevsetExpression::filter(RevsetFilterPredicate::AuthorDate(
-            pattern,
-        )))
+        parse_date_arguments(
+            diagnostics,
+            function,
+            context,
+            RevsetFilterPredicate::AuthorDate,
+        )
     });
     map.insert("signed", |_diagnostics, function, _context| {
         function.expect_no_arguments()?;
@@ -931,27 +1008,34 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         Ok(RevsetExpression::filter(predicate))
     });
     map.insert("committer_date", |diagnostics, function, context| {
-        let [arg] = function.expect_exact_arguments()?;
-        let pattern = expect_date_pattern(diagnostics, arg, context.date_pattern_context())?;
-        Ok(RevsetExpression::filter(
-            RevsetFilterPredicate::CommitterDate(pattern),
-        ))
+        parse_date_arguments(
+            diagnostics,
+            function,
+            context,
+            RevsetFilterPredicate::CommitterDate,
+        )
     });
     map.insert("empty", |_diagnostics, function, _context| {
         function.expect_no_arguments()?;
         Ok(RevsetExpression::is_empty())
     });
-    map.insert("files", |diagnostics, function, context| {
+    let files_function: RevsetFunction = |diagnostics, function, context| {
         let ctx = context.workspace.as_ref().ok_or_else(|| {
             RevsetParseError::with_span(
                 RevsetParseErrorKind::FsPathWithoutWorkspace,
                 function.args_span, // TODO: better to use name_span?
             )
         })?;
-        let [arg] = function.expect_exact_arguments()?;
-        let expr = expect_fileset_expression(diagnostics, arg, ctx.path_converter)?;
+        let ([arg], rest_args) = function.expect_some_arguments()?;
+        let expressions: Vec<_> = iter::once(arg)
+            .chain(rest_args)
+            .map(|arg| expect_fileset_expression(diagnostics, arg, ctx.path_converter))
+            .try_collect()?;
+        let expr = FilesetExpression::union_all(expressions);
         Ok(RevsetExpression::filter(RevsetFilterPredicate::File(expr)))
-    });
+    };
+    map.insert("files", files_function);
+    map.insert("file", files_function);
     map.insert("diff_contains", |diagnostics, function, context| {
         let ([text_arg], [files_opt_arg]) = function.expect_arguments()?;
         let text = expect_string_pattern(diagnostics, text_arg)?;
@@ -972,9 +1056,21 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
             RevsetFilterPredicate::DiffContains { text, files },
         ))
     });
-    map.insert("conflicts", |_diagnostics, function, _context| {
-        function.expect_no_arguments()?;
-        Ok(RevsetExpression::filter(RevsetFilterPredicate::HasConflict))
+    map.insert("conflicts", |diagnostics, function, context| {
+        let ([], [opt_arg]) = function.expect_arguments()?;
+        let Some(arg) = opt_arg else {
+            return Ok(RevsetExpression::filter(RevsetFilterPredicate::HasConflict));
+        };
+        let ctx = context.workspace.as_ref().ok_or_else(|| {
+            RevsetParseError::with_span(
+                RevsetParseErrorKind::FsPathWithoutWorkspace,
+                function.args_span,
+            )
+        })?;
+        let expr = expect_fileset_expression(diagnostics, arg, ctx.path_converter)?;
+        Ok(RevsetExpression::filter(
+            RevsetFilterPredicate::HasConflictIn(expr),
+        ))
     });
     map.insert("present", |diagnostics, function, context| {
         let [arg] = function.expect_exact_arguments()?;
@@ -1058,6 +1154,47 @@ pub fn expect_date_pattern(
     })
 }
 
+/// Parses `date_function(pattern)` or `date_function([after=date],
+/// [before=date])` into the intersection of the date filters.
+fn parse_date_arguments(
+    diagnostics: &mut RevsetDiagnostics,
+    function: &FunctionCallNode,
+    context: &LoweringContext,
+    to_predicate: fn(DatePattern) -> RevsetFilterPredicate,
+) -> Result<Rc<UserRevsetExpression>, RevsetParseError> {
+    let ([], [pattern_opt_arg, after_opt_arg, before_opt_arg]) =
+        function.expect_named_arguments(&["", "after", "before"])?;
+    let date_context = context.date_pattern_context();
+    let mut patterns = vec![];
+    if let Some(pattern_arg) = pattern_opt_arg {
+        patterns.push(expect_date_pattern(diagnostics, pattern_arg, date_context)?);
+    }
+    for (kind, bound_opt_arg) in [("after", after_opt_arg), ("before", before_opt_arg)] {
+        if let Some(bound_arg) = bound_opt_arg {
+            let pattern = revset_parser::catch_aliases(diagnostics, bound_arg, |_, node| {
+                let value = revset_parser::expect_string_literal("date", node)?;
+                date_context.parse_relative(value, kind).map_err(|err| {
+                    RevsetParseError::expression("Invalid date pattern", node.span).with_source(err)
+                })
+            })?;
+            patterns.push(pattern);
+        }
+    }
+    patterns
+        .into_iter()
+        .map(|pattern| RevsetExpression::filter(to_predicate(pattern)))
+        .reduce(|acc, expression| acc.intersection(&expression))
+        .ok_or_else(|| {
+            RevsetParseError::with_span(
+                RevsetParseErrorKind::InvalidFunctionArguments {
+                    name: function.name.to_owned(),
+                    message: "Expected date pattern, or `after` or `before` argument".to_owned(),
+                },
+                function.args_span,
+            )
+        })
+}
+
 fn parse_remote_bookmarks_arguments(
     diagnostics: &mut RevsetDiagnostics,
     function: &FunctionCallNode,
@@ -1092,10 +1229,20 @@ fn lower_function_call(
     if let Some(func) = function_map.get(function.name) {
         func(diagnostics, function, context)
     } else {
+        let renamed = RENAMED_FUNCTIONS
+            .iter()
+            .filter(|(old_name, _)| *old_name == function.name)
+            .map(|(_, new_name)| new_name.to_string());
+        let candidates = collect_similar(function.name, function_map.keys())
+            .into_iter()
+            .chain(renamed)
+            .sorted_unstable()
+            .dedup()
+            .collect();
         Err(RevsetParseError::with_span(
             RevsetParseErrorKind::NoSuchFunction {
                 name: function.name.to_owned(),
-                candidates: collect_similar(function.name, function_map.keys()),
+                candidates,
             },
             function.name_span,
         ))
@@ -1357,6 +1504,10 @@ fn try_transform_expression<St: ExpressionState, E>(
                     candidates,
                     count: *count,
                 }),
+            RevsetExpression::Closest { candidates, heads } => {
+                transform_rec_pair((candidates, heads), pre, post)?
+                    .map(|(candidates, heads)| RevsetExpression::Closest { candidates, heads })
+            }
             RevsetExpression::Filter(_) => None,
             RevsetExpression::AsFilter(candidates) => {
                 transform_rec(candidates, pre, post)?.map(RevsetExpression::AsFilter)
@@ -1578,6 +1729,11 @@ where
             let count = *count;
             RevsetExpression::Latest { candidates, count }.into()
         }
+        RevsetExpression::Closest { candidates, heads } => {
+            let candidates = folder.fold_expression(candidates)?;
+            let heads = folder.fold_expression(heads)?;
+            RevsetExpression::Closest { candidates, heads }.into()
+        }
         RevsetExpression::Filter(predicate) => RevsetExpression::Filter(predicate.clone()).into(),
         RevsetExpression::AsFilter(candidates) => {
             let candidates = folder.fold_expression(candidates)?;
@@ -1875,6 +2031,64 @@ fn internalize_filter<St: ExpressionState>(
     })
 }
 
+/// Moves cheaper filters to the left in intersections of filters. Since
+/// intersected predicates are evaluated from left to right, and the evaluation
+/// stops at the first predicate that doesn't match, expensive predicates like
+/// `files(_)` will be tested against fewer commits.
+///
+/// Sets should have been moved to the left of filters by `internalize_filter`.
+/// Sets are considered the cheapest, so they stay on the left.
+fn sort_filters_by_cost<St: ExpressionState>(
+    expression: &Rc<RevsetExpression<St>>,
+) -> TransformedExpression<St> {
+    #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    enum FilterCost {
+        Set,
+        CommitMetadata,
+        Other,
+        Diff,
+    }
+
+    fn get_cost<St: ExpressionState>(expression: &RevsetExpression<St>) -> FilterCost {
+        match expression {
+            RevsetExpression::Filter(predicate) => match predicate {
+                RevsetFilterPredicate::ParentCount(_)
+                | RevsetFilterPredicate::Description(_)
+                | RevsetFilterPredicate::Subject(_)
+                | RevsetFilterPredicate::AuthorName(_)
+                | RevsetFilterPredicate::AuthorEmail(_)
+                | RevsetFilterPredicate::AuthorDate(_)
+                | RevsetFilterPredicate::CommitterName(_)
+                | RevsetFilterPredicate::CommitterEmail(_)
+                | RevsetFilterPredicate::CommitterDate(_)
+                | RevsetFilterPredicate::HasConflict => FilterCost::CommitMetadata,
+                RevsetFilterPredicate::Signed | RevsetFilterPredicate::Extension(_) => {
+                    FilterCost::Other
+                }
+                RevsetFilterPredicate::File(_)
+                | RevsetFilterPredicate::DiffContains { .. }
+                | RevsetFilterPredicate::HasConflictIn(_) => FilterCost::Diff,
+            },
+            RevsetExpression::AsFilter(expression)
+            | RevsetExpression::Present(expression)
+            | RevsetExpression::NotIn(expression) => get_cost(expression),
+            RevsetExpression::Union(expression1, expression2)
+            | RevsetExpression::Intersection(expression1, expression2)
+            | RevsetExpression::Difference(expression1, expression2) => {
+                get_cost(expression1).max(get_cost(expression2))
+            }
+            _ => FilterCost::Set,
+        }
+    }
+
+    transform_expression_bottom_up(expression, |expression| match expression.as_ref() {
+        RevsetExpression::Intersection(expression1, expression2) => {
+            sort_intersection_by_key(expression1, expression2, get_cost)
+        }
+        _ => None,
+    })
+}
+
 /// Eliminates redundant nodes like `x & all()`, `~~x`.
 ///
 /// Since this function rewrites `x & none()` to `none()`, user symbols should
@@ -1908,6 +2122,18 @@ fn fold_redundant_expression<St: ExpressionState>(
                 _ => None,
             }
         }
+        // Heads and roots of a set in which no commit is an ancestor of
+        // another are the set itself.
+        RevsetExpression::Heads(candidates) | RevsetExpression::Roots(candidates) => {
+            match candidates.as_ref() {
+                RevsetExpression::None => Some(RevsetExpression::none()),
+                RevsetExpression::Heads(_) | RevsetExpression::Roots(_) => Some(candidates.clone()),
+                _ => None,
+            }
+        }
+        RevsetExpression::Latest { candidates, count } => (*count == 0
+            || matches!(candidates.as_ref(), RevsetExpression::None))
+        .then(RevsetExpression::none),
         _ => None,
     })
 }
@@ -2237,6 +2463,7 @@ pub fn optimize<St: ExpressionState>(
     let expression = flatten_intersections(&expression).unwrap_or(expression);
     let expression = sort_negations_and_ancestors(&expression).unwrap_or(expression);
     let expression = internalize_filter(&expression).unwrap_or(expression);
+    let expression = sort_filters_by_cost(&expression).unwrap_or(expression);
     let expression = fold_ancestors_union(&expression).unwrap_or(expression);
     let expression = fold_heads_range(&expression).unwrap_or(expression);
     let expression = fold_difference(&expression).unwrap_or(expression);
@@ -2571,6 +2798,34 @@ fn resolve_commit_ref(
             let resolver = &symbol_resolver.commit_id_resolver;
             Ok(resolver.try_resolve(repo, prefix)?.into_iter().collect())
         }
+        RevsetCommitRef::ExactIds {
+            commit_ids,
+            change_ids,
+        } => {
+            let index = repo.index();
+            let mut resolved_ids = Vec::with_capacity(commit_ids.len() + change_ids.len());
+            for id in commit_ids {
+                if !index.has_id(id) {
+                    return Err(RevsetResolutionError::NoSuchRevision {
+                        name: id.hex(),
+                        candidates: vec![],
+                    });
+                }
+                resolved_ids.push(id.clone());
+            }
+            for id in change_ids {
+                match repo.resolve_change_id(id) {
+                    Some(ids) if !ids.is_empty() => resolved_ids.extend(ids),
+                    _ => {
+                        return Err(RevsetResolutionError::NoSuchRevision {
+                            name: id.reverse_hex(),
+                            candidates: vec![],
+                        });
+                    }
+                }
+            }
+            Ok(resolved_ids)
+        }
         RevsetCommitRef::Bookmarks(pattern) => {
             let commit_ids = repo
                 .view()
@@ -2796,6 +3051,10 @@ impl VisibilityResolutionContext<'_> {
                 candidates: self.resolve(candidates).into(),
                 count: *count,
             },
+            RevsetExpression::Closest { candidates, heads } => ResolvedExpression::Closest {
+                candidates: self.resolve(candidates).into(),
+                heads: self.resolve(heads).into(),
+            },
             RevsetExpression::Filter(_) | RevsetExpression::AsFilter(_) => {
                 // Top-level filter without intersection: e.g. "~author(_)" is represented as
                 // `AsFilter(NotIn(Filter(Author(_))))`.
@@ -2915,7 +3174,8 @@ impl VisibilityResolutionContext<'_> {
             | RevsetExpression::HeadsRange { .. }
             | RevsetExpression::Roots(_)
             | RevsetExpression::ForkPoint(_)
-            | RevsetExpression::Latest { .. } => {
+            | RevsetExpression::Latest { .. }
+            | RevsetExpression::Closest { .. } => {
                 ResolvedPredicateExpression::Set(self.resolve(expression).into())
             }
             RevsetExpression::Filter(predicate) => {
@@ -3164,6 +3424,7 @@ mod tests {
     use assert_matches::assert_matches;
 
     use super::*;
+    use crate::repo_path::UiPathStyle;
 
     fn parse(revset_str: &str) -> Result<Rc<UserRevsetExpression>, RevsetParseError> {
         parse_with_aliases(revset_str, [] as [(&str, &str); 0])
@@ -3204,6 +3465,7 @@ mod tests {
         let path_converter = RepoPathUiConverter::Fs {
             cwd: PathBuf::from("/"),
             base: PathBuf::from("/"),
+            style: UiPathStyle::default(),
         };
         let workspace_ctx = RevsetWorkspaceContext {
             path_converter: &path_converter,
@@ -3725,11 +3987,22 @@ mod tests {
             parse("description(\"(foo)\")").unwrap(),
             @r#"Filter(Description(Substring("(foo)")))"#);
         assert!(parse("mine(foo)").is_err());
+        insta::assert_debug_snapshot!(parse("author_date()").unwrap_err().kind(), @r#"
+        InvalidFunctionArguments {
+            name: "author_date",
+            message: "Expected date pattern, or `after` or `before` argument",
+        }
+        "#);
+        assert!(parse("committer_date(since='2023-01-01')").is_err());
+        // A positional date must specify whether it is a lower or upper bound
+        assert!(parse("author_date('2023-01-01')").is_err());
+        assert!(parse("committer_date(after=after:'2023-01-01')").is_err());
         insta::assert_debug_snapshot!(
             parse_with_workspace("empty()", WorkspaceName::DEFAULT).unwrap(),
             @"NotIn(Filter(File(All)))");
         assert!(parse_with_workspace("empty(foo)", WorkspaceName::DEFAULT).is_err());
         assert!(parse_with_workspace("file()", WorkspaceName::DEFAULT).is_err());
+        assert!(parse_with_workspace("files()", WorkspaceName::DEFAULT).is_err());
         insta::assert_debug_snapshot!(
             parse_with_workspace("files(foo)", WorkspaceName::DEFAULT).unwrap(),
             @r#"Filter(File(Pattern(PrefixPath("foo"))))"#);
@@ -3755,6 +4028,30 @@ mod tests {
             ),
         )
         "#);
+        insta::assert_debug_snapshot!(
+            parse_with_workspace("files(foo, bar)", WorkspaceName::DEFAULT).unwrap(), @r#"
+        Filter(
+            File(
+                UnionAll(
+                    [
+                        Pattern(PrefixPath("foo")),
+                        Pattern(PrefixPath("bar")),
+                    ],
+                ),
+            ),
+        )
+        "#);
+        insta::assert_debug_snapshot!(
+            parse_with_workspace("file(foo)", WorkspaceName::DEFAULT).unwrap(),
+            @r#"Filter(File(Pattern(PrefixPath("foo"))))"#);
+        insta::assert_debug_snapshot!(parse("conflicts()").unwrap(), @"Filter(HasConflict)");
+        insta::assert_debug_snapshot!(
+            parse("conflicts(foo)").unwrap_err().kind(),
+            @"FsPathWithoutWorkspace");
+        insta::assert_debug_snapshot!(
+            parse_with_workspace("conflicts(foo)", WorkspaceName::DEFAULT).unwrap(),
+            @r#"Filter(HasConflictIn(Pattern(PrefixPath("foo"))))"#);
+        assert!(parse_with_workspace("conflicts(foo, bar)", WorkspaceName::DEFAULT).is_err());
         insta::assert_debug_snapshot!(parse("signed()").unwrap(), @"Filter(Signed)");
     }
 
@@ -3782,6 +4079,26 @@ mod tests {
         insta::assert_debug_snapshot!(
             parse("commit_id(xyzzy)").unwrap_err().kind(),
             @r#"Expression("Invalid commit ID prefix")"#);
+
+        insta::assert_debug_snapshot!(
+            parse("commits('0f', zk)").unwrap(), @r#"
+        CommitRef(
+            ExactIds {
+                commit_ids: [
+                    CommitId("0f"),
+                ],
+                change_ids: [
+                    ChangeId("0f"),
+                ],
+            },
+        )
+        "#);
+        insta::assert_debug_snapshot!(
+            parse("commits(xyzzy)").unwrap_err().kind(),
+            @r#"Expression("Invalid commit or change ID")"#);
+        insta::assert_debug_snapshot!(
+            parse("commits('')").unwrap_err().kind(),
+            @r#"Expression("Invalid commit or change ID")"#);
     }
 
     #[test]
@@ -4447,6 +4764,42 @@ mod tests {
             optimize(UserRevsetExpression::commits(vec![]).negated()), @"All");
     }
 
+    #[test]
+    fn test_optimize_heads_roots_latest() {
+        let settings = insta_settings();
+        let _guard = settings.bind_to_scope();
+
+        insta::assert_debug_snapshot!(optimize(parse("heads(none())").unwrap()), @"None");
+        insta::assert_debug_snapshot!(optimize(parse("roots(none())").unwrap()), @"None");
+        insta::assert_debug_snapshot!(optimize(parse("latest(none())").unwrap()), @"None");
+        insta::assert_debug_snapshot!(optimize(parse("latest(foo, 0)").unwrap()), @"None");
+        insta::assert_debug_snapshot!(optimize(parse("latest(foo, 2)").unwrap()), @r#"
+        Latest {
+            candidates: CommitRef(Symbol("foo")),
+            count: 2,
+        }
+        "#);
+
+        // Heads and roots are idempotent, and the heads of roots are the roots
+        insta::assert_debug_snapshot!(
+            optimize(parse("heads(heads(foo))").unwrap()), @r#"Heads(CommitRef(Symbol("foo")))"#);
+        insta::assert_debug_snapshot!(
+            optimize(parse("roots(roots(foo))").unwrap()), @r#"Roots(CommitRef(Symbol("foo")))"#);
+        insta::assert_debug_snapshot!(
+            optimize(parse("heads(roots(foo))").unwrap()), @r#"Roots(CommitRef(Symbol("foo")))"#);
+        insta::assert_debug_snapshot!(
+            optimize(parse("roots(heads(roots(foo)))").unwrap()),
+            @r#"Roots(CommitRef(Symbol("foo")))"#);
+        insta::assert_debug_snapshot!(
+            optimize(parse("heads(heads(::foo))").unwrap()), @r#"
+        HeadsRange {
+            roots: None,
+            heads: CommitRef(Symbol("foo")),
+            filter: All,
+        }
+        "#);
+    }
+
     #[test]
     fn test_optimize_difference() {
         let settings = insta_settings();
@@ -4794,9 +5147,9 @@ mod tests {
             Intersection(
                 Intersection(
                     Filter(CommitterName(Substring("foo"))),
-                    Filter(File(Pattern(PrefixPath("bar")))),
+                    Filter(AuthorName(Substring("baz"))),
                 ),
-                Filter(AuthorName(Substring("baz"))),
+                Filter(File(Pattern(PrefixPath("bar")))),
             ),
         )
         "#);
@@ -4945,6 +5298,61 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn test_optimize_filter_cost_order() {
+        let settings = insta_settings();
+        let _guard = settings.bind_to_scope();
+
+        // Diff-based filters should be evaluated last
+        insta::assert_debug_snapshot!(
+            optimize(parse_with_workspace(
+                "files(foo) & description(bar)",
+                WorkspaceName::DEFAULT).unwrap(),
+            ), @r#"
+        AsFilter(
+            Intersection(
+                Filter(Description(Substring("bar"))),
+                Filter(File(Pattern(PrefixPath("foo")))),
+            ),
+        )
+        "#);
+        insta::assert_debug_snapshot!(
+            optimize(parse_with_workspace(
+                "foo & files(bar) & signed() & author_name(baz)",
+                WorkspaceName::DEFAULT).unwrap(),
+            ), @r#"
+        Intersection(
+            CommitRef(Symbol("foo")),
+            AsFilter(
+                Intersection(
+                    Intersection(
+                        Filter(AuthorName(Substring("baz"))),
+                        Filter(Signed),
+                    ),
+                    Filter(File(Pattern(PrefixPath("bar")))),
+                ),
+            ),
+        )
+        "#);
+
+        // The cost of a subtree is the cost of the most expensive filter in it
+        insta::assert_debug_snapshot!(
+            optimize(parse_with_workspace(
+                "(files(foo) | author_name(bar)) & committer_name(baz)",
+                WorkspaceName::DEFAULT).unwrap(),
+            ), @r#"
+        AsFilter(
+            Intersection(
+                Filter(CommitterName(Substring("baz"))),
+                Union(
+                    Filter(File(Pattern(PrefixPath("foo")))),
+                    Filter(AuthorName(Substring("bar"))),
+                ),
+            ),
+        )
+        "#);
+    }
+
     #[test]
     fn test_optimize_filter_subtree() {
         let settings = insta_settings();
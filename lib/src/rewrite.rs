@@ -25,15 +25,21 @@ use indexmap::IndexMap;
 use indexmap::IndexSet;
 use itertools::Itertools as _;
 use pollster::FutureExt as _;
+use thiserror::Error;
 use tracing::instrument;
 
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::CommitId;
+use crate::backend::CopyId;
 use crate::backend::MergedTreeId;
+use crate::backend::TreeValue;
 use crate::commit::Commit;
 use crate::commit::CommitIteratorExt as _;
 use crate::commit_builder::CommitBuilder;
+use crate::commit_builder::DetachedCommitBuilder;
+use crate::evolution::walk_predecessors;
+use crate::evolution::WalkPredecessorsError;
 use crate::index::Index;
 use crate::index::IndexError;
 use crate::matchers::Matcher;
@@ -44,6 +50,7 @@ use crate::merged_tree::TreeDiffEntry;
 use crate::repo::MutableRepo;
 use crate::repo::Repo;
 use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
 use crate::revset::RevsetExpression;
 use crate::revset::RevsetIteratorExt as _;
 use crate::store::Store;
@@ -119,6 +126,68 @@ pub async fn restore_tree(
     }
 }
 
+/// Copies matching paths of `source` into `destination_store`, which may
+/// belong to another repository. Each path is mapped by `map_path`. Entries for
+/// which it returns `None` are left out.
+pub async fn copy_tree_to_store(
+    source: &MergedTree,
+    matcher: &dyn Matcher,
+    destination_store: &Arc<Store>,
+    map_path: impl Fn(&RepoPath) -> Option<RepoPathBuf>,
+) -> BackendResult<MergedTreeId> {
+    let source_store = source.store();
+    let mut tree_builder = MergedTreeBuilder::new(destination_store.empty_merged_tree_id());
+    for (source_path, values) in source.entries_matching(matcher) {
+        let Some(destination_path) = map_path(&source_path) else {
+            continue;
+        };
+        let new_values = values?
+            .try_map_async(|value| async {
+                let Some(value) = value else {
+                    return Ok(None);
+                };
+                let new_value = match value {
+                    TreeValue::File {
+                        id,
+                        executable,
+                        copy_id: _,
+                    } => {
+                        let mut contents = source_store.read_file(&source_path, id).await?;
+                        let id = destination_store
+                            .write_file(&destination_path, &mut contents)
+                            .await?;
+                        TreeValue::File {
+                            id,
+                            executable: *executable,
+                            copy_id: CopyId::placeholder(),
+                        }
+                    }
+                    TreeValue::Symlink(id) => {
+                        let target = source_store.read_symlink(&source_path, id).await?;
+                        let id = destination_store
+                            .write_symlink(&destination_path, &target)
+                            .await?;
+                        TreeValue::Symlink(id)
+                    }
+                    TreeValue::GitSubmodule(id) => TreeValue::GitSubmodule(id.clone()),
+                    TreeValue::Tree(_) | TreeValue::Conflict(_) => {
+                        return Err(BackendError::Other(
+                            format!(
+                                "Unexpected tree entry at {}: {value:?}",
+                                source_path.as_internal_file_string()
+                            )
+                            .into(),
+                        ));
+                    }
+                };
+                Ok::<_, BackendError>(Some(new_value))
+            })
+            .await?;
+        tree_builder.set_or_remove(destination_path, new_values);
+    }
+    tree_builder.write_tree(destination_store)
+}
+
 pub fn rebase_commit(
     mut_repo: &mut MutableRepo,
     old_commit: Commit,
@@ -422,6 +491,22 @@ pub enum MoveCommitsTarget {
     Roots(Vec<CommitId>),
 }
 
+/// Progress of [`ComputedMoveCommits::apply_with_progress()`].
+#[derive(Clone, Debug)]
+pub struct MoveCommitsProgress<'a> {
+    /// The commit which has just been processed.
+    pub old_commit_id: &'a CommitId,
+    /// The number of commits processed so far, including this one.
+    pub num_processed: usize,
+    /// The total number of commits to be processed.
+    pub num_total: usize,
+}
+
+/// Planned rebase of commits, computed by [`compute_move_commits()`].
+///
+/// The plan can be inspected before it is applied to the repo. Applying it is
+/// all-or-nothing: if it fails midway, the transaction should be discarded
+/// and the plan computed again.
 #[derive(Clone, Debug)]
 pub struct ComputedMoveCommits {
     target_commit_ids: IndexSet<CommitId>,
@@ -450,12 +535,60 @@ impl ComputedMoveCommits {
         self.to_abandon.extend(commit_ids);
     }
 
+    /// Returns the commits in the target set.
+    pub fn target_commit_ids(&self) -> impl ExactSizeIterator<Item = &CommitId> {
+        self.target_commit_ids.iter()
+    }
+
+    /// Returns true if the target commit set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.target_commit_ids.is_empty()
+    }
+
+    /// Returns the number of commits which will be visited when the plan is
+    /// applied. This includes target commits and their descendants.
+    pub fn num_commits_to_visit(&self) -> usize {
+        self.descendants.len()
+    }
+
+    /// Iterates over commits which will be visited when the plan is applied,
+    /// paired with their new parents.
+    ///
+    /// New parents are specified in terms of the original commit ids. Commits
+    /// whose parents are unchanged will still be rebased if any of their
+    /// ancestors are rewritten.
+    pub fn commits_to_visit(&self) -> impl Iterator<Item = (&Commit, &[CommitId])> {
+        self.descendants.iter().map(|commit| {
+            let new_parent_ids = self
+                .commit_new_parents_map
+                .get(commit.id())
+                .map_or(commit.parent_ids(), |ids| ids.as_slice());
+            (commit, new_parent_ids)
+        })
+    }
+
+    /// Returns true if the commit will be abandoned when the plan is applied.
+    pub fn is_to_abandon(&self, commit_id: &CommitId) -> bool {
+        self.to_abandon.contains(commit_id)
+    }
+
     pub fn apply(
         self,
         mut_repo: &mut MutableRepo,
         options: &RebaseOptions,
     ) -> BackendResult<MoveCommitsStats> {
-        apply_move_commits(mut_repo, self, options)
+        self.apply_with_progress(mut_repo, options, |_| {})
+    }
+
+    /// Like [`Self::apply()`], but calls `progress` after each commit is
+    /// processed.
+    pub fn apply_with_progress(
+        self,
+        mut_repo: &mut MutableRepo,
+        options: &RebaseOptions,
+        progress: impl FnMut(&MoveCommitsProgress),
+    ) -> BackendResult<MoveCommitsStats> {
+        apply_move_commits(mut_repo, self, options, progress)
     }
 }
 
@@ -767,7 +900,10 @@ fn apply_move_commits(
     mut_repo: &mut MutableRepo,
     commits: ComputedMoveCommits,
     options: &RebaseOptions,
+    mut progress: impl FnMut(&MoveCommitsProgress),
 ) -> BackendResult<MoveCommitsStats> {
+    let num_total = commits.descendants.len();
+    let mut num_processed = 0;
     let mut num_rebased_targets = 0;
     let mut num_rebased_descendants = 0;
     let mut num_skipped_rebases = 0;
@@ -806,11 +942,17 @@ fn apply_move_commits(
                 } else {
                     num_rebased_descendants += 1;
                 }
-                rebased_commits.insert(old_commit_id, rebased_commit);
+                rebased_commits.insert(old_commit_id.clone(), rebased_commit);
             } else {
                 num_skipped_rebases += 1;
             }
 
+            num_processed += 1;
+            progress(&MoveCommitsProgress {
+                old_commit_id: &old_commit_id,
+                num_processed,
+                num_total,
+            });
             Ok(())
         },
     )?;
@@ -1283,3 +1425,105 @@ pub fn find_duplicate_divergent_commits(
     }
     Ok(duplicate_divergent)
 }
+
+/// Error while merging divergent commits.
+#[derive(Debug, Error)]
+pub enum DivergentCommitsMergeError {
+    /// The divergent commits don't share a predecessor to use as merge base.
+    #[error("Divergent commits of change {0} have no common predecessor")]
+    NoCommonPredecessor(String),
+    /// Fewer than two commits were given, or they aren't of the same change.
+    #[error("Expected at least two commits of the same change")]
+    NotDivergent,
+    /// Failed to walk the predecessors of the divergent commits.
+    #[error(transparent)]
+    WalkPredecessors(#[from] WalkPredecessorsError),
+    /// Failed to read or merge commits.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// Divergent commits merged into a single commit, which hasn't been written
+/// yet.
+pub struct DivergentCommitsMerge {
+    /// Builder for the merged commit. It rewrites the first divergent commit,
+    /// and has all the divergent commits as predecessors.
+    pub commit_builder: DetachedCommitBuilder,
+    /// The divergent commits being merged.
+    pub divergent_commits: Vec<Commit>,
+}
+
+impl DivergentCommitsMerge {
+    /// Writes the merged commit and records all divergent commits as rewritten
+    /// into it. Caller is responsible for rebasing descendants.
+    pub fn write(self, repo: &mut MutableRepo) -> BackendResult<Commit> {
+        let commit = self.commit_builder.write(repo)?;
+        for old_commit in &self.divergent_commits[1..] {
+            repo.set_rewritten_commit(old_commit.id().clone(), commit.id().clone());
+        }
+        Ok(commit)
+    }
+}
+
+/// Merges `divergent_commits` of the same change into one commit.
+///
+/// Changes of each commit are rebased onto the parents of the first commit,
+/// and merged against the latest predecessor the commits have in common. The
+/// merged commit keeps the other metadata of the first commit. Caller is
+/// responsible for setting the description and writing the commit.
+pub fn merge_divergent_commits(
+    repo: &mut MutableRepo,
+    divergent_commits: Vec<Commit>,
+) -> Result<DivergentCommitsMerge, DivergentCommitsMergeError> {
+    let [first_commit, other_commits @ ..] = &divergent_commits[..] else {
+        return Err(DivergentCommitsMergeError::NotDivergent);
+    };
+    if other_commits.is_empty()
+        || other_commits
+            .iter()
+            .any(|commit| commit.change_id() != first_commit.change_id())
+    {
+        return Err(DivergentCommitsMergeError::NotDivergent);
+    }
+
+    let base_repo = repo.base_repo().clone();
+    let mut first_predecessors = vec![];
+    for entry in walk_predecessors(&base_repo, slice::from_ref(first_commit.id())) {
+        first_predecessors.push(entry?.commit.id().clone());
+    }
+    let mut common_predecessors: HashSet<CommitId> = first_predecessors.iter().cloned().collect();
+    for commit in other_commits {
+        let mut predecessors = HashSet::new();
+        for entry in walk_predecessors(&base_repo, slice::from_ref(commit.id())) {
+            predecessors.insert(entry?.commit.id().clone());
+        }
+        common_predecessors.retain(|id| predecessors.contains(id));
+    }
+    // Predecessors are emitted in reverse topological order, so the first
+    // common one is the latest.
+    let Some(base_id) = first_predecessors
+        .iter()
+        .find(|id| common_predecessors.contains(id))
+    else {
+        return Err(DivergentCommitsMergeError::NoCommonPredecessor(
+            first_commit.change_id().reverse_hex(),
+        ));
+    };
+    let base_commit = repo.store().get_commit(base_id)?;
+
+    let base_tree = rebase_to_dest_parent(repo, slice::from_ref(&base_commit), first_commit)?;
+    let mut merged_tree = first_commit.tree()?;
+    for commit in other_commits {
+        let tree = rebase_to_dest_parent(repo, slice::from_ref(commit), first_commit)?;
+        merged_tree = merged_tree.merge(&base_tree, &tree)?;
+    }
+    let commit_builder = repo
+        .rewrite_commit(first_commit)
+        .set_tree_id(merged_tree.id())
+        .set_predecessors(divergent_commits.iter().ids().cloned().collect())
+        .detach();
+    Ok(DivergentCommitsMerge {
+        commit_builder,
+        divergent_commits,
+    })
+}
@@ -55,6 +55,7 @@ struct UserSettingsData {
     operation_username: String,
     signing_behavior: SignBehavior,
     signing_key: Option<String>,
+    deterministic_change_ids: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +64,7 @@ pub struct GitSettings {
     pub abandon_unreachable_commits: bool,
     pub executable_path: PathBuf,
     pub write_change_id_header: bool,
+    pub write_change_id_trailer: bool,
 }
 
 impl GitSettings {
@@ -72,6 +74,7 @@ impl GitSettings {
             abandon_unreachable_commits: settings.get_bool("git.abandon-unreachable-commits")?,
             executable_path: settings.get("git.executable-path")?,
             write_change_id_header: settings.get("git.write-change-id-header")?,
+            write_change_id_trailer: settings.get("git.write-change-id-trailer")?,
         })
     }
 }
@@ -83,6 +86,7 @@ impl Default for GitSettings {
             abandon_unreachable_commits: true,
             executable_path: PathBuf::from("git"),
             write_change_id_header: true,
+            write_change_id_trailer: false,
         }
     }
 }
@@ -148,6 +152,7 @@ impl UserSettings {
         let operation_username = config.get("operation.username")?;
         let signing_behavior = config.get("signing.behavior")?;
         let signing_key = config.get("signing.key").optional()?;
+        let deterministic_change_ids = config.get("core.deterministic-change-ids")?;
         let data = UserSettingsData {
             user_name,
             user_email,
@@ -157,6 +162,7 @@ impl UserSettings {
             operation_username,
             signing_behavior,
             signing_key,
+            deterministic_change_ids,
         };
         Ok(UserSettings {
             config: Arc::new(config),
@@ -204,6 +210,12 @@ impl UserSettings {
         self.data.operation_timestamp
     }
 
+    /// Whether the change ids of new commits should be derived from their
+    /// content instead of being generated randomly.
+    pub fn deterministic_change_ids(&self) -> bool {
+        self.data.deterministic_change_ids
+    }
+
     pub fn operation_hostname(&self) -> &str {
         &self.data.operation_hostname
     }
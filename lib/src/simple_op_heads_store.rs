@@ -75,7 +75,17 @@ impl SimpleOpHeadsStore {
     }
 
     fn add_op_head(&self, id: &OperationId) -> io::Result<()> {
-        std::fs::write(self.dir.join(id.hex()), "")
+        let path = self.dir.join(id.hex());
+        std::fs::write(&path, "").or_else(|err| {
+            if err.kind() == io::ErrorKind::NotFound {
+                // The heads directory may have been deleted. Recreate it so
+                // that the op heads can be recovered.
+                fs::create_dir_all(&self.dir)?;
+                std::fs::write(&path, "")
+            } else {
+                Err(err)
+            }
+        })
     }
 
     fn remove_op_head(&self, id: &OperationId) -> io::Result<()> {
@@ -131,9 +141,14 @@ impl OpHeadsStore for SimpleOpHeadsStore {
 
     fn get_op_heads(&self) -> Result<Vec<OperationId>, OpHeadsStoreError> {
         let mut op_heads = vec![];
-        for op_head_entry in
-            std::fs::read_dir(&self.dir).map_err(|err| OpHeadsStoreError::Read(err.into()))?
-        {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            // The heads directory was deleted, which isn't different from
+            // having no heads.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(op_heads),
+            Err(err) => return Err(OpHeadsStoreError::Read(err.into())),
+        };
+        for op_head_entry in entries {
             let op_head_file_name = op_head_entry
                 .map_err(|err| OpHeadsStoreError::Read(err.into()))?
                 .file_name();
@@ -150,8 +165,16 @@ impl OpHeadsStore for SimpleOpHeadsStore {
     }
 
     fn lock(&self) -> Result<Box<dyn OpHeadsStoreLock + '_>, OpHeadsStoreError> {
+        fs::create_dir_all(&self.dir).map_err(|err| OpHeadsStoreError::Lock(err.into()))?;
         let lock = FileLock::lock(self.dir.join("lock"))
             .map_err(|err| OpHeadsStoreError::Lock(err.into()))?;
         Ok(Box::new(SimpleOpHeadsStoreLock { _lock: lock }))
     }
+
+    fn try_lock(&self) -> Result<Option<Box<dyn OpHeadsStoreLock + '_>>, OpHeadsStoreError> {
+        fs::create_dir_all(&self.dir).map_err(|err| OpHeadsStoreError::Lock(err.into()))?;
+        let lock = FileLock::try_lock(self.dir.join("lock"))
+            .map_err(|err| OpHeadsStoreError::Lock(err.into()))?;
+        Ok(lock.map(|lock| Box::new(SimpleOpHeadsStoreLock { _lock: lock }) as _))
+    }
 }
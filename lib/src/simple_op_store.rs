@@ -132,6 +132,74 @@ impl SimpleOpStore {
     fn operations_dir(&self) -> PathBuf {
         self.path.join("operations")
     }
+
+    /// Reads the stored record of the view without converting it.
+    pub fn read_view_proto(&self, id: &ViewId) -> OpStoreResult<crate::protos::op_store::View> {
+        let path = self.views_dir().join(id.hex());
+        let buf = fs::read(&path)
+            .context(&path)
+            .map_err(|err| io_to_read_error(err, id))?;
+        crate::protos::op_store::View::decode(&*buf).map_err(|err| to_read_error(err.into(), id))
+    }
+
+    /// Replaces the stored record of the view.
+    ///
+    /// Unlike [`OpStore::write_view()`], the record is stored under the given
+    /// `id` even if the id doesn't match the content hash. This is meant for
+    /// repairing corrupted records.
+    pub fn write_view_proto(
+        &self,
+        id: &ViewId,
+        proto: &crate::protos::op_store::View,
+    ) -> OpStoreResult<()> {
+        validate_view_proto(proto).map_err(|err| to_write_error(err.into(), "view"))?;
+        let path = self.views_dir().join(id.hex());
+        replace_record(&path, &proto.encode_to_vec()).map_err(|err| io_to_write_error(err, "view"))
+    }
+
+    /// Reads the stored record of the operation without converting it.
+    pub fn read_operation_proto(
+        &self,
+        id: &OperationId,
+    ) -> OpStoreResult<crate::protos::op_store::Operation> {
+        let path = self.operations_dir().join(id.hex());
+        let buf = fs::read(&path)
+            .context(&path)
+            .map_err(|err| io_to_read_error(err, id))?;
+        crate::protos::op_store::Operation::decode(&*buf)
+            .map_err(|err| to_read_error(err.into(), id))
+    }
+
+    /// Replaces the stored record of the operation.
+    ///
+    /// Unlike [`OpStore::write_operation()`], the record is stored under the
+    /// given `id` even if the id doesn't match the content hash. This is meant
+    /// for repairing corrupted records.
+    pub fn write_operation_proto(
+        &self,
+        id: &OperationId,
+        proto: &crate::protos::op_store::Operation,
+    ) -> OpStoreResult<()> {
+        operation_from_proto(proto.clone())
+            .map_err(|err| to_write_error(err.into(), "operation"))?;
+        let path = self.operations_dir().join(id.hex());
+        replace_record(&path, &proto.encode_to_vec())
+            .map_err(|err| io_to_write_error(err, "operation"))
+    }
+}
+
+fn replace_record(path: &Path, content: &[u8]) -> Result<(), PathError> {
+    let dir = path.parent().unwrap();
+    let temp_file = NamedTempFile::new_in(dir).context(dir)?;
+    temp_file
+        .as_file()
+        .write_all(content)
+        .context(temp_file.path())?;
+    temp_file
+        .persist(path)
+        .map_err(|err| err.error)
+        .context(path)?;
+    Ok(())
 }
 
 impl OpStore for SimpleOpStore {
@@ -274,6 +342,26 @@ impl OpStore for SimpleOpStore {
             .map_err(|err| OpStoreError::Other(err.into()))
     }
 
+    fn all_operation_ids(&self) -> OpStoreResult<Vec<OperationId>> {
+        let op_dir = self.operations_dir();
+        let collect = || -> Result<Vec<OperationId>, PathError> {
+            let mut ids = vec![];
+            for entry in op_dir.read_dir().context(&op_dir)? {
+                let entry = entry.context(&op_dir)?;
+                let Some(id) = entry
+                    .file_name()
+                    .to_str()
+                    .and_then(OperationId::try_from_hex)
+                else {
+                    continue; // Skip temporary and invalid files
+                };
+                ids.push(id);
+            }
+            Ok(ids)
+        };
+        collect().map_err(|err| OpStoreError::Other(err.into()))
+    }
+
     #[tracing::instrument(skip(self))]
     fn gc(&self, head_ids: &[OperationId], keep_newer: SystemTime) -> OpStoreResult<()> {
         let to_op_id = |entry: &fs::DirEntry| -> Option<OperationId> {
@@ -382,9 +470,16 @@ fn to_read_error(
 }
 
 fn io_to_write_error(err: PathError, object_type: &'static str) -> OpStoreError {
+    to_write_error(Box::new(err), object_type)
+}
+
+fn to_write_error(
+    source: Box<dyn std::error::Error + Send + Sync>,
+    object_type: &'static str,
+) -> OpStoreError {
     OpStoreError::WriteObject {
         object_type,
-        source: Box::new(err),
+        source,
     }
 }
 
@@ -392,6 +487,8 @@ fn io_to_write_error(err: PathError, object_type: &'static str) -> OpStoreError
 enum PostDecodeError {
     #[error("Invalid hash length (expected {expected} bytes, got {actual} bytes)")]
     InvalidHashLength { expected: usize, actual: usize },
+    #[error("Missing value of ref target")]
+    MissingRefTargetValue,
 }
 
 fn operation_id_from_proto(bytes: Vec<u8>) -> Result<OperationId, PostDecodeError> {
@@ -440,7 +537,7 @@ fn operation_metadata_to_proto(
         hostname: metadata.hostname.clone(),
         username: metadata.username.clone(),
         is_snapshot: metadata.is_snapshot,
-        tags: metadata.tags.clone(),
+        tags: metadata.tags.clone().into_iter().collect(),
     }
 }
 
@@ -457,7 +554,7 @@ fn operation_metadata_from_proto(
         hostname: proto.hostname,
         username: proto.username,
         is_snapshot: proto.is_snapshot,
-        tags: proto.tags,
+        tags: proto.tags.into_iter().collect(),
     }
 }
 
@@ -539,7 +636,9 @@ fn view_to_proto(view: &View) -> crate::protos::op_store::View {
             .wc_commit_ids
             .insert(name.into(), commit_id.to_bytes());
     }
-    for head_id in &view.head_ids {
+    // Heads are stored in a stable order so the same view is always written
+    // as the same bytes.
+    for head_id in view.head_ids.iter().sorted() {
         proto.head_ids.push(head_id.to_bytes());
     }
 
@@ -565,6 +664,28 @@ fn view_to_proto(view: &View) -> crate::protos::op_store::View {
     proto
 }
 
+/// Checks that the view record can be loaded by [`view_from_proto()`].
+fn validate_view_proto(proto: &crate::protos::op_store::View) -> Result<(), PostDecodeError> {
+    let ref_targets = itertools::chain!(
+        proto.bookmarks.iter().flat_map(|bookmark| itertools::chain(
+            &bookmark.local_target,
+            bookmark
+                .remote_bookmarks
+                .iter()
+                .flat_map(|remote_bookmark| &remote_bookmark.target)
+        )),
+        proto.tags.iter().flat_map(|tag| &tag.target),
+        proto.git_refs.iter().flat_map(|git_ref| &git_ref.target),
+        &proto.git_head,
+    );
+    for ref_target in ref_targets {
+        if ref_target.value.is_none() {
+            return Err(PostDecodeError::MissingRefTargetValue);
+        }
+    }
+    Ok(())
+}
+
 fn view_from_proto(proto: crate::protos::op_store::View) -> View {
     // TODO: validate commit id length?
     let mut view = View::empty();
@@ -788,7 +909,6 @@ mod tests {
     use insta::assert_snapshot;
     use itertools::Itertools as _;
     use maplit::btreemap;
-    use maplit::hashmap;
     use maplit::hashset;
 
     use super::*;
@@ -872,7 +992,7 @@ mod tests {
                 hostname: "some.host.example.com".to_string(),
                 username: "someone".to_string(),
                 is_snapshot: false,
-                tags: hashmap! {
+                tags: btreemap! {
                     "key1".to_string() => "value1".to_string(),
                     "key2".to_string() => "value2".to_string(),
                 },
@@ -918,6 +1038,15 @@ mod tests {
         assert_eq!(read_view, view);
     }
 
+    #[test]
+    fn test_view_to_proto_sorts_heads() {
+        let mut view = create_view();
+        view.head_ids = (0..20u8).map(|i| CommitId::new(vec![i; 4])).collect();
+        let proto = view_to_proto(&view);
+        assert!(proto.head_ids.is_sorted());
+        assert_eq!(proto.head_ids.len(), 20);
+    }
+
     #[test]
     fn test_read_write_operation() {
         let temp_dir = new_temp_dir();
@@ -931,6 +1060,40 @@ mod tests {
         assert_eq!(read_operation, operation);
     }
 
+    #[test]
+    fn test_read_write_proto_json_roundtrip() {
+        let temp_dir = new_temp_dir();
+        let root_data = RootOperationData {
+            root_commit_id: CommitId::from_hex("000000"),
+        };
+        let store = SimpleOpStore::init(temp_dir.path(), root_data).unwrap();
+        let view = create_view();
+        let view_id = store.write_view(&view).unwrap();
+        let operation = create_operation();
+        let op_id = store.write_operation(&operation).unwrap();
+
+        // Records can be restored from their JSON representation
+        let view_json = serde_json::to_string(&store.read_view_proto(&view_id).unwrap()).unwrap();
+        let view_proto = serde_json::from_str(&view_json).unwrap();
+        store.write_view_proto(&view_id, &view_proto).unwrap();
+        assert_eq!(store.read_view(&view_id).unwrap(), view);
+        let op_json = serde_json::to_string(&store.read_operation_proto(&op_id).unwrap()).unwrap();
+        assert!(op_json.contains(&format!(r#""view_id":"{}""#, operation.view_id.hex())));
+        let op_proto = serde_json::from_str(&op_json).unwrap();
+        store.write_operation_proto(&op_id, &op_proto).unwrap();
+        assert_eq!(store.read_operation(&op_id).unwrap(), operation);
+
+        // Invalid records are rejected
+        let mut op_proto = store.read_operation_proto(&op_id).unwrap();
+        op_proto.view_id.truncate(1);
+        assert!(store.write_operation_proto(&op_id, &op_proto).is_err());
+        let mut view_proto = store.read_view_proto(&view_id).unwrap();
+        view_proto.git_head = Some(Default::default());
+        assert!(store.write_view_proto(&view_id, &view_proto).is_err());
+        assert_eq!(store.read_operation(&op_id).unwrap(), operation);
+        assert_eq!(store.read_view(&view_id).unwrap(), view);
+    }
+
     #[test]
     fn test_bookmark_views_legacy_roundtrip() {
         let new_remote_ref = |target: &RefTarget| RemoteRef {
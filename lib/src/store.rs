@@ -44,11 +44,14 @@ use crate::index::Index;
 use crate::merge::Merge;
 use crate::merge::MergedTreeValue;
 use crate::merged_tree::MergedTree;
+use crate::metrics;
+use crate::metrics::DurationTimer;
 use crate::repo_path::RepoPath;
 use crate::repo_path::RepoPathBuf;
 use crate::signing::Signer;
 use crate::tree::Tree;
 use crate::tree_builder::TreeBuilder;
+use crate::tree_merge_cache::TreeMergeCache;
 
 // There are more tree objects than commits, and trees are often shared across
 // commits.
@@ -62,6 +65,7 @@ pub struct Store {
     signer: Signer,
     commit_cache: Mutex<CLruCache<CommitId, Arc<backend::Commit>>>,
     tree_cache: Mutex<CLruCache<(RepoPathBuf, TreeId), Arc<backend::Tree>>>,
+    tree_merge_cache: Option<TreeMergeCache>,
 }
 
 impl Debug for Store {
@@ -74,11 +78,22 @@ impl Debug for Store {
 
 impl Store {
     pub fn new(backend: Box<dyn Backend>, signer: Signer) -> Arc<Self> {
+        Self::new_with_tree_merge_cache(backend, signer, None)
+    }
+
+    /// Creates a store which memoizes the results of root tree merges in
+    /// `tree_merge_cache`.
+    pub fn new_with_tree_merge_cache(
+        backend: Box<dyn Backend>,
+        signer: Signer,
+        tree_merge_cache: Option<TreeMergeCache>,
+    ) -> Arc<Self> {
         Arc::new(Store {
             backend,
             signer,
             commit_cache: Mutex::new(CLruCache::new(COMMIT_CACHE_CAPACITY.try_into().unwrap())),
             tree_cache: Mutex::new(CLruCache::new(TREE_CACHE_CAPACITY.try_into().unwrap())),
+            tree_merge_cache,
         })
     }
 
@@ -94,6 +109,10 @@ impl Store {
         &self.signer
     }
 
+    pub fn tree_merge_cache(&self) -> Option<&TreeMergeCache> {
+        self.tree_merge_cache.as_ref()
+    }
+
     pub fn get_copy_records(
         &self,
         paths: Option<&[RepoPathBuf]>,
@@ -148,10 +167,14 @@ impl Store {
         {
             let mut locked_cache = self.commit_cache.lock().unwrap();
             if let Some(data) = locked_cache.get(id).cloned() {
+                metrics::increment_counter(metrics::STORE_COMMIT_CACHE_HITS);
                 return Ok(data);
             }
         }
+        metrics::increment_counter(metrics::STORE_COMMIT_READS);
+        let timer = DurationTimer::start(metrics::STORE_COMMIT_READ_DURATION);
         let commit = self.backend.read_commit(id).await?;
+        drop(timer);
         let data = Arc::new(commit);
         let mut locked_cache = self.commit_cache.lock().unwrap();
         locked_cache.put(id.clone(), data.clone());
@@ -197,10 +220,14 @@ impl Store {
         {
             let mut locked_cache = self.tree_cache.lock().unwrap();
             if let Some(data) = locked_cache.get(&key).cloned() {
+                metrics::increment_counter(metrics::STORE_TREE_CACHE_HITS);
                 return Ok(data);
             }
         }
+        metrics::increment_counter(metrics::STORE_TREE_READS);
+        let timer = DurationTimer::start(metrics::STORE_TREE_READ_DURATION);
         let data = self.backend.read_tree(dir, id).await?;
+        drop(timer);
         let data = Arc::new(data);
         let mut locked_cache = self.tree_cache.lock().unwrap();
         locked_cache.put(key, data.clone());
@@ -249,6 +276,7 @@ impl Store {
         path: &RepoPath,
         id: &FileId,
     ) -> BackendResult<Pin<Box<dyn AsyncRead + Send>>> {
+        metrics::increment_counter(metrics::STORE_FILE_READS);
         self.backend.read_file(path, id).await
     }
 
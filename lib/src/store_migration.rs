@@ -0,0 +1,683 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Versioned migrations of the on-disk formats of the repository stores.
+//!
+//! Each store directory under `.jj/repo` (see [`REPO_STORE_DIRS`]) and the
+//! working copy state under `.jj` (see [`WORKSPACE_STORE_DIRS`]) may record its
+//! format version in a `format_version` file. New repos and workspaces record
+//! the latest versions. A store without the file is at version 0. Store
+//! implementations that change their on-disk format register a migration from
+//! the previous version, and the migrations are run in order when the repo or
+//! the workspace is loaded. Stores that don't exist (e.g. the Git extra
+//! metadata of a repo not backed by Git) are skipped.
+//!
+//! A migration declares the paths in the store it rewrites. Only those are
+//! backed up to `<store>.backup-v<version>` next to the store before the
+//! migration is run, so that large stores don't have to be copied. If jj is
+//! interrupted during a migration, the backup is restored and the migration is
+//! run again the next time the store is loaded.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
+use crate::settings::UserSettings;
+
+/// Name of the file that records the format version of a store.
+pub const FORMAT_VERSION_FILE: &str = "format_version";
+
+/// Names of the store directories under the repo directory. `store/extra` is
+/// the extra metadata table of the Git backend.
+pub const REPO_STORE_DIRS: [&str; 6] = [
+    "store",
+    "store/extra",
+    "op_store",
+    "op_heads",
+    "index",
+    "submodule_store",
+];
+
+/// Names of the store directories under the workspace's `.jj` directory.
+pub const WORKSPACE_STORE_DIRS: [&str; 1] = ["working_copy"];
+
+/// Returns the migrations of the built-in stores.
+///
+/// None of the built-in stores has changed its on-disk format since format
+/// versions were introduced, so this is empty for now.
+pub fn default_store_migrations() -> StoreMigrations {
+    StoreMigrations::new()
+}
+
+/// Function that upgrades the store at the given path by one format version.
+pub type StoreMigrationFn =
+    Box<dyn Fn(&UserSettings, &Path) -> Result<(), Box<dyn Error + Send + Sync>>>;
+
+/// Error that may occur while checking or migrating store formats.
+#[derive(Debug, Error)]
+pub enum StoreMigrationError {
+    /// The store was written by a newer version of jj.
+    #[error(
+        "The {store} store has format version {version}, but this version of jj only supports \
+         up to version {supported}"
+    )]
+    UnsupportedVersion {
+        /// Name of the store directory.
+        store: String,
+        /// Format version found on disk.
+        version: u32,
+        /// Latest format version known to this binary.
+        supported: u32,
+    },
+    /// The format version file couldn't be parsed.
+    #[error("Invalid format version in {}", path.display())]
+    InvalidVersion {
+        /// Path to the format version file.
+        path: PathBuf,
+    },
+    /// A registered migration failed. The store has been restored from backup.
+    #[error("Failed to migrate {store} store from format version {from_version}")]
+    Migration {
+        /// Name of the store directory.
+        store: String,
+        /// Version the migration started from.
+        from_version: u32,
+        /// Underlying error.
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// Failed to lock the repo for migration.
+    #[error(transparent)]
+    Lock(#[from] FileLockError),
+    /// Failed to read or write files.
+    #[error(transparent)]
+    Path(#[from] PathError),
+}
+
+/// Registry of store format migrations, keyed by store directory name.
+///
+/// The migrations for a store must be registered in version order. The
+/// latest supported version of a store is the number of migrations registered
+/// for it.
+#[derive(Default)]
+pub struct StoreMigrations {
+    migrations: HashMap<String, Vec<StoreMigration>>,
+}
+
+struct StoreMigration {
+    /// Files and directories in the store which the migration may write,
+    /// relative to the store directory.
+    rewritten_paths: Vec<PathBuf>,
+    migrate: StoreMigrationFn,
+}
+
+impl StoreMigrations {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a migration of the `store` directory from the current latest
+    /// version to the next version.
+    ///
+    /// The migration must only create, modify, or delete files under the
+    /// `rewritten_paths`, which are relative to the store directory.
+    pub fn add(&mut self, store: &str, rewritten_paths: &[&str], migration: StoreMigrationFn) {
+        self.migrations
+            .entry(store.to_owned())
+            .or_default()
+            .push(StoreMigration {
+                rewritten_paths: rewritten_paths.iter().map(PathBuf::from).collect(),
+                migrate: migration,
+            });
+    }
+
+    /// Appends the migrations in `ext` after the ones already registered.
+    pub fn merge(&mut self, ext: StoreMigrations) {
+        for (store, migrations) in ext.migrations {
+            self.migrations.entry(store).or_default().extend(migrations);
+        }
+    }
+
+    /// Latest format version of the `store` directory known to this registry.
+    pub fn supported_version(&self, store: &str) -> u32 {
+        self.migrations
+            .get(store)
+            .map_or(0, |migrations| migrations.len().try_into().unwrap())
+    }
+
+    /// Checks the format versions of all stores in `repo_path`, and runs the
+    /// pending migrations.
+    ///
+    /// Migrations are run while holding the repo's migration lock. The paths
+    /// rewritten by a migration step are backed up before the step, and
+    /// restored if the step fails or was interrupted by a previous process.
+    pub fn migrate(
+        &self,
+        settings: &UserSettings,
+        repo_path: &Path,
+    ) -> Result<(), StoreMigrationError> {
+        self.migrate_stores(settings, repo_path, &REPO_STORE_DIRS)
+    }
+
+    /// Like [`Self::migrate()`], but for the stores in the workspace's `.jj`
+    /// directory.
+    pub fn migrate_workspace(
+        &self,
+        settings: &UserSettings,
+        jj_dir: &Path,
+    ) -> Result<(), StoreMigrationError> {
+        self.migrate_stores(settings, jj_dir, &WORKSPACE_STORE_DIRS)
+    }
+
+    /// Records the latest format versions of the `stores` in `base_path`.
+    /// Called when the stores are created so that the migrations aren't run on
+    /// them.
+    pub fn write_format_versions(
+        &self,
+        base_path: &Path,
+        stores: &[&str],
+    ) -> Result<(), PathError> {
+        for &store in stores {
+            let store_path = base_path.join(store);
+            if store_path.is_dir() {
+                write_format_version(&store_path, self.supported_version(store))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn migrate_stores(
+        &self,
+        settings: &UserSettings,
+        base_path: &Path,
+        stores: &[&str],
+    ) -> Result<(), StoreMigrationError> {
+        if !self.has_pending(base_path, stores)? {
+            return Ok(());
+        }
+        let _lock = FileLock::lock(base_path.join("migration.lock"))?;
+        // Another process might have migrated the stores while we were waiting
+        // for the lock, so the versions have to be read again.
+        for &store in stores {
+            let store_path = base_path.join(store);
+            if !store_path.is_dir() {
+                continue;
+            }
+            let mut version = self.check_version(store, &store_path)?;
+            let migrations = self.migrations.get(store).map_or(&[][..], |m| m.as_slice());
+            clean_up_backups(base_path, store, version, migrations)?;
+            while let Some(migration) = migrations.get(version as usize) {
+                tracing::info!(store, version, "migrating store format");
+                let backup_path = backup_path(base_path, store, version);
+                back_up_paths(&store_path, &backup_path, &migration.rewritten_paths)?;
+                if let Err(source) = (migration.migrate)(settings, &store_path) {
+                    restore_paths(&store_path, &backup_path, &migration.rewritten_paths)?;
+                    return Err(StoreMigrationError::Migration {
+                        store: store.to_owned(),
+                        from_version: version,
+                        source,
+                    });
+                }
+                version += 1;
+                write_format_version(&store_path, version)?;
+                fs::remove_dir_all(&backup_path).context(&backup_path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn has_pending(&self, base_path: &Path, stores: &[&str]) -> Result<bool, StoreMigrationError> {
+        let mut pending = false;
+        for &store in stores {
+            let store_path = base_path.join(store);
+            if !store_path.is_dir() {
+                continue;
+            }
+            let version = self.check_version(store, &store_path)?;
+            pending |= version < self.supported_version(store);
+            // Backups left by a previous process have to be cleaned up.
+            pending |= backup_path(base_path, store, version).exists();
+            pending |= version > 0 && backup_path(base_path, store, version - 1).exists();
+        }
+        Ok(pending)
+    }
+
+    fn check_version(&self, store: &str, store_path: &Path) -> Result<u32, StoreMigrationError> {
+        let version = read_format_version(store_path)?;
+        let supported = self.supported_version(store);
+        if version > supported {
+            return Err(StoreMigrationError::UnsupportedVersion {
+                store: store.to_owned(),
+                version,
+                supported,
+            });
+        }
+        Ok(version)
+    }
+}
+
+/// Reads the format version of the store at `store_path`. Returns 0 if the
+/// store doesn't record a version.
+pub fn read_format_version(store_path: &Path) -> Result<u32, StoreMigrationError> {
+    let path = store_path.join(FORMAT_VERSION_FILE);
+    match fs::read_to_string(&path) {
+        Ok(content) => content
+            .trim()
+            .parse()
+            .map_err(|_| StoreMigrationError::InvalidVersion { path }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(err) => Err(PathError { path, error: err }.into()),
+    }
+}
+
+/// Records the format version of the store at `store_path`.
+///
+/// Store initializers that create a store in a format newer than version 0
+/// should call this so that the migrations aren't run on the new store.
+pub fn write_format_version(store_path: &Path, version: u32) -> Result<(), PathError> {
+    let path = store_path.join(FORMAT_VERSION_FILE);
+    fs::write(&path, format!("{version}\n")).context(&path)
+}
+
+/// Returns the backup directory of the `store`, which is placed next to the
+/// store directory.
+fn backup_path(base_path: &Path, store: &str, version: u32) -> PathBuf {
+    base_path.join(format!("{store}.backup-v{version}"))
+}
+
+/// Removes or restores the backups of the `store` left by previous processes.
+///
+/// A backup of the current `version` means that the migration from it was
+/// interrupted, so the backup is restored. Backups of older versions were left
+/// after their migration completed, and incomplete backups (which have a
+/// `.tmp` suffix) were left before their migration started. These are removed.
+fn clean_up_backups(
+    base_path: &Path,
+    store: &str,
+    version: u32,
+    migrations: &[StoreMigration],
+) -> Result<(), PathError> {
+    let store_path = base_path.join(store);
+    let parent_path = store_path.parent().unwrap();
+    let store_name = store_path.file_name().unwrap().to_str().unwrap();
+    let prefix = format!("{store_name}.backup-v");
+    for entry in parent_path.read_dir().context(parent_path)? {
+        let entry = entry.context(parent_path)?;
+        let path = entry.path();
+        let Some(suffix) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+            .map(ToOwned::to_owned)
+        else {
+            continue;
+        };
+        if let Some(backup_version) = suffix.strip_suffix(".tmp") {
+            if backup_version.parse::<u32>().is_ok() {
+                fs::remove_dir_all(&path).context(&path)?;
+            }
+        } else if let Ok(backup_version) = suffix.parse::<u32>() {
+            if backup_version == version {
+                if let Some(migration) = migrations.get(version as usize) {
+                    tracing::info!(store, version, "restoring interrupted store migration");
+                    restore_paths(&store_path, &path, &migration.rewritten_paths)?;
+                }
+            } else if backup_version < version {
+                fs::remove_dir_all(&path).context(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copies the `paths` in `store_path` to `backup_path`. The backup is written
+/// to a temporary directory first so that an interrupted backup is never
+/// restored.
+fn back_up_paths(
+    store_path: &Path,
+    backup_path: &Path,
+    paths: &[PathBuf],
+) -> Result<(), PathError> {
+    let mut temp_path = backup_path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+    fs::create_dir(&temp_path).context(&temp_path)?;
+    for path in paths {
+        let src = store_path.join(path);
+        let dst = temp_path.join(path);
+        let Ok(metadata) = src.symlink_metadata() else {
+            continue;
+        };
+        if let Some(parent) = dst.parent() {
+            fs::create_dir_all(parent).context(parent)?;
+        }
+        if metadata.is_dir() {
+            copy_dir_all(&src, &dst)?;
+        } else {
+            fs::copy(&src, &dst).context(&src)?;
+        }
+    }
+    fs::rename(&temp_path, backup_path).context(backup_path)
+}
+
+/// Replaces the `paths` in `store_path` with their copies in `backup_path`,
+/// and removes the backup.
+fn restore_paths(
+    store_path: &Path,
+    backup_path: &Path,
+    paths: &[PathBuf],
+) -> Result<(), PathError> {
+    for path in paths {
+        let dst = store_path.join(path);
+        if let Ok(metadata) = dst.symlink_metadata() {
+            if metadata.is_dir() {
+                fs::remove_dir_all(&dst).context(&dst)?;
+            } else {
+                fs::remove_file(&dst).context(&dst)?;
+            }
+        }
+        let src = backup_path.join(path);
+        if src.symlink_metadata().is_ok() {
+            if let Some(parent) = dst.parent() {
+                fs::create_dir_all(parent).context(parent)?;
+            }
+            fs::rename(&src, &dst).context(&src)?;
+        }
+    }
+    fs::remove_dir_all(backup_path).context(backup_path)
+}
+
+fn copy_dir_all(src: &Path, dst: &Path) -> Result<(), PathError> {
+    fs::create_dir(dst).context(dst)?;
+    for entry in src.read_dir().context(src)? {
+        let entry = entry.context(src)?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        if entry.file_type().context(&src_path)?.is_dir() {
+            copy_dir_all(&src_path, &dst_path)?;
+        } else {
+            fs::copy(&src_path, &dst_path).context(&src_path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::config::StackedConfig;
+    use crate::tests::new_temp_dir;
+
+    fn new_repo_dir() -> tempfile::TempDir {
+        let temp_dir = new_temp_dir();
+        for store in REPO_STORE_DIRS {
+            fs::create_dir(temp_dir.path().join(store)).unwrap();
+        }
+        temp_dir
+    }
+
+    fn user_settings() -> UserSettings {
+        let config = StackedConfig::with_defaults();
+        UserSettings::from_config(config).unwrap()
+    }
+
+    #[test]
+    fn test_migrate_in_order() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "index",
+            &["v1"],
+            Box::new(|_settings, path| Ok(fs::write(path.join("v1"), "")?)),
+        );
+        migrations.add(
+            "index",
+            &["v2"],
+            Box::new(|_settings, path| {
+                assert!(path.join("v1").exists());
+                Ok(fs::write(path.join("v2"), "")?)
+            }),
+        );
+        migrations.migrate(&user_settings(), repo_path).unwrap();
+        let index_path = repo_path.join("index");
+        assert_eq!(read_format_version(&index_path).unwrap(), 2);
+        assert!(index_path.join("v2").exists());
+        assert_eq!(read_format_version(&repo_path.join("store")).unwrap(), 0);
+        assert!(!repo_path.join("index.backup-v0").exists());
+        assert!(!repo_path.join("index.backup-v1").exists());
+
+        // Already migrated stores are left alone.
+        migrations.migrate(&user_settings(), repo_path).unwrap();
+        assert_eq!(read_format_version(&index_path).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_migrate_failure_restores_backup() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        let index_path = repo_path.join("index");
+        fs::write(index_path.join("data"), "old").unwrap();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "index",
+            &["data", "new"],
+            Box::new(|_settings, path| {
+                fs::write(path.join("data"), "half-written")?;
+                fs::write(path.join("new"), "half-written")?;
+                Err("boom".into())
+            }),
+        );
+        assert_matches!(
+            migrations.migrate(&user_settings(), repo_path),
+            Err(StoreMigrationError::Migration {
+                from_version: 0,
+                ..
+            })
+        );
+        assert_eq!(fs::read_to_string(index_path.join("data")).unwrap(), "old");
+        assert!(!index_path.join("new").exists());
+        assert_eq!(read_format_version(&index_path).unwrap(), 0);
+        assert!(!repo_path.join("index.backup-v0").exists());
+    }
+
+    #[test]
+    fn test_migrate_backs_up_rewritten_paths_only() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        let index_path = repo_path.join("index");
+        fs::create_dir(index_path.join("segments")).unwrap();
+        fs::write(index_path.join("segments").join("a"), "old").unwrap();
+        fs::write(index_path.join("other"), "").unwrap();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "index",
+            &["segments"],
+            Box::new(|_settings, path| {
+                let backup_path = path.with_file_name("index.backup-v0");
+                assert!(backup_path.join("segments").join("a").exists());
+                assert!(!backup_path.join("other").exists());
+                Ok(fs::write(path.join("segments").join("a"), "new")?)
+            }),
+        );
+        migrations.migrate(&user_settings(), repo_path).unwrap();
+        let content = fs::read_to_string(index_path.join("segments").join("a")).unwrap();
+        assert_eq!(content, "new");
+        assert!(!repo_path.join("index.backup-v0").exists());
+    }
+
+    #[test]
+    fn test_migrate_restores_interrupted_migration() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        let index_path = repo_path.join("index");
+        fs::write(index_path.join("data"), "half-written").unwrap();
+        let backup_path = repo_path.join("index.backup-v0");
+        fs::create_dir(&backup_path).unwrap();
+        fs::write(backup_path.join("data"), "old").unwrap();
+        // An incomplete backup of another store is discarded.
+        let temp_backup_path = repo_path.join("store.backup-v0.tmp");
+        fs::create_dir(&temp_backup_path).unwrap();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "index",
+            &["data"],
+            Box::new(|_settings, path| {
+                assert_eq!(fs::read_to_string(path.join("data"))?, "old");
+                Ok(fs::write(path.join("data"), "new")?)
+            }),
+        );
+        migrations.add("store", &[], Box::new(|_settings, _path| Ok(())));
+        migrations.migrate(&user_settings(), repo_path).unwrap();
+        assert_eq!(fs::read_to_string(index_path.join("data")).unwrap(), "new");
+        assert_eq!(read_format_version(&index_path).unwrap(), 1);
+        assert!(!backup_path.exists());
+        assert!(!temp_backup_path.exists());
+    }
+
+    #[test]
+    fn test_migrate_removes_backup_of_completed_migration() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        let index_path = repo_path.join("index");
+        fs::write(index_path.join("data"), "new").unwrap();
+        write_format_version(&index_path, 1).unwrap();
+        let backup_path = repo_path.join("index.backup-v0");
+        fs::create_dir(&backup_path).unwrap();
+        fs::write(backup_path.join("data"), "old").unwrap();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "index",
+            &["data"],
+            Box::new(|_settings, _path| panic!("already migrated")),
+        );
+        migrations.migrate(&user_settings(), repo_path).unwrap();
+        assert_eq!(fs::read_to_string(index_path.join("data")).unwrap(), "new");
+        assert!(!backup_path.exists());
+    }
+
+    #[test]
+    fn test_migrate_nested_store() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        let extra_path = repo_path.join("store").join("extra");
+        fs::write(extra_path.join("data"), "half-written").unwrap();
+        // The backup of a nested store is placed next to it.
+        let backup_path = repo_path.join("store").join("extra.backup-v0");
+        fs::create_dir(&backup_path).unwrap();
+        fs::write(backup_path.join("data"), "old").unwrap();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "store/extra",
+            &["data"],
+            Box::new(|_settings, path| {
+                assert_eq!(fs::read_to_string(path.join("data"))?, "old");
+                Ok(fs::write(path.join("data"), "new")?)
+            }),
+        );
+        migrations.migrate(&user_settings(), repo_path).unwrap();
+        assert_eq!(fs::read_to_string(extra_path.join("data")).unwrap(), "new");
+        assert_eq!(read_format_version(&extra_path).unwrap(), 1);
+        assert_eq!(read_format_version(&repo_path.join("store")).unwrap(), 0);
+        assert!(!backup_path.exists());
+    }
+
+    #[test]
+    fn test_migrate_skips_missing_store() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        let extra_path = repo_path.join("store").join("extra");
+        fs::remove_dir(&extra_path).unwrap();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "store/extra",
+            &[],
+            Box::new(|_settings, _path| panic!("store doesn't exist")),
+        );
+        migrations.migrate(&user_settings(), repo_path).unwrap();
+        assert!(!extra_path.exists());
+    }
+
+    #[test]
+    fn test_migrate_workspace() {
+        let temp_dir = new_temp_dir();
+        let jj_dir = temp_dir.path();
+        let working_copy_path = jj_dir.join("working_copy");
+        fs::create_dir(&working_copy_path).unwrap();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "working_copy",
+            &["tree_state"],
+            Box::new(|_settings, path| Ok(fs::write(path.join("tree_state"), "")?)),
+        );
+        // Repo stores aren't migrated with the workspace.
+        migrations.add(
+            "index",
+            &[],
+            Box::new(|_settings, _path| panic!("not a workspace store")),
+        );
+        migrations
+            .migrate_workspace(&user_settings(), jj_dir)
+            .unwrap();
+        assert!(working_copy_path.join("tree_state").exists());
+        assert_eq!(read_format_version(&working_copy_path).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_write_format_versions() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        fs::remove_dir(repo_path.join("store").join("extra")).unwrap();
+        let mut migrations = StoreMigrations::new();
+        migrations.add(
+            "index",
+            &[],
+            Box::new(|_settings, _path| panic!("new stores shouldn't be migrated")),
+        );
+        migrations
+            .write_format_versions(repo_path, &REPO_STORE_DIRS)
+            .unwrap();
+        assert_eq!(read_format_version(&repo_path.join("index")).unwrap(), 1);
+        assert_eq!(read_format_version(&repo_path.join("store")).unwrap(), 0);
+        assert!(repo_path.join("store").join(FORMAT_VERSION_FILE).exists());
+        // Missing stores aren't created.
+        assert!(!repo_path.join("store").join("extra").exists());
+        migrations.migrate(&user_settings(), repo_path).unwrap();
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let temp_dir = new_repo_dir();
+        let repo_path = temp_dir.path();
+        write_format_version(&repo_path.join("op_store"), 3).unwrap();
+        assert_matches!(
+            StoreMigrations::new().migrate(&user_settings(), repo_path),
+            Err(StoreMigrationError::UnsupportedVersion {
+                version: 3,
+                supported: 0,
+                ..
+            })
+        );
+    }
+}
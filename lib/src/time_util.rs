@@ -72,6 +72,19 @@ pub enum DatePatternParseError {
     ParseError(#[from] DateError),
 }
 
+/// Parses a human-readable date such as "2 hours ago" or "2024-01-02 10:00",
+/// relative to `now`.
+pub fn parse_timestamp<Tz: TimeZone>(
+    s: &str,
+    now: DateTime<Tz>,
+) -> Result<MillisSinceEpoch, DatePatternParseError>
+where
+    Tz::Offset: Copy,
+{
+    let d = parse_date_string(s, now, Dialect::Us)?;
+    Ok(MillisSinceEpoch(d.timestamp_millis()))
+}
+
 /// Represents an range of dates that may be matched against.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum DatePattern {
@@ -103,9 +116,7 @@ impl DatePattern {
     where
         Tz::Offset: Copy,
     {
-        let d =
-            parse_date_string(s, now, Dialect::Us).map_err(DatePatternParseError::ParseError)?;
-        let millis_since_epoch = MillisSinceEpoch(d.timestamp_millis());
+        let millis_since_epoch = parse_timestamp(s, now)?;
         match kind {
             "after" => Ok(DatePattern::AtOrAfter(millis_since_epoch)),
             "before" => Ok(DatePattern::Before(millis_since_epoch)),
@@ -14,15 +14,23 @@
 
 #![allow(missing_docs)]
 
+use std::fmt::Debug;
+use std::io::Write as _;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use itertools::Itertools as _;
+use tempfile::NamedTempFile;
 use thiserror::Error;
 
+use crate::backend::BackendError;
+use crate::backend::CommitId;
 use crate::backend::Timestamp;
 use crate::dag_walk;
 use crate::index::IndexWriteError;
 use crate::index::ReadonlyIndex;
+use crate::object_id::ObjectId as _;
 use crate::op_heads_store::OpHeadsStore;
 use crate::op_heads_store::OpHeadsStoreError;
 use crate::op_store;
@@ -47,6 +55,21 @@ pub enum TransactionCommitError {
     OpStore(#[from] OpStoreError),
 }
 
+/// Error from attempts to revert an operation.
+#[derive(Debug, Error)]
+pub enum RevertOperationError {
+    #[error("Cannot revert the root operation")]
+    RootOperation,
+    #[error("Cannot revert a merge operation")]
+    MergeOperation,
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    #[error(transparent)]
+    OpStore(#[from] OpStoreError),
+    #[error(transparent)]
+    RepoLoader(#[from] RepoLoaderError),
+}
+
 /// An in-memory representation of a repo and any changes being made to it.
 ///
 /// Within the scope of a transaction, changes to the repository are made
@@ -111,6 +134,25 @@ impl Transaction {
         Ok(())
     }
 
+    /// Reverts the changes made by `op`, keeping the effects of any later
+    /// operations.
+    ///
+    /// This is a 3-way merge of the current view with the view of `op`'s
+    /// parent, using the view of `op` itself as the base. The root operation
+    /// and merge operations can't be reverted.
+    pub fn revert_operation(&mut self, op: &Operation) -> Result<(), RevertOperationError> {
+        let parent_op = match op.parents().at_most_one() {
+            Ok(Some(parent_op)) => parent_op?,
+            Ok(None) => return Err(RevertOperationError::RootOperation),
+            Err(_) => return Err(RevertOperationError::MergeOperation),
+        };
+        let repo_loader = self.base_repo().loader();
+        let bad_repo = repo_loader.load_at(op)?;
+        let parent_repo = repo_loader.load_at(&parent_op)?;
+        self.repo_mut().merge(&bad_repo, &parent_repo)?;
+        Ok(())
+    }
+
     pub fn set_is_snapshot(&mut self, is_snapshot: bool) {
         self.op_metadata.is_snapshot = is_snapshot;
     }
@@ -155,7 +197,8 @@ impl Transaction {
         };
 
         let index = base_repo.index_store().write_index(mut_index, &operation)?;
-        let unpublished = UnpublishedOperation::new(base_repo.loader(), operation, view, index);
+        let unpublished =
+            UnpublishedOperation::new(base_repo.loader(), &base_repo, operation, view, index);
         Ok(unpublished)
     }
 }
@@ -194,18 +237,23 @@ pub fn create_op_metadata(
 #[must_use = "Either publish() or leave_unpublished() must be called to finish the operation."]
 pub struct UnpublishedOperation {
     op_heads_store: Arc<dyn OpHeadsStore>,
+    observers: TransactionObservers,
+    base_repo: Arc<ReadonlyRepo>,
     repo: Arc<ReadonlyRepo>,
 }
 
 impl UnpublishedOperation {
     fn new(
         repo_loader: &RepoLoader,
+        base_repo: &Arc<ReadonlyRepo>,
         operation: Operation,
         view: View,
         index: Box<dyn ReadonlyIndex>,
     ) -> Self {
         UnpublishedOperation {
             op_heads_store: repo_loader.op_heads_store().clone(),
+            observers: repo_loader.transaction_observers().clone(),
+            base_repo: base_repo.clone(),
             repo: repo_loader.create_from(operation, view, index),
         }
     }
@@ -215,9 +263,15 @@ impl UnpublishedOperation {
     }
 
     pub fn publish(self) -> Result<Arc<ReadonlyRepo>, TransactionCommitError> {
-        let _lock = self.op_heads_store.lock()?;
-        self.op_heads_store
-            .update_op_heads(self.operation().parent_ids(), self.operation().id())?;
+        {
+            let _lock = self.op_heads_store.lock()?;
+            self.op_heads_store
+                .update_op_heads(self.operation().parent_ids(), self.operation().id())?;
+        }
+        self.observers.notify(&TransactionEvent {
+            old_repo: &self.base_repo,
+            new_repo: &self.repo,
+        });
         Ok(self.repo)
     }
 
@@ -225,3 +279,111 @@ impl UnpublishedOperation {
         self.repo
     }
 }
+
+/// Describes a transaction that has just been published.
+#[derive(Clone, Copy, Debug)]
+pub struct TransactionEvent<'a> {
+    /// The repo the transaction was started from.
+    pub old_repo: &'a Arc<ReadonlyRepo>,
+    /// The repo at the newly published operation.
+    pub new_repo: &'a Arc<ReadonlyRepo>,
+}
+
+impl TransactionEvent<'_> {
+    pub fn old_view(&self) -> &View {
+        self.old_repo.view()
+    }
+
+    pub fn new_view(&self) -> &View {
+        self.new_repo.view()
+    }
+
+    /// Iterates over commits rewritten by the transaction, paired with their
+    /// predecessors. Newly created commits without predecessors are omitted.
+    pub fn rewritten_commits(&self) -> impl Iterator<Item = (&CommitId, &[CommitId])> {
+        self.new_repo
+            .operation()
+            .store_operation()
+            .commit_predecessors
+            .iter()
+            .flatten()
+            .filter(|(_, predecessors)| !predecessors.is_empty())
+            .map(|(id, predecessors)| (id, predecessors.as_slice()))
+    }
+}
+
+/// Callback invoked after a transaction has been published.
+///
+/// Observers are registered with [`RepoLoader::add_transaction_observer()`]
+/// and are called synchronously after the operation heads have been updated
+/// and the lock has been released. Failures can't be reported back to the
+/// transaction, so observers should handle their own errors.
+pub trait TransactionObserver: Send + Sync {
+    fn transaction_published(&self, event: &TransactionEvent<'_>);
+}
+
+/// Set of observers shared between clones of a [`RepoLoader`].
+#[derive(Clone, Default)]
+pub(crate) struct TransactionObservers {
+    observers: Arc<Mutex<Vec<Arc<dyn TransactionObserver>>>>,
+}
+
+impl TransactionObservers {
+    pub fn add(&self, observer: Arc<dyn TransactionObserver>) {
+        self.observers.lock().unwrap().push(observer);
+    }
+
+    fn notify(&self, event: &TransactionEvent<'_>) {
+        // Clone the list so observers may register other observers.
+        let observers = self.observers.lock().unwrap().clone();
+        for observer in &observers {
+            observer.transaction_published(event);
+        }
+    }
+}
+
+/// Name of the file in the repo directory that records the id of the last
+/// published operation.
+pub const LAST_OPERATION_FILE_NAME: &str = "last_operation";
+
+/// Observer that records the id of each published operation in a file, so
+/// that other processes can watch it for changes instead of polling the
+/// operation heads.
+///
+/// The file is only updated if it exists. Processes that watch the file
+/// create it first, so nothing is written unless the file is consumed.
+/// [`RepoLoader::init_from_file_system()`] registers this observer.
+///
+/// [`RepoLoader::init_from_file_system()`]: crate::repo::RepoLoader::init_from_file_system
+#[derive(Debug)]
+pub struct OperationFileNotifier {
+    path: PathBuf,
+}
+
+impl OperationFileNotifier {
+    pub fn new(path: PathBuf) -> Self {
+        OperationFileNotifier { path }
+    }
+
+    fn write_operation_id(&self, event: &TransactionEvent<'_>) -> std::io::Result<()> {
+        let dir = self
+            .path
+            .parent()
+            .expect("notification path should have a parent");
+        if !self.path.try_exists()? {
+            return Ok(());
+        }
+        let mut temp_file = NamedTempFile::new_in(dir)?;
+        writeln!(temp_file, "{}", event.new_repo.op_id().hex())?;
+        temp_file.persist(&self.path).map_err(|err| err.error)?;
+        Ok(())
+    }
+}
+
+impl TransactionObserver for OperationFileNotifier {
+    fn transaction_published(&self, event: &TransactionEvent<'_>) {
+        if let Err(err) = self.write_operation_id(event) {
+            tracing::warn!(?err, path = ?self.path, "failed to write last operation file");
+        }
+    }
+}
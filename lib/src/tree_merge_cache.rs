@@ -0,0 +1,227 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistent cache of tree merge results.
+//!
+//! Rebasing a stack of commits repeatedly merges the same trees, so the
+//! results are memoized by the ids of the input trees and reused across
+//! commands. Each result is stored in its own file named after the hash of the
+//! input trees. The files are written atomically and carry a checksum, so
+//! entries written concurrently by several processes can't be mixed up, and
+//! damaged entries are ignored.
+
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use blake2::Blake2b512;
+use clru::CLruCache;
+use digest::Digest as _;
+use itertools::Itertools as _;
+use tempfile::NamedTempFile;
+
+use crate::backend::TreeId;
+use crate::file_util::persist_content_addressed_temp_file;
+use crate::hex_util;
+use crate::merge::Merge;
+use crate::object_id::ObjectId as _;
+
+/// Header of the cache entries. Bump the version if the merge algorithm
+/// changes in a way that could produce different results for the same inputs.
+const ENTRY_HEADER: &str = "jj-tree-merge-cache v2";
+/// Default maximum number of merge results to keep.
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Memoizes the results of root tree merges on disk.
+///
+/// The cache is best-effort. I/O errors are logged and otherwise ignored.
+#[derive(Debug)]
+pub struct TreeMergeCache {
+    dir: PathBuf,
+    capacity: NonZeroUsize,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug)]
+struct CacheState {
+    // Entries read or written by this process.
+    entries: CLruCache<Merge<TreeId>, Merge<TreeId>>,
+    // Number of entry files on disk. Counted on first insertion.
+    num_files: Option<usize>,
+}
+
+impl TreeMergeCache {
+    /// Creates a cache stored in `dir` with the default capacity. The
+    /// directory is created when the first result is inserted.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self::with_capacity(dir, DEFAULT_CAPACITY.try_into().unwrap())
+    }
+
+    /// Creates a cache stored in `dir` which keeps at most about `capacity`
+    /// merge results.
+    pub fn with_capacity(dir: impl Into<PathBuf>, capacity: NonZeroUsize) -> Self {
+        TreeMergeCache {
+            dir: dir.into(),
+            capacity,
+            state: Mutex::new(CacheState {
+                entries: CLruCache::new(capacity),
+                num_files: None,
+            }),
+        }
+    }
+
+    /// Looks up the result of merging the `input` trees.
+    pub fn get(&self, input: &Merge<TreeId>) -> Option<Merge<TreeId>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(output) = state.entries.get(input) {
+            return Some(output.clone());
+        }
+        let path = self.entry_path(input);
+        let output = match fs::read_to_string(&path) {
+            Ok(content) => parse_entry(&content, input),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                tracing::warn!(?err, ?path, "failed to read tree merge cache entry");
+                return None;
+            }
+        };
+        let Some(output) = output else {
+            tracing::warn!(?path, "ignoring invalid tree merge cache entry");
+            return None;
+        };
+        state.entries.put(input.clone(), output.clone());
+        Some(output)
+    }
+
+    /// Records that merging the `input` trees resulted in `output`.
+    pub fn insert(&self, input: Merge<TreeId>, output: Merge<TreeId>) {
+        let mut state = self.state.lock().unwrap();
+        if state.entries.get(&input) == Some(&output) {
+            return;
+        }
+        if let Err(err) = self.write_entry(&input, &output) {
+            tracing::warn!(?err, "failed to write tree merge cache entry");
+            return;
+        }
+        state.entries.put(input, output);
+        let num_files = match state.num_files {
+            Some(num_files) => num_files + 1,
+            None => self.entry_files().map_or(0, |files| files.len()),
+        };
+        state.num_files = Some(num_files);
+        if num_files > 2 * self.capacity.get() {
+            match self.prune() {
+                Ok(num_files) => state.num_files = Some(num_files),
+                Err(err) => tracing::warn!(?err, "failed to prune tree merge cache"),
+            }
+        }
+    }
+
+    fn entry_path(&self, input: &Merge<TreeId>) -> PathBuf {
+        let hash = Blake2b512::digest(format_tree_ids(input));
+        self.dir.join(hex_util::encode_hex(&hash[..32]))
+    }
+
+    fn write_entry(&self, input: &Merge<TreeId>, output: &Merge<TreeId>) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut temp_file = NamedTempFile::new_in(&self.dir)?;
+        temp_file.write_all(format_entry(input, output).as_bytes())?;
+        persist_content_addressed_temp_file(temp_file, self.entry_path(input))?;
+        Ok(())
+    }
+
+    fn entry_files(&self) -> io::Result<Vec<PathBuf>> {
+        let mut files = vec![];
+        for entry in self.dir.read_dir()? {
+            let path = entry?.path();
+            if is_entry_file(&path) {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+
+    /// Removes the least recently modified entries so that `capacity` remain.
+    /// Returns the number of remaining entries.
+    fn prune(&self) -> io::Result<usize> {
+        let mut files = self
+            .entry_files()?
+            .into_iter()
+            .map(|path| {
+                let mtime = path
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                (mtime, path)
+            })
+            .collect_vec();
+        let num_to_remove = files.len().saturating_sub(self.capacity.get());
+        files.sort_unstable();
+        for (_, path) in files.drain(..num_to_remove) {
+            // Another process may have removed the file already.
+            fs::remove_file(path).ok();
+        }
+        Ok(files.len())
+    }
+}
+
+fn is_entry_file(path: &Path) -> bool {
+    // Skip temporary files.
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.len() == 64 && hex_util::decode_hex(name).is_some())
+}
+
+fn format_tree_ids(ids: &Merge<TreeId>) -> String {
+    ids.iter().map(|id| id.hex()).join(" ")
+}
+
+fn format_entry(input: &Merge<TreeId>, output: &Merge<TreeId>) -> String {
+    let body = format!(
+        "{ENTRY_HEADER}\n{}\n{}\n",
+        format_tree_ids(input),
+        format_tree_ids(output)
+    );
+    let checksum = hex_util::encode_hex(&Blake2b512::digest(&body)[..32]);
+    format!("{body}{checksum}\n")
+}
+
+/// Parses the entry `content`, and returns the output if the entry is valid
+/// and was recorded for the `input` trees.
+fn parse_entry(content: &str, input: &Merge<TreeId>) -> Option<Merge<TreeId>> {
+    let (body, checksum) = content.strip_suffix('\n')?.rsplit_once('\n')?;
+    let body = &content[..body.len() + 1];
+    if hex_util::encode_hex(&Blake2b512::digest(body)[..32]) != checksum {
+        return None;
+    }
+    let mut lines = body.lines();
+    if lines.next()? != ENTRY_HEADER || lines.next()? != format_tree_ids(input) {
+        return None;
+    }
+    let output = parse_tree_ids(lines.next()?)?;
+    lines.next().is_none().then_some(output)
+}
+
+fn parse_tree_ids(text: &str) -> Option<Merge<TreeId>> {
+    let ids: Vec<_> = text
+        .split(' ')
+        .map(TreeId::try_from_hex)
+        .collect::<Option<_>>()?;
+    (ids.len() % 2 == 1).then(|| Merge::from_vec(ids))
+}
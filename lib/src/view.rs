@@ -73,6 +73,8 @@ impl View {
         self.data.wc_commit_ids.values().contains(commit_id)
     }
 
+    /// Returns the head commits in no particular order. Callers should sort
+    /// them if the order can be observed by users.
     pub fn heads(&self) -> &HashSet<CommitId> {
         &self.data.head_ids
     }
@@ -82,6 +84,23 @@ impl View {
         op_store::merge_join_bookmark_views(&self.data.local_bookmarks, &self.data.remote_views)
     }
 
+    /// Returns local and remote bookmarks of the given name.
+    pub fn get_bookmark(&self, name: &RefName) -> BookmarkTarget<'_> {
+        let remote_refs = self
+            .data
+            .remote_views
+            .iter()
+            .filter_map(|(remote_name, remote_view)| {
+                let remote_ref = remote_view.bookmarks.get(name)?;
+                Some((remote_name.as_ref(), remote_ref))
+            })
+            .collect();
+        BookmarkTarget {
+            local_target: self.get_local_bookmark(name),
+            remote_refs,
+        }
+    }
+
     pub fn tags(&self) -> &BTreeMap<RefNameBuf, RefTarget> {
         &self.data.tags
     }
@@ -195,6 +195,14 @@ pub enum SnapshotError {
     },
 }
 
+/// Name of the file which represents an empty directory in a tree.
+///
+/// Trees can't contain empty directories, so an empty directory is recorded
+/// as a directory containing an empty file by this name. Working copies
+/// materialize it as an empty directory on disk instead of a file if empty
+/// directories are tracked.
+pub const EMPTY_DIRECTORY_MARKER: &str = ".jjkeep";
+
 /// Options used when snapshotting the working copy. Some of them may be ignored
 /// by some `WorkingCopy` implementations.
 #[derive(Clone)]
@@ -222,6 +230,16 @@ pub struct SnapshotOptions<'a> {
     pub max_new_file_size: u64,
     /// Expected conflict marker style for checking for changed files.
     pub conflict_marker_style: ConflictMarkerStyle,
+    /// Start tracking new empty directories by adding an
+    /// [`EMPTY_DIRECTORY_MARKER`] file to them in the tree.
+    pub track_empty_directories: bool,
+    /// Report previously conflicted files which were resolved but still
+    /// contain conflict markers in [`SnapshotStats::conflict_marker_paths`].
+    pub detect_conflict_markers: bool,
+    /// Only look for changed files, and report them in
+    /// [`SnapshotStats::changed_paths`]. Nothing is written to the store, and
+    /// the working-copy state isn't updated.
+    pub dry_run: bool,
 }
 
 impl SnapshotOptions<'_> {
@@ -234,6 +252,9 @@ impl SnapshotOptions<'_> {
             start_tracking_matcher: &EverythingMatcher,
             max_new_file_size: u64::MAX,
             conflict_marker_style: ConflictMarkerStyle::default(),
+            track_empty_directories: false,
+            detect_conflict_markers: false,
+            dry_run: false,
         }
     }
 }
@@ -246,6 +267,38 @@ pub type SnapshotProgress<'a> = dyn Fn(&RepoPath) + 'a + Sync;
 pub struct SnapshotStats {
     /// List of new (previously untracked) files which are still untracked.
     pub untracked_paths: BTreeMap<RepoPathBuf, UntrackedReason>,
+    /// The number of files that were added to the tree.
+    pub added_files: u32,
+    /// The number of files that were modified in the tree.
+    pub modified_files: u32,
+    /// The number of files that were removed from the tree.
+    pub removed_files: u32,
+    /// Previously conflicted files which were snapshotted as resolved, but
+    /// which still contain conflict markers.
+    pub conflict_marker_paths: Vec<RepoPathBuf>,
+    /// Files which would be added, modified, or removed in the tree, and why
+    /// the snapshot looked at them. Only populated by a
+    /// [dry run](SnapshotOptions::dry_run).
+    pub changed_paths: BTreeMap<RepoPathBuf, SnapshotChangeReason>,
+}
+
+/// Reason why a snapshot found a file to be changed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SnapshotChangeReason {
+    /// File wasn't tracked before.
+    NewFile,
+    /// File no longer exists on disk.
+    Removed,
+    /// File type (including the executable bit) differs from the recorded
+    /// state.
+    FileTypeChanged,
+    /// File size differs from the recorded state.
+    SizeChanged,
+    /// File modification time differs from the recorded state.
+    MtimeChanged,
+    /// File metadata is unchanged, but the file was modified too close to the
+    /// previous snapshot to tell whether its contents changed.
+    AmbiguousMtime,
 }
 
 /// Reason why the new path isn't tracked.
@@ -267,6 +320,9 @@ pub enum UntrackedReason {
 pub struct CheckoutOptions {
     /// Conflict marker style to use when materializing files
     pub conflict_marker_style: ConflictMarkerStyle,
+    /// Materialize empty [`EMPTY_DIRECTORY_MARKER`] files as empty
+    /// directories instead of files.
+    pub track_empty_directories: bool,
 }
 
 impl CheckoutOptions {
@@ -274,6 +330,7 @@ impl CheckoutOptions {
     pub fn empty_for_test() -> Self {
         CheckoutOptions {
             conflict_marker_style: ConflictMarkerStyle::default(),
+            track_empty_directories: false,
         }
     }
 }
@@ -53,6 +53,8 @@ use crate::settings::UserSettings;
 use crate::signing::SignInitError;
 use crate::signing::Signer;
 use crate::simple_backend::SimpleBackend;
+use crate::store_migration::default_store_migrations;
+use crate::store_migration::WORKSPACE_STORE_DIRS;
 use crate::transaction::TransactionCommitError;
 use crate::working_copy::CheckoutError;
 use crate::working_copy::CheckoutOptions;
@@ -150,6 +152,7 @@ fn init_working_copy(
     )?;
     let working_copy_type_path = working_copy_state_path.join("type");
     fs::write(&working_copy_type_path, working_copy.name()).context(&working_copy_type_path)?;
+    default_store_migrations().write_format_versions(jj_dir, &WORKSPACE_STORE_DIRS)?;
     Ok((working_copy, repo))
 }
 
@@ -585,6 +588,8 @@ impl WorkspaceLoader for DefaultWorkspaceLoader {
     ) -> Result<Workspace, WorkspaceLoadError> {
         let repo_loader =
             RepoLoader::init_from_file_system(user_settings, &self.repo_path, store_factories)?;
+        let jj_dir = self.working_copy_state_path.parent().unwrap();
+        store_factories.migrate_workspace_stores(user_settings, jj_dir)?;
         let working_copy_factory = get_working_copy_factory(self, working_copy_factories)?;
         let working_copy = working_copy_factory.load_working_copy(
             repo_loader.store().clone(),
@@ -8,6 +8,7 @@ fn test_no_forgotten_test_files() {
 
 mod test_annotate;
 mod test_bad_locking;
+mod test_bisect;
 mod test_commit_builder;
 mod test_commit_concurrent;
 mod test_conflicts;
@@ -17,6 +18,7 @@ mod test_fix;
 mod test_git;
 mod test_git_backend;
 mod test_gpg;
+mod test_hunk_selection;
 mod test_id_prefix;
 mod test_index;
 mod test_init;
@@ -26,7 +28,9 @@ mod test_local_working_copy_concurrent;
 mod test_local_working_copy_sparse;
 mod test_merge_trees;
 mod test_merged_tree;
+mod test_metrics;
 mod test_mut_repo;
+mod test_obsolescence;
 mod test_operations;
 mod test_refs;
 mod test_revset;
@@ -36,5 +40,6 @@ mod test_rewrite_duplicate;
 mod test_rewrite_transform;
 mod test_signing;
 mod test_ssh_signing;
+mod test_tree_merge_cache;
 mod test_view;
 mod test_workspace;
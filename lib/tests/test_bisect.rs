@@ -0,0 +1,158 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use assert_matches::assert_matches;
+use jj_lib::backend::CommitId;
+use jj_lib::bisect::BisectionResult;
+use jj_lib::bisect::Bisector;
+use jj_lib::bisect::Evaluation;
+use jj_lib::bisect::NextStep;
+use jj_lib::commit::Commit;
+use testutils::CommitGraphBuilder;
+use testutils::TestRepo;
+
+/// Runs the bisection to the end, evaluating commits with `evaluate`. Returns
+/// the evaluated commits and the result.
+fn run_bisection(
+    bisector: &mut Bisector,
+    evaluate: impl Fn(&Commit) -> Evaluation,
+) -> (Vec<CommitId>, BisectionResult) {
+    let mut evaluated = vec![];
+    loop {
+        match bisector.next_step().unwrap() {
+            NextStep::Evaluate(commit) => {
+                evaluated.push(commit.id().clone());
+                bisector.mark(commit.id().clone(), evaluate(&commit));
+            }
+            NextStep::Done(result) => return (evaluated, result),
+        }
+    }
+}
+
+#[test]
+fn test_bisect_linear() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let mut commits = vec![graph_builder.initial_commit()];
+    for _ in 1..8 {
+        let parent = commits.last().unwrap();
+        let commit = graph_builder.commit_with_parents(&[parent]);
+        commits.push(commit);
+    }
+    let repo = tx.commit("test").unwrap();
+
+    // commits[5] is the first bad commit
+    let is_bad = |commit: &Commit| commits[5..].contains(commit);
+    let mut bisector = Bisector::new(
+        repo.as_ref(),
+        [commits[0].id().clone()],
+        [commits[7].id().clone()],
+    );
+    let (evaluated, result) = run_bisection(&mut bisector, |commit| {
+        if is_bad(commit) {
+            Evaluation::Bad
+        } else {
+            Evaluation::Good
+        }
+    });
+    // 6 candidates should need no more than 3 evaluations
+    assert!(evaluated.len() <= 3, "{evaluated:?}");
+    assert_eq!(result, BisectionResult::Found(vec![commits[5].clone()]));
+}
+
+#[test]
+fn test_bisect_bad_is_first_candidate() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let repo = tx.commit("test").unwrap();
+
+    let bisector = Bisector::new(
+        repo.as_ref(),
+        [commit_a.id().clone()],
+        [commit_b.id().clone()],
+    );
+    assert_matches!(
+        bisector.next_step().unwrap(),
+        NextStep::Done(BisectionResult::Found(commits)) if commits == [commit_b.clone()]
+    );
+}
+
+#[test]
+fn test_bisect_skipped() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_b]);
+    let repo = tx.commit("test").unwrap();
+
+    // The first bad commit is either B or C, but B can't be evaluated.
+    let mut bisector = Bisector::new(
+        repo.as_ref(),
+        [commit_a.id().clone()],
+        [commit_c.id().clone()],
+    );
+    let (evaluated, result) = run_bisection(&mut bisector, |_| Evaluation::Skip);
+    assert_eq!(evaluated, [commit_b.id().clone()]);
+    assert_matches!(result, BisectionResult::Indeterminate(commits) => {
+        assert_eq!(commits.len(), 2);
+        assert!(commits.contains(&commit_b));
+        assert!(commits.contains(&commit_c));
+    });
+    assert!(bisector.skipped_commits().contains(commit_b.id()));
+}
+
+#[test]
+fn test_bisect_merge() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // D
+    // |\
+    // B C
+    // |/
+    // A
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_d = graph_builder.commit_with_parents(&[&commit_b, &commit_c]);
+    let repo = tx.commit("test").unwrap();
+
+    let mut bisector = Bisector::new(
+        repo.as_ref(),
+        [commit_a.id().clone()],
+        [commit_d.id().clone()],
+    );
+    let (_, result) = run_bisection(&mut bisector, |commit| {
+        if commit == &commit_c || commit == &commit_d {
+            Evaluation::Bad
+        } else {
+            Evaluation::Good
+        }
+    });
+    assert_eq!(result, BisectionResult::Found(vec![commit_c.clone()]));
+}
@@ -419,6 +419,140 @@ fn test_rewrite_to_identical_commit(backend: TestRepoBackend) {
     tx.commit("test").unwrap();
 }
 
+#[test_case(TestRepoBackend::Simple ; "simple backend")]
+#[test_case(TestRepoBackend::Git ; "git backend")]
+fn test_deterministic_change_ids(backend: TestRepoBackend) {
+    let mut config = config_with_commit_timestamp("2001-02-03T04:05:06+07:00");
+    let mut layer = ConfigLayer::empty(ConfigSource::User);
+    layer
+        .set_value("core.deterministic-change-ids", true)
+        .unwrap();
+    config.add_layer(layer);
+    let settings = UserSettings::from_config(config).unwrap();
+
+    let create_commits = || {
+        let test_repo = TestRepo::init_with_backend_and_settings(backend, &settings);
+        let repo = test_repo.repo;
+        let store = repo.store();
+        let mut tx = repo.start_transaction();
+        let commit_a = tx
+            .repo_mut()
+            .new_commit(
+                vec![store.root_commit_id().clone()],
+                store.empty_merged_tree_id(),
+            )
+            .set_description("a")
+            .write()
+            .unwrap();
+        let commit_b = tx
+            .repo_mut()
+            .new_commit(
+                vec![store.root_commit_id().clone()],
+                store.empty_merged_tree_id(),
+            )
+            .set_description("b")
+            .write()
+            .unwrap();
+        let commit_c = tx
+            .repo_mut()
+            .new_commit(
+                vec![store.root_commit_id().clone()],
+                store.empty_merged_tree_id(),
+            )
+            .set_change_id(ChangeId::from_hex("11111111111111111111111111111111"))
+            .write()
+            .unwrap();
+        let rewritten_a = tx
+            .repo_mut()
+            .rewrite_commit(&commit_a)
+            .set_description("rewritten")
+            .write()
+            .unwrap();
+        (commit_a, commit_b, commit_c, rewritten_a)
+    };
+
+    // The same content produces the same change ids in separate repos
+    let (commit_a1, commit_b1, commit_c1, rewritten_a1) = create_commits();
+    let (commit_a2, commit_b2, _, _) = create_commits();
+    assert_eq!(commit_a1.change_id(), commit_a2.change_id());
+    assert_eq!(commit_b1.change_id(), commit_b2.change_id());
+    // Different content produces different change ids
+    assert_ne!(commit_a1.change_id(), commit_b1.change_id());
+    // Explicitly set change ids are kept
+    assert_eq!(
+        commit_c1.change_id(),
+        &ChangeId::from_hex("11111111111111111111111111111111")
+    );
+    // Rewriting doesn't change the change id
+    assert_eq!(rewritten_a1.change_id(), commit_a1.change_id());
+}
+
+#[test]
+fn test_deterministic_change_ids_content_only() {
+    let mut config = config_with_commit_timestamp("2001-02-03T04:05:06+07:00");
+    let mut layer = ConfigLayer::empty(ConfigSource::User);
+    layer
+        .set_value("core.deterministic-change-ids", true)
+        .unwrap();
+    config.add_layer(layer);
+    let settings = UserSettings::from_config(config).unwrap();
+    let test_repo = TestRepo::init_with_settings(&settings);
+    let repo = test_repo.repo;
+    let store = repo.store().clone();
+
+    let mut tx = repo.start_transaction();
+    let commit_a = tx
+        .repo_mut()
+        .new_commit(
+            vec![store.root_commit_id().clone()],
+            store.empty_merged_tree_id(),
+        )
+        .set_description("a")
+        .write()
+        .unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    // The operation and the committer don't affect the change id
+    let mut tx = repo.start_transaction();
+    let mut committer = settings.signature();
+    committer.name = "Other Committer".to_owned();
+    let same_as_a = tx
+        .repo_mut()
+        .new_commit(
+            vec![store.root_commit_id().clone()],
+            store.empty_merged_tree_id(),
+        )
+        .set_description("a")
+        .set_committer(committer)
+        .write()
+        .unwrap();
+    assert_ne!(same_as_a.id(), commit_a.id());
+    assert_eq!(same_as_a.change_id(), commit_a.change_id());
+
+    // Identical commits on different parents get distinct change ids
+    let on_a = tx
+        .repo_mut()
+        .new_commit(vec![commit_a.id().clone()], store.empty_merged_tree_id())
+        .set_description("a")
+        .write()
+        .unwrap();
+    assert_ne!(on_a.change_id(), commit_a.change_id());
+
+    // Duplicates get distinct change ids since the original is recorded as
+    // predecessor
+    let duplicate_a = tx
+        .repo_mut()
+        .rewrite_commit(&commit_a)
+        .generate_new_change_id()
+        .write()
+        .unwrap();
+    assert_eq!(
+        duplicate_a.store_commit().predecessors,
+        [commit_a.id().clone()]
+    );
+    assert_ne!(duplicate_a.change_id(), commit_a.change_id());
+}
+
 #[test_case(TestRepoBackend::Simple ; "simple backend")]
 // #[test_case(TestRepoBackend::Git ; "git backend")]
 fn test_commit_builder_descendants(backend: TestRepoBackend) {
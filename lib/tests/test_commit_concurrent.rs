@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::cmp::max;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::thread;
 
@@ -101,3 +103,59 @@ fn test_commit_parallel_instances(backend: TestRepoBackend) {
     // initial commit.
     assert_eq!(count_non_merge_operations(&repo), num_threads + 2);
 }
+
+#[test_case(TestRepoBackend::Simple ; "simple backend")]
+#[test_case(TestRepoBackend::Git ; "git backend")]
+fn test_read_while_committing(backend: TestRepoBackend) {
+    // One thread commits many operations in sequence while other threads
+    // repeatedly load the repo at head. Each load should see a consistent
+    // state, and the readers shouldn't create any operations.
+    let settings = testutils::user_settings();
+    let test_workspace = TestWorkspace::init_with_backend_and_settings(backend, &settings);
+    let test_env = &test_workspace.env;
+    let repo_path = test_workspace.repo_path();
+
+    let num_commits = 50;
+    let num_readers = max(num_cpus::get() - 1, 3);
+    let initial_num_heads = test_workspace.repo.view().heads().len();
+    let done = AtomicBool::new(false);
+    thread::scope(|s| {
+        let writer_repo = test_env.load_repo_at_head(&settings, repo_path);
+        let done = &done;
+        s.spawn(move || {
+            let mut repo = writer_repo;
+            for _ in 0..num_commits {
+                let mut tx = repo.start_transaction();
+                write_random_commit(tx.repo_mut());
+                repo = tx.commit("test").unwrap();
+            }
+            done.store(true, Ordering::Release);
+        });
+        for _ in 0..num_readers {
+            let settings = &settings;
+            s.spawn(move || {
+                let mut last_num_heads = initial_num_heads;
+                while !done.load(Ordering::Acquire) {
+                    let repo = test_env.load_repo_at_head(settings, repo_path);
+                    let heads = repo.view().heads();
+                    // Operations are published in order, so a later load
+                    // can't see fewer commits.
+                    assert!(heads.len() >= last_num_heads);
+                    last_num_heads = heads.len();
+                    for id in heads {
+                        assert!(repo.index().has_id(id));
+                        repo.store().get_commit(id).unwrap();
+                    }
+                }
+            });
+        }
+    });
+
+    let repo = test_env.load_repo_at_head(&settings, repo_path);
+    assert_eq!(repo.view().heads().len(), initial_num_heads + num_commits);
+    // No merge operations should have been created by the readers. One
+    // additional operation for the root operation, one for checking out the
+    // initial commit.
+    assert_eq!(count_non_merge_operations(&repo), num_commits + 2);
+    assert_eq!(repo.operation().parent_ids().len(), 1);
+}
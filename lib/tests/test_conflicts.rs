@@ -17,6 +17,7 @@ use itertools::Itertools as _;
 use jj_lib::backend::FileId;
 use jj_lib::conflicts::choose_materialized_conflict_marker_len;
 use jj_lib::conflicts::extract_as_single_hunk;
+use jj_lib::conflicts::has_conflict_markers;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
 use jj_lib::conflicts::parse_conflict;
 use jj_lib::conflicts::update_from_content;
@@ -1637,6 +1638,47 @@ fn test_parse_conflict_mixed_header_styles() {
     );
 }
 
+#[test]
+fn test_has_conflict_markers() {
+    assert!(!has_conflict_markers(b"", 7));
+    assert!(!has_conflict_markers(
+        indoc! {b"
+        line 1
+        =======
+        -------
+        +++++++
+        line 2
+        "},
+        7
+    ));
+    // A start or end marker left behind by itself is detected
+    assert!(has_conflict_markers(
+        indoc! {b"
+        line 1
+        <<<<<<< Conflict 1 of 1
+        line 2
+        "},
+        7
+    ));
+    assert!(has_conflict_markers(
+        indoc! {b"
+        line 1
+        >>>>>>> Conflict 1 of 1 ends
+        "},
+        7
+    ));
+    // Markers shorter than the expected length are ignored
+    assert!(!has_conflict_markers(
+        indoc! {b"
+        <<<<<<<
+        >>>>>>>
+        "},
+        9
+    ));
+    // Text after the marker must be separated by whitespace
+    assert!(!has_conflict_markers(b"<<<<<<<<<<foo\n", 7));
+}
+
 #[test]
 fn test_update_conflict_from_content() {
     let test_repo = TestRepo::init();
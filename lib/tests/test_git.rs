@@ -41,6 +41,7 @@ use jj_lib::git::FailedRefExportReason;
 use jj_lib::git::GitBranchPushTargets;
 use jj_lib::git::GitFetch;
 use jj_lib::git::GitFetchError;
+use jj_lib::git::GitFetchTags;
 use jj_lib::git::GitImportError;
 use jj_lib::git::GitPushError;
 use jj_lib::git::GitPushStats;
@@ -66,6 +67,7 @@ use jj_lib::repo::Repo as _;
 use jj_lib::settings::GitSettings;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::Signer;
+use jj_lib::store_migration::FORMAT_VERSION_FILE;
 use jj_lib::str_util::StringPattern;
 use jj_lib::workspace::Workspace;
 use maplit::btreemap;
@@ -145,6 +147,7 @@ fn git_fetch(
     git_fetch.fetch(
         remote_name,
         branch_names,
+        GitFetchTags::default(),
         git::RemoteCallbacks::default(),
         None,
     )?;
@@ -3754,7 +3757,9 @@ fn test_bulk_update_extra_on_import_refs() {
         extra_dir
             .read_dir()
             .unwrap()
-            .filter(|entry| entry.as_ref().unwrap().metadata().unwrap().is_file())
+            .map(|entry| entry.unwrap())
+            .filter(|entry| entry.metadata().unwrap().is_file())
+            .filter(|entry| entry.file_name() != FORMAT_VERSION_FILE)
             .count()
     };
     let import_refs = |repo: &Arc<ReadonlyRepo>| {
@@ -0,0 +1,135 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use assert_matches::assert_matches;
+use jj_lib::hunk_selection::select_hunks;
+use jj_lib::hunk_selection::FileSelection;
+use jj_lib::hunk_selection::HunkSelectionError;
+use jj_lib::repo::Repo as _;
+use maplit::btreemap;
+use pollster::FutureExt as _;
+use testutils::assert_tree_eq;
+use testutils::create_tree;
+use testutils::create_tree_with;
+use testutils::repo_path;
+use testutils::TestRepo;
+
+fn hunks(indices: impl IntoIterator<Item = usize>) -> FileSelection {
+    FileSelection::Hunks(BTreeSet::from_iter(indices))
+}
+
+#[test]
+fn test_select_hunks_modified_file() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let store = repo.store();
+
+    let path = repo_path("file");
+    let other_path = repo_path("other");
+    let left_tree = create_tree(repo, &[(path, "a\nb\nc\nd\n"), (other_path, "1\n")]);
+    let right_tree = create_tree(repo, &[(path, "A\nb\nc\nD\n"), (other_path, "2\n")]);
+
+    // Select the second hunk only. Unselected paths are left unchanged.
+    let selection = btreemap! { path.to_owned() => hunks([1]) };
+    let tree_id = select_hunks(&left_tree, &right_tree, &selection)
+        .block_on()
+        .unwrap();
+    let expected_tree = create_tree(repo, &[(path, "a\nb\nc\nD\n"), (other_path, "1\n")]);
+    assert_tree_eq!(&tree_id, &expected_tree.id(), store);
+
+    // Select all hunks, and unknown indices
+    let selection = btreemap! { path.to_owned() => hunks([0, 1, 2]) };
+    let tree_id = select_hunks(&left_tree, &right_tree, &selection)
+        .block_on()
+        .unwrap();
+    let expected_tree = create_tree(repo, &[(path, "A\nb\nc\nD\n"), (other_path, "1\n")]);
+    assert_tree_eq!(&tree_id, &expected_tree.id(), store);
+
+    // Select no hunks
+    let selection = btreemap! { path.to_owned() => hunks([]) };
+    let tree_id = select_hunks(&left_tree, &right_tree, &selection)
+        .block_on()
+        .unwrap();
+    assert_tree_eq!(&tree_id, &left_tree.id(), store);
+}
+
+#[test]
+fn test_select_hunks_added_and_deleted_file() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let store = repo.store();
+
+    let added_path = repo_path("added");
+    let deleted_path = repo_path("deleted");
+    let left_tree = create_tree(repo, &[(deleted_path, "a\n")]);
+    let right_tree = create_tree(repo, &[(added_path, "b\n")]);
+
+    // Nothing selected: the added file stays absent, and the deleted file is
+    // kept
+    let selection = btreemap! {
+        added_path.to_owned() => hunks([]),
+        deleted_path.to_owned() => hunks([]),
+    };
+    let tree_id = select_hunks(&left_tree, &right_tree, &selection)
+        .block_on()
+        .unwrap();
+    assert_tree_eq!(&tree_id, &left_tree.id(), store);
+
+    // Everything selected
+    let selection = btreemap! {
+        added_path.to_owned() => hunks([0]),
+        deleted_path.to_owned() => hunks([0]),
+    };
+    let tree_id = select_hunks(&left_tree, &right_tree, &selection)
+        .block_on()
+        .unwrap();
+    assert_tree_eq!(&tree_id, &right_tree.id(), store);
+}
+
+#[test]
+fn test_select_hunks_whole_file() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let store = repo.store();
+
+    let file_path = repo_path("file");
+    let symlink_path = repo_path("symlink");
+    let left_tree = create_tree_with(repo, |builder| {
+        builder.file(file_path, "a\n");
+    });
+    let right_tree = create_tree_with(repo, |builder| {
+        builder.file(file_path, "b\n").executable(true);
+        builder.symlink(symlink_path, "target");
+    });
+
+    // The whole file is selected, including the executable bit and symlinks
+    let selection = btreemap! {
+        file_path.to_owned() => FileSelection::All,
+        symlink_path.to_owned() => FileSelection::All,
+    };
+    let tree_id = select_hunks(&left_tree, &right_tree, &selection)
+        .block_on()
+        .unwrap();
+    assert_tree_eq!(&tree_id, &right_tree.id(), store);
+
+    // Hunks can't be selected in a symlink
+    let selection = btreemap! { symlink_path.to_owned() => hunks([0]) };
+    let result = select_hunks(&left_tree, &right_tree, &selection).block_on();
+    assert_matches!(
+        result,
+        Err(HunkSelectionError::UnsupportedFile { path, .. }) if *path == *symlink_path
+    );
+}
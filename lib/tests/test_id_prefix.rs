@@ -600,6 +600,15 @@ fn test_id_prefix_shadowed_by_ref() {
         index.shortest_change_prefix_len(tx.repo(), commit.change_id()),
         3
     );
+    // Exact lengths don't take refs into account
+    assert_eq!(
+        index.shortest_commit_prefix_len_exact(tx.repo(), commit.id()),
+        1
+    );
+    assert_eq!(
+        index.shortest_change_prefix_len_exact(tx.repo(), commit.change_id()),
+        1
+    );
 
     // Many-char conflicts
     for n in 3..commit_id_sym.len() {
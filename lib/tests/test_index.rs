@@ -17,16 +17,20 @@ use std::fs;
 use std::sync::Arc;
 
 use assert_matches::assert_matches;
+use itertools::Itertools as _;
 use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::commit_builder::CommitBuilder;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
 use jj_lib::default_index::AsCompositeIndex as _;
 use jj_lib::default_index::CompositeIndex;
 use jj_lib::default_index::DefaultIndexStore;
 use jj_lib::default_index::DefaultIndexStoreError;
 use jj_lib::default_index::DefaultMutableIndex;
 use jj_lib::default_index::DefaultReadonlyIndex;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::index::Index as _;
 use jj_lib::object_id::HexPrefix;
 use jj_lib::object_id::ObjectId as _;
@@ -39,11 +43,16 @@ use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::repo::MutableRepo;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::ResolvedExpression;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::GENERATION_RANGE_FULL;
+use jj_lib::settings::UserSettings;
 use maplit::hashset;
 use testutils::commit_transactions;
 use testutils::create_random_commit;
+use testutils::create_tree;
 use testutils::test_backend::TestBackend;
 use testutils::write_random_commit;
 use testutils::CommitGraphBuilder;
@@ -506,6 +515,111 @@ fn test_index_commits_incremental_already_indexed() {
     assert_eq!(as_mutable_composite(mut_repo).num_commits(), 1 + 1);
 }
 
+#[test]
+fn test_index_commits_lazy_load() {
+    let mut config = testutils::base_user_config();
+    let mut layer = ConfigLayer::empty(ConfigSource::User);
+    layer.set_value("index.lazy-load", true).unwrap();
+    config.add_layer(layer);
+    let settings = UserSettings::from_config(config).unwrap();
+    let test_repo = TestRepo::init();
+    let test_env = &test_repo.env;
+    let repo = &test_repo.repo;
+
+    // Create A and B in one operation, then C and D in another, so the index
+    // has a parent segment.
+    // o   D
+    // |\
+    // o | C
+    // | o B
+    // |/
+    // o A
+    // o root
+
+    let root_commit = repo.store().root_commit();
+    let mut tx = repo.start_transaction();
+    let commit_a = child_commit(tx.repo_mut(), &root_commit).write().unwrap();
+    let commit_b = child_commit(tx.repo_mut(), &commit_a).write().unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx = repo.start_transaction();
+    let commit_c = child_commit(tx.repo_mut(), &commit_a).write().unwrap();
+    let commit_d = tx
+        .repo_mut()
+        .new_commit(
+            vec![commit_c.id().clone(), commit_b.id().clone()],
+            root_commit.tree_id().clone(),
+        )
+        .write()
+        .unwrap();
+    tx.commit("test").unwrap();
+
+    let repo = test_env.load_repo_at_head(&settings, test_repo.repo_path());
+    let index = as_readonly_composite(&repo);
+    assert_eq!(index.num_commits(), 1 + 4);
+    assert_eq!(generation_number(index, commit_b.id()), 2);
+    assert_eq!(generation_number(index, commit_d.id()), 3);
+    assert!(index.is_ancestor(commit_b.id(), commit_d.id()));
+    assert!(!index.is_ancestor(commit_b.id(), commit_c.id()));
+    assert_eq!(
+        repo.resolve_change_id(commit_c.change_id()),
+        Some(vec![commit_c.id().clone()])
+    );
+}
+
+#[test]
+fn test_index_commits_lazy_load_many() {
+    let mut config = testutils::base_user_config();
+    let mut layer = ConfigLayer::empty(ConfigSource::User);
+    layer.set_value("index.lazy-load", true).unwrap();
+    layer.set_value("index.changed-path-filters", true).unwrap();
+    config.add_layer(layer);
+    let settings = UserSettings::from_config(config).unwrap();
+    let test_repo = TestRepo::init_with_settings(&settings);
+    let test_env = &test_repo.env;
+    let repo = &test_repo.repo;
+
+    // More commits than fit in a single page of the lazily-loaded tables
+    let root_commit_id = repo.store().root_commit_id();
+    let mut tx = repo.start_transaction();
+    let commits = (0..1500)
+        .map(|i| {
+            let path = RepoPathBuf::from_internal_string(format!("file{i}")).unwrap();
+            let tree = create_tree(repo, &[(&path, "")]);
+            let commit = tx
+                .repo_mut()
+                .new_commit(vec![root_commit_id.clone()], tree.id())
+                .write()
+                .unwrap();
+            (path, commit)
+        })
+        .collect_vec();
+    tx.commit("test").unwrap();
+
+    let repo = test_env.load_repo_at_head(&settings, test_repo.repo_path());
+    let index = as_readonly_composite(&repo);
+    assert_eq!(index.num_commits(), 1 + 1500);
+    for (_, commit) in &commits {
+        assert!(repo.index().has_id(commit.id()));
+        assert_eq!(generation_number(index, commit.id()), 1);
+        assert_eq!(
+            repo.resolve_change_id(commit.change_id()),
+            Some(vec![commit.id().clone()])
+        );
+    }
+
+    // Changed-path filters are read from the pages of the commits
+    for i in [0, 1023, 1024, 1499] {
+        let (path, commit) = &commits[i];
+        let expression = RevsetExpression::filter(RevsetFilterPredicate::File(
+            FilesetExpression::file_path(path.clone()),
+        ));
+        let revset = expression.evaluate(repo.as_ref()).unwrap();
+        let ids: Vec<_> = revset.iter().try_collect().unwrap();
+        assert_eq!(ids, vec![commit.id().clone()]);
+    }
+}
+
 #[must_use]
 fn create_n_commits(repo: &Arc<ReadonlyRepo>, num_commits: i32) -> Arc<ReadonlyRepo> {
     let mut tx = repo.start_transaction();
@@ -823,6 +937,20 @@ fn test_change_id_index() {
     );
     // No match
     assert_eq!(resolve_prefix("ba"), PrefixResolution::NoMatch);
+    // Exact lookup
+    let resolve_change_id = |commit: &Commit| {
+        change_id_index
+            .resolve_change_id(commit.change_id())
+            .map(HashSet::from_iter)
+    };
+    assert_eq!(
+        resolve_change_id(&commit_1),
+        Some(hashset! {commit_1.id().clone()})
+    );
+    assert_eq!(
+        resolve_change_id(&commit_4),
+        Some(hashset! {commit_4.id().clone(), commit_5.id().clone()})
+    );
 
     // Test with an index containing only some of the commits. The shortest
     // length doesn't have to be minimized further, but unreachable commits
@@ -844,4 +972,12 @@ fn test_change_id_index() {
     assert_eq!(resolve_prefix("aaaaaa"), PrefixResolution::NoMatch);
     assert_eq!(resolve_prefix("a"), PrefixResolution::AmbiguousMatch);
     assert_eq!(resolve_prefix("b"), PrefixResolution::NoMatch);
+    // Exact lookup also excludes unreachable commits
+    let resolve_change_id = |commit: &Commit| change_id_index.resolve_change_id(commit.change_id());
+    assert_eq!(
+        resolve_change_id(&commit_2),
+        Some(vec![commit_2.id().clone()])
+    );
+    assert_eq!(resolve_change_id(&commit_3), None);
+    assert_eq!(resolve_change_id(&commit_4), None);
 }
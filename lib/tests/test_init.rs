@@ -21,6 +21,10 @@ use jj_lib::git_backend::GitBackend;
 use jj_lib::ref_name::WorkspaceName;
 use jj_lib::repo::Repo as _;
 use jj_lib::settings::UserSettings;
+use jj_lib::store_migration::read_format_version;
+use jj_lib::store_migration::FORMAT_VERSION_FILE;
+use jj_lib::store_migration::REPO_STORE_DIRS;
+use jj_lib::store_migration::WORKSPACE_STORE_DIRS;
 use jj_lib::workspace::Workspace;
 use test_case::test_case;
 use testutils::git;
@@ -222,3 +226,29 @@ fn test_init_load_non_utf8_path() {
     let mut tx = repo.start_transaction();
     write_random_commit(tx.repo_mut());
 }
+
+#[test]
+fn test_init_records_format_versions() {
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let (canonical, uncanonical) = canonicalize(temp_dir.path());
+    Workspace::init_internal_git(&settings, &uncanonical).unwrap();
+    let jj_dir = canonical.join(".jj");
+    let repo_path = jj_dir.join("repo");
+    for store in REPO_STORE_DIRS {
+        let version = read_format_version(&repo_path.join(store)).unwrap();
+        assert_eq!(version, 0, "{store}");
+        assert!(repo_path.join(store).join(FORMAT_VERSION_FILE).exists());
+    }
+    for store in WORKSPACE_STORE_DIRS {
+        assert!(jj_dir.join(store).join(FORMAT_VERSION_FILE).exists());
+    }
+
+    // The Git extra metadata doesn't exist in repos backed by other backends
+    let temp_dir = testutils::new_temp_dir();
+    let (canonical, uncanonical) = canonicalize(temp_dir.path());
+    Workspace::init_simple(&settings, &uncanonical).unwrap();
+    let store_path = canonical.join(".jj").join("repo").join("store");
+    assert!(store_path.join(FORMAT_VERSION_FILE).exists());
+    assert!(!store_path.join("extra").exists());
+}
@@ -52,3 +52,37 @@ fn test_load_at_operation() {
     let old_repo = loader.load_at(repo.operation()).unwrap();
     assert!(old_repo.view().heads().contains(commit.id()));
 }
+
+#[test]
+fn test_load_at_head_while_publishing() {
+    let test_repo = TestRepo::init();
+    let repo0 = &test_repo.repo;
+
+    let mut tx = repo0.start_transaction();
+    let commit = write_random_commit(tx.repo_mut());
+    let repo1 = tx.commit("add commit").unwrap();
+
+    // Simulate a process that has added the new op head, but hasn't removed
+    // the old one yet.
+    let op_heads_store = repo1.op_heads_store();
+    op_heads_store.update_op_heads(&[], repo0.op_id()).unwrap();
+    assert_eq!(op_heads_store.get_op_heads().unwrap().len(), 2);
+
+    // The new operation should be loaded without merging the operations or
+    // waiting for the writer's lock. The stale op head is left to the writer.
+    {
+        let _lock = op_heads_store.lock().unwrap();
+        let head_repo = repo0.reload_at_head().unwrap();
+        assert_eq!(head_repo.op_id(), repo1.op_id());
+        assert!(head_repo.view().heads().contains(commit.id()));
+        assert_eq!(op_heads_store.get_op_heads().unwrap().len(), 2);
+    }
+
+    // Once the lock is free, the stale op head should be removed.
+    let head_repo = repo0.reload_at_head().unwrap();
+    assert_eq!(head_repo.op_id(), repo1.op_id());
+    assert_eq!(
+        op_heads_store.get_op_heads().unwrap(),
+        vec![repo1.op_id().clone()]
+    );
+}
@@ -46,6 +46,7 @@ use jj_lib::secret_backend::SecretBackend;
 use jj_lib::working_copy::CheckoutError;
 use jj_lib::working_copy::CheckoutOptions;
 use jj_lib::working_copy::CheckoutStats;
+use jj_lib::working_copy::SnapshotChangeReason;
 use jj_lib::working_copy::SnapshotOptions;
 use jj_lib::working_copy::UntrackedReason;
 use jj_lib::working_copy::WorkingCopy as _;
@@ -994,6 +995,50 @@ fn test_materialize_snapshot_conflicted_files() {
     );
 }
 
+#[test]
+fn test_snapshot_detect_leftover_conflict_markers() {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo.clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let file1_path = repo_path("file1");
+    let file2_path = repo_path("file2");
+    let side1_tree = create_tree(repo, &[(file1_path, "a\n"), (file2_path, "1\n")]);
+    let base_tree = create_tree(repo, &[(file1_path, "b\n"), (file2_path, "2\n")]);
+    let side2_tree = create_tree(repo, &[(file1_path, "c\n"), (file2_path, "3\n")]);
+    let merged_tree = side1_tree.merge(&base_tree, &side2_tree).unwrap();
+    let commit = commit_with_tree(repo.store(), merged_tree.id());
+    test_workspace
+        .workspace
+        .check_out(
+            repo.op_id().clone(),
+            None,
+            &commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+
+    // file1 is resolved, but the end marker was left behind
+    testutils::write_working_copy_file(
+        &workspace_root,
+        file1_path,
+        indoc! {"
+            resolved
+            >>>>>>> Conflict 1 of 1 ends
+        "},
+    );
+    testutils::write_working_copy_file(&workspace_root, file2_path, "resolved\n");
+
+    let options = SnapshotOptions {
+        detect_conflict_markers: true,
+        ..SnapshotOptions::empty_for_test()
+    };
+    let (tree, stats) = test_workspace.snapshot_with_options(&options).unwrap();
+    assert!(tree.path_value(file1_path).unwrap().is_resolved());
+    assert!(tree.path_value(file2_path).unwrap().is_resolved());
+    assert_eq!(stats.conflict_marker_paths, [file1_path.to_owned()]);
+}
+
 #[test]
 fn test_snapshot_racy_timestamps() {
     // Tests that file modifications are detected even if they happen the same
@@ -2157,6 +2202,51 @@ fn test_fsmonitor() {
     }
 }
 
+#[test]
+fn test_snapshot_dry_run() {
+    let mut test_workspace = TestWorkspace::init_with_backend(TestRepoBackend::Simple);
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let files_dir = test_workspace.repo_path().join("store").join("files");
+    let count_files = || std::fs::read_dir(&files_dir).unwrap().count();
+    let modified_path = repo_path("modified");
+    let touched_path = repo_path("touched");
+    let removed_path = repo_path("removed");
+    let added_path = repo_path("added");
+    for path in [modified_path, touched_path, removed_path] {
+        std::fs::write(path.to_fs_path_unchecked(&workspace_root), "old").unwrap();
+    }
+    let (old_tree, _stats) = test_workspace
+        .snapshot_with_options(&SnapshotOptions::empty_for_test())
+        .unwrap();
+    let num_files = count_files();
+
+    std::fs::write(modified_path.to_fs_path_unchecked(&workspace_root), "new!").unwrap();
+    // Rewriting the same content updates the mtime only
+    std::fs::write(touched_path.to_fs_path_unchecked(&workspace_root), "old").unwrap();
+    std::fs::remove_file(removed_path.to_fs_path_unchecked(&workspace_root)).unwrap();
+    std::fs::write(added_path.to_fs_path_unchecked(&workspace_root), "added").unwrap();
+    let options = SnapshotOptions {
+        dry_run: true,
+        ..SnapshotOptions::empty_for_test()
+    };
+    let (new_tree, stats) = test_workspace.snapshot_with_options(&options).unwrap();
+    assert_eq!(new_tree, old_tree);
+    assert_eq!(
+        stats.changed_paths.into_iter().collect_vec(),
+        [
+            (added_path.to_owned(), SnapshotChangeReason::NewFile),
+            (modified_path.to_owned(), SnapshotChangeReason::SizeChanged),
+            (removed_path.to_owned(), SnapshotChangeReason::Removed),
+        ]
+    );
+    assert_eq!(
+        (stats.added_files, stats.modified_files, stats.removed_files),
+        (1, 1, 1)
+    );
+    // Nothing was written to the store
+    assert_eq!(count_files(), num_files);
+}
+
 #[test]
 fn test_snapshot_max_new_file_size() {
     let mut test_workspace = TestWorkspace::init();
@@ -2239,3 +2329,163 @@ fn test_snapshot_max_new_file_size() {
         UntrackedReason::FileTooLarge { .. }
     );
 }
+
+#[test]
+fn test_snapshot_empty_directories() {
+    let mut test_workspace = TestWorkspace::init();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let file_path = repo_path("dir/file");
+    let empty_marker_path = repo_path("empty/.jjkeep");
+    let nested_marker_path = repo_path("nested/sub/.jjkeep");
+    std::fs::create_dir_all(workspace_root.join("dir")).unwrap();
+    std::fs::write(file_path.to_fs_path_unchecked(&workspace_root), "").unwrap();
+    std::fs::create_dir(workspace_root.join("empty")).unwrap();
+    std::fs::create_dir_all(workspace_root.join("nested").join("sub")).unwrap();
+
+    // Empty directories aren't tracked by default
+    let tree = test_workspace.snapshot().unwrap();
+    assert_eq!(
+        tree.entries().map(|(path, _)| path).collect_vec(),
+        [file_path.to_owned()]
+    );
+
+    // Only leaf directories get a marker
+    let options = SnapshotOptions {
+        track_empty_directories: true,
+        ..SnapshotOptions::empty_for_test()
+    };
+    let (tree, _stats) = test_workspace.snapshot_with_options(&options).unwrap();
+    assert_eq!(
+        tree.entries().map(|(path, _)| path).collect_vec(),
+        [
+            file_path.to_owned(),
+            empty_marker_path.to_owned(),
+            nested_marker_path.to_owned(),
+        ]
+    );
+    assert!(!empty_marker_path
+        .to_fs_path_unchecked(&workspace_root)
+        .exists());
+
+    // The markers stay tracked while the directories are empty
+    let (tree, _stats) = test_workspace.snapshot_with_options(&options).unwrap();
+    assert_eq!(
+        tree.entries().map(|(path, _)| path).collect_vec(),
+        [
+            file_path.to_owned(),
+            empty_marker_path.to_owned(),
+            nested_marker_path.to_owned(),
+        ]
+    );
+
+    // The marker is removed when a file is added to the directory, or when
+    // the directory is removed
+    let added_path = repo_path("empty/added");
+    std::fs::write(added_path.to_fs_path_unchecked(&workspace_root), "").unwrap();
+    std::fs::remove_dir_all(workspace_root.join("nested")).unwrap();
+    let (tree, _stats) = test_workspace.snapshot_with_options(&options).unwrap();
+    assert_eq!(
+        tree.entries().map(|(path, _)| path).collect_vec(),
+        [file_path.to_owned(), added_path.to_owned()]
+    );
+}
+
+#[test]
+fn test_check_out_empty_directories() {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = test_workspace.repo.clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let file_path = repo_path("file");
+    let marker_path = repo_path("empty/.jjkeep");
+    let tree1 = create_tree(&repo, &[(file_path, "")]);
+    let tree2 = create_tree(&repo, &[(file_path, ""), (marker_path, "")]);
+    let commit1 = commit_with_tree(repo.store(), tree1.id());
+    let commit2 = commit_with_tree(repo.store(), tree2.id());
+    let checkout_options = CheckoutOptions {
+        track_empty_directories: true,
+        ..CheckoutOptions::empty_for_test()
+    };
+    let snapshot_options = SnapshotOptions {
+        track_empty_directories: true,
+        ..SnapshotOptions::empty_for_test()
+    };
+
+    // The marker is materialized as an empty directory
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(repo.op_id().clone(), None, &commit2, &checkout_options)
+        .unwrap();
+    let empty_dir = workspace_root.join("empty");
+    assert!(empty_dir.is_dir());
+    assert_eq!(std::fs::read_dir(&empty_dir).unwrap().count(), 0);
+
+    // Snapshotting doesn't report the marker as removed
+    let (tree, _stats) = test_workspace
+        .snapshot_with_options(&snapshot_options)
+        .unwrap();
+    assert_eq!(tree.id(), tree2.id());
+
+    // The directory is removed along with the marker
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(repo.op_id().clone(), None, &commit1, &checkout_options)
+        .unwrap();
+    assert!(!empty_dir.exists());
+}
+
+#[test]
+fn test_check_out_regular_marker_files() {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = test_workspace.repo.clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let empty_marker_path = repo_path("empty/.jjkeep");
+    let non_empty_marker_path = repo_path("non-empty/.jjkeep");
+    let tree = create_tree(
+        &repo,
+        &[(empty_marker_path, ""), (non_empty_marker_path, "keep\n")],
+    );
+    let commit = commit_with_tree(repo.store(), tree.id());
+
+    // Markers are regular files unless empty directories are tracked
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+    let empty_marker_disk_path = empty_marker_path.to_fs_path_unchecked(&workspace_root);
+    let non_empty_marker_disk_path = non_empty_marker_path.to_fs_path_unchecked(&workspace_root);
+    assert_eq!(std::fs::read(&empty_marker_disk_path).unwrap(), b"");
+    assert_eq!(
+        std::fs::read(&non_empty_marker_disk_path).unwrap(),
+        b"keep\n"
+    );
+    let tree = test_workspace.snapshot().unwrap();
+    assert_eq!(tree.id(), *commit.tree_id());
+
+    // A non-empty marker file is never an empty directory
+    let root_commit = repo.store().root_commit();
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(
+        repo.op_id().clone(),
+        None,
+        &root_commit,
+        &CheckoutOptions::empty_for_test(),
+    )
+    .unwrap();
+    let checkout_options = CheckoutOptions {
+        track_empty_directories: true,
+        ..CheckoutOptions::empty_for_test()
+    };
+    let ws = &mut test_workspace.workspace;
+    ws.check_out(repo.op_id().clone(), None, &commit, &checkout_options)
+        .unwrap();
+    assert!(!empty_marker_disk_path.exists());
+    assert!(workspace_root.join("empty").is_dir());
+    assert_eq!(
+        std::fs::read(&non_empty_marker_disk_path).unwrap(),
+        b"keep\n"
+    );
+}
@@ -41,6 +41,7 @@ use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use pollster::FutureExt as _;
 use pretty_assertions::assert_eq;
+use test_case::test_case;
 use testutils::create_single_tree;
 use testutils::create_tree;
 use testutils::repo_path;
@@ -48,6 +49,7 @@ use testutils::repo_path_buf;
 use testutils::repo_path_component;
 use testutils::write_file;
 use testutils::TestRepo;
+use testutils::TestRepoBackend;
 
 fn file_value(file_id: &FileId) -> TreeValue {
     TreeValue::File {
@@ -305,6 +307,36 @@ fn test_merged_tree_builder_resolves_conflict() {
     assert_eq!(tree_id, MergedTreeId::resolved(tree2.id().clone()));
 }
 
+#[test_case(TestRepoBackend::Simple ; "simple backend")]
+#[test_case(TestRepoBackend::Git ; "git backend")]
+fn test_entries_order(backend: TestRepoBackend) {
+    let test_repo = TestRepo::init_with_backend(backend);
+    let repo = &test_repo.repo;
+
+    // Git sorts "dir.txt" before "dir/", but entries are ordered by path
+    // components regardless of the backend
+    let paths = [
+        repo_path("dir/file"),
+        repo_path("dir.txt"),
+        repo_path("dir0"),
+        repo_path("file"),
+    ];
+    let tree = create_tree(repo, &paths.map(|path| (path, "contents")));
+    let entry_paths = tree.entries().map(|(path, _)| path).collect_vec();
+    assert_eq!(entry_paths, paths.map(RepoPath::to_owned));
+
+    let empty_tree = repo
+        .store()
+        .get_root_tree(&repo.store().empty_merged_tree_id())
+        .unwrap();
+    let diff_paths = empty_tree
+        .diff_stream(&tree, &EverythingMatcher)
+        .map(|entry| entry.path)
+        .collect::<Vec<_>>()
+        .block_on();
+    assert_eq!(diff_paths, paths.map(RepoPath::to_owned));
+}
+
 #[test]
 fn test_path_value_and_entries() {
     let test_repo = TestRepo::init();
@@ -0,0 +1,92 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use jj_lib::metrics;
+use jj_lib::metrics::MetricsSink;
+use jj_lib::repo::Repo as _;
+use testutils::user_settings;
+use testutils::write_random_commit;
+use testutils::TestRepo;
+
+#[derive(Debug, Default)]
+struct TestMetricsSink {
+    counters: Mutex<HashMap<&'static str, u64>>,
+    durations: Mutex<HashMap<&'static str, usize>>,
+}
+
+impl TestMetricsSink {
+    fn counter(&self, name: &str) -> u64 {
+        self.counters
+            .lock()
+            .unwrap()
+            .get(name)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn num_durations(&self, name: &str) -> usize {
+        self.durations
+            .lock()
+            .unwrap()
+            .get(name)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl MetricsSink for &'static TestMetricsSink {
+    fn add_to_counter(&self, name: &'static str, value: u64) {
+        *self.counters.lock().unwrap().entry(name).or_default() += value;
+    }
+
+    fn record_duration(&self, name: &'static str, _duration: Duration) {
+        *self.durations.lock().unwrap().entry(name).or_default() += 1;
+    }
+}
+
+// The sink is process-wide, so other tests running concurrently may also
+// record metrics. Only lower bounds are checked.
+#[test]
+fn test_metrics_sink() {
+    let sink: &'static TestMetricsSink = Box::leak(Box::default());
+    metrics::set_metrics_sink(Box::new(sink)).unwrap();
+    // Only one sink can be installed
+    assert!(metrics::set_metrics_sink(Box::new(sink)).is_err());
+
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let mut tx = repo.start_transaction();
+    let commit = write_random_commit(tx.repo_mut());
+    tx.commit("test").unwrap();
+
+    // Reloading the repo builds the index and reads the commit from the backend
+    let commit_reads = sink.counter(metrics::STORE_COMMIT_READS);
+    let index_loads = sink.num_durations(metrics::INDEX_STORE_LOAD_DURATION);
+    let repo = test_repo
+        .env
+        .load_repo_at_head(&user_settings(), test_repo.repo_path());
+    assert!(sink.num_durations(metrics::INDEX_STORE_LOAD_DURATION) > index_loads);
+    repo.store().get_commit(commit.id()).unwrap();
+    assert!(sink.counter(metrics::STORE_COMMIT_READS) > commit_reads);
+    assert!(sink.num_durations(metrics::STORE_COMMIT_READ_DURATION) > 0);
+
+    // The second read is served from the cache
+    let cache_hits = sink.counter(metrics::STORE_COMMIT_CACHE_HITS);
+    repo.store().get_commit(commit.id()).unwrap();
+    assert!(sink.counter(metrics::STORE_COMMIT_CACHE_HITS) > cache_hits);
+}
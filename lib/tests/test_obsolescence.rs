@@ -0,0 +1,230 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::backend::Timestamp;
+use jj_lib::obsolescence::export_markers;
+use jj_lib::obsolescence::import_markers;
+use jj_lib::obsolescence::ObsMarker;
+use jj_lib::repo::Repo as _;
+use testutils::create_random_commit;
+use testutils::write_random_commit;
+use testutils::TestRepo;
+
+fn marker_edges(markers: &[ObsMarker]) -> Vec<(CommitId, Vec<CommitId>)> {
+    markers
+        .iter()
+        .map(|marker| (marker.predecessor.clone(), marker.successors.clone()))
+        .collect()
+}
+
+fn new_marker(predecessor: &CommitId, successors: &[&CommitId]) -> ObsMarker {
+    ObsMarker {
+        predecessor: predecessor.clone(),
+        successors: successors.iter().copied().cloned().collect(),
+        date: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+        user: "test-user".to_owned(),
+    }
+}
+
+#[test]
+fn test_export_markers() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let commit_b = write_random_commit(tx.repo_mut());
+    let commit_c = write_random_commit(tx.repo_mut());
+    let commit_d = write_random_commit(tx.repo_mut());
+    let commit_e = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+    // Creating commits doesn't rewrite anything
+    assert_eq!(export_markers(&repo).unwrap(), []);
+
+    let mut tx = repo.start_transaction();
+    // Rewrite A into A2
+    let commit_a2 = tx
+        .repo_mut()
+        .rewrite_commit(&commit_a)
+        .set_description("rewritten")
+        .write()
+        .unwrap();
+    // Split B into B1 and B2
+    let commit_b1 = tx
+        .repo_mut()
+        .rewrite_commit(&commit_b)
+        .set_description("first")
+        .write()
+        .unwrap();
+    let commit_b2 = tx
+        .repo_mut()
+        .rewrite_commit(&commit_b)
+        .set_description("second")
+        .write()
+        .unwrap();
+    // Fold C and D into CD
+    let commit_cd = tx
+        .repo_mut()
+        .rewrite_commit(&commit_c)
+        .set_predecessors(vec![commit_c.id().clone(), commit_d.id().clone()])
+        .write()
+        .unwrap();
+    tx.repo_mut().record_abandoned_commit(&commit_d);
+    // Abandon E
+    tx.repo_mut().record_abandoned_commit(&commit_e);
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    let markers = export_markers(&repo).unwrap();
+    let mut expected = vec![
+        (commit_a.id().clone(), vec![commit_a2.id().clone()]),
+        (
+            commit_b.id().clone(),
+            [commit_b1.id(), commit_b2.id()]
+                .into_iter()
+                .sorted()
+                .cloned()
+                .collect(),
+        ),
+        (commit_c.id().clone(), vec![commit_cd.id().clone()]),
+        (commit_d.id().clone(), vec![commit_cd.id().clone()]),
+        (commit_e.id().clone(), vec![]),
+    ];
+    expected.sort();
+    assert_eq!(marker_edges(&markers), expected);
+    let op_metadata = repo.operation().metadata();
+    assert!(markers
+        .iter()
+        .all(|marker| marker.date == op_metadata.time.end));
+    assert!(markers
+        .iter()
+        .all(|marker| marker.user == op_metadata.username));
+}
+
+#[test]
+fn test_import_markers() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let commit_a_child = create_random_commit(tx.repo_mut())
+        .set_parents(vec![commit_a.id().clone()])
+        .write()
+        .unwrap();
+    let commit_b = write_random_commit(tx.repo_mut());
+    let commit_c = write_random_commit(tx.repo_mut());
+    let commit_d = write_random_commit(tx.repo_mut());
+    let commit_hidden = write_random_commit(tx.repo_mut());
+    tx.repo_mut().remove_head(commit_hidden.id());
+    // Successors as they'd be fetched from the other side
+    let commit_a2 = write_random_commit(tx.repo_mut());
+    let commit_b1 = write_random_commit(tx.repo_mut());
+    let commit_b2 = write_random_commit(tx.repo_mut());
+    let commit_hidden2 = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx = repo.start_transaction();
+    let markers = [
+        new_marker(commit_a.id(), &[commit_a2.id()]),
+        // Two markers for the same predecessor make it divergent
+        new_marker(commit_b.id(), &[commit_b1.id()]),
+        new_marker(commit_b.id(), &[commit_b2.id()]),
+        // Prune
+        new_marker(commit_c.id(), &[]),
+        // A prune marker doesn't win over a rewrite
+        new_marker(commit_d.id(), &[]),
+        new_marker(commit_d.id(), &[commit_a2.id()]),
+        // Hidden and unknown predecessors are ignored
+        new_marker(commit_hidden.id(), &[commit_hidden2.id()]),
+        new_marker(&CommitId::from_hex("0123456789"), &[commit_a2.id()]),
+    ];
+    import_markers(tx.repo_mut(), &markers).unwrap();
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    let heads = repo.view().heads();
+    assert!(!heads.contains(commit_b.id()));
+    assert!(!heads.contains(commit_c.id()));
+    assert!(!heads.contains(commit_d.id()));
+    assert!(heads.contains(commit_hidden2.id()));
+    // The child of A got rebased onto A2
+    let rebased_child = heads
+        .iter()
+        .map(|id| repo.store().get_commit(id).unwrap())
+        .find(|commit| commit.description() == commit_a_child.description())
+        .unwrap();
+    assert_eq!(rebased_child.parent_ids(), [commit_a2.id().clone()]);
+
+    let op = repo.operation();
+    assert_eq!(
+        op.predecessors_for_commit(commit_a2.id())
+            .map(|ids| ids.iter().sorted().collect_vec()),
+        Some(
+            [commit_a.id(), commit_d.id()]
+                .into_iter()
+                .sorted()
+                .collect_vec()
+        )
+    );
+    assert_eq!(
+        op.predecessors_for_commit(commit_b1.id()),
+        Some([commit_b.id().clone()].as_slice())
+    );
+    assert_eq!(
+        op.predecessors_for_commit(commit_b2.id()),
+        Some([commit_b.id().clone()].as_slice())
+    );
+    assert_eq!(op.predecessors_for_commit(commit_hidden2.id()), None);
+}
+
+#[test]
+fn test_import_then_export_markers() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let commit_b = write_random_commit(tx.repo_mut());
+    let commit_c = write_random_commit(tx.repo_mut());
+    let commit_c1 = write_random_commit(tx.repo_mut());
+    let commit_c2 = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+
+    // Markers imported into jj are exported unchanged, so the interop layer
+    // can recognize them as already exchanged.
+    let markers = vec![
+        new_marker(commit_a.id(), &[]),
+        new_marker(commit_b.id(), &[commit_c1.id()]),
+        new_marker(commit_c.id(), &[commit_c1.id(), commit_c2.id()]),
+    ];
+    let mut tx = repo.start_transaction();
+    import_markers(tx.repo_mut(), &markers).unwrap();
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    let exported = export_markers(&repo).unwrap();
+    let mut expected = marker_edges(&markers);
+    for (_, successors) in &mut expected {
+        successors.sort();
+    }
+    expected.sort();
+    assert_eq!(marker_edges(&exported), expected);
+}
@@ -15,6 +15,9 @@
 use std::path::Path;
 use std::slice;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
 use std::time::SystemTime;
 
 use assert_matches::assert_matches;
@@ -24,14 +27,22 @@ use jj_lib::config::ConfigLayer;
 use jj_lib::config::ConfigSource;
 use jj_lib::evolution::walk_predecessors;
 use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_heads_store;
+use jj_lib::op_heads_store::OpHeadResolutionError;
 use jj_lib::op_store::OperationId;
+use jj_lib::op_store::RefTarget;
 use jj_lib::op_walk;
 use jj_lib::op_walk::OpsetEvaluationError;
 use jj_lib::op_walk::OpsetResolutionError;
 use jj_lib::operation::Operation;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
+use jj_lib::repo::RepoLoaderError;
 use jj_lib::settings::UserSettings;
+use jj_lib::transaction::RevertOperationError;
+use jj_lib::transaction::TransactionEvent;
+use jj_lib::transaction::TransactionObserver;
+use jj_lib::transaction::LAST_OPERATION_FILE_NAME;
 use test_case::test_case;
 use testutils::create_random_commit;
 use testutils::write_random_commit;
@@ -225,6 +236,181 @@ fn test_isolation() {
     assert_heads(repo.as_ref(), vec![rewrite1.id(), rewrite2.id()]);
 }
 
+#[test]
+fn test_revert_operation() {
+    // Test that reverting an older operation keeps the changes made by later
+    // operations.
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    tx.repo_mut()
+        .set_local_bookmark_target("a".as_ref(), RefTarget::normal(commit_a.id().clone()));
+    let repo = tx.commit("op a").unwrap();
+    let op_a = repo.operation().clone();
+
+    let mut tx = repo.start_transaction();
+    let commit_b = write_random_commit(tx.repo_mut());
+    tx.repo_mut()
+        .set_local_bookmark_target("b".as_ref(), RefTarget::normal(commit_b.id().clone()));
+    let repo = tx.commit("op b").unwrap();
+    assert_heads(repo.as_ref(), vec![commit_a.id(), commit_b.id()]);
+
+    let mut tx = repo.start_transaction();
+    tx.revert_operation(&op_a).unwrap();
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("revert op a").unwrap();
+    assert_heads(repo.as_ref(), vec![commit_b.id()]);
+    assert!(repo.view().get_local_bookmark("a".as_ref()).is_absent());
+    assert_eq!(
+        *repo.view().get_local_bookmark("b".as_ref()),
+        RefTarget::normal(commit_b.id().clone())
+    );
+}
+
+#[test]
+fn test_revert_operation_root() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let root_op = repo.loader().root_operation();
+
+    let mut tx = repo.start_transaction();
+    assert_matches!(
+        tx.revert_operation(&root_op),
+        Err(RevertOperationError::RootOperation)
+    );
+}
+
+#[test]
+fn test_transaction_observer() {
+    // (old op, new op, rewritten commits with their predecessors)
+    type Event = (OperationId, OperationId, Vec<(CommitId, Vec<CommitId>)>);
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<Event>>,
+    }
+
+    impl TransactionObserver for Recorder {
+        fn transaction_published(&self, event: &TransactionEvent<'_>) {
+            let rewritten = event
+                .rewritten_commits()
+                .map(|(id, predecessors)| (id.clone(), predecessors.to_vec()))
+                .collect();
+            self.events.lock().unwrap().push((
+                event.old_repo.op_id().clone(),
+                event.new_repo.op_id().clone(),
+                rewritten,
+            ));
+        }
+    }
+
+    let test_repo = TestRepo::init();
+    let repo_0 = test_repo.repo.clone();
+    let recorder = Arc::new(Recorder::default());
+    repo_0.loader().add_transaction_observer(recorder.clone());
+
+    let mut tx = repo_0.start_transaction();
+    let commit1 = write_random_commit(tx.repo_mut());
+    let repo_1 = tx.commit("test").unwrap();
+
+    let mut tx = repo_1.start_transaction();
+    let commit2 = tx
+        .repo_mut()
+        .rewrite_commit(&commit1)
+        .set_description("rewritten")
+        .write()
+        .unwrap();
+    tx.repo_mut().rebase_descendants().unwrap();
+    let unpublished = tx.write("test").unwrap();
+    // Nothing is reported until the operation is published
+    assert_eq!(recorder.events.lock().unwrap().len(), 1);
+    let repo_2 = unpublished.publish().unwrap();
+
+    assert_eq!(
+        *recorder.events.lock().unwrap(),
+        vec![
+            (repo_0.op_id().clone(), repo_1.op_id().clone(), vec![]),
+            (
+                repo_1.op_id().clone(),
+                repo_2.op_id().clone(),
+                vec![(commit2.id().clone(), vec![commit1.id().clone()])],
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_last_operation_file() {
+    let test_repo = TestRepo::init();
+    let settings = testutils::user_settings();
+    let path = test_repo.repo_path().join(LAST_OPERATION_FILE_NAME);
+    let repo_0 = test_repo
+        .env
+        .load_repo_at_head(&settings, test_repo.repo_path());
+
+    // Nothing is written unless the file exists
+    let mut tx = repo_0.start_transaction();
+    write_random_commit(tx.repo_mut());
+    let repo_1 = tx.commit("test").unwrap();
+    assert!(!path.exists());
+
+    // The id of the last published operation is recorded once a consumer has
+    // created the file
+    std::fs::write(&path, "").unwrap();
+    let mut tx = repo_1.start_transaction();
+    write_random_commit(tx.repo_mut());
+    let repo_2 = tx.commit("test").unwrap();
+    let last_op = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(last_op.trim_end(), repo_2.op_id().hex());
+}
+
+#[test]
+fn test_wait_for_op_heads_change() {
+    let test_repo = TestRepo::init();
+    // The repo loaded from disk records the last operation
+    let repo_0 = test_repo
+        .env
+        .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    let op_heads_store = repo_0.op_heads_store().as_ref();
+    let last_operation_path = test_repo.repo_path().join(LAST_OPERATION_FILE_NAME);
+    let wait = |old_op_id: &OperationId, timeout: Duration| {
+        op_heads_store::wait_for_op_heads_change(
+            op_heads_store,
+            slice::from_ref(old_op_id),
+            &last_operation_path,
+            Some(timeout),
+        )
+        .unwrap()
+    };
+
+    // Times out if no operation is recorded
+    assert_eq!(wait(repo_0.op_id(), Duration::ZERO), None);
+
+    // Returns immediately if the op heads have already changed
+    let mut tx = repo_0.start_transaction();
+    write_random_commit(tx.repo_mut());
+    let repo_1 = tx.commit("test").unwrap();
+    assert_eq!(
+        wait(repo_0.op_id(), Duration::ZERO),
+        Some(vec![repo_1.op_id().clone()])
+    );
+
+    // Wakes up when an operation is recorded by another thread
+    thread::scope(|s| {
+        let handle = s.spawn(|| {
+            thread::sleep(Duration::from_millis(50));
+            let mut tx = repo_1.start_transaction();
+            write_random_commit(tx.repo_mut());
+            tx.commit("test").unwrap()
+        });
+        let op_heads = wait(repo_1.op_id(), Duration::from_secs(60));
+        let repo_2 = handle.join().unwrap();
+        assert_eq!(op_heads, Some(vec![repo_2.op_id().clone()]));
+    });
+}
+
 #[test]
 fn test_stored_commit_predecessors() {
     let test_repo = TestRepo::init();
@@ -257,6 +443,30 @@ fn test_stored_commit_predecessors() {
     assert!(!op.stores_commit_predecessors());
 }
 
+#[test]
+fn test_stored_tags_order() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let loader = repo.loader();
+
+    let mut tx = repo.start_transaction();
+    for key in ["c", "a", "b"] {
+        tx.set_tag(key.to_owned(), format!("value {key}"));
+    }
+    let repo = tx.commit("test").unwrap();
+
+    // Tags are iterated in key order, regardless of insertion order
+    let op = loader.load_operation(repo.op_id()).unwrap();
+    assert_eq!(
+        op.metadata().tags.iter().collect_vec(),
+        [
+            (&"a".to_owned(), &"value a".to_owned()),
+            (&"b".to_owned(), &"value b".to_owned()),
+            (&"c".to_owned(), &"value c".to_owned()),
+        ]
+    );
+}
+
 #[test]
 fn test_reparent_range_linear() {
     let test_repo = TestRepo::init();
@@ -1054,6 +1264,92 @@ fn test_gc() {
     assert_eq!(expected_view_entries.len(), 1);
 }
 
+#[test]
+fn test_recover_op_heads() {
+    let test_repo = TestRepo::init();
+    let heads_dir = test_repo.repo_path().join("op_heads").join("heads");
+    let repo_0 = test_repo.repo.clone();
+    let loader = repo_0.loader();
+    let op_store = loader.op_store();
+    let op_heads_store = loader.op_heads_store();
+
+    let random_tx = |repo: &Arc<ReadonlyRepo>| {
+        let mut tx = repo.start_transaction();
+        write_random_commit(tx.repo_mut());
+        tx
+    };
+    let repo_a = random_tx(&repo_0).commit("op A").unwrap();
+    let repo_b = random_tx(&repo_a).commit("op B").unwrap();
+    let repo_c = random_tx(&repo_a).commit("op C").unwrap();
+    let expected_head_ids = [repo_b.op_id().clone(), repo_c.op_id().clone()]
+        .into_iter()
+        .sorted()
+        .collect_vec();
+
+    // The repo can't be loaded without op heads
+    std::fs::remove_dir_all(&heads_dir).unwrap();
+    assert_matches!(
+        loader.load_at_head(),
+        Err(RepoLoaderError::OpHeadResolution(
+            OpHeadResolutionError::NoHeads
+        ))
+    );
+
+    let recovered =
+        op_heads_store::recover_op_heads::<RepoLoaderError>(op_heads_store.as_ref(), op_store)
+            .unwrap();
+    assert_eq!(recovered.head_ids, expected_head_ids);
+    assert!(recovered.unreadable_ops.is_empty());
+    assert_eq!(
+        op_heads_store
+            .get_op_heads()
+            .unwrap()
+            .into_iter()
+            .sorted()
+            .collect_vec(),
+        expected_head_ids
+    );
+    // Divergent operations are merged as usual
+    let repo = loader.load_at_head().unwrap();
+    assert_eq!(
+        repo.operation().parent_ids().iter().sorted().collect_vec(),
+        expected_head_ids.iter().collect_vec()
+    );
+
+    // Heads pointing to missing operations are removed
+    let missing_id = OperationId::new(vec![0xab; 64]);
+    op_heads_store.update_op_heads(&[], &missing_id).unwrap();
+    let recovered =
+        op_heads_store::recover_op_heads::<RepoLoaderError>(op_heads_store.as_ref(), op_store)
+            .unwrap();
+    assert_eq!(recovered.head_ids, vec![repo.op_id().clone()]);
+    assert_eq!(op_heads_store.get_op_heads().unwrap(), recovered.head_ids);
+
+    // Unreadable operations are skipped, and their parents become heads
+    let repo_d = random_tx(&repo).commit("op D").unwrap();
+    std::fs::write(
+        test_repo
+            .repo_path()
+            .join("op_store")
+            .join("operations")
+            .join(repo_d.op_id().hex()),
+        b"garbage",
+    )
+    .unwrap();
+    let recovered =
+        op_heads_store::recover_op_heads::<RepoLoaderError>(op_heads_store.as_ref(), op_store)
+            .unwrap();
+    assert_eq!(recovered.head_ids, vec![repo.op_id().clone()]);
+    assert_eq!(
+        recovered
+            .unreadable_ops
+            .iter()
+            .map(|(id, _)| id)
+            .collect_vec(),
+        vec![repo_d.op_id()]
+    );
+}
+
 #[track_caller]
 fn extract_multiple_operations_error(
     error: &OpsetEvaluationError,
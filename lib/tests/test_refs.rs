@@ -14,7 +14,9 @@
 
 use jj_lib::merge::Merge;
 use jj_lib::op_store::RefTarget;
+use jj_lib::refs::count_ahead_behind;
 use jj_lib::refs::merge_ref_targets;
+use jj_lib::refs::AheadBehindCounts;
 use jj_lib::repo::Repo as _;
 use testutils::CommitGraphBuilder;
 use testutils::TestWorkspace;
@@ -434,3 +436,52 @@ fn test_merge_ref_targets() {
         )
     );
 }
+
+#[test]
+fn test_count_ahead_behind() {
+    let test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+
+    // 3 4
+    // | |
+    // 2 |
+    // |/
+    // 1
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit2]);
+    let commit4 = graph_builder.commit_with_parents(&[&commit1]);
+    let repo = tx.commit("test").unwrap();
+
+    let target1 = RefTarget::normal(commit1.id().clone());
+    let target3 = RefTarget::normal(commit3.id().clone());
+    let target4 = RefTarget::normal(commit4.id().clone());
+    let counts = |target: &RefTarget, base: &RefTarget| {
+        count_ahead_behind(repo.as_ref(), target, base).unwrap()
+    };
+
+    assert!(counts(&target3, &target3).is_synced());
+    assert_eq!(
+        counts(&target3, &target1),
+        AheadBehindCounts {
+            ahead: (2, Some(2)),
+            behind: (0, Some(0)),
+        }
+    );
+    assert_eq!(
+        counts(&target1, &target3),
+        AheadBehindCounts {
+            ahead: (0, Some(0)),
+            behind: (2, Some(2)),
+        }
+    );
+    assert_eq!(
+        counts(&target3, &target4),
+        AheadBehindCounts {
+            ahead: (2, Some(2)),
+            behind: (1, Some(1)),
+        }
+    );
+}
@@ -25,6 +25,8 @@ use jj_lib::backend::MillisSinceEpoch;
 use jj_lib::backend::Signature;
 use jj_lib::backend::Timestamp;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
 use jj_lib::fileset::FilesetExpression;
 use jj_lib::git;
 use jj_lib::graph::reverse_graph;
@@ -42,6 +44,7 @@ use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::repo_path::UiPathStyle;
 use jj_lib::revset::parse;
 use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetAliasesMap;
@@ -54,6 +57,7 @@ use jj_lib::revset::RevsetResolutionError;
 use jj_lib::revset::RevsetWorkspaceContext;
 use jj_lib::revset::SymbolResolver;
 use jj_lib::revset::SymbolResolverExtension;
+use jj_lib::settings::UserSettings;
 use jj_lib::signing::SignBehavior;
 use jj_lib::signing::Signer;
 use jj_lib::test_signing_backend::TestSigningBackend;
@@ -980,6 +984,7 @@ fn resolve_commit_ids_in_workspace(
     let path_converter = RepoPathUiConverter::Fs {
         cwd: cwd.unwrap_or_else(|| workspace.workspace_root()).to_owned(),
         base: workspace.workspace_root().to_owned(),
+        style: UiPathStyle::default(),
     };
     let workspace_ctx = RevsetWorkspaceContext {
         path_converter: &path_converter,
@@ -1128,6 +1133,55 @@ fn test_evaluate_expression_root_and_checkout() {
     assert!(expression.evaluate(tx.base_repo().as_ref()).is_err());
 }
 
+#[test]
+fn test_evaluate_expression_commits() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let commit3 = write_random_commit(mut_repo);
+
+    // Commit ids and change ids can be mixed
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!(
+                "commits('{}', '{}')",
+                commit1.id().hex(),
+                commit3.change_id().reverse_hex()
+            )
+        ),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("commits('{}')", commit2.id().hex())),
+        vec![commit2.id().clone()]
+    );
+
+    // Prefixes aren't resolved
+    assert_matches!(
+        try_resolve_commit_ids(
+            mut_repo,
+            &format!("commits('{}')", &commit1.id().hex()[..12])
+        ),
+        Err(RevsetResolutionError::NoSuchRevision { .. })
+    );
+    assert_matches!(
+        try_resolve_commit_ids(
+            mut_repo,
+            &format!(
+                "commits('{}', '{}')",
+                commit1.id().hex(),
+                &commit2.change_id().reverse_hex()[..12]
+            )
+        ),
+        Err(RevsetResolutionError::NoSuchRevision { .. })
+    );
+}
+
 #[test]
 fn test_evaluate_expression_heads() {
     let test_repo = TestRepo::init();
@@ -2830,6 +2884,71 @@ fn test_evaluate_expression_fork_point_merge_with_ancestor() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_closest() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    //     6
+    //     |
+    //   5 |
+    //   |\|
+    //   3 4
+    //   | |
+    //   2 |
+    //   | |
+    //   1 |
+    //   |/
+    //   0
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let mut graph_builder = CommitGraphBuilder::new(mut_repo);
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit2]);
+    let commit4 = graph_builder.initial_commit();
+    let commit5 = graph_builder.commit_with_parents(&[&commit3, &commit4]);
+    let commit6 = graph_builder.commit_with_parents(&[&commit4]);
+
+    let closest = |candidates: &[&Commit], head: &Commit| {
+        let candidates = candidates.iter().map(|commit| commit.id()).join("|");
+        let expression = format!("closest({candidates}, {})", head.id());
+        resolve_commit_ids(mut_repo, &expression)
+    };
+
+    // 3..5 has 2 commits, whereas 4..5 has 4 commits. 4 is the latest
+    // candidate, but isn't the closest one.
+    assert_eq!(
+        closest(&[&commit3, &commit4], &commit5),
+        vec![commit3.id().clone()]
+    );
+    // Ancestors of other candidates are farther away
+    assert_eq!(
+        closest(&[&commit1, &commit2, &commit4], &commit5),
+        vec![commit2.id().clone()]
+    );
+    // Candidates that aren't ancestors are ignored
+    assert_eq!(
+        closest(&[&commit1, &commit6], &commit5),
+        vec![commit1.id().clone()]
+    );
+    assert_eq!(closest(&[&commit3], &commit6), vec![]);
+    // A candidate is the closest to itself
+    assert_eq!(
+        closest(&[&commit3, &commit5], &commit5),
+        vec![commit5.id().clone()]
+    );
+    // The latest one is picked if candidates are equally close
+    assert_eq!(
+        closest(&[&commit1, &commit4], &commit5),
+        vec![commit4.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("closest(none(), {})", commit5.id())),
+        vec![]
+    );
+}
+
 #[test]
 fn test_evaluate_expression_merges() {
     let test_repo = TestRepo::init();
@@ -2913,6 +3032,28 @@ fn test_evaluate_expression_description() {
         vec![mut_repo.store().root_commit_id().clone()]
     );
 
+    // Regex and glob patterns match the whole description
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"description(regex:"^commit [12]\n")"#),
+        vec![commit2.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"description(regex:"^blah")"#),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"description(regex:"(?m)^blah")"#),
+        vec![commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"description(glob:"commit ?\n")"#),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"description(glob-i:"COMMIT 2*")"#),
+        vec![commit2.id().clone()]
+    );
+
     // Match subject line
     assert_eq!(
         resolve_commit_ids(mut_repo, "subject(glob:'commit ?')"),
@@ -3000,6 +3141,16 @@ fn test_evaluate_expression_author() {
             commit1.id().clone(),
         ]
     );
+    // Can match by regex or glob
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"author(regex:"^(name|email)[12]$")"#),
+        vec![commit2.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "author(glob:'email[!2]')"),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(resolve_commit_ids(mut_repo, "author(exact:name)"), vec![]);
 
     // Can match name or email explicitly
     assert_eq!(
@@ -3102,6 +3253,26 @@ fn test_evaluate_expression_author_date() {
         resolve_commit_ids(mut_repo, "author_date(before:'2023-03-25 12:00')"),
         vec![commit1.id().clone(), root_commit.id().clone()]
     );
+
+    // Bounds can be specified as named arguments
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "author_date(after='2023-03-25 12:00')"),
+        vec![commit3.id().clone(), commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            "author_date(after='2023-03-25 12:00', before='2023-03-25 13:00')"
+        ),
+        vec![commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            "author_date(before:'2023-03-25 13:00', after='2023-03-25 11:00')"
+        ),
+        vec![commit2.id().clone(), commit1.id().clone()]
+    );
 }
 
 #[test]
@@ -3168,6 +3339,15 @@ fn test_evaluate_expression_committer_date() {
         resolve_commit_ids(mut_repo, "committer_date(before:'2023-03-25 12:00')"),
         vec![commit1.id().clone(), root_commit.id().clone()]
     );
+
+    // Bounds can be specified as named arguments
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            "committer_date(after='2023-03-25 12:00', before='2023-03-25 13:00')"
+        ),
+        vec![commit2.id().clone()]
+    );
 }
 
 #[test]
@@ -3351,6 +3531,19 @@ fn test_evaluate_expression_committer() {
             commit1.id().clone(),
         ]
     );
+    // Can match by regex or glob
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"committer(regex:"^(name|email)[12]$")"#),
+        vec![commit2.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "committer(glob:'email[!2]')"),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "committer(exact:name)"),
+        vec![]
+    );
 
     // Can match name or email explicitly
     assert_eq!(
@@ -3979,6 +4172,143 @@ fn test_evaluate_expression_file() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_file_with_index_reloaded() {
+    // Paths changed by each commit are also looked up in the index if enabled.
+    // Make sure that the loaded index gives the same results.
+    let mut config = testutils::base_user_config();
+    config.add_layer(
+        ConfigLayer::parse(ConfigSource::User, "index.changed-path-filters = true").unwrap(),
+    );
+    let settings = UserSettings::from_config(config).unwrap();
+    let test_repo = TestRepo::init_with_settings(&settings);
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+
+    let dir_file = repo_path("dir/sub/file");
+    let other_file = repo_path("other");
+    let tree1 = create_tree(repo, &[(dir_file, "1")]);
+    let tree2 = create_tree(repo, &[(dir_file, "1"), (other_file, "1")]);
+    let tree3 = create_tree(repo, &[(dir_file, "2"), (other_file, "1")]);
+    let commit1 = mut_repo
+        .new_commit(vec![repo.store().root_commit_id().clone()], tree1.id())
+        .write()
+        .unwrap();
+    let commit2 = mut_repo
+        .new_commit(vec![commit1.id().clone()], tree2.id())
+        .write()
+        .unwrap();
+    let commit3 = mut_repo
+        .new_commit(vec![commit2.id().clone()], tree3.id())
+        .write()
+        .unwrap();
+    let commit4 = mut_repo
+        .new_commit(vec![commit3.id().clone()], tree3.id())
+        .write()
+        .unwrap();
+    tx.commit("test").unwrap();
+    let repo = test_repo
+        .env
+        .load_repo_at_head(&settings, test_repo.repo_path());
+
+    let resolve = |expression: FilesetExpression| -> Vec<CommitId> {
+        let expression = RevsetExpression::filter(RevsetFilterPredicate::File(expression));
+        let revset = expression.evaluate(repo.as_ref()).unwrap();
+        revset.iter().map(Result::unwrap).collect()
+    };
+
+    let prefix = |value: &str| FilesetExpression::prefix_path(repo_path(value).to_owned());
+    let file = |value: &str| FilesetExpression::file_path(repo_path(value).to_owned());
+    assert_eq!(
+        resolve(prefix("dir")),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve(prefix("dir/sub")),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve(file("dir/sub/file")),
+        vec![commit3.id().clone(), commit1.id().clone()]
+    );
+    // Directory isn't a file
+    assert_eq!(resolve(file("dir")), vec![]);
+    assert_eq!(resolve(prefix("other")), vec![commit2.id().clone()]);
+    assert_eq!(resolve(prefix("missing")), vec![]);
+    assert_eq!(
+        resolve(FilesetExpression::union_all(vec![
+            file("other"),
+            prefix("dir/sub"),
+        ])),
+        vec![
+            commit3.id().clone(),
+            commit2.id().clone(),
+            commit1.id().clone()
+        ]
+    );
+    assert_eq!(
+        resolve(prefix("dir").difference(file("dir/sub/file"))),
+        vec![]
+    );
+    // commit4 changes nothing
+    assert!(!resolve(FilesetExpression::all()).contains(commit4.id()));
+}
+
+#[test]
+fn test_evaluate_expression_empty() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+
+    let file_a = repo_path("a");
+    let file_b = repo_path("b");
+    let file_c = repo_path("c");
+    let tree_a = create_tree(repo, &[(file_a, "a")]);
+    let tree_b = create_tree(repo, &[(file_b, "b")]);
+    let tree_ab = create_tree(repo, &[(file_a, "a"), (file_b, "b")]);
+    let tree_abc = create_tree(repo, &[(file_a, "a"), (file_b, "b"), (file_c, "c")]);
+    let root_commit_id = repo.store().root_commit_id().clone();
+    let commit_a = mut_repo
+        .new_commit(vec![root_commit_id.clone()], tree_a.id())
+        .write()
+        .unwrap();
+    let commit_b = mut_repo
+        .new_commit(vec![root_commit_id.clone()], tree_b.id())
+        .write()
+        .unwrap();
+    let parent_ids = vec![commit_a.id().clone(), commit_b.id().clone()];
+    // The tree equals the auto-merged parent tree
+    let merge_clean = mut_repo
+        .new_commit(parent_ids.clone(), tree_ab.id())
+        .write()
+        .unwrap();
+    let merge_modified = mut_repo
+        .new_commit(parent_ids.clone(), tree_abc.id())
+        .write()
+        .unwrap();
+    let child_clean = mut_repo
+        .new_commit(vec![merge_clean.id().clone()], tree_ab.id())
+        .write()
+        .unwrap();
+
+    assert!(!commit_a.is_empty(mut_repo).unwrap());
+    assert!(merge_clean.is_empty(mut_repo).unwrap());
+    assert!(!merge_modified.is_empty(mut_repo).unwrap());
+    assert!(child_clean.is_empty(mut_repo).unwrap());
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("{}:: & empty()", commit_a.id())),
+        vec![child_clean.id().clone(), merge_clean.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "empty() & root()"),
+        vec![root_commit_id]
+    );
+}
+
 #[test]
 fn test_evaluate_expression_diff_contains() {
     let test_workspace = TestWorkspace::init();
@@ -4247,6 +4577,18 @@ fn test_evaluate_expression_conflict() {
         resolve_commit_ids(mut_repo, "conflicts()"),
         vec![commit4.id().clone()]
     );
+
+    // Conflicts can be restricted to paths
+    let resolve = |revset_str: &str| -> Vec<CommitId> {
+        let mut_repo = &*mut_repo;
+        resolve_commit_ids_in_workspace(mut_repo, revset_str, &test_workspace.workspace, None)
+    };
+    assert_eq!(resolve("conflicts(file1)"), vec![commit4.id().clone()]);
+    assert_eq!(resolve("conflicts(file2)"), vec![]);
+    assert_eq!(
+        resolve("conflicts(file2 | glob:'file*')"),
+        vec![commit4.id().clone()]
+    );
 }
 
 #[test]
@@ -4286,6 +4628,7 @@ fn test_reverse_graph() {
     );
     let commits = reverse_graph(revset.iter_graph(), |id| id).unwrap();
     assert_eq!(commits.len(), 5);
+    let commits = commits.collect_vec();
     assert_eq!(commits[0].0, *commit_a.id());
     assert_eq!(commits[1].0, *commit_c.id());
     assert_eq!(commits[2].0, *commit_d.id());
@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use assert_matches::assert_matches;
 use itertools::Itertools as _;
 use jj_lib::backend::ChangeId;
 use jj_lib::commit::Commit;
@@ -28,14 +32,21 @@ use jj_lib::ref_name::RemoteRefSymbol;
 use jj_lib::ref_name::WorkspaceName;
 use jj_lib::ref_name::WorkspaceNameBuf;
 use jj_lib::repo::Repo as _;
+use jj_lib::rewrite::compute_move_commits;
+use jj_lib::rewrite::copy_tree_to_store;
 use jj_lib::rewrite::find_duplicate_divergent_commits;
+use jj_lib::rewrite::merge_divergent_commits;
+use jj_lib::rewrite::move_commits;
 use jj_lib::rewrite::rebase_commit_with_options;
 use jj_lib::rewrite::restore_tree;
 use jj_lib::rewrite::CommitRewriter;
 use jj_lib::rewrite::CommitWithSelection;
+use jj_lib::rewrite::DivergentCommitsMergeError;
 use jj_lib::rewrite::EmptyBehaviour;
+use jj_lib::rewrite::MoveCommitsLocation;
 use jj_lib::rewrite::MoveCommitsTarget;
 use jj_lib::rewrite::RebaseOptions;
+use jj_lib::rewrite::RebasedCommit;
 use jj_lib::rewrite::RewriteRefsOptions;
 use maplit::hashmap;
 use maplit::hashset;
@@ -43,6 +54,7 @@ use pollster::FutureExt as _;
 use test_case::test_case;
 use testutils::assert_abandoned_with_parent;
 use testutils::assert_rebased_onto;
+use testutils::commit_transactions;
 use testutils::create_random_commit;
 use testutils::create_tree;
 use testutils::create_tree_with;
@@ -410,6 +422,130 @@ fn test_rebase_descendants_external_merge() {
     );
 }
 
+#[test]
+fn test_rebase_descendants_criss_cross_merge() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // Commit B was replaced by commit G. The criss-cross merges D and E, and
+    // their merge F, should keep all their parents in the same order.
+    //
+    //   F
+    //   |\
+    //   D E
+    //   |X|
+    //   B C G
+    //   |/  |
+    //   A---+
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_d = graph_builder.commit_with_parents(&[&commit_b, &commit_c]);
+    let commit_e = graph_builder.commit_with_parents(&[&commit_c, &commit_b]);
+    let commit_f = graph_builder.commit_with_parents(&[&commit_d, &commit_e]);
+    let commit_g = graph_builder.commit_with_parents(&[&commit_a]);
+
+    tx.repo_mut()
+        .set_rewritten_commit(commit_b.id().clone(), commit_g.id().clone());
+    let rebase_map =
+        rebase_descendants_with_options_return_map(tx.repo_mut(), &RebaseOptions::default());
+    let new_commit_d = assert_rebased_onto(
+        tx.repo_mut(),
+        &rebase_map,
+        &commit_d,
+        &[commit_g.id(), commit_c.id()],
+    );
+    let new_commit_e = assert_rebased_onto(
+        tx.repo_mut(),
+        &rebase_map,
+        &commit_e,
+        &[commit_c.id(), commit_g.id()],
+    );
+    let new_commit_f = assert_rebased_onto(
+        tx.repo_mut(),
+        &rebase_map,
+        &commit_f,
+        &[new_commit_d.id(), new_commit_e.id()],
+    );
+    assert_eq!(rebase_map.len(), 3);
+
+    assert_eq!(
+        *tx.repo().view().heads(),
+        hashset! {new_commit_f.id().clone()}
+    );
+}
+
+#[test]
+fn test_rebase_descendants_merge_with_some_parents_rewritten() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let path1 = repo_path("file1");
+    let path2 = repo_path("file2");
+    let path3 = repo_path("file3");
+
+    // Commit B was replaced by commit B2. Merge commit D, which also modifies
+    // file1, should be rebased onto B2 and C, keeping its own changes and the
+    // changes from C.
+    //
+    //   D
+    //   |\
+    //   B C B2
+    //   |/  |
+    //   A---+
+    let mut tx = repo.start_transaction();
+    let root_commit_id = repo.store().root_commit_id();
+    let tree_a = create_tree(repo, &[(path1, "a\n")]);
+    let tree_b = create_tree(repo, &[(path1, "a\n"), (path2, "b\n")]);
+    let tree_b2 = create_tree(repo, &[(path1, "a\n"), (path2, "b2\n")]);
+    let tree_c = create_tree(repo, &[(path1, "a\n"), (path3, "c\n")]);
+    let tree_d = create_tree(repo, &[(path1, "d\n"), (path2, "b\n"), (path3, "c\n")]);
+    let commit_a = tx
+        .repo_mut()
+        .new_commit(vec![root_commit_id.clone()], tree_a.id())
+        .write()
+        .unwrap();
+    let commit_b = tx
+        .repo_mut()
+        .new_commit(vec![commit_a.id().clone()], tree_b.id())
+        .write()
+        .unwrap();
+    let commit_c = tx
+        .repo_mut()
+        .new_commit(vec![commit_a.id().clone()], tree_c.id())
+        .write()
+        .unwrap();
+    let commit_d = tx
+        .repo_mut()
+        .new_commit(
+            vec![commit_b.id().clone(), commit_c.id().clone()],
+            tree_d.id(),
+        )
+        .write()
+        .unwrap();
+    let commit_b2 = tx
+        .repo_mut()
+        .rewrite_commit(&commit_b)
+        .set_tree_id(tree_b2.id())
+        .write()
+        .unwrap();
+
+    let rebase_map =
+        rebase_descendants_with_options_return_map(tx.repo_mut(), &RebaseOptions::default());
+    let new_commit_d = assert_rebased_onto(
+        tx.repo_mut(),
+        &rebase_map,
+        &commit_d,
+        &[commit_b2.id(), commit_c.id()],
+    );
+    assert_eq!(rebase_map.len(), 1);
+    assert_eq!(
+        *new_commit_d.tree_id(),
+        create_tree(repo, &[(path1, "d\n"), (path2, "b2\n"), (path3, "c\n")]).id()
+    );
+}
+
 #[test]
 fn test_rebase_descendants_abandon() {
     let test_repo = TestRepo::init();
@@ -2008,3 +2144,237 @@ fn test_find_duplicate_divergent_commits() {
     // Commit c2 is a duplicate
     assert_eq!(duplicate_commits, std::slice::from_ref(&commit_c2));
 }
+
+#[test]
+fn test_compute_move_commits_inspect_and_apply() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // Move B:: onto D.
+    //
+    // C
+    // B D
+    // |/
+    // A
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_b]);
+    let commit_d = graph_builder.commit_with_parents(&[&commit_a]);
+
+    let loc = MoveCommitsLocation {
+        new_parent_ids: vec![commit_d.id().clone()],
+        new_child_ids: vec![],
+        target: MoveCommitsTarget::Roots(vec![commit_b.id().clone()]),
+    };
+    let computed = compute_move_commits(tx.repo(), &loc).unwrap();
+
+    // The plan can be inspected without modifying the repo
+    assert!(!computed.is_empty());
+    assert_eq!(
+        computed
+            .target_commit_ids()
+            .cloned()
+            .collect::<HashSet<_>>(),
+        hashset! {commit_b.id().clone(), commit_c.id().clone()}
+    );
+    assert_eq!(computed.num_commits_to_visit(), 2);
+    assert_eq!(
+        computed
+            .commits_to_visit()
+            .map(|(commit, parent_ids)| (commit.id().clone(), parent_ids.to_vec()))
+            .collect::<HashMap<_, _>>(),
+        hashmap! {
+            commit_b.id().clone() => vec![commit_d.id().clone()],
+            commit_c.id().clone() => vec![commit_b.id().clone()],
+        }
+    );
+    assert!(!computed.is_to_abandon(commit_b.id()));
+    assert!(!tx.repo().has_rewrites());
+
+    let mut progress = vec![];
+    let stats = computed
+        .apply_with_progress(tx.repo_mut(), &RebaseOptions::default(), |p| {
+            progress.push((p.old_commit_id.clone(), p.num_processed, p.num_total));
+        })
+        .unwrap();
+    assert_eq!(stats.num_rebased_targets, 2);
+    assert_eq!(
+        progress,
+        vec![(commit_b.id().clone(), 1, 2), (commit_c.id().clone(), 2, 2),]
+    );
+    let Some(RebasedCommit::Rewritten(new_commit_b)) = stats.rebased_commits.get(commit_b.id())
+    else {
+        panic!("commit B should have been rewritten");
+    };
+    assert_eq!(new_commit_b.parent_ids(), &[commit_d.id().clone()]);
+}
+
+#[test]
+fn test_move_commits_insert_between() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // Insert D::E between A and B. C is rebased along with B.
+    //
+    // C
+    // B E
+    // | D
+    // |/
+    // A
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_b]);
+    let commit_d = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_e = graph_builder.commit_with_parents(&[&commit_d]);
+
+    let loc = MoveCommitsLocation {
+        new_parent_ids: vec![commit_a.id().clone()],
+        new_child_ids: vec![commit_b.id().clone()],
+        target: MoveCommitsTarget::Commits(vec![commit_e.id().clone(), commit_d.id().clone()]),
+    };
+    let stats = move_commits(tx.repo_mut(), &loc, &RebaseOptions::default()).unwrap();
+    let new_commit = |old_commit: &Commit| match stats.rebased_commits.get(old_commit.id()) {
+        Some(RebasedCommit::Rewritten(new_commit)) => new_commit.clone(),
+        _ => panic!("commit should have been rewritten"),
+    };
+
+    // D is already on A, so only B and its descendants need to be rewritten.
+    assert_eq!(stats.num_skipped_rebases, 2);
+    assert_eq!(stats.num_rebased_descendants, 2);
+    assert!(!stats.rebased_commits.contains_key(commit_d.id()));
+    assert!(!stats.rebased_commits.contains_key(commit_e.id()));
+    let new_commit_b = new_commit(&commit_b);
+    assert_eq!(new_commit_b.parent_ids(), &[commit_e.id().clone()]);
+    let new_commit_c = new_commit(&commit_c);
+    assert_eq!(new_commit_c.parent_ids(), &[new_commit_b.id().clone()]);
+    assert_eq!(
+        *tx.repo().view().heads(),
+        hashset! {new_commit_c.id().clone()}
+    );
+}
+
+#[test]
+fn test_merge_divergent_commits() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let path = repo_path("file");
+    let other_path = repo_path("other");
+
+    // A is rewritten concurrently into A1 and A2, which have the same change id.
+    // A2 is also moved onto B. C is a child of A1.
+    let mut tx = repo.start_transaction();
+    let tree_a = create_tree(repo, &[(path, "a\nb\nc\n")]);
+    let commit_a = tx
+        .repo_mut()
+        .new_commit(vec![repo.store().root_commit_id().clone()], tree_a.id())
+        .write()
+        .unwrap();
+    let tree_b = create_tree(repo, &[(other_path, "b\n")]);
+    let commit_b = tx
+        .repo_mut()
+        .new_commit(vec![repo.store().root_commit_id().clone()], tree_b.id())
+        .write()
+        .unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx1 = repo.start_transaction();
+    let tree_a1 = create_tree(&repo, &[(path, "A\nb\nc\n")]);
+    let commit_a1 = tx1
+        .repo_mut()
+        .rewrite_commit(&commit_a)
+        .set_tree_id(tree_a1.id())
+        .write()
+        .unwrap();
+    let commit_c = tx1
+        .repo_mut()
+        .new_commit(vec![commit_a1.id().clone()], tree_a1.id())
+        .write()
+        .unwrap();
+    let mut tx2 = repo.start_transaction();
+    let tree_a2 = create_tree(&repo, &[(path, "a\nb\nC\n"), (other_path, "b\n")]);
+    let commit_a2 = tx2
+        .repo_mut()
+        .rewrite_commit(&commit_a)
+        .set_parents(vec![commit_b.id().clone()])
+        .set_tree_id(tree_a2.id())
+        .write()
+        .unwrap();
+    tx1.repo_mut().rebase_descendants().unwrap();
+    tx2.repo_mut().rebase_descendants().unwrap();
+    let repo = commit_transactions(vec![tx1, tx2]);
+    assert_eq!(
+        repo.resolve_change_id(commit_a.change_id())
+            .unwrap()
+            .into_iter()
+            .collect::<HashSet<_>>(),
+        hashset! {commit_a1.id().clone(), commit_a2.id().clone()}
+    );
+
+    let mut tx = repo.start_transaction();
+    assert_matches!(
+        merge_divergent_commits(tx.repo_mut(), vec![commit_a1.clone()]).err(),
+        Some(DivergentCommitsMergeError::NotDivergent)
+    );
+    assert_matches!(
+        merge_divergent_commits(tx.repo_mut(), vec![commit_a1.clone(), commit_b.clone()]).err(),
+        Some(DivergentCommitsMergeError::NotDivergent)
+    );
+    let merged =
+        merge_divergent_commits(tx.repo_mut(), vec![commit_a1.clone(), commit_a2.clone()]).unwrap();
+    let new_commit = merged.write(tx.repo_mut()).unwrap();
+    let rebase_map =
+        rebase_descendants_with_options_return_map(tx.repo_mut(), &RebaseOptions::default());
+
+    // The changes of A2 are rebased onto the parent of A1 and merged.
+    assert_eq!(new_commit.change_id(), commit_a.change_id());
+    assert_eq!(new_commit.parent_ids(), commit_a1.parent_ids());
+    assert_eq!(
+        *new_commit.tree_id(),
+        create_tree(&repo, &[(path, "A\nb\nC\n")]).id()
+    );
+    assert_eq!(
+        new_commit.store_commit().predecessors,
+        vec![commit_a1.id().clone(), commit_a2.id().clone()]
+    );
+    assert_eq!(rebase_map.len(), 1);
+    assert_rebased_onto(tx.repo_mut(), &rebase_map, &commit_c, &[new_commit.id()]);
+    assert_eq!(
+        tx.repo().resolve_change_id(commit_a.change_id()),
+        Some(vec![new_commit.id().clone()])
+    );
+}
+
+#[test]
+fn test_copy_tree_to_store() {
+    let source_repo = TestRepo::init();
+    let dest_repo = TestRepo::init();
+    let source_tree = create_tree(
+        &source_repo.repo,
+        &[
+            (repo_path("lib/file"), "file\n"),
+            (repo_path("lib/sub/file"), "sub\n"),
+            (repo_path("other"), "other\n"),
+        ],
+    );
+
+    let lib_dir = repo_path("lib");
+    let vendor_dir = repo_path("vendor");
+    let copied_tree_id = copy_tree_to_store(
+        &source_tree,
+        &FilesMatcher::new([repo_path("lib/file"), repo_path("other")]),
+        dest_repo.repo.store(),
+        |path| {
+            let mut new_path = vendor_dir.to_owned();
+            new_path.extend(path.strip_prefix(lib_dir)?.components());
+            Some(new_path)
+        },
+    )
+    .block_on()
+    .unwrap();
+    let expected_tree = create_tree(&dest_repo.repo, &[(repo_path("vendor/file"), "file\n")]);
+    assert_eq!(copied_tree_id, expected_tree.id());
+}
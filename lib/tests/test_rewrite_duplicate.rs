@@ -18,9 +18,12 @@ use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::repo::Repo as _;
 use jj_lib::rewrite::duplicate_commits;
+use jj_lib::rewrite::duplicate_commits_onto_parents;
 use jj_lib::transaction::Transaction;
+use maplit::hashset;
 use testutils::create_tree;
 use testutils::repo_path;
+use testutils::write_random_commit;
 use testutils::TestRepo;
 
 #[test]
@@ -180,3 +183,68 @@ fn test_duplicate_linear_contents() {
         &tree_1_2.id()
     );
 }
+
+#[test]
+fn test_duplicate_keeps_originals() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let path_1 = repo_path("file1");
+    let tree_1 = create_tree(repo, &[(path_1, "content1")]);
+
+    // B [+file1] "fix"
+    // | C "release"
+    // |/
+    // A
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let commit_b = tx
+        .repo_mut()
+        .new_commit(vec![commit_a.id().clone()], tree_1.id())
+        .set_description("fix")
+        .write()
+        .unwrap();
+    let commit_c = tx
+        .repo_mut()
+        .new_commit(vec![commit_a.id().clone()], commit_a.tree_id().clone())
+        .set_description("release")
+        .write()
+        .unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    // Duplicate B onto C
+    let mut tx = repo.start_transaction();
+    let stats = duplicate_commits(
+        tx.repo_mut(),
+        &[commit_b.id().clone()],
+        &HashMap::new(),
+        &[commit_c.id().clone()],
+        &[],
+    )
+    .unwrap();
+    assert_eq!(stats.num_rebased, 0);
+    let new_commit_b = &stats.duplicated_commits[commit_b.id()];
+    assert_ne!(new_commit_b.change_id(), commit_b.change_id());
+    assert_eq!(new_commit_b.parent_ids(), [commit_c.id().clone()]);
+    assert_eq!(new_commit_b.description(), "fix");
+    assert_eq!(new_commit_b.tree_id(), &tree_1.id());
+    assert_eq!(
+        *tx.repo().view().heads(),
+        hashset! {commit_b.id().clone(), new_commit_b.id().clone()}
+    );
+
+    // Duplicate B onto its own parent with a new description
+    let mut tx = repo.start_transaction();
+    let stats = duplicate_commits_onto_parents(
+        tx.repo_mut(),
+        &[commit_b.id().clone()],
+        &HashMap::from([(commit_b.id().clone(), "fix (copy)".to_owned())]),
+    )
+    .unwrap();
+    let new_commit_b = &stats.duplicated_commits[commit_b.id()];
+    assert_ne!(new_commit_b.change_id(), commit_b.change_id());
+    assert_eq!(new_commit_b.parent_ids(), [commit_a.id().clone()]);
+    assert_eq!(new_commit_b.description(), "fix (copy)");
+    assert!(tx.repo().view().heads().contains(commit_b.id()));
+    assert!(tx.repo().view().heads().contains(commit_c.id()));
+}
@@ -0,0 +1,152 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
+
+use itertools::Itertools as _;
+use jj_lib::backend::TreeId;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
+use jj_lib::merge::Merge;
+use jj_lib::repo::Repo as _;
+use jj_lib::settings::UserSettings;
+use jj_lib::tree_merge_cache::TreeMergeCache;
+use testutils::create_tree;
+use testutils::new_temp_dir;
+use testutils::repo_path;
+use testutils::TestRepo;
+
+fn tree_ids(ids: &[u8]) -> Merge<TreeId> {
+    Merge::from_vec(ids.iter().map(|&id| TreeId::new(vec![id; 4])).collect_vec())
+}
+
+#[test]
+fn test_tree_merge_cache_persisted() {
+    let temp_dir = new_temp_dir();
+    let cache_dir = temp_dir.path().join("cache");
+
+    let cache = TreeMergeCache::new(&cache_dir);
+    assert_eq!(cache.get(&tree_ids(&[1, 2, 3])), None);
+    cache.insert(tree_ids(&[1, 2, 3]), tree_ids(&[4]));
+    cache.insert(tree_ids(&[1, 2, 5]), tree_ids(&[1, 2, 5]));
+    assert_eq!(cache.get(&tree_ids(&[1, 2, 3])), Some(tree_ids(&[4])));
+
+    // A new instance reads the results from disk
+    let cache = TreeMergeCache::new(&cache_dir);
+    assert_eq!(cache.get(&tree_ids(&[1, 2, 3])), Some(tree_ids(&[4])));
+    assert_eq!(cache.get(&tree_ids(&[1, 2, 5])), Some(tree_ids(&[1, 2, 5])));
+    assert_eq!(cache.get(&tree_ids(&[3, 2, 1])), None);
+}
+
+fn entry_files(cache_dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(cache_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect()
+}
+
+#[test]
+fn test_tree_merge_cache_eviction() {
+    let temp_dir = new_temp_dir();
+    let cache_dir = temp_dir.path().join("cache");
+    let capacity = NonZeroUsize::new(2).unwrap();
+
+    let cache = TreeMergeCache::with_capacity(&cache_dir, capacity);
+    for id in 1..=6 {
+        cache.insert(tree_ids(&[id, 0, id]), tree_ids(&[id]));
+    }
+    // Old entries were pruned from disk
+    let num_files = entry_files(&cache_dir).len();
+    assert!(num_files <= 2 * capacity.get(), "{num_files}");
+    let cache = TreeMergeCache::with_capacity(&cache_dir, capacity);
+    assert_eq!(cache.get(&tree_ids(&[6, 0, 6])), Some(tree_ids(&[6])));
+}
+
+#[test]
+fn test_tree_merge_cache_invalid_entry() {
+    let temp_dir = new_temp_dir();
+    let cache_dir = temp_dir.path().join("cache");
+
+    let cache = TreeMergeCache::new(&cache_dir);
+    cache.insert(tree_ids(&[1, 2, 3]), tree_ids(&[4]));
+    let [entry_path] = &*entry_files(&cache_dir) else {
+        panic!("expected a single entry");
+    };
+
+    // A truncated entry is ignored
+    let content = fs::read_to_string(entry_path).unwrap();
+    fs::write(entry_path, &content[..content.len() - 10]).unwrap();
+    let cache = TreeMergeCache::new(&cache_dir);
+    assert_eq!(cache.get(&tree_ids(&[1, 2, 3])), None);
+
+    // An entry with a modified result doesn't match the checksum
+    let tampered = content.replace(&"04".repeat(4), &"05".repeat(4));
+    assert_ne!(tampered, content);
+    fs::write(entry_path, tampered).unwrap();
+    let cache = TreeMergeCache::new(&cache_dir);
+    assert_eq!(cache.get(&tree_ids(&[1, 2, 3])), None);
+
+    // The entry is overwritten by new insertion
+    cache.insert(tree_ids(&[1, 2, 3]), tree_ids(&[4]));
+    let cache = TreeMergeCache::new(&cache_dir);
+    assert_eq!(cache.get(&tree_ids(&[1, 2, 3])), Some(tree_ids(&[4])));
+}
+
+#[test]
+fn test_tree_merge_cache_in_repo() {
+    let mut config = testutils::base_user_config();
+    config
+        .add_layer(ConfigLayer::parse(ConfigSource::User, "core.tree-merge-cache = true").unwrap());
+    let settings = UserSettings::from_config(config).unwrap();
+    let test_repo = TestRepo::init_with_settings(&settings);
+    let repo = &test_repo.repo;
+
+    let path = repo_path("file");
+    let base_tree = create_tree(repo, &[(path, "a\nb\nc\n")]);
+    let left_tree = create_tree(repo, &[(path, "A\nb\nc\n")]);
+    let right_tree = create_tree(repo, &[(path, "a\nb\nC\n")]);
+    let merged_tree = left_tree.merge(&base_tree, &right_tree).unwrap();
+    let expected_tree = create_tree(repo, &[(path, "A\nb\nC\n")]);
+    assert_eq!(merged_tree.id(), expected_tree.id());
+
+    // The result is recorded in the repo's cache
+    let input_ids = Merge::from_vec(vec![
+        left_tree.id().to_merge().into_resolved().unwrap(),
+        base_tree.id().to_merge().into_resolved().unwrap(),
+        right_tree.id().to_merge().into_resolved().unwrap(),
+    ]);
+    let cache = TreeMergeCache::new(test_repo.repo_path().join("tree_merge_cache"));
+    assert_eq!(cache.get(&input_ids), Some(expected_tree.id().to_merge()));
+
+    // Merging again gives the same result
+    let merged_tree = left_tree.merge(&base_tree, &right_tree).unwrap();
+    assert_eq!(merged_tree.id(), expected_tree.id());
+}
+
+#[test]
+fn test_tree_merge_cache_disabled_by_default() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let path = repo_path("file");
+    let base_tree = create_tree(repo, &[(path, "a\nb\nc\n")]);
+    let left_tree = create_tree(repo, &[(path, "A\nb\nc\n")]);
+    let right_tree = create_tree(repo, &[(path, "a\nb\nC\n")]);
+    left_tree.merge(&base_tree, &right_tree).unwrap();
+    assert!(repo.store().tree_merge_cache().is_none());
+    assert!(!test_repo.repo_path().join("tree_merge_cache").exists());
+}
@@ -91,6 +91,75 @@ fn test_heads_merge() {
     assert_eq!(*repo.view().heads(), hashset! {merge.id().clone()});
 }
 
+#[test]
+fn test_bookmark_status() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let tracked = |commit: &jj_lib::commit::Commit| RemoteRef {
+        target: RefTarget::normal(commit.id().clone()),
+        state: RemoteRefState::Tracked,
+    };
+    let untracked = |commit: &jj_lib::commit::Commit| RemoteRef {
+        target: RefTarget::normal(commit.id().clone()),
+        state: RemoteRefState::New,
+    };
+
+    // Local and tracked remote are in sync, untracked remote is ignored
+    mut_repo.set_local_bookmark_target("synced".as_ref(), RefTarget::normal(commit1.id().clone()));
+    mut_repo.set_remote_bookmark(remote_symbol("synced", "origin"), tracked(&commit1));
+    mut_repo.set_remote_bookmark(remote_symbol("synced", "upstream"), untracked(&commit2));
+    // Tracked remote points elsewhere
+    mut_repo
+        .set_local_bookmark_target("diverged".as_ref(), RefTarget::normal(commit1.id().clone()));
+    mut_repo.set_remote_bookmark(remote_symbol("diverged", "origin"), tracked(&commit2));
+    mut_repo.set_remote_bookmark(remote_symbol("diverged", "upstream"), tracked(&commit1));
+    // Deleted locally
+    mut_repo.set_remote_bookmark(remote_symbol("deleted", "origin"), tracked(&commit1));
+    // Conflicted locally
+    mut_repo.set_local_bookmark_target(
+        "conflicted".as_ref(),
+        RefTarget::from_legacy_form([], [commit1.id().clone(), commit2.id().clone()]),
+    );
+
+    let view = tx.repo().view();
+    let synced = view.get_bookmark("synced".as_ref());
+    assert_eq!(synced.remote_refs.len(), 2);
+    assert_eq!(synced.tracked_remote_refs().count(), 1);
+    assert!(synced.is_synced());
+    assert!(!synced.has_conflict());
+    assert!(!synced.is_deleted_locally());
+
+    let diverged = view.get_bookmark("diverged".as_ref());
+    let origin: &RemoteName = "origin".as_ref();
+    assert_eq!(
+        diverged.diverged_remotes().collect::<Vec<_>>(),
+        vec![origin]
+    );
+    assert!(!diverged.is_synced());
+
+    let deleted = view.get_bookmark("deleted".as_ref());
+    assert!(deleted.local_target.is_absent());
+    assert!(deleted.is_deleted_locally());
+    assert!(!deleted.is_synced());
+
+    let conflicted = view.get_bookmark("conflicted".as_ref());
+    assert!(conflicted.has_conflict());
+    assert!(conflicted.is_synced());
+
+    // Consistent with the bookmarks iterator
+    for (name, target) in view.bookmarks() {
+        assert_eq!(view.get_bookmark(name), target);
+    }
+    let missing = view.get_bookmark("missing".as_ref());
+    assert!(missing.local_target.is_absent());
+    assert!(missing.remote_refs.is_empty());
+}
+
 #[test]
 fn test_merge_views_heads() {
     // Tests merging of the view's heads (by performing divergent operations).